@@ -71,6 +71,9 @@ function(cargo_build)
         list(APPEND CARGO_CARGS "--profile" "dev")
     endif()
 
+    # The C library serves the allocations with the functions set by cass_alloc_set_functions().
+    list(APPEND CARGO_ARGS "--features" "custom-allocator")
+
     file(GLOB_RECURSE LIB_SOURCES "*.rs")
 
     set(CARGO_ENV_COMMAND ${CMAKE_COMMAND} -E env "CARGO_TARGET_DIR=${CARGO_TARGET_DIR}" "RUSTFLAGS=${CMAKE_Rust_FLAGS}")
@@ -153,6 +153,15 @@ typedef struct CassUuid_ {
  */
 typedef struct CassCluster_ CassCluster;
 
+/**
+ * Credentials provided by a credentials callback.
+ *
+ * @struct CassCredentials
+ *
+ * @see cass_cluster_set_credentials_callback()
+ */
+typedef struct CassCredentials_ CassCredentials;
+
 /**
  * A session object is used to execute queries and maintains cluster state through
  * the control connection. The control connection is used to auto-discover nodes and
@@ -165,6 +174,14 @@ typedef struct CassCluster_ CassCluster;
  */
 typedef struct CassSession_ CassSession;
 
+/**
+ * A handle to the rust-driver session underlying a connected session.
+ * It's meant to be consumed by the Rust code linked with the driver.
+ *
+ * @struct CassRustSession
+ */
+typedef struct CassRustSession_ CassRustSession;
+
 /**
  * A statement object is an executable query. It represents either a regular
  * (adhoc) statement or a prepared statement. It maintains the queries' parameter
@@ -235,6 +252,23 @@ typedef struct CassErrorResult_ CassErrorResult;
  */
 typedef struct CassNode_ CassNode;
 
+/**
+ * Information about the execution of a request: the attempts made (with
+ * their hosts and errors), the achieved consistency and whether a
+ * speculative execution was used.
+ *
+ * @struct CassExecutionInfo
+ */
+typedef struct CassExecutionInfo_ CassExecutionInfo;
+
+/**
+ * The ordered list of hosts (and shards) the driver would try when
+ * executing a statement.
+ *
+ * @struct CassQueryPlan
+ */
+typedef struct CassQueryPlan_ CassQueryPlan;
+
 /**
  * An object used to iterate over a group of rows, columns or collection values.
  *
@@ -431,7 +465,7 @@ typedef struct CassMetrics_ {
 
   struct {
     cass_uint64_t connection_timeouts; /**< Occurrences of a connection timeout */
-    cass_uint64_t pending_request_timeouts; /**< Deprecated */
+    cass_uint64_t pending_request_timeouts; /**< Occurrences of requests that timed out before being sent */
     cass_uint64_t request_timeouts; /**< Occurrences of requests that timed out waiting for a request to finish */
   } errors; /**< Error metrics */
 } CassMetrics;
@@ -451,6 +485,44 @@ typedef struct CassSpeculativeExecutionMetrics_ {
   cass_double_t percentage; /**< Fraction of requests that are aborted speculative retries */
 } CassSpeculativeExecutionMetrics;
 
+/**
+ * Request queue and backpressure metrics of the session.
+ *
+ * A request is pending from the moment it is executed by the session,
+ * until its first attempt is sent to a host.
+ *
+ * @struct CassQueueMetrics
+ */
+typedef struct CassQueueMetrics_ {
+  cass_uint64_t pending_requests; /**< The number of requests currently waiting to be sent */
+  cass_uint64_t throttled_requests; /**< Occurrences of attempts rejected, because the connection had no free stream ids */
+  cass_uint64_t pending_request_timeouts; /**< Occurrences of requests that timed out before being sent */
+} CassQueueMetrics;
+
+/**
+ * Metrics of the requests sent to a single host. Each attempt (including
+ * retries and speculative executions) counts as a separate request.
+ * Latencies are recorded for requests that got a response from the host.
+ *
+ * @struct CassHostMetrics
+ */
+typedef struct CassHostMetrics_ {
+  CassInet address; /**< The address of the host */
+  cass_uint64_t requests; /**< The number of requests sent to the host */
+  cass_uint64_t errors; /**< The number of requests that failed */
+  cass_uint64_t in_flight; /**< The number of requests currently in flight */
+  cass_uint64_t min; /**< Minimum in microseconds */
+  cass_uint64_t max; /**< Maximum in microseconds */
+  cass_uint64_t mean; /**< Mean in microseconds */
+  cass_uint64_t stddev; /**< Standard deviation in microseconds */
+  cass_uint64_t median; /**< Median in microseconds */
+  cass_uint64_t percentile_75th; /**< 75th percentile in microseconds */
+  cass_uint64_t percentile_95th; /**< 95th percentile in microseconds */
+  cass_uint64_t percentile_98th; /**< 98th percentile in microseconds */
+  cass_uint64_t percentile_99th; /**< 99the percentile in microseconds */
+  cass_uint64_t percentile_999th; /**< 99.9th percentile in microseconds */
+} CassHostMetrics;
+
 typedef enum CassConsistency_ {
   CASS_CONSISTENCY_UNKNOWN      = 0xFFFF,
   CASS_CONSISTENCY_ANY          = 0x0000,
@@ -582,6 +654,19 @@ typedef enum CassBatchType_ {
   CASS_BATCH_TYPE_COUNTER  = 0x02
 } CassBatchType;
 
+typedef enum CassRequestPriority_ {
+  CASS_REQUEST_PRIORITY_LOW,
+  CASS_REQUEST_PRIORITY_NORMAL,
+  CASS_REQUEST_PRIORITY_HIGH
+} CassRequestPriority;
+
+typedef enum CassAddressFamily_ {
+  CASS_ADDRESS_FAMILY_PREFER_IPV4,
+  CASS_ADDRESS_FAMILY_PREFER_IPV6,
+  CASS_ADDRESS_FAMILY_IPV4_ONLY,
+  CASS_ADDRESS_FAMILY_IPV6_ONLY
+} CassAddressFamily;
+
 typedef enum CassIteratorType_ {
   CASS_ITERATOR_TYPE_RESULT,
   CASS_ITERATOR_TYPE_ROW,
@@ -685,6 +770,8 @@ typedef enum  CassErrorSource_ {
   XX(CASS_ERROR_SOURCE_LIB, CASS_ERROR_LIB_NO_CUSTOM_PAYLOAD, 33, "No custom payload") \
   XX(CASS_ERROR_SOURCE_LIB, CASS_ERROR_LIB_EXECUTION_PROFILE_INVALID, 34, "Invalid execution profile specified") \
   XX(CASS_ERROR_SOURCE_LIB, CASS_ERROR_LIB_NO_TRACING_ID, 35, "No tracing ID") \
+  XX(CASS_ERROR_SOURCE_LIB, CASS_ERROR_LIB_REQUEST_CANCELLED, 36, "Request cancelled") \
+  XX(CASS_ERROR_SOURCE_LIB, CASS_ERROR_LIB_RESULT_MEMORY_LIMIT_EXCEEDED, 37, "Result memory limit exceeded") \
   XX(CASS_ERROR_SOURCE_SERVER, CASS_ERROR_SERVER_SERVER_ERROR, 0x0000, "Server error") \
   XX(CASS_ERROR_SOURCE_SERVER, CASS_ERROR_SERVER_PROTOCOL_ERROR, 0x000A, "Protocol error") \
   XX(CASS_ERROR_SOURCE_SERVER, CASS_ERROR_SERVER_BAD_CREDENTIALS, 0x0100, "Bad credentials") \
@@ -913,6 +1000,117 @@ typedef void(*CassHostListenerCallback)(CassHostListenerEvent event,
                                         const CassInet address,
                                         void* data);
 
+/**
+ * A callback used to obtain credentials for plain text authentication.
+ * It's invoked each time a new connection needs to authenticate, so it
+ * can provide fresh (e.g. short-lived) credentials.
+ *
+ * The callback is invoked on a driver's thread dedicated to blocking
+ * operations, so it may block (e.g. to fetch a token from an external service).
+ * It should set the credentials using cass_credentials_set(). If it doesn't,
+ * the connection fails to authenticate.
+ *
+ * @param[in] credentials The credentials to be filled. The pointer is only
+ * valid for the duration of the callback.
+ * @param[in] data
+ * @see cass_cluster_set_credentials_callback()
+ */
+typedef void(*CassCredentialsCallback)(CassCredentials* credentials,
+                                       void* data);
+
+typedef enum CassClusterEventType_ {
+  CASS_CLUSTER_EVENT_NEW_NODE,
+  CASS_CLUSTER_EVENT_REMOVED_NODE,
+  CASS_CLUSTER_EVENT_UP,
+  CASS_CLUSTER_EVENT_DOWN
+} CassClusterEventType;
+
+/**
+ * A callback used to notify about topology and status changes in the cluster.
+ *
+ * @param[in] event
+ * @param[in] address
+ * @param[in] timestamp_ms Time (milliseconds since UNIX epoch) at which
+ * the driver observed the change.
+ * @param[in] data
+ * @see cass_cluster_set_cluster_event_callback()
+ */
+typedef void(*CassClusterEventCallback)(CassClusterEventType event,
+                                        const CassInet address,
+                                        cass_int64_t timestamp_ms,
+                                        void* data);
+
+/**
+ * A callback invoked when a request is started.
+ *
+ * @param[in] request_id Identifier of the request, unique within the process.
+ * @param[in] data
+ * @see cass_cluster_set_request_tracker()
+ */
+typedef void(*CassRequestStartCallback)(cass_uint64_t request_id,
+                                        void* data);
+
+/**
+ * A callback invoked when an attempt (initial execution, retry or speculative
+ * execution) of a request is sent to a host.
+ *
+ * @param[in] request_id
+ * @param[in] attempt Zero-based number of the attempt.
+ * @param[in] host
+ * @param[in] data
+ * @see cass_cluster_set_request_tracker()
+ */
+typedef void(*CassRequestAttemptStartCallback)(cass_uint64_t request_id,
+                                               cass_uint32_t attempt,
+                                               const CassInet host,
+                                               void* data);
+
+/**
+ * A callback invoked when an attempt of a request completes.
+ *
+ * @param[in] request_id
+ * @param[in] attempt Zero-based number of the attempt.
+ * @param[in] host
+ * @param[in] error CASS_OK if the attempt succeeded.
+ * @param[in] latency_us Latency of the attempt in microseconds.
+ * @param[in] data
+ * @see cass_cluster_set_request_tracker()
+ */
+typedef void(*CassRequestAttemptEndCallback)(cass_uint64_t request_id,
+                                             cass_uint32_t attempt,
+                                             const CassInet host,
+                                             CassError error,
+                                             cass_uint64_t latency_us,
+                                             void* data);
+
+/**
+ * A callback invoked when a request completes.
+ *
+ * @param[in] request_id
+ * @param[in] error CASS_OK if the request succeeded.
+ * @param[in] latency_us Latency of the whole request in microseconds.
+ * @param[in] coordinator Host that served the request. If the request failed,
+ * this is the host of the last attempt. Address length is 0 if no attempt
+ * was made.
+ * @param[in] data
+ * @see cass_cluster_set_request_tracker()
+ */
+typedef void(*CassRequestEndCallback)(cass_uint64_t request_id,
+                                      CassError error,
+                                      cass_uint64_t latency_us,
+                                      const CassInet coordinator,
+                                      void* data);
+
+/**
+ * Request tracker callbacks. Any of the callbacks can be NULL.
+ */
+typedef struct CassRequestTrackerCallbacks_ {
+  CassRequestStartCallback on_request_start;
+  CassRequestAttemptStartCallback on_attempt_start;
+  CassRequestAttemptEndCallback on_attempt_end;
+  CassRequestEndCallback on_request_end;
+} CassRequestTrackerCallbacks;
+
 typedef enum CassCompressionType_ {
   CASS_COMPRESSION_LZ4,
   CASS_COMPRESSION_SNAPPY,
@@ -994,12 +1192,18 @@ cass_execution_profile_set_consistency(CassExecProfile* profile,
 /**
  * Sets the serial consistency level.
  *
+ * The serial consistency set on a statement (or a batch) takes precedence
+ * over the one set on its execution profile, which in turn takes precedence
+ * over the cluster-level serial consistency.
+ *
  * <b>Default:</b> Disabled (uses the default serial consistency)
  *
  * @public @memberof CassExecProfile
  *
  * @param[in] profile
- * @param[in] serial_consistency
+ * @param[in] serial_consistency CASS_CONSISTENCY_SERIAL or
+ * CASS_CONSISTENCY_LOCAL_SERIAL. CASS_CONSISTENCY_UNKNOWN disables the serial
+ * consistency for the profile, regardless of the cluster-level setting.
  * @return CASS_OK if successful, otherwise an error occurred.
  *
  * @see cass_statement_set_serial_consistency()
@@ -1008,6 +1212,25 @@ CASS_EXPORT CassError
 cass_execution_profile_set_serial_consistency(CassExecProfile* profile,
                                               CassConsistency serial_consistency);
 
+/**
+ * Sets the page size of the statements executed with the profile.
+ * The page size set on a statement takes precedence over the profile's one.
+ *
+ * <b>Default:</b> The page size of the cluster's default profile (see
+ * cass_cluster_set_paging_size())
+ *
+ * @public @memberof CassExecProfile
+ *
+ * @param[in] profile
+ * @param[in] page_size Non-positive value disables paging.
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_statement_set_paging_size()
+ */
+CASS_EXPORT CassError
+cass_execution_profile_set_paging_size(CassExecProfile* profile,
+                                       int page_size);
+
 /**
  * Configures the execution profile to use round-robin load balancing.
  *
@@ -1268,8 +1491,8 @@ cass_execution_profile_set_latency_aware_routing_settings(CassExecProfile* profi
  * Examples: "127.0.0.1" "127.0.0.1,127.0.0.2"
  *
  * <b>Note:</b> Execution profiles use the cluster-level load balancing policy
- * unless enabled. This setting is not applicable unless a load balancing policy
- * is enabled on the execution profile.
+ * unless enabled. In such case, the filtering is applied on top of the
+ * cluster-level policy (and its own filtering).
  *
  * @public @memberof CassExecProfile
  *
@@ -1319,8 +1542,8 @@ cass_execution_profile_set_whitelist_filtering_n(CassExecProfile* profile,
  * Examples: "127.0.0.1" "127.0.0.1,127.0.0.2"
  *
  * <b>Note:</b> Execution profiles use the cluster-level load balancing policy
- * unless enabled. This setting is not applicable unless a load balancing policy
- * is enabled on the execution profile.
+ * unless enabled. In such case, the filtering is applied on top of the
+ * cluster-level policy (and its own filtering).
  *
  * @public @memberof CassExecProfile
  *
@@ -1362,6 +1585,10 @@ cass_execution_profile_set_blacklist_filtering_n(CassExecProfile* profile,
  *
  * Examples: "dc1", "dc1,dc2"
  *
+ * <b>Note:</b> Execution profiles use the cluster-level load balancing policy
+ * unless enabled. In such case, the filtering is applied on top of the
+ * cluster-level policy (and its own filtering).
+ *
  * @public @memberof CassExecProfile
  *
  * @param[in] profile
@@ -1401,6 +1628,10 @@ cass_execution_profile_set_whitelist_dc_filtering_n(CassExecProfile* profile,
  *
  * Examples: "dc1", "dc1,dc2"
  *
+ * <b>Note:</b> Execution profiles use the cluster-level load balancing policy
+ * unless enabled. In such case, the filtering is applied on top of the
+ * cluster-level policy (and its own filtering).
+ *
  * @public @memberof CassExecProfile
  *
  * @param[in] profile
@@ -1493,6 +1724,140 @@ cass_execution_profile_set_constant_speculative_execution_policy(CassExecProfile
 CASS_EXPORT CassError
 cass_execution_profile_set_no_speculative_execution_policy(CassExecProfile* profile);
 
+/**
+ * Gets the consistency set on the execution profile.
+ *
+ * @public @memberof CassExecProfile
+ *
+ * @param[in] profile
+ * @return The consistency. CASS_CONSISTENCY_UNKNOWN if not set (the
+ * cluster-level consistency is used).
+ *
+ * @see cass_execution_profile_set_consistency()
+ */
+CASS_EXPORT CassConsistency
+cass_execution_profile_get_consistency(const CassExecProfile* profile);
+
+/**
+ * Gets the serial consistency set on the execution profile.
+ *
+ * @public @memberof CassExecProfile
+ *
+ * @param[in] profile
+ * @return The serial consistency. CASS_CONSISTENCY_UNKNOWN if not set or
+ * disabled on the profile.
+ *
+ * @see cass_execution_profile_set_serial_consistency()
+ */
+CASS_EXPORT CassConsistency
+cass_execution_profile_get_serial_consistency(const CassExecProfile* profile);
+
+/**
+ * Gets the request timeout set on the execution profile.
+ *
+ * @public @memberof CassExecProfile
+ *
+ * @param[in] profile
+ * @return The request timeout in milliseconds. CASS_UINT64_MAX if not set
+ * (the cluster-level request timeout is used).
+ *
+ * @see cass_execution_profile_set_request_timeout()
+ */
+CASS_EXPORT cass_uint64_t
+cass_execution_profile_get_request_timeout(const CassExecProfile* profile);
+
+/**
+ * Gets the page size set on the execution profile.
+ *
+ * @public @memberof CassExecProfile
+ *
+ * @param[in] profile
+ * @return The page size. -1 if paging is disabled.
+ *
+ * @see cass_execution_profile_set_paging_size()
+ */
+CASS_EXPORT int
+cass_execution_profile_get_paging_size(const CassExecProfile* profile);
+
+/**
+ * Gets the retry policy set on the execution profile.
+ *
+ * @public @memberof CassExecProfile
+ *
+ * @param[in] profile
+ * @return The retry policy (owned by the profile, do not free it).
+ * NULL if not set (the cluster-level retry policy is used).
+ *
+ * @see cass_execution_profile_set_retry_policy()
+ */
+CASS_EXPORT const CassRetryPolicy*
+cass_execution_profile_get_retry_policy(const CassExecProfile* profile);
+
+/**
+ * Gets whether token-aware routing is enabled on the execution profile.
+ *
+ * @public @memberof CassExecProfile
+ *
+ * @param[in] profile
+ * @return cass_true if enabled, otherwise cass_false.
+ *
+ * @see cass_execution_profile_set_token_aware_routing()
+ */
+CASS_EXPORT cass_bool_t
+cass_execution_profile_get_token_aware_routing(const CassExecProfile* profile);
+
+/**
+ * Gets whether latency-aware routing is enabled on the execution profile.
+ *
+ * @public @memberof CassExecProfile
+ *
+ * @param[in] profile
+ * @return cass_true if enabled, otherwise cass_false.
+ *
+ * @see cass_execution_profile_set_latency_aware_routing()
+ */
+CASS_EXPORT cass_bool_t
+cass_execution_profile_get_latency_aware_routing(const CassExecProfile* profile);
+
+/**
+ * Gets the local datacenter of the DC-aware or rack-aware load balancing
+ * policy set on the execution profile.
+ *
+ * @public @memberof CassExecProfile
+ *
+ * @param[in] profile
+ * @param[out] local_dc The local datacenter (owned by the profile).
+ * @param[out] local_dc_length
+ * @return CASS_OK if successful, CASS_ERROR_LIB_NULL_VALUE if the profile
+ * does not use DC-aware nor rack-aware load balancing.
+ *
+ * @see cass_execution_profile_set_load_balance_dc_aware()
+ * @see cass_execution_profile_set_load_balance_rack_aware()
+ */
+CASS_EXPORT CassError
+cass_execution_profile_get_load_balance_local_dc(const CassExecProfile* profile,
+                                                 const char** local_dc,
+                                                 size_t* local_dc_length);
+
+/**
+ * Gets the local rack of the rack-aware load balancing policy set on
+ * the execution profile.
+ *
+ * @public @memberof CassExecProfile
+ *
+ * @param[in] profile
+ * @param[out] local_rack The local rack (owned by the profile).
+ * @param[out] local_rack_length
+ * @return CASS_OK if successful, CASS_ERROR_LIB_NULL_VALUE if the profile
+ * does not use rack-aware load balancing.
+ *
+ * @see cass_execution_profile_set_load_balance_rack_aware()
+ */
+CASS_EXPORT CassError
+cass_execution_profile_get_load_balance_local_rack(const CassExecProfile* profile,
+                                                   const char** local_rack,
+                                                   size_t* local_rack_length);
+
 /***********************************************************************************
  *
  * Cluster
@@ -1511,6 +1876,94 @@ cass_execution_profile_set_no_speculative_execution_policy(CassExecProfile* prof
 CASS_EXPORT CassCluster*
 cass_cluster_new();
 
+/**
+ * Creates a new cluster configured from a configuration file. Settings
+ * can be overridden with environment variables.
+ *
+ * The file consists of "key = value" lines (values may be quoted). Lines
+ * starting with '#' are comments. Supported keys:
+ *
+ * <ul>
+ *   <li>contact_points, contact_points_srv, port</li>
+ *   <li>username, password</li>
+ *   <li>connect_timeout_ms, request_timeout_ms</li>
+ *   <li>consistency, serial_consistency (e.g. LOCAL_QUORUM)</li>
+ *   <li>local_dc (enables DC-aware load balancing)</li>
+ *   <li>ssl_trusted_cert, ssl_cert, ssl_private_key (paths to PEM files),
+ *   ssl_private_key_password, ssl_verify_flags (none, peer_cert,
+ *   peer_identity or peer_identity_dns, combined with '|')</li>
+ * </ul>
+ *
+ * Execution profiles are defined in "[profile.<name>]" sections. Supported
+ * profile keys are request_timeout_ms, consistency, serial_consistency and
+ * local_dc.
+ *
+ * Each of the keys above (outside of the profiles) can be overridden by the
+ * "CASS_<KEY>" environment variable, e.g. CASS_CONTACT_POINTS.
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] path Path to the configuration file. If NULL or empty, the
+ * cluster is configured from the environment variables only.
+ * @return Returns a cluster that must be freed, or NULL if the configuration
+ * is invalid (the reason is logged).
+ *
+ * @see cass_cluster_free()
+ */
+CASS_EXPORT CassCluster*
+cass_cluster_new_from_config(const char* path);
+
+/**
+ * Same as cass_cluster_new_from_config(), but with lengths for string
+ * parameters.
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] path
+ * @param[in] path_length
+ * @return same as cass_cluster_new_from_config()
+ *
+ * @see cass_cluster_new_from_config()
+ */
+CASS_EXPORT CassCluster*
+cass_cluster_new_from_config_n(const char* path,
+                               size_t path_length);
+
+/**
+ * Creates a new cluster configured from a ScyllaDB Cloud (serverless)
+ * connection profile - a YAML file defining the datacenters (with their SNI
+ * proxies and server names), authentication info and TLS material.
+ *
+ * Contact points must not be set on the returned cluster - the nodes defined
+ * in the connection profile are used instead.
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] path Path to the connection profile.
+ * @return Returns a cluster that must be freed, or NULL if the connection
+ * profile could not be loaded (the reason is logged).
+ *
+ * @see cass_cluster_free()
+ */
+CASS_EXPORT CassCluster*
+cass_cluster_new_from_cloud_config(const char* path);
+
+/**
+ * Same as cass_cluster_new_from_cloud_config(), but with lengths for string
+ * parameters.
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] path
+ * @param[in] path_length
+ * @return same as cass_cluster_new_from_cloud_config()
+ *
+ * @see cass_cluster_new_from_cloud_config()
+ */
+CASS_EXPORT CassCluster*
+cass_cluster_new_from_cloud_config_n(const char* path,
+                                     size_t path_length);
+
 /**
  * Frees a cluster instance.
  *
@@ -1521,6 +1974,36 @@ cass_cluster_new();
 CASS_EXPORT void
 cass_cluster_free(CassCluster* cluster);
 
+/**
+ * Validates the cluster configuration before connecting a session, checking
+ * for missing or conflicting settings, e.g.:
+ * <ul>
+ *   <li>no contact points provided</li>
+ *   <li>a host or a datacenter that is both whitelisted and blacklisted</li>
+ *   <li>a local datacenter that is blacklisted (or not whitelisted)</li>
+ * </ul>
+ * The load balancing settings of the execution profiles are validated too.
+ *
+ * <b>Note:</b> The validation is not exhaustive - e.g. the SSL context and
+ * the reachability of the nodes are not checked.
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[out] message Description of the first problem found, or an empty
+ * string if none was found. The memory is owned by the cluster and stays
+ * valid until the next validation or until the cluster is freed. Can be NULL.
+ * @param[out] message_length Can be NULL.
+ * @return CASS_OK if no problem was found, otherwise an error code
+ * describing the problem.
+ *
+ * @see cass_session_connect()
+ */
+CASS_EXPORT CassError
+cass_cluster_validate(CassCluster* cluster,
+                      const char** message,
+                      size_t* message_length);
+
 /**
  * Sets/Appends contact points. This *MUST* be set. The first call sets
  * the contact points and any subsequent calls appends additional contact
@@ -1529,6 +2012,11 @@ cass_cluster_free(CassCluster* cluster);
  *
  * Examples: "127.0.0.1" "127.0.0.1,127.0.0.2", "server1.domain.com"
  *
+ * A contact point can specify its own port as "host:port" (or
+ * "[address]:port" for IPv6 addresses), e.g. "127.0.0.1:9043,[::1]:9044".
+ * Contact points without the port use the one set by cass_cluster_set_port().
+ * Malformed contact points (e.g. with an invalid port) are ignored.
+ *
  * @public @memberof CassCluster
  *
  * @param[in] cluster
@@ -1561,29 +2049,101 @@ cass_cluster_set_contact_points_n(CassCluster* cluster,
                                   size_t contact_points_length);
 
 /**
- * Sets the port.
+ * Appends contact points, each with its own port. Useful for clusters
+ * whose nodes listen on non-default or mixed ports.
  *
- * <b>Default:</b> 9042
+ * The hosts are addresses or names, without the port. If any of the entries
+ * is invalid, no contact point is appended.
  *
  * @public @memberof CassCluster
  *
  * @param[in] cluster
- * @param[in] port
+ * @param[in] hosts An array of `count` null-terminated hosts.
+ * @param[in] ports An array of `count` ports, corresponding to the hosts.
+ * @param[in] count
  * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_cluster_set_contact_points()
  */
 CASS_EXPORT CassError
-cass_cluster_set_port(CassCluster* cluster,
-                      int port);
+cass_cluster_set_contact_points_with_ports(CassCluster* cluster,
+                                           const char** hosts,
+                                           const int* ports,
+                                           size_t count);
 
 /**
- * Sets the local address to bind when connecting to the cluster,
- * if desired.
+ * Sets the name of a DNS SRV record the contact points are discovered from,
+ * e.g. "_cql._tcp.scylla.default.svc.cluster.local". Useful in Kubernetes or
+ * Consul environments. The discovered targets (with their ports, ordered by
+ * priority) are used along with the contact points set by
+ * cass_cluster_set_contact_points().
+ *
+ * The record is queried when the session connects. If the lookup fails and
+ * no other contact points are set, connecting fails with
+ * CASS_ERROR_LIB_NO_HOSTS_AVAILABLE. Afterwards, the hostnames of the
+ * discovered targets are re-resolved like the other contact points, and the
+ * record is queried again (see
+ * cass_cluster_set_contact_points_reresolution_interval()). If it lists new
+ * targets, the session reconnects, contacting the new targets as well.
+ *
+ * <b>Default:</b> Disabled
  *
  * @public @memberof CassCluster
  *
  * @param[in] cluster
- * @param[in] name IP address to bind, or empty string for no binding.
- * Only numeric addresses are supported; no resolution is done.
+ * @param[in] name Name of the SRV record. An empty string disables the
+ * discovery.
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_cluster_set_contact_points()
+ * @see cass_cluster_set_resolve_timeout()
+ */
+CASS_EXPORT CassError
+cass_cluster_set_contact_points_srv(CassCluster* cluster,
+                                    const char* name);
+
+/**
+ * Same as cass_cluster_set_contact_points_srv(), but with lengths for string
+ * parameters.
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] name
+ * @param[in] name_length
+ * @return same as cass_cluster_set_contact_points_srv()
+ *
+ * @see cass_cluster_set_contact_points_srv()
+ */
+CASS_EXPORT CassError
+cass_cluster_set_contact_points_srv_n(CassCluster* cluster,
+                                      const char* name,
+                                      size_t name_length);
+
+/**
+ * Sets the port.
+ *
+ * <b>Default:</b> 9042
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] port
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_cluster_set_port(CassCluster* cluster,
+                      int port);
+
+/**
+ * Sets the local address to bind when connecting to the cluster,
+ * if desired.
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] name IP address to bind, or empty string for no binding.
+ * Only numeric addresses are supported; no resolution is done.
  * @return CASS_OK if successful, otherwise an error occurred.
  */
 CASS_EXPORT CassError
@@ -1631,9 +2191,37 @@ CassError cass_cluster_set_local_port_range(CassCluster* cluster,
                                             int lo,
                                             int hi);
 
+/**
+ * Enables/Disables connecting to the shard-aware port of ScyllaDB nodes
+ * (19042, or 19142 for SSL connections by default). Connecting to it allows
+ * the driver to choose the shard of the new connection, based on the
+ * client-side port number. When disabled, the connections are established
+ * to the regular CQL port, and the shards are assigned by the node.
+ *
+ * The shard-aware port numbers are advertised by the nodes, hence they are
+ * not configurable on the client side.
+ *
+ * <b>Default:</b> cass_true (enabled).
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] enabled
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_cluster_set_local_port_range()
+ */
+CASS_EXPORT CassError
+cass_cluster_set_shard_aware_port_enabled(CassCluster* cluster,
+                                          cass_bool_t enabled);
+
 /**
  * Sets the SSL context and enables SSL.
  *
+ * <b>Note:</b> If the TLS configuration can't be built from the SSL context
+ * (e.g. because of an invalid trusted certificate), the sessions fail to
+ * connect with CASS_ERROR_SSL_INVALID_CERT, instead of connecting without SSL.
+ *
  * @public @memberof CassCluster
  *
  * @param[in] cluster
@@ -1728,6 +2316,26 @@ CASS_EXPORT CassError
 cass_cluster_set_serial_consistency(CassCluster* cluster,
                                     CassConsistency consistency);
 
+/**
+ * Sets the page size of the statements executed with the default execution
+ * profile, and with the execution profiles that don't set their own one.
+ * The page size set on a statement takes precedence over this one.
+ *
+ * <b>Default:</b> -1 (Disabled)
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] page_size Non-positive value disables paging.
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_statement_set_paging_size()
+ * @see cass_execution_profile_set_paging_size()
+ */
+CASS_EXPORT CassError
+cass_cluster_set_paging_size(CassCluster* cluster,
+                             int page_size);
+
 /**
  * Sets the number of IO threads. This is the number of threads
  * that will handle query requests.
@@ -2132,6 +2740,118 @@ CASS_EXPORT void
 cass_cluster_set_resolve_timeout(CassCluster* cluster,
                                  unsigned timeout_ms);
 
+/**
+ * Sets the interval of re-resolving the contact points while all known
+ * hosts are unreachable. The hostnames of the contact points are resolved
+ * again, so the driver can reconnect to the cluster if its nodes were
+ * replaced (and the hostnames point at the new nodes).
+ *
+ * The driver also re-resolves the contact points on each periodic
+ * metadata refresh if all known hosts are unreachable; this setting
+ * makes it happen more often.
+ *
+ * If the contact points are discovered from a DNS SRV record, the record
+ * is queried again as well.
+ *
+ * <b>Default:</b> 0 (disabled)
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] interval_ms Interval in milliseconds. Use 0 to disable.
+ *
+ * @see cass_cluster_set_resolve_timeout()
+ */
+CASS_EXPORT void
+cass_cluster_set_contact_points_reresolution_interval(CassCluster* cluster,
+                                                      unsigned interval_ms);
+
+/**
+ * Sets the address family preference.
+ *
+ * The preference determines which address is chosen when a contact point
+ * resolves to both IPv4 and IPv6 addresses. Additionally, with
+ * CASS_ADDRESS_FAMILY_IPV4_ONLY or CASS_ADDRESS_FAMILY_IPV6_ONLY, the driver
+ * doesn't connect to the nodes whose addresses belong to the other family.
+ *
+ * <b>Note:</b> With CASS_ADDRESS_FAMILY_PREFER_IPV6 or
+ * CASS_ADDRESS_FAMILY_IPV6_ONLY, the hostnames of the contact points that
+ * resolve to both IPv4 and IPv6 addresses are resolved once, when the session
+ * connects, so they are not re-resolved later (see
+ * cass_cluster_set_contact_points_reresolution_interval()).
+ *
+ * <b>Default:</b> CASS_ADDRESS_FAMILY_PREFER_IPV4
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] address_family
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_cluster_set_address_family(CassCluster* cluster,
+                                CassAddressFamily address_family);
+
+/**
+ * Enables (or disables) the numeric widening compatibility mode for the
+ * values of the results returned by the sessions connected with this cluster.
+ * In this mode, the numeric value getters accept values of narrower numeric
+ * types as well, and convert them losslessly instead of returning
+ * CASS_ERROR_LIB_INVALID_VALUE_TYPE:
+ *
+ * <ul>
+ *   <li>cass_value_get_int16(): "tinyint"</li>
+ *   <li>cass_value_get_int32(): "tinyint", "smallint"</li>
+ *   <li>cass_value_get_int64(): "tinyint", "smallint", "int"</li>
+ *   <li>cass_value_get_float(): "tinyint", "smallint"</li>
+ *   <li>cass_value_get_double(): "tinyint", "smallint", "int", "float"</li>
+ * </ul>
+ *
+ * Conversions that may lose precision (e.g. "bigint" to double) are still
+ * rejected. The mode is meant for ports of code that reads columns of
+ * varying numeric types.
+ *
+ * <b>Default:</b> cass_false
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] enabled
+ */
+CASS_EXPORT void
+cass_cluster_set_numeric_widening(CassCluster* cluster,
+                                  cass_bool_t enabled);
+
+/**
+ * Sets the key used to sign the paging state tokens. The tokens returned by
+ * cass_result_paging_state_token() for the results of the sessions connected
+ * with this cluster are signed with HMAC-SHA256, and the statements with a
+ * paging state set by cass_statement_set_paging_state_token() fail to execute
+ * unless the token was signed with the same key. This allows to safely
+ * round-trip the tokens through untrusted clients (e.g. the clients of a web
+ * service).
+ *
+ * The signature covers the statement (the query string, or the id of the
+ * prepared statement) and its keyspace, so a token can't be used to resume
+ * a different statement.
+ *
+ * <b>Default:</b> NULL (tokens are not signed)
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] key The secret key. Use NULL to stop signing the tokens.
+ * @param[in] key_length
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_result_paging_state_token()
+ * @see cass_statement_set_paging_state_token()
+ */
+CASS_EXPORT CassError
+cass_cluster_set_paging_state_token_signing_key(CassCluster* cluster,
+                                                const char* key,
+                                                size_t key_length);
+
 /**
  * Sets the maximum time to wait for schema agreement after a schema change
  * is made (e.g. creating, altering, dropping a table/keyspace/view/index etc).
@@ -2237,6 +2957,76 @@ cass_cluster_set_credentials_n(CassCluster* cluster,
                                const char* password,
                                size_t password_length);
 
+/**
+ * Sets credentials for plain text authentication together with an
+ * authorization id. The user authenticates with the given username and password,
+ * but acts on behalf of the role named by the authorization id (e.g. a service
+ * account executing requests as another role). The server has to permit
+ * the user to act as that role.
+ *
+ * An empty authorization id is equivalent to cass_cluster_set_credentials().
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] username
+ * @param[in] password
+ * @param[in] authorization_id
+ */
+CASS_EXPORT void
+cass_cluster_set_credentials_with_authorization_id(CassCluster* cluster,
+                                                   const char* username,
+                                                   const char* password,
+                                                   const char* authorization_id);
+
+/**
+ * Same as cass_cluster_set_credentials_with_authorization_id(), but with lengths
+ * for string parameters.
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] username
+ * @param[in] username_length
+ * @param[in] password
+ * @param[in] password_length
+ * @param[in] authorization_id
+ * @param[in] authorization_id_length
+ *
+ * @see cass_cluster_set_credentials_with_authorization_id();
+ */
+CASS_EXPORT void
+cass_cluster_set_credentials_with_authorization_id_n(CassCluster* cluster,
+                                                     const char* username,
+                                                     size_t username_length,
+                                                     const char* password,
+                                                     size_t password_length,
+                                                     const char* authorization_id,
+                                                     size_t authorization_id_length);
+
+/**
+ * Sets a callback used to obtain credentials for plain text authentication
+ * each time a new connection is established. This allows rotating
+ * credentials (e.g. short-lived tokens or passwords) without recreating
+ * the session.
+ *
+ * The callback takes precedence over credentials set with
+ * cass_cluster_set_credentials().
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] callback NULL disables the callback.
+ * @param[in] data An opaque data object passed to the callback.
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_credentials_set()
+ */
+CASS_EXPORT CassError
+cass_cluster_set_credentials_callback(CassCluster* cluster,
+                                      CassCredentialsCallback callback,
+                                      void* data);
+
 /**
  * Configures the cluster to use round-robin load balancing.
  *
@@ -2659,6 +3449,101 @@ CASS_EXPORT void
 cass_cluster_set_connection_heartbeat_interval(CassCluster* cluster,
                                                unsigned interval_secs);
 
+/**
+ * Sets the amount of time between refreshes of the request latency histogram
+ * reported by cass_session_get_metrics(). When set, the reported latencies
+ * are those of the requests completed during the last complete refresh
+ * interval. Otherwise, the histogram accumulates the latencies of all
+ * requests completed since the session was connected.
+ *
+ * <b>Default:</b> 0 (the histogram is never refreshed)
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] refresh_interval Refresh interval in milliseconds. Use 0 to
+ * disable refreshing.
+ *
+ * @see cass_session_get_metrics()
+ */
+CASS_EXPORT void
+cass_cluster_set_histogram_refresh_interval(CassCluster* cluster,
+                                            unsigned refresh_interval);
+
+/**
+ * Sets the maximum number of requests executed concurrently by the session.
+ * Requests exceeding the limit wait on the client side until one of the
+ * executing requests completes. The waiting requests are admitted in the
+ * order of their priority (and in FIFO order within the same priority),
+ * so e.g. health checks are not starved behind bulk scans.
+ *
+ * The time spent waiting counts towards the request timeout.
+ *
+ * <b>Default:</b> 0 (unlimited)
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] max_concurrent_requests Use 0 to disable the limit.
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_statement_set_priority()
+ * @see cass_batch_set_priority()
+ */
+CASS_EXPORT CassError
+cass_cluster_set_max_concurrent_requests(CassCluster* cluster,
+                                         unsigned max_concurrent_requests);
+
+/**
+ * Sets the soft limit of the memory occupied by the buffered results, i.e.
+ * the total size of the result pages held by the results that have not been
+ * freed yet. Once the limit is reached, further SELECT statements (including
+ * fetches of the subsequent pages) fail with
+ * CASS_ERROR_LIB_RESULT_MEMORY_LIMIT_EXCEEDED until enough results are freed.
+ * Other requests (e.g. writes, schema changes, batches) do not buffer rows,
+ * so they are not rejected.
+ *
+ * The limit is soft: the page that crosses the limit is still delivered.
+ *
+ * <b>Default:</b> 0 (unlimited)
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] max_bytes Use 0 to disable the limit.
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_result_free()
+ */
+CASS_EXPORT CassError
+cass_cluster_set_max_result_memory(CassCluster* cluster,
+                                   cass_uint64_t max_bytes);
+
+/**
+ * Sets the maximum number of requests in flight to a single host (across all
+ * of its connections). Hosts that reached the limit are skipped by the query
+ * plans, so the requests are diverted to the other hosts (e.g. other
+ * replicas), and a single slow host can't absorb the whole concurrency of
+ * the session. If all hosts of the query plan reached the limit, the request
+ * fails immediately with CASS_ERROR_LIB_NO_HOSTS_AVAILABLE.
+ *
+ * Each attempt (including retries and speculative executions) counts as
+ * a separate request. The limit applies to all execution profiles.
+ *
+ * <b>Default:</b> 0 (unlimited)
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] max_in_flight_requests Use 0 to disable the limit.
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_cluster_set_max_concurrent_requests()
+ */
+CASS_EXPORT CassError
+cass_cluster_set_max_in_flight_requests_per_host(CassCluster* cluster,
+                                                 unsigned max_in_flight_requests);
+
 /**
  * Sets the amount of time a connection is allowed to be without a successful
  * heartbeat response before being terminated and scheduled for reconnection.
@@ -2674,6 +3559,32 @@ CASS_EXPORT void
 cass_cluster_set_connection_idle_timeout(CassCluster* cluster,
                                          unsigned timeout_secs);
 
+/**
+ * Sets the number of consecutive heartbeat intervals without a successful
+ * heartbeat response after which the connection is terminated and scheduled
+ * for reconnection. Once all connections to a host are terminated, the host
+ * is marked down. This detects dead connections without waiting for
+ * a request to fail on them.
+ *
+ * A heartbeat that fails with an error terminates the connection immediately.
+ *
+ * <b>Note:</b> If set, it takes precedence over the idle timeout
+ * (see cass_cluster_set_connection_idle_timeout()). It has no effect
+ * if heartbeat messages are disabled.
+ *
+ * <b>Default:</b> 0 (the idle timeout is used)
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] threshold Use 0 to fall back to the idle timeout.
+ *
+ * @see cass_cluster_set_connection_heartbeat_interval()
+ */
+CASS_EXPORT void
+cass_cluster_set_connection_heartbeat_failure_threshold(CassCluster* cluster,
+                                                        unsigned threshold);
+
 /**
  * Sets the retry policy used for all requests unless overridden by setting
  * a retry policy on a statement or a batch.
@@ -2717,6 +3628,30 @@ CASS_EXPORT void
 cass_cluster_set_use_schema(CassCluster* cluster,
                             cass_bool_t enabled);
 
+/**
+ * Sets the interval at which the driver refreshes the cluster metadata
+ * (topology and, if enabled, schema), regardless of the received
+ * schema/topology change events.
+ *
+ * <b>Note:</b> Schema change events are not debounced - each of them triggers
+ * a refresh. A longer interval reduces the amount of background metadata
+ * fetching for sessions that do not rely on up-to-date metadata.
+ *
+ * <b>Default:</b> 60000 milliseconds
+ *
+ * @public @memberof CassCluster
+ *
+ * @param[in] cluster
+ * @param[in] interval_ms Refresh interval in milliseconds. Must be greater
+ * than 0.
+ * @return CASS_OK if successful, otherwise an error occurred
+ *
+ * @see cass_cluster_set_use_schema()
+ */
+CASS_EXPORT CassError
+cass_cluster_set_metadata_refresh_interval(CassCluster* cluster,
+                                           unsigned interval_ms);
+
 /**
  * Enable/Disable retrieving hostnames for IP addresses using reverse IP lookup.
  *
@@ -2740,6 +3675,9 @@ cass_cluster_set_use_hostname_resolution(CassCluster* cluster,
 /**
  * Enable/Disable the randomization of the contact points list.
  *
+ * The targets discovered from a DNS SRV record are not randomized - they
+ * follow the other contact points, ordered by priority.
+ *
  * <b>Default:</b> cass_true (enabled).
  *
  * <b>Important:</b> This setting should only be disabled for debugging or
@@ -2908,8 +3846,16 @@ cass_cluster_set_no_compact(CassCluster* cluster,
 /**
  * Sets a callback for handling host state changes in the cluster.
  *
- * <b>Note:</b> The callback is invoked only when state changes in the cluster
- * are applicable to the configured load balancing policy(s).
+ * <b>Note:</b> The callback is invoked for every node known to the driver,
+ * including the nodes ignored by the configured load balancing policy(s).
+ * Nodes known at the time the session connects are not reported.
+ * The changes are detected by periodically inspecting the driver's view of
+ * the cluster, so they are reported with a delay of up to a second.
+ *
+ * The callback is invoked on a dedicated thread of the session, outside of
+ * the driver's I/O threads, so it may call the blocking driver functions
+ * (e.g. cass_session_get_metrics()). Passing a NULL callback disables the
+ * notifications.
  *
  * @public @memberof CassCluster
  *
@@ -2917,6 +3863,8 @@ cass_cluster_set_no_compact(CassCluster* cluster,
  * @param[in] callback
  * @param[in] data
  * @return CASS_OK if successful, otherwise and error occurred
+ *
+ * @see cass_cluster_set_cluster_event_callback()
  */
 CASS_EXPORT CassError
 cass_cluster_set_host_listener_callback(CassCluster* cluster,
@@ -2924,28 +3872,86 @@ cass_cluster_set_host_listener_callback(CassCluster* cluster,
                                         void* data);
 
 /**
- * Sets the secure connection bundle path for processing DBaaS credentials.
+ * Sets a callback for handling topology (NEW_NODE/REMOVED_NODE) and
+ * status (UP/DOWN) changes in the cluster.
  *
- * This will pre-configure a cluster using the credentials format provided by
- * the DBaaS cloud provider.
+ * This callback is invoked for every node known to the driver, regardless
+ * of the configured load balancing policy(s). Nodes known at the time the
+ * session connects are not reported. Unlike the host listener callback,
+ * it is also given the time at which the change was observed.
+ *
+ * The callback is invoked on a dedicated thread of the session, outside of
+ * the driver's I/O threads, so it may call the blocking driver functions
+ * (e.g. cass_session_get_metrics()). Passing a NULL callback disables the
+ * notifications.
+ *
+ * @public @memberof CassCluster
  *
  * @param[in] cluster
- * @param[in] path Absolute path to DBaaS credentials file.
- * @return CASS_OK if successful, otherwise error occured.
+ * @param[in] callback
+ * @param[in] data
+ * @return CASS_OK if successful, otherwise an error occurred
  */
 CASS_EXPORT CassError
-cass_cluster_set_cloud_secure_connection_bundle(CassCluster* cluster,
-                                                const char* path);
+cass_cluster_set_cluster_event_callback(CassCluster* cluster,
+                                        CassClusterEventCallback callback,
+                                        void* data);
 
 /**
- * Same as cass_cluster_set_cloud_secure_connection_bundle(), but with lengths
- * for string parameters.
+ * Sets the callbacks observing the lifecycle of every request (statement
+ * and batch execution) sent by the session: start of the request, start
+ * and completion of each attempt, and completion of the request with its
+ * latency, error and coordinator.
  *
- * @see cass_cluster_set_cloud_secure_connection_bundle()
+ * The callbacks are invoked on the driver's threads and should not block.
+ * Passing NULL callbacks disables the tracking.
+ *
+ * <b>Note:</b> The completion callbacks are not invoked for requests aborted
+ * because of the per-statement (or per-batch) request timeout.
+ *
+ * @public @memberof CassCluster
  *
  * @param[in] cluster
- * @param[in] path Absolute path to DBaaS credentials file.
- * @param[in] path_length Length of path variable.
+ * @param[in] callbacks
+ * @param[in] data
+ * @return CASS_OK if successful, otherwise an error occurred
+ */
+CASS_EXPORT CassError
+cass_cluster_set_request_tracker(CassCluster* cluster,
+                                 const CassRequestTrackerCallbacks* callbacks,
+                                 void* data);
+
+/**
+ * Sets the secure connection bundle path for processing DBaaS credentials.
+ *
+ * This will pre-configure a cluster using the credentials format provided by
+ * the DBaaS cloud provider.
+ *
+ * <b>Note:</b> The bundle is a ScyllaDB Cloud (serverless) connection profile
+ * - a YAML file defining the datacenters (with their SNI proxies and server
+ * names), authentication info and TLS material, as accepted by
+ * cass_cluster_new_from_cloud_config(). The TLS setup and the nodes to connect
+ * to are taken from it, so contact points must not be set on the cluster
+ * (CASS_ERROR_LIB_BAD_PARAMS is returned if they are). The other settings
+ * applied to the cluster before this call are kept.
+ *
+ * @param[in] cluster
+ * @param[in] path Absolute path to DBaaS credentials file.
+ * @return CASS_OK if successful, otherwise error occured.
+ */
+CASS_EXPORT CassError
+cass_cluster_set_cloud_secure_connection_bundle(CassCluster* cluster,
+                                                const char* path);
+
+/**
+ * Same as cass_cluster_set_cloud_secure_connection_bundle(), but with lengths
+ * for string parameters.
+ *
+ * @see cass_cluster_set_cloud_secure_connection_bundle()
+ *
+ * @param[in] cluster
+ * @param[in] path Absolute path to DBaaS credentials file.
+ * @param[in] path_length Length of path variable.
  * @return CASS_OK if successful, otherwise error occured.
  */
 CASS_EXPORT CassError
@@ -3074,6 +4080,9 @@ cass_cluster_set_client_id(CassCluster* cluster, CassUuid client_id);
  *
  * <b>Default:</b> 300 seconds.
  *
+ * <b>Note:</b> Monitor reporting (Insights) is supported only by DataStax
+ * Enterprise, so no events are sent - the setting has no effect.
+ *
  * @public @memberof CassCluster
  *
  * @param[in] cluster
@@ -3088,6 +4097,9 @@ cass_cluster_set_monitor_reporting_interval(CassCluster* cluster,
  * <b>Default:</b> no compression.
  * If it is not supported by database server Session will fall back to no compression.
  *
+ * <b>Note:</b> Unlike some drivers, there is no ordered preference list - the
+ * fallback is always no compression, not another algorithm.
+ *
  * @public @memberof CassCluster
  *
  * @param[in] cluster
@@ -3097,6 +4109,52 @@ CASS_EXPORT void
 cass_cluster_set_compression(CassCluster* cluster,
                              CassCompressionType compression_type);
 
+/***********************************************************************************
+ *
+ * Credentials
+ *
+ ***********************************************************************************/
+
+/**
+ * Sets the username and password for plain text authentication.
+ * Intended to be called from a credentials callback.
+ *
+ * @public @memberof CassCredentials
+ *
+ * @param[in] credentials
+ * @param[in] username
+ * @param[in] password
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_cluster_set_credentials_callback()
+ */
+CASS_EXPORT CassError
+cass_credentials_set(CassCredentials* credentials,
+                     const char* username,
+                     const char* password);
+
+/**
+ * Same as cass_credentials_set(), but with lengths for string
+ * parameters.
+ *
+ * @public @memberof CassCredentials
+ *
+ * @param[in] credentials
+ * @param[in] username
+ * @param[in] username_length
+ * @param[in] password
+ * @param[in] password_length
+ * @return same as cass_credentials_set()
+ *
+ * @see cass_credentials_set()
+ */
+CASS_EXPORT CassError
+cass_credentials_set_n(CassCredentials* credentials,
+                       const char* username,
+                       size_t username_length,
+                       const char* password,
+                       size_t password_length);
+
 /***********************************************************************************
  *
  * Session
@@ -3196,6 +4254,27 @@ cass_session_connect_keyspace_n(CassSession* session,
 CASS_EXPORT CassFuture*
 cass_session_close(CassSession* session);
 
+/**
+ * Waits until all nodes reachable by the session agree on the schema version.
+ * Useful after executing DDL statements, before using the altered schema.
+ *
+ * The nodes are polled every schema agreement interval, for at most the
+ * maximum schema wait time configured on the cluster.
+ *
+ * @public @memberof CassSession
+ *
+ * @param[in] session
+ * @return A future that must be freed. It resolves with CASS_OK once
+ * the schema versions agree, with CASS_ERROR_LIB_REQUEST_TIMED_OUT
+ * if they do not converge in time, or with CASS_ERROR_LIB_NO_HOSTS_AVAILABLE
+ * if the nodes could not be reached.
+ *
+ * @see cass_cluster_set_max_schema_wait_time()
+ * @see cass_cluster_set_schema_agreement_interval()
+ */
+CASS_EXPORT CassFuture*
+cass_session_check_schema_agreement(CassSession* session);
+
 /**
  * Create a prepared statement.
  *
@@ -3300,6 +4379,9 @@ cass_session_get_schema_meta(const CassSession* session);
 /**
  * Gets a copy of this session's performance/diagnostic metrics.
  *
+ * Request latencies are recorded for successfully completed requests
+ * (statements and batches), with a relative error of less than 1%.
+ *
  * @public @memberof CassSession
  *
  * @param[in] session
@@ -3322,16 +4404,170 @@ cass_session_get_speculative_execution_metrics(const CassSession* session,
                                                CassSpeculativeExecutionMetrics* output);
 
 /**
- * Get the client id.
+ * Gets a copy of this session's request queue and backpressure metrics.
+ *
+ * @public @memberof CassSession
+ *
+ * @param[in] session
+ * @param[out] output
+ */
+CASS_EXPORT void
+cass_session_get_queue_metrics(const CassSession* session,
+                               CassQueueMetrics* output);
+
+/**
+ * Gets a copy of the metrics of the hosts this session sent requests to,
+ * ordered by the host address. The latency histograms are refreshed
+ * the same way as the session's histogram.
+ *
+ * Call with NULL output to get the number of hosts.
+ *
+ * @public @memberof CassSession
+ *
+ * @param[in] session
+ * @param[out] output Array of at least output_count elements. May be NULL.
+ * @param[in] output_count
+ * @return The number of hosts. If greater than output_count, only the first
+ * output_count hosts are copied.
+ *
+ * @see cass_cluster_set_histogram_refresh_interval()
+ */
+CASS_EXPORT size_t
+cass_session_get_host_metrics(const CassSession* session,
+                              CassHostMetrics* output,
+                              size_t output_count);
+
+/**
+ * Gets a copy of the metrics of a single host.
+ *
+ * @public @memberof CassSession
+ *
+ * @param[in] session
+ * @param[in] address
+ * @param[out] output
+ * @return CASS_OK if successful, CASS_ERROR_LIB_HOST_RESOLUTION if
+ * the session has not sent any request to the host, otherwise
+ * an error occurred.
+ */
+CASS_EXPORT CassError
+cass_session_get_host_metrics_by_address(const CassSession* session,
+                                         CassInet address,
+                                         CassHostMetrics* output);
+
+/**
+ * Same as cass_session_get_host_metrics_by_address(), but using the
+ * `CassNode` type, e.g. the coordinator of a request obtained with
+ * cass_future_coordinator().
+ *
+ * @public @memberof CassSession
+ *
+ * @param[in] session
+ * @param[in] node
+ * @param[out] output
+ * @return same as cass_session_get_host_metrics_by_address()
+ *
+ * @see cass_future_coordinator()
+ */
+CASS_EXPORT CassError
+cass_session_get_host_metrics_by_node(const CassSession* session,
+                                      const CassNode* node,
+                                      CassHostMetrics* output);
+
+/**
+ * Renders the session's metrics (including the per-host metrics)
+ * in Prometheus text exposition format.
+ *
+ * The output is null-terminated and truncated to fit the buffer,
+ * the same way as with snprintf(). Call with NULL buffer to get
+ * the required size.
+ *
+ * @public @memberof CassSession
+ *
+ * @param[in] session
+ * @param[out] buffer May be NULL.
+ * @param[in] buffer_size
+ * @return The length of the rendered metrics, excluding the null terminator.
+ * If not less than buffer_size, the output was truncated. Returns 0 if the
+ * session is not connected.
+ *
+ * @see cass_session_get_metrics()
+ * @see cass_session_get_host_metrics()
+ */
+CASS_EXPORT size_t
+cass_metrics_export_prometheus(const CassSession* session,
+                               char* buffer,
+                               size_t buffer_size);
+
+/**
+ * Get the client id - the one set with cass_cluster_set_client_id(),
+ * or the random UUID generated upon connection otherwise. The client id
+ * is sent to the cluster to identify the connections of this client.
  *
  * @public @memberof CassSession
  *
  * @param[in] session
- * @return Client id.
+ * @return Client id. Nil UUID if the session is not connected.
+ *
+ * @see cass_cluster_set_client_id()
  */
 CASS_EXPORT CassUuid
 cass_session_get_client_id(CassSession* session);
 
+/**
+ * Gets the version of the native protocol used by the connected session.
+ *
+ * The same protocol version is used for all hosts, so it can be used to
+ * branch on the available protocol features at runtime.
+ *
+ * <b>Note:</b> Only CASS_PROTOCOL_VERSION_V4 is currently supported, so it
+ * is always returned for a connected session. The protocol version of
+ * particular hosts is not available.
+ *
+ * @public @memberof CassSession
+ *
+ * @param[in] session
+ * @return The protocol version (see CassProtocolVersion), or 0 if the session
+ * is not connected.
+ *
+ * @see cass_cluster_set_protocol_version()
+ */
+CASS_EXPORT int
+cass_session_get_protocol_version(const CassSession* session);
+
+/**
+ * Gets a handle to the rust-driver session underlying the connected session.
+ *
+ * The handle allows the Rust parts of mixed C/Rust applications to use the
+ * rust-driver features that are not exposed by this API, sharing the
+ * connection pool with the session. On the Rust side, the pointer is
+ * converted to the rust-driver session with
+ * `scylla_cpp_driver::session::CassRustSession::from_raw()` and
+ * `CassRustSession::session()`.
+ *
+ * The handle keeps the underlying session alive, even if the session is
+ * closed or freed.
+ *
+ * @public @memberof CassSession
+ *
+ * @param[in] session
+ * @return Returns a handle that must be freed, or NULL if the session is not
+ * connected.
+ *
+ * @see cass_rust_session_free()
+ */
+CASS_EXPORT CassRustSession*
+cass_session_get_rust_session(const CassSession* session);
+
+/**
+ * Frees a handle to the rust-driver session.
+ *
+ * @public @memberof CassRustSession
+ *
+ * @param[in] rust_session
+ */
+CASS_EXPORT void
+cass_rust_session_free(CassRustSession* rust_session);
+
 /***********************************************************************************
  *
  * Schema Metadata
@@ -3462,6 +4698,45 @@ cass_keyspace_meta_table_by_name_n(const CassKeyspaceMeta* keyspace_meta,
                                    const char* table,
                                    size_t table_length);
 
+/**
+ * Gets the metadata of the CDC log table of the provided table.
+ *
+ * <b>Note:</b> This is a ScyllaDB specific feature. The CDC log table's
+ * name can be retrieved with cass_table_meta_name(), so CDC consumers
+ * do not depend on ScyllaDB's naming convention of the log tables.
+ *
+ * @public @memberof CassKeyspaceMeta
+ *
+ * @param[in] keyspace_meta
+ * @param[in] table The name of the base table.
+ *
+ * @return The metadata for the CDC log table. NULL if the table does not
+ * exist, or CDC is not enabled for it.
+ *
+ * @see cass_table_meta_is_cdc_enabled()
+ */
+CASS_EXPORT const CassTableMeta*
+cass_keyspace_meta_cdc_log_table_by_name(const CassKeyspaceMeta* keyspace_meta,
+                                         const char* table);
+
+/**
+ * Same as cass_keyspace_meta_cdc_log_table_by_name(), but with lengths for
+ * string parameters.
+ *
+ * @public @memberof CassKeyspaceMeta
+ *
+ * @param[in] keyspace_meta
+ * @param[in] table
+ * @param[in] table_length
+ * @return same as cass_keyspace_meta_cdc_log_table_by_name()
+ *
+ * @see cass_keyspace_meta_cdc_log_table_by_name()
+ */
+CASS_EXPORT const CassTableMeta*
+cass_keyspace_meta_cdc_log_table_by_name_n(const CassKeyspaceMeta* keyspace_meta,
+                                           const char* table,
+                                           size_t table_length);
+
 /**
  * Gets the materialized view metadata for the provided view name.
  *
@@ -3666,6 +4941,36 @@ cass_table_meta_name(const CassTableMeta* table_meta,
                      const char** name,
                      size_t* name_length);
 
+/**
+ * Determines if CDC (Change Data Capture) is enabled for the table.
+ *
+ * <b>Note:</b> This is a ScyllaDB specific feature. CDC is considered enabled
+ * when the table's CDC log table exists.
+ *
+ * @public @memberof CassTableMeta
+ *
+ * @param[in] table_meta
+ * @return cass_true if CDC is enabled for the table, otherwise cass_false.
+ *
+ * @see cass_keyspace_meta_cdc_log_table_by_name()
+ */
+CASS_EXPORT cass_bool_t
+cass_table_meta_is_cdc_enabled(const CassTableMeta* table_meta);
+
+/**
+ * Determines if the table is a CDC log table, i.e. the table that
+ * ScyllaDB maintains for a CDC-enabled table to store its changes.
+ *
+ * <b>Note:</b> This is a ScyllaDB specific feature.
+ *
+ * @public @memberof CassTableMeta
+ *
+ * @param[in] table_meta
+ * @return cass_true if the table is a CDC log table, otherwise cass_false.
+ */
+CASS_EXPORT cass_bool_t
+cass_table_meta_is_cdc_log(const CassTableMeta* table_meta);
+
 /**
  * Determine if the table is a virtual table.
  *
@@ -4999,6 +6304,24 @@ cass_authenticator_set_error_n(CassAuthenticator* auth,
 CASS_EXPORT void
 cass_future_free(CassFuture* future);
 
+/**
+ * Cancels the operation associated with the future. The in-flight request
+ * (including fetching of subsequent pages, if any) is aborted, and the future
+ * is resolved with CASS_ERROR_LIB_REQUEST_CANCELLED. The callback set on the
+ * future (if any) is invoked.
+ *
+ * <b>Note:</b> A request that has already been sent to the server may still be
+ * executed by the server; the driver just stops waiting for the response.
+ *
+ * @public @memberof CassFuture
+ *
+ * @param[in] future
+ * @return CASS_OK if the future has been cancelled. CASS_ERROR_LIB_INVALID_STATE
+ * if the future had already been resolved.
+ */
+CASS_EXPORT CassError
+cass_future_cancel(CassFuture* future);
+
 /**
  * Sets a callback that is called when a future is set
  *
@@ -5196,6 +6519,25 @@ cass_future_custom_payload_item(CassFuture* future,
 CASS_EXPORT const CassNode*
 cass_future_coordinator(CassFuture* future);
 
+/**
+ * Gets the execution info of the request. Unlike cass_result_execution_info(),
+ * it is available for both successful and failed requests.
+ *
+ * <b>Note:</b> This function will block if the future is not set.
+ *
+ * @public @memberof CassFuture
+ *
+ * @param[in] future
+ * @return The execution info of the request. NULL if the future is not
+ * a result of executing a statement or a batch, or the request failed
+ * before it was sent (e.g. the session is not connected). The lifetime of
+ * the returned object is bound to the future.
+ *
+ * @see cass_result_execution_info()
+ */
+CASS_EXPORT const CassExecutionInfo*
+cass_future_execution_info(CassFuture* future);
+
 /***********************************************************************************
  *
  * Statement
@@ -5354,7 +6696,8 @@ cass_statement_set_serial_consistency(CassStatement* statement,
  *
  * @cassandra{2.0+}
  *
- * <b>Default:</b> -1 (Disabled)
+ * <b>Default:</b> -1 (Disabled, unless the statement's execution profile
+ * sets the page size)
  *
  * @public @memberof CassStatement
  *
@@ -5390,16 +6733,20 @@ cass_statement_set_paging_state(CassStatement* statement,
  *
  * <b>Warning:</b> The paging state should not be exposed to or come from
  * untrusted environments. The paging state could be spoofed and potentially
- * used to gain access to other data.
+ * used to gain access to other data, unless the tokens are signed
+ * (see cass_cluster_set_paging_state_token_signing_key()).
  *
  * @public @memberof CassStatement
  *
  * @param[in] statement
  * @param[in] paging_state
  * @param[in] paging_state_size
- * @return CASS_OK if successful, otherwise an error occurred.
+ * @return CASS_OK if successful, otherwise an error occurred. If the session
+ * signs the tokens, and the token's signature is invalid for the statement,
+ * the execution of the statement fails with CASS_ERROR_LIB_INVALID_DATA.
  *
  * @see cass_result_paging_state_token()
+ * @see cass_cluster_set_paging_state_token_signing_key()
  */
 CASS_EXPORT CassError
 cass_statement_set_paging_state_token(CassStatement* statement,
@@ -5439,6 +6786,54 @@ CASS_EXPORT CassError
 cass_statement_set_request_timeout(CassStatement* statement,
                                    cass_uint64_t timeout_ms);
 
+/**
+ * Sets the server-side timeout of the statement (ScyllaDB's USING TIMEOUT).
+ * Unlike the request timeout, which only makes the client stop waiting for
+ * the response, the server aborts the query once the timeout is exceeded.
+ *
+ * The clause is appended to the query string, so only (unprepared) SELECT
+ * statements are supported. Other statements, and prepared statements,
+ * must include the USING TIMEOUT clause in the query string, e.g.
+ * "UPDATE ks.t USING TIMEOUT 500ms SET ...". The server-side timeout is
+ * kept when the statement is prepared with cass_session_prepare_from_existing().
+ * It is ignored if the statement is added to a batch - the statements of
+ * a batch must include the USING TIMEOUT clause in their query strings.
+ *
+ * <b>Note:</b> Only supported by ScyllaDB.
+ *
+ * <b>Default:</b> Disabled (the server uses its configured timeout)
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @param[in] timeout_ms Server-side timeout in milliseconds. Use 0 to disable.
+ * @return CASS_OK if successful, CASS_ERROR_LIB_INVALID_STATEMENT_TYPE if the
+ * statement is not an unprepared SELECT statement, otherwise an error occurred.
+ *
+ * @see cass_statement_set_request_timeout()
+ */
+CASS_EXPORT CassError
+cass_statement_set_server_timeout(CassStatement* statement,
+                                  cass_uint64_t timeout_ms);
+
+/**
+ * Sets the statement's priority class. It's taken into account when the
+ * number of concurrent requests of the session is limited.
+ *
+ * <b>Default:</b> CASS_REQUEST_PRIORITY_NORMAL
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @param[in] priority
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_cluster_set_max_concurrent_requests()
+ */
+CASS_EXPORT CassError
+cass_statement_set_priority(CassStatement* statement,
+                            CassRequestPriority priority);
+
 /**
  * Sets whether the statement is idempotent. Idempotent statements are able to be
  * automatically retried after timeouts/errors and can be speculatively executed.
@@ -5484,6 +6879,55 @@ CASS_EXPORT CassError
 cass_statement_set_custom_payload(CassStatement* statement,
                                   const CassCustomPayload* payload);
 
+/**
+ * Sets the distributed tracing context of the application's span that
+ * the statement is executed within. Both W3C <b>traceparent</b>
+ * (e.g. "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01") and
+ * B3 single header (e.g. "80f198ee56343ba864fe8b2a57d3eff7-e457b5a2e4d86bd1-1")
+ * formats are accepted.
+ *
+ * For each execution of the statement, the driver records a client span
+ * (named "cql_request") that is a child of the provided context. The span
+ * carries the W3C traceparent of the child span, and encloses the driver's
+ * logs emitted during the request, including an event per request attempt.
+ * The span is tagged with the request id reported to the request tracker
+ * (see cass_cluster_set_request_tracker()).
+ *
+ * <b>Note:</b> The context is not propagated to the server, because the
+ * underlying driver does not support sending custom payloads. The request
+ * tracker callbacks are not passed the context either - use the request id
+ * to correlate them with the span.
+ *
+ * <b>Note:</b> NULL or empty string will clear the trace context from statement
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @param[in] header
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_statement_set_trace_context(CassStatement* statement,
+                                 const char* header);
+
+/**
+ * Same as cass_statement_set_trace_context(), but with lengths for string
+ * parameters.
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @param[in] header
+ * @param[in] header_length
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_statement_set_trace_context()
+ */
+CASS_EXPORT CassError
+cass_statement_set_trace_context_n(CassStatement* statement,
+                                   const char* header,
+                                   size_t header_length);
+
 /**
  * Sets the execution profile to execute the statement with.
  *
@@ -5520,7 +6964,95 @@ cass_statement_set_execution_profile_n(CassStatement* statement,
                                        size_t name_length);
 
 /**
- * Sets whether the statement should use tracing.
+ * Gets the name of the execution profile set on the statement.
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @param[out] name The name of the execution profile. The string is bound
+ * to the lifetime of the statement, and is invalidated once the execution
+ * profile of the statement is changed.
+ * @param[out] name_length
+ * @return CASS_OK if successful, CASS_ERROR_LIB_NULL_VALUE if no execution
+ * profile is set, otherwise an error occurred.
+ *
+ * @see cass_statement_set_execution_profile()
+ */
+CASS_EXPORT CassError
+cass_statement_get_execution_profile(const CassStatement* statement,
+                                     const char** name,
+                                     size_t* name_length);
+
+/**
+ * Gets the consistency set on the statement.
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @return The consistency. CASS_CONSISTENCY_UNKNOWN if not set (the
+ * consistency of the execution profile is used).
+ *
+ * @see cass_statement_set_consistency()
+ */
+CASS_EXPORT CassConsistency
+cass_statement_get_consistency(const CassStatement* statement);
+
+/**
+ * Gets the serial consistency set on the statement.
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @return The serial consistency. CASS_CONSISTENCY_UNKNOWN if not set (the
+ * serial consistency of the execution profile is used).
+ *
+ * @see cass_statement_set_serial_consistency()
+ */
+CASS_EXPORT CassConsistency
+cass_statement_get_serial_consistency(const CassStatement* statement);
+
+/**
+ * Gets the page size set on the statement.
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @return The page size. -1 if paging is disabled.
+ *
+ * @see cass_statement_set_paging_size()
+ */
+CASS_EXPORT int
+cass_statement_get_paging_size(const CassStatement* statement);
+
+/**
+ * Gets the request timeout set on the statement.
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @return The request timeout in milliseconds. CASS_UINT64_MAX if not set
+ * (the cluster-level request timeout is used).
+ *
+ * @see cass_statement_set_request_timeout()
+ */
+CASS_EXPORT cass_uint64_t
+cass_statement_get_request_timeout(const CassStatement* statement);
+
+/**
+ * Gets whether the statement is idempotent.
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @return cass_true if the statement is idempotent, otherwise cass_false.
+ *
+ * @see cass_statement_set_is_idempotent()
+ */
+CASS_EXPORT cass_bool_t
+cass_statement_get_is_idempotent(const CassStatement* statement);
+
+/**
+ * Sets whether the statement should use tracing.
  *
  * @cassandra{2.2+}
  *
@@ -6320,6 +7852,84 @@ cass_statement_bind_custom_by_name_n(CassStatement* statement,
                                      const cass_byte_t* value,
                                      size_t value_size);
 
+/**
+ * Binds a value of the provided data type, which is already serialized,
+ * to a query or bound statement at the specified index.
+ *
+ * <b>Note:</b> The value is not validated. It must already be in the CQL
+ * native protocol format of the provided data type (without the leading
+ * length), e.g. a serialized list, map, set, tuple or user defined type.
+ * This allows bulk loading tools that already have the serialized data to
+ * skip building the value element by element.
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @param[in] index
+ * @param[in] data_type
+ * @param[in] value The value is copied into the statement object; the
+ * memory pointed to by this parameter can be freed after this call.
+ * @param[in] value_size
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_statement_bind_serialized(CassStatement* statement,
+                               size_t index,
+                               const CassDataType* data_type,
+                               const cass_byte_t* value,
+                               size_t value_size);
+
+/**
+ * Binds a value of the provided data type, which is already serialized,
+ * to all the values with the specified name.
+ *
+ * <b>Note:</b> The value is not validated. It must already be in the CQL
+ * native protocol format of the provided data type (without the leading
+ * length), e.g. a serialized list, map, set, tuple or user defined type.
+ * This allows bulk loading tools that already have the serialized data to
+ * skip building the value element by element.
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @param[in] name
+ * @param[in] data_type
+ * @param[in] value The value is copied into the statement object; the
+ * memory pointed to by this parameter can be freed after this call.
+ * @param[in] value_size
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_statement_bind_serialized_by_name(CassStatement* statement,
+                                       const char* name,
+                                       const CassDataType* data_type,
+                                       const cass_byte_t* value,
+                                       size_t value_size);
+
+/**
+ * Same as cass_statement_bind_serialized_by_name(), but with lengths for string
+ * parameters.
+ *
+ * @public @memberof CassStatement
+ *
+ * @param[in] statement
+ * @param[in] name
+ * @param[in] name_length
+ * @param[in] data_type
+ * @param[in] value
+ * @param[in] value_size
+ * @return same as cass_statement_bind_serialized_by_name()
+ *
+ * @see cass_statement_bind_serialized_by_name()
+ */
+CASS_EXPORT CassError
+cass_statement_bind_serialized_by_name_n(CassStatement* statement,
+                                         const char* name,
+                                         size_t name_length,
+                                         const CassDataType* data_type,
+                                         const cass_byte_t* value,
+                                         size_t value_size);
+
 /**
  * Binds a "uuid" or "timeuuid" to a query or bound statement at the specified index.
  *
@@ -6759,6 +8369,60 @@ cass_prepared_free(const CassPrepared* prepared);
 CASS_EXPORT CassStatement*
 cass_prepared_bind(const CassPrepared* prepared);
 
+/**
+ * Gets the query string of the prepared statement.
+ *
+ * @public @memberof CassPrepared
+ *
+ * @param[in] prepared
+ * @param[out] query The query string. The string is bound to the
+ * lifetime of the prepared.
+ * @param[out] query_length
+ */
+CASS_EXPORT void
+cass_prepared_query_string(const CassPrepared* prepared,
+                           const char** query,
+                           size_t* query_length);
+
+/**
+ * Gets the keyspace of the table the prepared statement operates on.
+ *
+ * <b>Note:</b> The keyspace is known only if the statement has bind markers.
+ *
+ * @public @memberof CassPrepared
+ *
+ * @param[in] prepared
+ * @param[out] keyspace The keyspace name. The string is bound to the
+ * lifetime of the prepared.
+ * @param[out] keyspace_length
+ * @return CASS_OK if successful, CASS_ERROR_LIB_NULL_VALUE if the keyspace
+ * is not known, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_prepared_keyspace(const CassPrepared* prepared,
+                       const char** keyspace,
+                       size_t* keyspace_length);
+
+/**
+ * Determines whether the prepared statement is a lightweight transaction
+ * (i.e. a conditional statement, e.g. INSERT ... IF NOT EXISTS or
+ * UPDATE ... IF ...).
+ *
+ * The statements detected as LWTs are automatically routed to the replicas
+ * in the same order by the token-aware policies, which reduces contention
+ * between the coordinators of concurrent LWTs on the same partition.
+ *
+ * <b>Note:</b> LWT detection requires ScyllaDB (the LWT flag is sent by the
+ * server in the prepared metadata). For other clusters cass_false is returned.
+ *
+ * @public @memberof CassPrepared
+ *
+ * @param[in] prepared
+ * @return cass_true if the statement is a LWT, otherwise cass_false.
+ */
+CASS_EXPORT cass_bool_t
+cass_prepared_is_lwt(const CassPrepared* prepared);
+
 /**
  * Gets the name of a parameter at the specified index.
  *
@@ -6776,6 +8440,54 @@ cass_prepared_parameter_name(const CassPrepared* prepared,
                              const char** name,
                              size_t* name_length);
 
+/**
+ * Determines whether the parameter at the specified index is bound
+ * to a partition key column (i.e. is a part of the routing key).
+ *
+ * @public @memberof CassPrepared
+ *
+ * @param[in] prepared
+ * @param[in] index
+ * @return cass_true if the parameter is a part of the partition key,
+ * otherwise cass_false.
+ */
+CASS_EXPORT cass_bool_t
+cass_prepared_parameter_is_partition_key(const CassPrepared* prepared,
+                                         size_t index);
+
+/**
+ * Gets the number of the partition key columns bound by the parameters
+ * of the prepared statement. It's 0 if the statement has no routing key
+ * (e.g. the partition key is not fully bound).
+ *
+ * @public @memberof CassPrepared
+ *
+ * @param[in] prepared
+ * @return The number of the partition key columns.
+ */
+CASS_EXPORT size_t
+cass_prepared_partition_key_count(const CassPrepared* prepared);
+
+/**
+ * Gets the index of the parameter bound to the partition key column
+ * at the specified position of the partition key. The routing key is
+ * composed of the parameters in the order of the positions.
+ *
+ * @public @memberof CassPrepared
+ *
+ * @param[in] prepared
+ * @param[in] position The position of the column in the partition key.
+ * @param[out] index The index of the parameter.
+ * @return CASS_OK if successful, CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS if
+ * the position is out of bounds, otherwise an error occurred.
+ *
+ * @see cass_prepared_partition_key_count()
+ */
+CASS_EXPORT CassError
+cass_prepared_partition_key_parameter_index(const CassPrepared* prepared,
+                                            size_t position,
+                                            size_t* index);
+
 /**
  * Gets the data type of a parameter at the specified index.
  *
@@ -6957,6 +8669,60 @@ CASS_EXPORT CassError
 cass_batch_set_request_timeout(CassBatch* batch,
                                cass_uint64_t timeout_ms);
 
+/**
+ * Sets the batch's priority class. It's taken into account when the
+ * number of concurrent requests of the session is limited.
+ *
+ * <b>Default:</b> CASS_REQUEST_PRIORITY_NORMAL
+ *
+ * @public @memberof CassBatch
+ *
+ * @param[in] batch
+ * @param[in] priority
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_cluster_set_max_concurrent_requests()
+ */
+CASS_EXPORT CassError
+cass_batch_set_priority(CassBatch* batch,
+                        CassRequestPriority priority);
+
+/**
+ * Sets the distributed tracing context of the application's span that
+ * the batch is executed within.
+ *
+ * <b>Note:</b> NULL or empty string will clear the trace context from batch
+ *
+ * @public @memberof CassBatch
+ *
+ * @param[in] batch
+ * @param[in] header
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_statement_set_trace_context()
+ */
+CASS_EXPORT CassError
+cass_batch_set_trace_context(CassBatch* batch,
+                             const char* header);
+
+/**
+ * Same as cass_batch_set_trace_context(), but with lengths for string
+ * parameters.
+ *
+ * @public @memberof CassBatch
+ *
+ * @param[in] batch
+ * @param[in] header
+ * @param[in] header_length
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_batch_set_trace_context()
+ */
+CASS_EXPORT CassError
+cass_batch_set_trace_context_n(CassBatch* batch,
+                               const char* header,
+                               size_t header_length);
+
 /**
  * Sets whether the statements in a batch are idempotent. Idempotent batches
  * are able to be automatically retried after timeouts/errors and can be
@@ -7035,6 +8801,19 @@ CASS_EXPORT CassError
 cass_batch_add_statement(CassBatch* batch,
                          CassStatement* statement);
 
+/**
+ * Removes all statements from the batch. Settings of the batch (e.g. the
+ * consistency, timestamp or execution profile) are kept, so the batch can
+ * be reused instead of creating a new one.
+ *
+ * @public @memberof CassBatch
+ *
+ * @param[in] batch
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_batch_clear(CassBatch* batch);
+
 /**
  * Sets the execution profile to execute the batch with.
  *
@@ -7162,6 +8941,56 @@ cass_data_type_type(const CassDataType* data_type);
 CASS_EXPORT cass_bool_t
 cass_data_type_is_frozen(const CassDataType* data_type);
 
+/**
+ * Determines whether two data types are equal. Unlike the type checks
+ * performed when binding values, the comparison is strict: untyped
+ * collections and tuples are not equal to the typed ones, and frozen-ness
+ * is taken into account.
+ *
+ * @public @memberof CassDataType
+ *
+ * @param[in] data_type
+ * @param[in] other_data_type
+ * @return cass_true if the data types are equal, otherwise cass_false.
+ */
+CASS_EXPORT cass_bool_t
+cass_data_type_equals(const CassDataType* data_type,
+                      const CassDataType* other_data_type);
+
+/**
+ * Computes a hash of the data type. Equal data types (see
+ * cass_data_type_equals()) have equal hashes. The hash is only stable
+ * within a single process.
+ *
+ * @public @memberof CassDataType
+ *
+ * @param[in] data_type
+ * @return The hash of the data type.
+ */
+CASS_EXPORT cass_uint64_t
+cass_data_type_hash(const CassDataType* data_type);
+
+/**
+ * Renders the data type the way it is written in CQL,
+ * e.g. "frozen<map<text, list<int>>>".
+ *
+ * The output is null-terminated and truncated to fit the buffer,
+ * the same way as with snprintf(). Call with NULL buffer to get
+ * the required size.
+ *
+ * @public @memberof CassDataType
+ *
+ * @param[in] data_type
+ * @param[out] buffer May be NULL.
+ * @param[in] buffer_size
+ * @return The length of the rendered type, excluding the null terminator.
+ * If not less than buffer_size, the output was truncated.
+ */
+CASS_EXPORT size_t
+cass_data_type_to_string(const CassDataType* data_type,
+                         char* buffer,
+                         size_t buffer_size);
+
 /**
  * Gets the type name of a UDT data type.
  *
@@ -7719,152 +9548,443 @@ cass_collection_append_bytes(CassCollection* collection,
                              size_t value_size);
 
 /**
- * Appends a "custom" to the collection.
+ * Appends a "custom" to the collection.
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] class_name
+ * @param[in] value The value is copied into the collection object; the
+ * memory pointed to by this parameter can be freed after this call.
+ * @param[in] value_size
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_collection_append_custom(CassCollection* collection,
+                              const char* class_name,
+                              const cass_byte_t* value,
+                              size_t value_size);
+
+/**
+ * Same as cass_collection_append_custom(), but with lengths for string
+ * parameters.
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] class_name
+ * @param[in] class_name_length
+ * @param[in] value
+ * @param[in] value_size
+ * @return same as cass_collection_append_custom()
+ *
+ * @see cass_collection_append_custom()
+ */
+CASS_EXPORT CassError
+cass_collection_append_custom_n(CassCollection* collection,
+                                const char* class_name,
+                                size_t class_name_length,
+                                const cass_byte_t* value,
+                                size_t value_size);
+
+/**
+ * Appends a value of the provided data type, which is already serialized,
+ * to the collection.
+ *
+ * <b>Note:</b> The value is not validated. It must already be in the CQL
+ * native protocol format of the provided data type (without the leading
+ * length), e.g. a serialized list, map, set, tuple or user defined type.
+ * This allows bulk loading tools that already have the serialized data to
+ * skip building the value element by element.
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] data_type
+ * @param[in] value The value is copied into the collection object; the
+ * memory pointed to by this parameter can be freed after this call.
+ * @param[in] value_size
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_collection_append_serialized(CassCollection* collection,
+                                  const CassDataType* data_type,
+                                  const cass_byte_t* value,
+                                  size_t value_size);
+
+/**
+ * Appends a "uuid" or "timeuuid"  to the collection.
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] value
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_collection_append_uuid(CassCollection* collection,
+                            CassUuid value);
+
+/**
+ * Appends an "inet" to the collection.
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] value
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_collection_append_inet(CassCollection* collection,
+                            CassInet value);
+
+/**
+ * Appends a "decimal" to the collection.
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] varint The value is copied into the collection object; the
+ * memory pointed to by this parameter can be freed after this call.
+ * @param[in] varint_size
+ * @param[in] scale
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_collection_append_decimal(CassCollection* collection,
+                               const cass_byte_t* varint,
+                               size_t varint_size,
+                               cass_int32_t scale);
+
+/**
+ * Appends a "duration" to the collection.
+ *
+ * @cassandra{3.10+}
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] months
+ * @param[in] days
+ * @param[in] nanos
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_collection_append_duration(CassCollection* collection,
+                                cass_int32_t months,
+                                cass_int32_t days,
+                                cass_int64_t nanos);
+
+/**
+ * Appends a "list", "map" or "set" to the collection.
+ *
+ * @cassandra{2.1+}
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] value
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_collection_append_collection(CassCollection* collection,
+                                  const CassCollection* value);
+
+/**
+ * Appends a "tuple" to the collection.
+ *
+ * @cassandra{2.1+}
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] value
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_collection_append_tuple(CassCollection* collection,
+                             const CassTuple* value);
+
+/**
+ * Appends a "udt" to the collection.
+ *
+ * @cassandra{2.1+}
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] value
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_collection_append_user_type(CassCollection* collection,
+                                 const CassUserType* value);
+
+/**
+ * Appends all the elements of an array of "tinyint" values to the
+ * collection. This is equivalent to calling
+ * cass_collection_append_int8() for each element, but crosses the
+ * library boundary only once.
+ *
+ * If any of the values is rejected, none of them is appended.
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] values
+ * @param[in] count The number of elements in values.
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_collection_append_int8()
+ */
+CASS_EXPORT CassError
+cass_collection_append_int8_array(CassCollection* collection,
+                                  const cass_int8_t* values,
+                                  size_t count);
+
+/**
+ * Appends all the elements of an array of "smallint" values to the
+ * collection. This is equivalent to calling
+ * cass_collection_append_int16() for each element, but crosses the
+ * library boundary only once.
+ *
+ * If any of the values is rejected, none of them is appended.
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] values
+ * @param[in] count The number of elements in values.
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_collection_append_int16()
+ */
+CASS_EXPORT CassError
+cass_collection_append_int16_array(CassCollection* collection,
+                                   const cass_int16_t* values,
+                                   size_t count);
+
+/**
+ * Appends all the elements of an array of "int" values to the
+ * collection. This is equivalent to calling
+ * cass_collection_append_int32() for each element, but crosses the
+ * library boundary only once.
+ *
+ * If any of the values is rejected, none of them is appended.
+ *
+ * @public @memberof CassCollection
+ *
+ * @param[in] collection
+ * @param[in] values
+ * @param[in] count The number of elements in values.
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_collection_append_int32()
+ */
+CASS_EXPORT CassError
+cass_collection_append_int32_array(CassCollection* collection,
+                                   const cass_int32_t* values,
+                                   size_t count);
+
+/**
+ * Appends all the elements of an array of "date" values to the
+ * collection. This is equivalent to calling
+ * cass_collection_append_uint32() for each element, but crosses the
+ * library boundary only once.
+ *
+ * If any of the values is rejected, none of them is appended.
  *
  * @public @memberof CassCollection
  *
  * @param[in] collection
- * @param[in] class_name
- * @param[in] value The value is copied into the collection object; the
- * memory pointed to by this parameter can be freed after this call.
- * @param[in] value_size
+ * @param[in] values
+ * @param[in] count The number of elements in values.
  * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_collection_append_uint32()
  */
 CASS_EXPORT CassError
-cass_collection_append_custom(CassCollection* collection,
-                              const char* class_name,
-                              const cass_byte_t* value,
-                              size_t value_size);
+cass_collection_append_uint32_array(CassCollection* collection,
+                                    const cass_uint32_t* values,
+                                    size_t count);
 
 /**
- * Same as cass_collection_append_custom(), but with lengths for string
- * parameters.
+ * Appends all the elements of an array of "bigint", "counter", "timestamp" or "time" values to the
+ * collection. This is equivalent to calling
+ * cass_collection_append_int64() for each element, but crosses the
+ * library boundary only once.
+ *
+ * If any of the values is rejected, none of them is appended.
  *
  * @public @memberof CassCollection
  *
  * @param[in] collection
- * @param[in] class_name
- * @param[in] class_name_length
- * @param[in] value
- * @param[in] value_size
- * @return same as cass_collection_append_custom()
+ * @param[in] values
+ * @param[in] count The number of elements in values.
+ * @return CASS_OK if successful, otherwise an error occurred.
  *
- * @see cass_collection_append_custom()
+ * @see cass_collection_append_int64()
  */
 CASS_EXPORT CassError
-cass_collection_append_custom_n(CassCollection* collection,
-                                const char* class_name,
-                                size_t class_name_length,
-                                const cass_byte_t* value,
-                                size_t value_size);
+cass_collection_append_int64_array(CassCollection* collection,
+                                   const cass_int64_t* values,
+                                   size_t count);
 
 /**
- * Appends a "uuid" or "timeuuid"  to the collection.
+ * Appends all the elements of an array of "float" values to the
+ * collection. This is equivalent to calling
+ * cass_collection_append_float() for each element, but crosses the
+ * library boundary only once.
+ *
+ * If any of the values is rejected, none of them is appended.
  *
  * @public @memberof CassCollection
  *
  * @param[in] collection
- * @param[in] value
+ * @param[in] values
+ * @param[in] count The number of elements in values.
  * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_collection_append_float()
  */
 CASS_EXPORT CassError
-cass_collection_append_uuid(CassCollection* collection,
-                            CassUuid value);
+cass_collection_append_float_array(CassCollection* collection,
+                                   const cass_float_t* values,
+                                   size_t count);
 
 /**
- * Appends an "inet" to the collection.
+ * Appends all the elements of an array of "double" values to the
+ * collection. This is equivalent to calling
+ * cass_collection_append_double() for each element, but crosses the
+ * library boundary only once.
+ *
+ * If any of the values is rejected, none of them is appended.
  *
  * @public @memberof CassCollection
  *
  * @param[in] collection
- * @param[in] value
+ * @param[in] values
+ * @param[in] count The number of elements in values.
  * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_collection_append_double()
  */
 CASS_EXPORT CassError
-cass_collection_append_inet(CassCollection* collection,
-                            CassInet value);
+cass_collection_append_double_array(CassCollection* collection,
+                                    const cass_double_t* values,
+                                    size_t count);
 
 /**
- * Appends a "decimal" to the collection.
+ * Appends all the elements of an array of "boolean" values to the
+ * collection. This is equivalent to calling
+ * cass_collection_append_bool() for each element, but crosses the
+ * library boundary only once.
+ *
+ * If any of the values is rejected, none of them is appended.
  *
  * @public @memberof CassCollection
  *
  * @param[in] collection
- * @param[in] varint The value is copied into the collection object; the
- * memory pointed to by this parameter can be freed after this call.
- * @param[in] varint_size
- * @param[in] scale
+ * @param[in] values
+ * @param[in] count The number of elements in values.
  * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_collection_append_bool()
  */
 CASS_EXPORT CassError
-cass_collection_append_decimal(CassCollection* collection,
-                               const cass_byte_t* varint,
-                               size_t varint_size,
-                               cass_int32_t scale);
+cass_collection_append_bool_array(CassCollection* collection,
+                                  const cass_bool_t* values,
+                                  size_t count);
 
 /**
- * Appends a "duration" to the collection.
+ * Appends all the elements of an array of "ascii", "text" or "varchar" values to the
+ * collection. This is equivalent to calling
+ * cass_collection_append_string() for each element, but crosses the
+ * library boundary only once.
  *
- * @cassandra{3.10+}
+ * If any of the values is rejected, none of them is appended.
+ *
+ * The strings must be null-terminated.
  *
  * @public @memberof CassCollection
  *
  * @param[in] collection
- * @param[in] months
- * @param[in] days
- * @param[in] nanos
+ * @param[in] values
+ * @param[in] count The number of elements in values.
  * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_collection_append_string()
  */
 CASS_EXPORT CassError
-cass_collection_append_duration(CassCollection* collection,
-                                cass_int32_t months,
-                                cass_int32_t days,
-                                cass_int64_t nanos);
+cass_collection_append_string_array(CassCollection* collection,
+                                    const char* const* values,
+                                    size_t count);
 
 /**
- * Appends a "list", "map" or "set" to the collection.
+ * Appends all the elements of an array of "uuid" or "timeuuid" values to the
+ * collection. This is equivalent to calling
+ * cass_collection_append_uuid() for each element, but crosses the
+ * library boundary only once.
  *
- * @cassandra{2.1+}
+ * If any of the values is rejected, none of them is appended.
  *
  * @public @memberof CassCollection
  *
  * @param[in] collection
- * @param[in] value
+ * @param[in] values
+ * @param[in] count The number of elements in values.
  * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_collection_append_uuid()
  */
 CASS_EXPORT CassError
-cass_collection_append_collection(CassCollection* collection,
-                                  const CassCollection* value);
+cass_collection_append_uuid_array(CassCollection* collection,
+                                  const CassUuid* values,
+                                  size_t count);
 
 /**
- * Appends a "tuple" to the collection.
+ * Appends all the elements of an array of "inet" values to the
+ * collection. This is equivalent to calling
+ * cass_collection_append_inet() for each element, but crosses the
+ * library boundary only once.
  *
- * @cassandra{2.1+}
+ * If any of the values is rejected, none of them is appended.
  *
  * @public @memberof CassCollection
  *
  * @param[in] collection
- * @param[in] value
+ * @param[in] values
+ * @param[in] count The number of elements in values.
  * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_collection_append_inet()
  */
 CASS_EXPORT CassError
-cass_collection_append_tuple(CassCollection* collection,
-                             const CassTuple* value);
+cass_collection_append_inet_array(CassCollection* collection,
+                                  const CassInet* values,
+                                  size_t count);
 
 /**
- * Appends a "udt" to the collection.
+ * Appends entries to the map. The keys and the values of the entries are
+ * given by two lists (or sets) of the same size, e.g. built with the
+ * cass_collection_append_*_array() functions. The n-th key is paired with
+ * the n-th value.
  *
- * @cassandra{2.1+}
+ * If any of the keys or values is rejected, none of them is appended.
  *
  * @public @memberof CassCollection
  *
- * @param[in] collection
- * @param[in] value
- * @return CASS_OK if successful, otherwise an error occurred.
+ * @param[in] collection The map. It can't have a key without its value
+ * appended.
+ * @param[in] keys
+ * @param[in] values
+ * @return CASS_OK if successful, CASS_ERROR_LIB_INVALID_ITEM_COUNT if the
+ * sizes of keys and values differ, otherwise an error occurred.
  */
 CASS_EXPORT CassError
-cass_collection_append_user_type(CassCollection* collection,
-                                 const CassUserType* value);
+cass_collection_append_map_entries(CassCollection* collection,
+                                   const CassCollection* keys,
+                                   const CassCollection* values);
 
 /***********************************************************************************
  *
@@ -8181,6 +10301,33 @@ cass_tuple_set_custom_n(CassTuple* tuple,
                         const cass_byte_t* value,
                         size_t value_size);
 
+/**
+ * Sets a value of the provided data type, which is already serialized,
+ * in a tuple at the specified index.
+ *
+ * <b>Note:</b> The value is not validated. It must already be in the CQL
+ * native protocol format of the provided data type (without the leading
+ * length), e.g. a serialized list, map, set, tuple or user defined type.
+ * This allows bulk loading tools that already have the serialized data to
+ * skip building the value element by element.
+ *
+ * @public @memberof CassTuple
+ *
+ * @param[in] tuple
+ * @param[in] index
+ * @param[in] data_type
+ * @param[in] value The value is copied into the tuple object; the
+ * memory pointed to by this parameter can be freed after this call.
+ * @param[in] value_size
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_tuple_set_serialized(CassTuple* tuple,
+                          size_t index,
+                          const CassDataType* data_type,
+                          const cass_byte_t* value,
+                          size_t value_size);
+
 /**
  * Sets a "uuid" or "timeuuid" in a tuple at the specified index.
  *
@@ -8356,6 +10503,24 @@ cass_user_type_free(CassUserType* user_type);
 CASS_EXPORT const CassDataType*
 cass_user_type_data_type(const CassUserType* user_type);
 
+/**
+ * Sets whether the field names passed to cass_user_type_set_*_by_name()
+ * functions are always matched case-sensitively. By default, only the quoted
+ * names (e.g. "\"MixedCase\"") are case-sensitive, and the unquoted names
+ * are matched case-insensitively.
+ *
+ * <b>Default:</b> cass_false
+ *
+ * @public @memberof CassUserType
+ *
+ * @param[in] user_type
+ * @param[in] enabled
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_user_type_set_case_sensitive_field_names(CassUserType* user_type,
+                                              cass_bool_t enabled);
+
 /**
  * Sets a null in a user defined type at the specified index.
  *
@@ -9090,6 +11255,79 @@ cass_user_type_set_custom_by_name_n(CassUserType* user_type,
                                     const cass_byte_t* value,
                                     size_t value_size);
 
+/**
+ * Sets a value of the provided data type, which is already serialized,
+ * in a user defined type at the specified index.
+ *
+ * <b>Note:</b> The value is not validated. It must already be in the CQL
+ * native protocol format of the provided data type (without the leading
+ * length), e.g. a serialized list, map, set, tuple or user defined type.
+ * This allows bulk loading tools that already have the serialized data to
+ * skip building the value element by element.
+ *
+ * @public @memberof CassUserType
+ *
+ * @param[in] user_type
+ * @param[in] index
+ * @param[in] data_type
+ * @param[in] value The value is copied into the user type object; the
+ * memory pointed to by this parameter can be freed after this call.
+ * @param[in] value_size
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_user_type_set_serialized(CassUserType* user_type,
+                              size_t index,
+                              const CassDataType* data_type,
+                              const cass_byte_t* value,
+                              size_t value_size);
+
+/**
+ * Sets a value of the provided data type, which is already serialized,
+ * in a user defined type at the specified name.
+ *
+ * @public @memberof CassUserType
+ *
+ * @param[in] user_type
+ * @param[in] name
+ * @param[in] data_type
+ * @param[in] value
+ * @param[in] value_size
+ * @return CASS_OK if successful, otherwise an error occurred.
+ *
+ * @see cass_user_type_set_serialized()
+ */
+CASS_EXPORT CassError
+cass_user_type_set_serialized_by_name(CassUserType* user_type,
+                                      const char* name,
+                                      const CassDataType* data_type,
+                                      const cass_byte_t* value,
+                                      size_t value_size);
+
+/**
+ * Same as cass_user_type_set_serialized_by_name(), but with lengths for string
+ * parameters.
+ *
+ * @public @memberof CassUserType
+ *
+ * @param[in] user_type
+ * @param[in] name
+ * @param[in] name_length
+ * @param[in] data_type
+ * @param[in] value
+ * @param[in] value_size
+ * @return same as cass_user_type_set_serialized_by_name()
+ *
+ * @see cass_user_type_set_serialized_by_name()
+ */
+CASS_EXPORT CassError
+cass_user_type_set_serialized_by_name_n(CassUserType* user_type,
+                                        const char* name,
+                                        size_t name_length,
+                                        const CassDataType* data_type,
+                                        const cass_byte_t* value,
+                                        size_t value_size);
+
 /**
  * Sets a "uuid" or "timeuuid" in a user defined type at the specified index.
  *
@@ -9549,6 +11787,33 @@ cass_result_row_count(const CassResult* result);
 CASS_EXPORT size_t
 cass_result_column_count(const CassResult* result);
 
+/**
+ * Renders the rows of the result (i.e. the current page) as a JSON array
+ * of objects keyed by the column names. Intended for debugging purposes.
+ *
+ * The values are rendered similarly to CQL's SELECT JSON: numbers as JSON
+ * numbers (except for varints and decimals, which are rendered as strings),
+ * collections and tuples as arrays, maps and UDTs as objects, blobs as
+ * base64-encoded strings, and the remaining types as strings.
+ *
+ * The output is null-terminated and truncated to fit the buffer,
+ * the same way as with snprintf(). Call with NULL buffer to get
+ * the required size.
+ *
+ * @public @memberof CassResult
+ *
+ * @param[in] result
+ * @param[out] buffer May be NULL.
+ * @param[in] buffer_size
+ * @return The length of the rendered JSON, excluding the null terminator.
+ * If not less than buffer_size, the output was truncated. Returns 0 if
+ * the rows could not be deserialized.
+ */
+CASS_EXPORT size_t
+cass_result_to_json(const CassResult* result,
+                    char* buffer,
+                    size_t buffer_size);
+
 /**
  * Gets the column name at index for the specified result.
  *
@@ -9624,7 +11889,8 @@ cass_result_has_more_pages(const CassResult* result);
  *
  * <b>Warning:</b> The paging state should not be exposed to or come from
  * untrusted environments. The paging state could be spoofed and potentially
- * used to gain access to other data.
+ * used to gain access to other data, unless the tokens are signed
+ * (see cass_cluster_set_paging_state_token_signing_key()).
  *
  * @cassandra{2.0+}
  *
@@ -9636,12 +11902,182 @@ cass_result_has_more_pages(const CassResult* result);
  * @return CASS_OK if successful, otherwise error occurred
  *
  * @see cass_statement_set_paging_state_token()
+ * @see cass_cluster_set_paging_state_token_signing_key()
  */
 CASS_EXPORT CassError
 cass_result_paging_state_token(const CassResult* result,
                                const char** paging_state,
                                size_t* paging_state_size);
 
+/**
+ * Gets the execution info of the request that produced the result.
+ *
+ * @public @memberof CassResult
+ *
+ * @param[in] result
+ * @return The execution info. The lifetime of the returned object is bound
+ * to the result.
+ *
+ * @see cass_future_execution_info()
+ */
+CASS_EXPORT const CassExecutionInfo*
+cass_result_execution_info(const CassResult* result);
+
+/***********************************************************************************
+ *
+ * Execution info
+ *
+ ***********************************************************************************/
+
+/**
+ * Gets the number of attempts made to execute the request. This includes
+ * retries and speculative executions.
+ *
+ * @public @memberof CassExecutionInfo
+ *
+ * @param[in] execution_info
+ * @return The number of attempts.
+ */
+CASS_EXPORT size_t
+cass_execution_info_attempt_count(const CassExecutionInfo* execution_info);
+
+/**
+ * Gets the address of the host the attempt at the specified index was sent to.
+ * Attempts are ordered by their start time.
+ *
+ * @public @memberof CassExecutionInfo
+ *
+ * @param[in] execution_info
+ * @param[in] index
+ * @param[out] host
+ * @return CASS_OK if successful, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_execution_info_attempt_host(const CassExecutionInfo* execution_info,
+                                 size_t index,
+                                 CassInet* host);
+
+/**
+ * Gets the outcome of the attempt at the specified index.
+ *
+ * <b>Note:</b> Attempts that were still in flight when the request completed
+ * (e.g. speculative executions that lost the race, or attempts cancelled
+ * due to the request timeout) are reported with CASS_ERROR_LIB_REQUEST_TIMED_OUT.
+ *
+ * @public @memberof CassExecutionInfo
+ *
+ * @param[in] execution_info
+ * @param[in] index
+ * @return CASS_OK if the attempt succeeded, CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS
+ * if there is no attempt at the specified index, otherwise the error
+ * the attempt failed with.
+ */
+CASS_EXPORT CassError
+cass_execution_info_attempt_error(const CassExecutionInfo* execution_info,
+                                  size_t index);
+
+/**
+ * Gets the consistency the request was successfully executed with. It may
+ * differ from the requested consistency if the retry policy decided to
+ * retry with a different consistency (e.g. downgrading consistency retry policy).
+ *
+ * @public @memberof CassExecutionInfo
+ *
+ * @param[in] execution_info
+ * @return The achieved consistency. CASS_CONSISTENCY_UNKNOWN if the request failed.
+ */
+CASS_EXPORT CassConsistency
+cass_execution_info_achieved_consistency(const CassExecutionInfo* execution_info);
+
+/**
+ * Determines whether a speculative execution was started for the request.
+ *
+ * @public @memberof CassExecutionInfo
+ *
+ * @param[in] execution_info
+ * @return cass_true if a speculative execution was started, otherwise cass_false.
+ *
+ * @see cass_cluster_set_constant_speculative_execution_policy()
+ */
+CASS_EXPORT cass_bool_t
+cass_execution_info_speculative_execution_used(const CassExecutionInfo* execution_info);
+
+/***********************************************************************************
+ *
+ * Query plan
+ *
+ ***********************************************************************************/
+
+/**
+ * Computes the query plan of the statement, i.e. the ordered list of hosts
+ * (and shards) the driver would try when executing the statement. The plan
+ * is computed by the load balancing policy of the statement's execution
+ * profile (or the default one), using the routing key of the bound values.
+ *
+ * This allows verifying the token-aware and DC-aware configuration.
+ * Note that the plan reflects the current state of the cluster, and
+ * randomized policies may return a different plan upon each call.
+ * Unprepared statements carry no routing information, so their plans
+ * are not token-aware.
+ *
+ * For tables of tablet-enabled ScyllaDB keyspaces, the replicas are those of
+ * the tablet owning the token, as long as the driver already knows it. The
+ * tablets are learned lazily, from the responses of the requests routed to
+ * non-replicas, so early plans may be computed from the token ring instead.
+ *
+ * @public @memberof CassSession
+ *
+ * @param[in] session
+ * @param[in] statement
+ * @return Returns a query plan that must be freed, or NULL if the session
+ * is not connected or the routing key could not be computed.
+ *
+ * @see cass_query_plan_free()
+ */
+CASS_EXPORT CassQueryPlan*
+cass_session_get_query_plan(const CassSession* session,
+                            const CassStatement* statement);
+
+/**
+ * Frees a query plan instance.
+ *
+ * @public @memberof CassQueryPlan
+ *
+ * @param[in] query_plan
+ */
+CASS_EXPORT void
+cass_query_plan_free(CassQueryPlan* query_plan);
+
+/**
+ * Gets the number of hosts in the query plan.
+ *
+ * @public @memberof CassQueryPlan
+ *
+ * @param[in] query_plan
+ * @return The number of hosts.
+ */
+CASS_EXPORT size_t
+cass_query_plan_host_count(const CassQueryPlan* query_plan);
+
+/**
+ * Gets the host (and shard) at the specified position of the query plan.
+ *
+ * @public @memberof CassQueryPlan
+ *
+ * @param[in] query_plan
+ * @param[in] index
+ * @param[out] host May be NULL.
+ * @param[out] shard May be NULL. Set to -1 if the request is not
+ * targeted at any particular shard.
+ * @return CASS_OK if successful, CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS if
+ * the index is out of bounds, otherwise an error occurred.
+ */
+CASS_EXPORT CassError
+cass_query_plan_host(const CassQueryPlan* query_plan,
+                     size_t index,
+                     CassInet* host,
+                     cass_int32_t* shard);
+
 /***********************************************************************************
  *
  * Error result
@@ -11647,13 +14083,16 @@ cass_date_time_to_epoch(cass_uint32_t date,
 /**
  * Set custom allocation functions.
  *
- * <b>Note:</b> This is not thread-safe. The allocation functions must be set
- * before any other library function is called.
+ * <b>Note:</b> The allocation functions must be set before any other library
+ * function is called. Otherwise, they are ignored. They can be set only once.
+ * They are supported only by the builds of the library with the
+ * "custom-allocator" feature enabled (which the CMake build does) - otherwise,
+ * they are ignored as well.
  *
  * <b>Default:</b> The C runtime's malloc(), realloc() and free()
  *
- * <b>Important:</b> The C runtime's malloc(), realloc() and free() will be
- * used by libuv when using versions 1.5 or earlier.
+ * <b>Important:</b> Allocations requiring alignment stricter than 8 bytes
+ * are always served by the C runtime's allocator.
  *
  * @param[in] malloc_func
  * @param[in] realloc_func
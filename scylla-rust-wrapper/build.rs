@@ -155,7 +155,32 @@ fn main() {
     );
     prepare_cppdriver_data(
         "cppdriver_metrics_types.rs",
-        &["CassMetrics_", "CassMetrics"],
+        &[
+            "CassMetrics_",
+            "CassMetrics",
+            "CassQueueMetrics_",
+            "CassQueueMetrics",
+        ],
+        &out_path,
+    );
+    prepare_cppdriver_data(
+        "cppdriver_cluster_event_types.rs",
+        &[
+            "CassClusterEventType_",
+            "CassClusterEventType",
+            "CassHostListenerEvent_",
+            "CassHostListenerEvent",
+        ],
+        &out_path,
+    );
+    prepare_cppdriver_data(
+        "cppdriver_request_priority_types.rs",
+        &["CassRequestPriority_", "CassRequestPriority"],
+        &out_path,
+    );
+    prepare_cppdriver_data(
+        "cppdriver_address_family_types.rs",
+        &["CassAddressFamily_", "CassAddressFamily"],
         &out_path,
     );
 }
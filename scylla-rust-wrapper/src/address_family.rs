@@ -0,0 +1,210 @@
+use crate::cass_address_family_types::CassAddressFamily;
+use crate::cass_error::CassError;
+use crate::contact_points;
+use scylla::cluster::metadata::Peer;
+use scylla::policies::host_filter::HostFilter;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Address family preference for the contact points and the nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum AddressFamily {
+    /// rust-driver's behaviour - hostnames are resolved to IPv4 addresses, if possible.
+    #[default]
+    PreferIpv4,
+    PreferIpv6,
+    Ipv4Only,
+    Ipv6Only,
+}
+
+impl TryFrom<CassAddressFamily> for AddressFamily {
+    type Error = CassError;
+
+    fn try_from(family: CassAddressFamily) -> Result<Self, Self::Error> {
+        match family {
+            CassAddressFamily::CASS_ADDRESS_FAMILY_PREFER_IPV4 => Ok(AddressFamily::PreferIpv4),
+            CassAddressFamily::CASS_ADDRESS_FAMILY_PREFER_IPV6 => Ok(AddressFamily::PreferIpv6),
+            CassAddressFamily::CASS_ADDRESS_FAMILY_IPV4_ONLY => Ok(AddressFamily::Ipv4Only),
+            CassAddressFamily::CASS_ADDRESS_FAMILY_IPV6_ONLY => Ok(AddressFamily::Ipv6Only),
+            _ => Err(CassError::CASS_ERROR_LIB_BAD_PARAMS),
+        }
+    }
+}
+
+impl AddressFamily {
+    fn is_preferred(self, ip: &IpAddr) -> bool {
+        match self {
+            AddressFamily::PreferIpv4 | AddressFamily::Ipv4Only => ip.is_ipv4(),
+            AddressFamily::PreferIpv6 | AddressFamily::Ipv6Only => ip.is_ipv6(),
+        }
+    }
+
+    fn is_exclusive(self) -> bool {
+        matches!(self, AddressFamily::Ipv4Only | AddressFamily::Ipv6Only)
+    }
+
+    /// Whether the node with given address can be connected to.
+    fn accepts(self, ip: &IpAddr) -> bool {
+        !self.is_exclusive() || self.is_preferred(ip)
+    }
+
+    /// Chooses the address of a contact point out of the resolved addresses.
+    fn choose(self, addrs: impl Iterator<Item = SocketAddr>) -> Option<SocketAddr> {
+        let (preferred, other): (Vec<_>, Vec<_>) =
+            addrs.partition(|addr| self.is_preferred(&addr.ip()));
+        match preferred.first() {
+            Some(addr) => Some(*addr),
+            None if !self.is_exclusive() => other.first().copied(),
+            None => None,
+        }
+    }
+
+    /// Resolves the contact points, choosing the addresses according to the preference.
+    /// Contact points that fail to resolve (or resolve only to addresses of the rejected family)
+    /// are skipped.
+    ///
+    /// rust-driver re-resolves the hostnames of the contact points (e.g. when all known nodes
+    /// are down), but it always prefers IPv4 addresses. So the hostname is kept, if rust-driver
+    /// chooses the same address as the preference does - and only otherwise, the contact point
+    /// is pinned to the chosen address.
+    pub(crate) async fn resolve_contact_points(
+        self,
+        contact_points: &[(String, u16)],
+        resolution_timeout: Option<Duration>,
+    ) -> Vec<ResolvedContactPoint> {
+        let mut resolved = Vec::with_capacity(contact_points.len());
+        for (contact_point, port) in contact_points {
+            let lookup = tokio::net::lookup_host((contact_point.as_str(), *port));
+            let addrs = match resolution_timeout {
+                Some(timeout) => tokio::time::timeout(timeout, lookup)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            "hostname resolution timed out",
+                        ))
+                    }),
+                None => lookup.await,
+            };
+
+            match addrs.map(|addrs| self.contact_point(contact_point, *port, addrs.collect())) {
+                Ok(Some(resolved_contact_point)) => resolved.push(resolved_contact_point),
+                Ok(None) => tracing::warn!(
+                    "Contact point {} does not resolve to an address of the allowed family",
+                    contact_point
+                ),
+                Err(err) => {
+                    tracing::warn!("Failed to resolve contact point {}: {}", contact_point, err)
+                }
+            }
+        }
+        resolved
+    }
+
+    fn contact_point(
+        self,
+        host: &str,
+        port: u16,
+        addrs: Vec<SocketAddr>,
+    ) -> Option<ResolvedContactPoint> {
+        let addr = self.choose(addrs.iter().copied())?;
+        if AddressFamily::PreferIpv4.choose(addrs.into_iter()) == Some(addr) {
+            Some(ResolvedContactPoint::Hostname(
+                contact_points::format_endpoint(host, port),
+            ))
+        } else {
+            Some(ResolvedContactPoint::Address(addr))
+        }
+    }
+
+    /// Wraps the host filter, so it additionally rejects the nodes with addresses
+    /// of the rejected family.
+    pub(crate) fn wrap_host_filter(self, host_filter: Arc<dyn HostFilter>) -> Arc<dyn HostFilter> {
+        if self.is_exclusive() {
+            Arc::new(AddressFamilyHostFilter {
+                family: self,
+                inner: host_filter,
+            })
+        } else {
+            host_filter
+        }
+    }
+}
+
+/// Contact point passed to rust-driver.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ResolvedContactPoint {
+    /// Resolved (and re-resolved) by rust-driver.
+    Hostname(String),
+    Address(SocketAddr),
+}
+
+struct AddressFamilyHostFilter {
+    family: AddressFamily,
+    inner: Arc<dyn HostFilter>,
+}
+
+impl HostFilter for AddressFamilyHostFilter {
+    fn accept(&self, peer: &Peer) -> bool {
+        self.family.accepts(&peer.address.ip()) && self.inner.accept(peer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_address_family_choose() {
+        let v4 = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9042);
+        let v6 = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 9042);
+
+        let both = || [v6, v4].into_iter();
+        assert_eq!(AddressFamily::PreferIpv4.choose(both()), Some(v4));
+        assert_eq!(AddressFamily::PreferIpv6.choose(both()), Some(v6));
+        assert_eq!(AddressFamily::Ipv4Only.choose(both()), Some(v4));
+        assert_eq!(AddressFamily::Ipv6Only.choose(both()), Some(v6));
+
+        let only_v4 = || [v4].into_iter();
+        assert_eq!(AddressFamily::PreferIpv6.choose(only_v4()), Some(v4));
+        assert_eq!(AddressFamily::Ipv6Only.choose(only_v4()), None);
+
+        assert!(AddressFamily::PreferIpv6.accepts(&v4.ip()));
+        assert!(!AddressFamily::Ipv6Only.accepts(&v4.ip()));
+        assert!(AddressFamily::Ipv4Only.accepts(&v4.ip()));
+    }
+
+    #[test]
+    fn test_address_family_contact_point() {
+        let v4 = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9042);
+        let v6 = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 9042);
+        let hostname = || ResolvedContactPoint::Hostname("localhost:9042".to_owned());
+
+        // rust-driver chooses the same address - it can re-resolve the hostname.
+        assert_eq!(
+            AddressFamily::Ipv4Only.contact_point("localhost", 9042, vec![v6, v4]),
+            Some(hostname())
+        );
+        assert_eq!(
+            AddressFamily::PreferIpv6.contact_point("localhost", 9042, vec![v6]),
+            Some(hostname())
+        );
+        assert_eq!(
+            AddressFamily::Ipv6Only.contact_point("localhost", 9042, vec![v6]),
+            Some(hostname())
+        );
+
+        // rust-driver would choose the IPv4 address.
+        assert_eq!(
+            AddressFamily::PreferIpv6.contact_point("localhost", 9042, vec![v4, v6]),
+            Some(ResolvedContactPoint::Address(v6))
+        );
+        assert_eq!(
+            AddressFamily::Ipv6Only.contact_point("localhost", 9042, vec![v4]),
+            None
+        );
+    }
+}
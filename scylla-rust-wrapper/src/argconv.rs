@@ -1,3 +1,4 @@
+use crate::ptr_validation::{self, Origin};
 use crate::types::size_t;
 use std::cmp::min;
 use std::ffi::CStr;
@@ -47,6 +48,26 @@ pub unsafe fn write_str_to_c(s: &str, c_str: *mut *const c_char, c_strlen: *mut
     }
 }
 
+/// Copies the string into the user-provided buffer, with the same semantics as snprintf:
+/// the output is truncated to fit the buffer (including null terminator), and the full
+/// length of the string is returned.
+pub(crate) unsafe fn write_str_to_c_buffer(
+    s: &str,
+    buffer: *mut c_char,
+    buffer_size: size_t,
+) -> size_t {
+    if !buffer.is_null() && buffer_size > 0 {
+        let written = s.len().min(buffer_size as usize - 1);
+        // SAFETY: We assume that user provided a buffer of at least `buffer_size` bytes.
+        unsafe {
+            std::ptr::copy_nonoverlapping(s.as_ptr(), buffer as *mut u8, written);
+            *buffer.add(written) = 0;
+        }
+    }
+
+    s.len() as size_t
+}
+
 pub unsafe fn strlen(ptr: *const c_char) -> size_t {
     if ptr.is_null() {
         return 0;
@@ -248,6 +269,11 @@ impl<T: Sized, P: Properties> CassPtr<'_, T, P> {
         self.ptr.is_none()
     }
 
+    /// Checks whether both pointers point to the same object.
+    pub(crate) fn is_same_pointee<P2: Properties>(&self, other: &CassPtr<'_, T, P2>) -> bool {
+        self.ptr.is_some() && self.ptr == other.ptr
+    }
+
     /// Constructs [`CassPtr`] from raw pointer.
     ///
     /// ## Safety
@@ -345,6 +371,7 @@ pub trait BoxFFI: Sized + origin_sealed::FromBoxSealed {
     fn into_ptr<CM: CMutability>(self: Box<Self>) -> CassPtr<'static, Self, (Exclusive, CM)> {
         #[allow(clippy::disallowed_methods)]
         let ptr = Box::into_raw(self);
+        ptr_validation::register(ptr, Origin::Box);
 
         // SAFETY:
         // 1. validity guarantee - pointer is obviously valid. It comes from box allocation.
@@ -365,6 +392,9 @@ pub trait BoxFFI: Sized + origin_sealed::FromBoxSealed {
         // The only way to obtain an owned pointer (with 'static lifetime) is BoxFFI::into_ptr().
         // It creates a pointer based on Box allocation. It is thus safe to convert the pointer
         // back to owned `Box`.
+        if let Some(p) = ptr.to_raw() {
+            ptr_validation::validate_owned(p, Origin::Box, true);
+        }
         unsafe {
             ptr.to_raw().map(|p| {
                 #[allow(clippy::disallowed_methods)]
@@ -379,6 +409,9 @@ pub trait BoxFFI: Sized + origin_sealed::FromBoxSealed {
     fn as_ref<'a, O: Ownership, CM: CMutability>(
         ptr: CassPtr<'a, Self, (O, CM)>,
     ) -> Option<&'a Self> {
+        if let Some(p) = ptr.to_raw() {
+            ptr_validation::validate_owned(p, Origin::Box, false);
+        }
         ptr.into_ref()
     }
 
@@ -388,9 +421,23 @@ pub trait BoxFFI: Sized + origin_sealed::FromBoxSealed {
     fn as_mut_ref<'a, CM: CMutability>(
         ptr: CassPtr<'a, Self, (Exclusive, CM)>,
     ) -> Option<&'a mut Self> {
+        if let Some(p) = ptr.to_raw() {
+            ptr_validation::validate_owned(p, Origin::Box, false);
+        }
         ptr.into_mut_ref()
     }
 
+    /// Same as [`BoxFFI::as_mut_ref`], but additionally marks the pointee as in use
+    /// until the returned guard is dropped. With the `debug-pointer-validation` feature,
+    /// concurrent use of the pointee by another thread is then reported.
+    #[allow(clippy::needless_lifetimes)]
+    fn as_mut_ref_guarded<'a, CM: CMutability>(
+        ptr: CassPtr<'a, Self, (Exclusive, CM)>,
+    ) -> Option<(&'a mut Self, ptr_validation::ExclusiveUseGuard)> {
+        let guard = ptr_validation::enter_exclusive_use(ptr.to_raw()?);
+        ptr.into_mut_ref().map(|r| (r, guard))
+    }
+
     /// Frees the pointee.
     fn free<CM: CMutability>(ptr: CassPtr<'static, Self, (Exclusive, CM)>) {
         std::mem::drop(BoxFFI::from_ptr(ptr));
@@ -434,6 +481,7 @@ pub trait ArcFFI: Sized + origin_sealed::FromArcSealed {
     fn into_ptr<CM: CMutability>(self: Arc<Self>) -> CassPtr<'static, Self, (Shared, CM)> {
         #[allow(clippy::disallowed_methods)]
         let ptr = Arc::into_raw(self);
+        ptr_validation::register(ptr, Origin::Arc);
 
         // SAFETY:
         // 1. validity guarantee - pointer is valid, since it's obtained from Arc allocation
@@ -450,6 +498,9 @@ pub trait ArcFFI: Sized + origin_sealed::FromArcSealed {
         // The only way to obtain a pointer with shared ownership ('static lifetime) is
         // ArcFFI::into_ptr(). It converts an owned Arc into the pointer. It is thus safe
         // to convert such pointer back to owned Arc.
+        if let Some(p) = ptr.to_raw() {
+            ptr_validation::validate_owned(p, Origin::Arc, true);
+        }
         unsafe {
             ptr.to_raw().map(|p| {
                 #[allow(clippy::disallowed_methods)]
@@ -465,6 +516,9 @@ pub trait ArcFFI: Sized + origin_sealed::FromArcSealed {
         // It is thus safe to increase the reference count of the pointer, and convert
         // it to Arc. Because of the borrow-checker, it is not possible for the user
         // to provide a pointer that points to already deallocated memory.
+        if let Some(p) = ptr.to_raw() {
+            ptr_validation::validate_borrowed(p, true);
+        }
         unsafe {
             ptr.to_raw().map(|p| {
                 #[allow(clippy::disallowed_methods)]
@@ -479,6 +533,9 @@ pub trait ArcFFI: Sized + origin_sealed::FromArcSealed {
     /// The reference inherits the lifetime of pointer's borrow.
     #[allow(clippy::needless_lifetimes)]
     fn as_ref<'a, CM: CMutability>(ptr: CassPtr<'a, Self, (Shared, CM)>) -> Option<&'a Self> {
+        if let Some(p) = ptr.to_raw() {
+            ptr_validation::validate_borrowed(p, true);
+        }
         ptr.into_ref()
     }
 
@@ -558,6 +615,10 @@ pub trait RefFFI: Sized + origin_sealed::FromRefSealed {
     /// The reference inherits the lifetime of pointer's borrow.
     #[allow(clippy::needless_lifetimes)]
     fn as_ref<'a, CM: CMutability>(ptr: CassPtr<'a, Self, (Shared, CM)>) -> Option<&'a Self> {
+        if let Some(p) = ptr.to_raw() {
+            // The pointer may point to the beginning of an owned object enclosing the pointee.
+            ptr_validation::validate_borrowed(p, false);
+        }
         ptr.into_ref()
     }
 
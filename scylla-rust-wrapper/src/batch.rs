@@ -1,25 +1,34 @@
 use crate::argconv::{
     ArcFFI, BoxFFI, CMut, CassBorrowedExclusivePtr, CassBorrowedSharedPtr, CassOwnedExclusivePtr,
-    FFI, FromBox,
+    FFI, FromBox, strlen,
 };
 use crate::cass_error::CassError;
+use crate::cass_request_priority_types::CassRequestPriority;
 use crate::cass_types::CassConsistency;
 use crate::cass_types::{CassBatchType, make_batch_type};
 use crate::exec_profile::PerStatementExecProfile;
+use crate::request_queue::RequestPriority;
 use crate::retry_policy::CassRetryPolicy;
 use crate::statement::{BoundStatement, CassStatement};
+use crate::trace_context::TraceContext;
 use crate::types::*;
 use crate::value::CassCqlValue;
+use scylla::serialize::SerializationError;
+use scylla::serialize::row::{RowSerializationContext, SerializeRow};
+use scylla::serialize::writers::RowWriter;
 use scylla::statement::batch::Batch;
 use scylla::value::MaybeUnset;
 use std::convert::TryInto;
-use std::sync::Arc;
+use std::os::raw::c_char;
+use std::sync::{Arc, OnceLock};
 
 pub struct CassBatch {
     pub state: Arc<CassBatchState>,
     pub batch_request_timeout_ms: Option<cass_uint64_t>,
 
     pub(crate) exec_profile: Option<PerStatementExecProfile>,
+    pub(crate) priority: RequestPriority,
+    pub(crate) trace_context: Option<TraceContext>,
 }
 
 impl FFI for CassBatch {
@@ -32,6 +41,78 @@ pub struct CassBatchState {
     pub bound_values: Vec<Vec<MaybeUnset<Option<CassCqlValue>>>>,
 }
 
+/// Values bound to a single statement of the batch, serialized lazily by the first
+/// attempt of the request.
+///
+/// Rust driver serializes the batch values each time the batch frame is built,
+/// i.e. once per every retry and speculative execution. Wrapping the values
+/// lets later attempts copy the cells that were already serialized.
+pub(crate) struct SerializedOnceRow<'a> {
+    values: &'a Vec<MaybeUnset<Option<CassCqlValue>>>,
+    /// Serialized cells (each preceded by its length), and the number of cells.
+    serialized: OnceLock<Result<(Vec<u8>, usize), SerializationError>>,
+}
+
+impl<'a> SerializedOnceRow<'a> {
+    pub(crate) fn new(values: &'a Vec<MaybeUnset<Option<CassCqlValue>>>) -> Self {
+        Self {
+            values,
+            serialized: OnceLock::new(),
+        }
+    }
+}
+
+impl SerializeRow for SerializedOnceRow<'_> {
+    fn serialize(
+        &self,
+        ctx: &RowSerializationContext<'_>,
+        writer: &mut RowWriter,
+    ) -> Result<(), SerializationError> {
+        // Every attempt of the batch request serializes the same statements,
+        // so the context is the same for each call.
+        let (serialized, cell_count) = self
+            .serialized
+            .get_or_init(|| {
+                let mut buf = Vec::new();
+                let mut row_writer = RowWriter::new(&mut buf);
+                <_ as SerializeRow>::serialize(self.values, ctx, &mut row_writer)?;
+                let cell_count = row_writer.value_count();
+                Ok((buf, cell_count))
+            })
+            .as_ref()
+            .map_err(Clone::clone)?;
+
+        let mut remaining = serialized.as_slice();
+        for _ in 0..*cell_count {
+            let (len, rest) = remaining.split_at(4);
+            let cell_writer = writer.make_cell_writer();
+            remaining = match i32::from_be_bytes(len.try_into().unwrap()) {
+                -1 => {
+                    cell_writer.set_null();
+                    rest
+                }
+                -2 => {
+                    cell_writer.set_unset();
+                    rest
+                }
+                len => {
+                    let (value, rest) = rest.split_at(len as usize);
+                    cell_writer
+                        .set_value(value)
+                        .map_err(SerializationError::new)?;
+                    rest
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_batch_new(
     type_: CassBatchType,
@@ -44,6 +125,8 @@ pub unsafe extern "C" fn cass_batch_new(
             }),
             batch_request_timeout_ms: None,
             exec_profile: None,
+            priority: RequestPriority::default(),
+            trace_context: None,
         }))
     } else {
         BoxFFI::null_mut()
@@ -154,6 +237,53 @@ pub unsafe extern "C" fn cass_batch_set_request_timeout(
     CassError::CASS_OK
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_batch_set_priority(
+    batch: CassBorrowedExclusivePtr<CassBatch, CMut>,
+    priority: CassRequestPriority,
+) -> CassError {
+    let Some(batch) = BoxFFI::as_mut_ref(batch) else {
+        tracing::error!("Provided null batch pointer to cass_batch_set_priority!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    match RequestPriority::try_from(priority) {
+        Ok(priority) => {
+            batch.priority = priority;
+            CassError::CASS_OK
+        }
+        Err(e) => e,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_batch_set_trace_context(
+    batch: CassBorrowedExclusivePtr<CassBatch, CMut>,
+    header: *const c_char,
+) -> CassError {
+    unsafe { cass_batch_set_trace_context_n(batch, header, strlen(header)) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_batch_set_trace_context_n(
+    batch: CassBorrowedExclusivePtr<CassBatch, CMut>,
+    header: *const c_char,
+    header_length: size_t,
+) -> CassError {
+    let Some(batch) = BoxFFI::as_mut_ref(batch) else {
+        tracing::error!("Provided null batch pointer to cass_batch_set_trace_context_n!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    match unsafe { TraceContext::from_header_n(header, header_length) } {
+        Ok(trace_context) => {
+            batch.trace_context = trace_context;
+            CassError::CASS_OK
+        }
+        Err(e) => e,
+    }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_batch_set_is_idempotent(
     batch: CassBorrowedExclusivePtr<CassBatch, CMut>,
@@ -217,3 +347,159 @@ pub unsafe extern "C" fn cass_batch_add_statement(
 
     CassError::CASS_OK
 }
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_batch_clear(
+    batch: CassBorrowedExclusivePtr<CassBatch, CMut>,
+) -> CassError {
+    let Some(batch) = BoxFFI::as_mut_ref(batch) else {
+        tracing::error!("Provided null batch pointer to cass_batch_clear!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    // Settings of the batch (consistency, timestamp, etc.) are kept.
+    let state = Arc::make_mut(&mut batch.state);
+    state.batch.statements.clear();
+    state.bound_values.clear();
+
+    CassError::CASS_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use scylla::cluster::metadata::{ColumnType, NativeType};
+    use scylla::frame::response::result::{ColumnSpec, TableSpec};
+    use scylla::serialize::row::{RowSerializationContext, SerializeRow};
+    use scylla::serialize::writers::RowWriter;
+    use scylla::value::MaybeUnset::{Set, Unset};
+
+    use crate::argconv::BoxFFI;
+    use crate::cass_error::CassError;
+    use crate::cass_types::CassBatchType;
+    use crate::statement::{cass_statement_free, cass_statement_new};
+    use crate::testing::assert_cass_error_eq;
+    use crate::value::CassCqlValue;
+
+    use super::{
+        SerializedOnceRow, cass_batch_add_statement, cass_batch_clear, cass_batch_free,
+        cass_batch_new, cass_batch_set_trace_context,
+    };
+
+    fn do_serialize(row: &impl SerializeRow, ctx: &RowSerializationContext<'_>) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let mut writer = RowWriter::new(&mut buf);
+        row.serialize(ctx, &mut writer).unwrap();
+        assert_eq!(writer.value_count(), 4);
+        buf
+    }
+
+    #[test]
+    fn test_serialized_once_row() {
+        let table = TableSpec::borrowed("ks", "tbl");
+        let specs = [
+            ColumnSpec::borrowed("a", ColumnType::Native(NativeType::Int), table.clone()),
+            ColumnSpec::borrowed("b", ColumnType::Native(NativeType::Text), table.clone()),
+            ColumnSpec::borrowed("c", ColumnType::Native(NativeType::Int), table.clone()),
+            ColumnSpec::borrowed("d", ColumnType::Native(NativeType::Text), table),
+        ];
+        let ctx = RowSerializationContext::from_specs(&specs);
+
+        let values = vec![
+            Set(Some(CassCqlValue::Int(42))),
+            Set(Some(CassCqlValue::Text("foo".into()))),
+            Unset,
+            Set(None),
+        ];
+        let expected = do_serialize(&values, &ctx);
+
+        let row = SerializedOnceRow::new(&values);
+        // The second call copies the cells serialized by the first one.
+        assert_eq!(do_serialize(&row, &ctx), expected);
+        assert!(row.serialized.get().is_some());
+        assert_eq!(do_serialize(&row, &ctx), expected);
+    }
+
+    #[test]
+    fn test_batch_clear() {
+        unsafe {
+            let mut batch_raw = cass_batch_new(CassBatchType::CASS_BATCH_TYPE_LOGGED);
+            let statement_raw = cass_statement_new(c"INSERT INTO ks.t (a) VALUES (1)".as_ptr(), 0);
+
+            for _ in 0..2 {
+                assert_cass_error_eq!(
+                    CassError::CASS_OK,
+                    cass_batch_add_statement(batch_raw.borrow_mut(), statement_raw.borrow())
+                );
+            }
+            assert_cass_error_eq!(CassError::CASS_OK, cass_batch_clear(batch_raw.borrow_mut()));
+            {
+                let batch = BoxFFI::as_ref(batch_raw.borrow()).unwrap();
+                assert!(batch.state.batch.statements.is_empty());
+                assert!(batch.state.bound_values.is_empty());
+            }
+
+            // The batch can be reused.
+            assert_cass_error_eq!(
+                CassError::CASS_OK,
+                cass_batch_add_statement(batch_raw.borrow_mut(), statement_raw.borrow())
+            );
+            assert_eq!(
+                BoxFFI::as_ref(batch_raw.borrow())
+                    .unwrap()
+                    .state
+                    .bound_values
+                    .len(),
+                1
+            );
+
+            cass_statement_free(statement_raw);
+            cass_batch_free(batch_raw);
+        }
+    }
+
+    #[test]
+    fn test_batch_set_trace_context() {
+        unsafe {
+            let mut batch_raw = cass_batch_new(CassBatchType::CASS_BATCH_TYPE_LOGGED);
+
+            assert_cass_error_eq!(
+                CassError::CASS_OK,
+                cass_batch_set_trace_context(
+                    batch_raw.borrow_mut(),
+                    c"00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".as_ptr()
+                )
+            );
+            let trace_context = BoxFFI::as_ref(batch_raw.borrow())
+                .unwrap()
+                .trace_context
+                .unwrap();
+            assert_eq!(trace_context.parent_span_id, 0x00f067aa0ba902b7);
+
+            // Invalid header does not change the context.
+            assert_cass_error_eq!(
+                CassError::CASS_ERROR_LIB_BAD_PARAMS,
+                cass_batch_set_trace_context(batch_raw.borrow_mut(), c"invalid".as_ptr())
+            );
+            assert!(
+                BoxFFI::as_ref(batch_raw.borrow())
+                    .unwrap()
+                    .trace_context
+                    .is_some()
+            );
+
+            // Empty header clears the context.
+            assert_cass_error_eq!(
+                CassError::CASS_OK,
+                cass_batch_set_trace_context(batch_raw.borrow_mut(), c"".as_ptr())
+            );
+            assert!(
+                BoxFFI::as_ref(batch_raw.borrow())
+                    .unwrap()
+                    .trace_context
+                    .is_none()
+            );
+
+            cass_batch_free(batch_raw);
+        }
+    }
+}
@@ -60,7 +60,7 @@ macro_rules! make_index_binder {
             // For some reason detected as unused, which is not true
             #[allow(unused_imports)]
             use crate::value::CassCqlValue::*;
-            let Some(this) = BoxFFI::as_mut_ref(this) else {
+            let Some((this, _guard)) = BoxFFI::as_mut_ref_guarded(this) else {
                 tracing::error!("Provided null pointer to {}!", stringify!($fn_by_idx));
                 return CassError::CASS_ERROR_LIB_BAD_PARAMS;
             };
@@ -84,7 +84,7 @@ macro_rules! make_name_binder {
             // For some reason detected as unused, which is not true
             #[allow(unused_imports)]
             use crate::value::CassCqlValue::*;
-            let Some(this) = BoxFFI::as_mut_ref(this) else {
+            let Some((this, _guard)) = BoxFFI::as_mut_ref_guarded(this) else {
                 tracing::error!("Provided null pointer to {}!", stringify!($fn_by_name));
                 return CassError::CASS_ERROR_LIB_BAD_PARAMS;
             };
@@ -110,7 +110,7 @@ macro_rules! make_name_n_binder {
             // For some reason detected as unused, which is not true
             #[allow(unused_imports)]
             use crate::value::CassCqlValue::*;
-            let Some(this) = BoxFFI::as_mut_ref(this) else {
+            let Some((this, _guard)) = BoxFFI::as_mut_ref_guarded(this) else {
                 tracing::error!("Provided null pointer to {}!", stringify!($fn_by_name_n));
                 return CassError::CASS_ERROR_LIB_BAD_PARAMS;
             };
@@ -134,7 +134,7 @@ macro_rules! make_appender {
             // For some reason detected as unused, which is not true
             #[allow(unused_imports)]
             use crate::value::CassCqlValue::*;
-            let Some(this) = BoxFFI::as_mut_ref(this) else {
+            let Some((this, _guard)) = BoxFFI::as_mut_ref_guarded(this) else {
                 tracing::error!("Provided null pointer to {}!", stringify!($fn_append));
                 return CassError::CASS_ERROR_LIB_BAD_PARAMS;
             };
@@ -146,9 +146,6 @@ macro_rules! make_appender {
     }
 }
 
-// Types for which binding is not implemented:
-// custom - Not implemented in Rust driver
-
 macro_rules! invoke_binder_maker_macro_with_type {
     (null, $macro_name:ident, $this:ty, $consume_v:expr, $fn:ident) => {
         $macro_name!($this, $consume_v, $fn, || Ok(None), []);
@@ -233,7 +230,7 @@ macro_rules! invoke_binder_maker_macro_with_type {
             $this,
             $consume_v,
             $fn,
-            |v| Ok(Some(Text(unsafe { ptr_to_cstr(v) }.unwrap().to_string()))),
+            |v| Ok(Some(Text(unsafe { ptr_to_cstr(v) }.unwrap().into()))),
             [v @ *const std::os::raw::c_char]
         );
     };
@@ -242,7 +239,7 @@ macro_rules! invoke_binder_maker_macro_with_type {
             $this,
             $consume_v,
             $fn,
-            |v, n| Ok(Some(Text(unsafe { ptr_to_cstr_n(v, n) }.unwrap().to_string()))),
+            |v, n| Ok(Some(Text(unsafe { ptr_to_cstr_n(v, n) }.unwrap().into()))),
             [v @ *const std::os::raw::c_char, n @ size_t]
         );
     };
@@ -252,12 +249,72 @@ macro_rules! invoke_binder_maker_macro_with_type {
             $consume_v,
             $fn,
             |v, v_size| {
-                let v_vec = unsafe { std::slice::from_raw_parts(v, v_size as usize) }.to_vec();
-                Ok(Some(Blob(v_vec)))
+                let v_slice = unsafe { std::slice::from_raw_parts(v, v_size as usize) };
+                Ok(Some(Blob(v_slice.into())))
             },
             [v @ *const cass_byte_t, v_size @ size_t]
         );
     };
+    (custom, $macro_name:ident, $this:ty, $consume_v:expr, $fn:ident) => {
+        $macro_name!(
+            $this,
+            $consume_v,
+            $fn,
+            |class_name, v, v_size| {
+                let class_name = unsafe { ptr_to_cstr(class_name) }.unwrap();
+                let v_slice = unsafe { std::slice::from_raw_parts(v, v_size as usize) };
+                Ok(Some(Custom {
+                    class_name: class_name.into(),
+                    value: v_slice.into(),
+                }))
+            },
+            [class_name @ *const std::os::raw::c_char, v @ *const cass_byte_t, v_size @ size_t]
+        );
+    };
+    (custom_n, $macro_name:ident, $this:ty, $consume_v:expr, $fn:ident) => {
+        $macro_name!(
+            $this,
+            $consume_v,
+            $fn,
+            |class_name, class_name_length, v, v_size| {
+                let class_name = unsafe { ptr_to_cstr_n(class_name, class_name_length) }.unwrap();
+                let v_slice = unsafe { std::slice::from_raw_parts(v, v_size as usize) };
+                Ok(Some(Custom {
+                    class_name: class_name.into(),
+                    value: v_slice.into(),
+                }))
+            },
+            [
+                class_name @ *const std::os::raw::c_char,
+                class_name_length @ size_t,
+                v @ *const cass_byte_t,
+                v_size @ size_t
+            ]
+        );
+    };
+    (serialized, $macro_name:ident, $this:ty, $consume_v:expr, $fn:ident) => {
+        $macro_name!(
+            $this,
+            $consume_v,
+            $fn,
+            |data_type, v, v_size| {
+                let Some(data_type) = ArcFFI::cloned_from_ptr(data_type) else {
+                    tracing::error!("Provided null data type pointer to {}!", stringify!($fn));
+                    return Err(CassError::CASS_ERROR_LIB_BAD_PARAMS);
+                };
+                let v_slice = unsafe { std::slice::from_raw_parts(v, v_size as usize) };
+                Ok(Some(Serialized {
+                    data_type,
+                    value: v_slice.into(),
+                }))
+            },
+            [
+                data_type @ CassBorrowedSharedPtr<crate::cass_types::CassDataType, CConst>,
+                v @ *const cass_byte_t,
+                v_size @ size_t
+            ]
+        );
+    };
     (uuid, $macro_name:ident, $this:ty, $consume_v:expr, $fn:ident) => {
         $macro_name!(
             $this,
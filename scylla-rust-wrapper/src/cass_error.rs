@@ -38,13 +38,26 @@ impl ToCassError for ExecutionError {
             ExecutionError::PrepareError(e) => e.to_cass_error(),
             ExecutionError::LastAttemptError(e) => e.to_cass_error(),
             ExecutionError::UseKeyspaceError(_) => CassError::CASS_ERROR_LIB_UNABLE_TO_SET_KEYSPACE,
-            ExecutionError::SchemaAgreementError(_) => CassError::CASS_ERROR_LIB_INVALID_STATE,
+            ExecutionError::SchemaAgreementError(e) => e.to_cass_error(),
             // ExecutionError is non_exhaustive
             _ => CassError::CASS_ERROR_LAST_ENTRY,
         }
     }
 }
 
+impl ToCassError for RequestError {
+    fn to_cass_error(&self) -> CassError {
+        match self {
+            RequestError::EmptyPlan => CassError::CASS_ERROR_LIB_NO_HOSTS_AVAILABLE,
+            RequestError::ConnectionPoolError(e) => e.to_cass_error(),
+            RequestError::RequestTimeout(_) => CassError::CASS_ERROR_LIB_REQUEST_TIMED_OUT,
+            RequestError::LastAttemptError(e) => e.to_cass_error(),
+            // RequestError is non_exhaustive
+            _ => CassError::CASS_ERROR_LAST_ENTRY,
+        }
+    }
+}
+
 impl ToCassError for ConnectionPoolError {
     fn to_cass_error(&self) -> CassError {
         CassError::CASS_ERROR_LIB_NO_HOSTS_AVAILABLE
@@ -200,6 +213,17 @@ impl ToCassError for MetadataError {
     }
 }
 
+impl ToCassError for SchemaAgreementError {
+    fn to_cass_error(&self) -> CassError {
+        match self {
+            SchemaAgreementError::ConnectionPoolError(e) => e.to_cass_error(),
+            SchemaAgreementError::Timeout(_) => CassError::CASS_ERROR_LIB_REQUEST_TIMED_OUT,
+            // Remaining errors mean that the schema versions could not be fetched or parsed.
+            _ => CassError::CASS_ERROR_LIB_INVALID_STATE,
+        }
+    }
+}
+
 impl ToCassError for BadKeyspaceName {
     fn to_cass_error(&self) -> CassError {
         match self {
@@ -263,6 +287,12 @@ impl CassErrorMessage for NewSessionError {
     }
 }
 
+impl CassErrorMessage for SchemaAgreementError {
+    fn msg(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl CassErrorMessage for BadKeyspaceName {
     fn msg(&self) -> String {
         self.to_string()
@@ -274,3 +304,40 @@ impl CassErrorMessage for str {
         self.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::testing::assert_cass_error_eq;
+
+    #[test]
+    fn test_schema_agreement_error_to_cass_error() {
+        let timeout = SchemaAgreementError::Timeout(Duration::from_secs(10));
+        let pool_error =
+            SchemaAgreementError::ConnectionPoolError(ConnectionPoolError::Initializing);
+
+        unsafe {
+            assert_cass_error_eq!(
+                timeout.to_cass_error(),
+                CassError::CASS_ERROR_LIB_REQUEST_TIMED_OUT
+            );
+
+            assert_cass_error_eq!(
+                pool_error.to_cass_error(),
+                CassError::CASS_ERROR_LIB_NO_HOSTS_AVAILABLE
+            );
+
+            // Schema agreement awaited after a schema change is mapped the same way.
+            assert_cass_error_eq!(
+                ExecutionError::SchemaAgreementError(timeout).to_cass_error(),
+                CassError::CASS_ERROR_LIB_REQUEST_TIMED_OUT
+            );
+            assert_cass_error_eq!(
+                ExecutionError::SchemaAgreementError(pool_error).to_cass_error(),
+                CassError::CASS_ERROR_LIB_NO_HOSTS_AVAILABLE
+            );
+        }
+    }
+}
@@ -6,16 +6,17 @@ use scylla::frame::response::result::ColumnType;
 use scylla::frame::types::{Consistency, SerialConsistency};
 use scylla::statement::batch::BatchType;
 use std::cell::UnsafeCell;
+use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::os::raw::c_char;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock, RwLock};
 
 pub(crate) use crate::cass_batch_types::CassBatchType;
 pub(crate) use crate::cass_consistency_types::CassConsistency;
 pub(crate) use crate::cass_data_types::CassValueType;
 
-#[derive(Clone, Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct UDTDataType {
     // Vec to preserve the order of types
     pub field_types: Vec<(String, Arc<CassDataType>)>,
@@ -107,8 +108,7 @@ impl Default for UDTDataType {
     }
 }
 
-#[derive(Clone, Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum MapDataType {
     Untyped,
     Key(Arc<CassDataType>),
@@ -117,12 +117,11 @@ pub enum MapDataType {
 
 #[derive(Debug)]
 pub struct CassColumnSpec {
-    pub name: String,
+    pub name: Arc<str>,
     pub data_type: Arc<CassDataType>,
 }
 
-#[derive(Clone, Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CassDataTypeInner {
     Value(CassValueType),
     UDT(UDTDataType),
@@ -153,6 +152,11 @@ impl CassDataTypeInner {
     /// Checks for equality during typechecks.
     ///
     /// This takes into account the fact that tuples/collections may be untyped.
+    ///
+    /// Frozen-ness is deliberately not compared. Frozen and non-frozen values
+    /// are serialized the same way, so the server never rejects a value because of it.
+    /// Moreover, data types created via `cass_data_type_new` are non-frozen,
+    /// and cpp-driver accepts them for frozen columns - validating it would break such users.
     pub fn typecheck_equals(&self, other: &CassDataTypeInner) -> bool {
         match self {
             CassDataTypeInner::Value(t) => *t == other.get_value_type(),
@@ -224,9 +228,10 @@ impl CassDataTypeInner {
                 }
                 _ => false,
             },
-            CassDataTypeInner::Custom(_) => {
-                unimplemented!("Cpp-rust-driver does not support custom types!")
-            }
+            CassDataTypeInner::Custom(class_name) => match other {
+                CassDataTypeInner::Custom(other_class_name) => class_name == other_class_name,
+                _ => false,
+            },
         }
     }
 }
@@ -235,14 +240,13 @@ impl CassDataTypeInner {
 #[repr(transparent)]
 pub struct CassDataType(UnsafeCell<CassDataTypeInner>);
 
-/// PartialEq and Eq for test purposes.
-#[cfg(test)]
+/// Strict structural equality - unlike `typecheck_equals`, untyped collections
+/// and frozen-ness are taken into account.
 impl PartialEq for CassDataType {
     fn eq(&self, other: &Self) -> bool {
         unsafe { self.get_unchecked() == other.get_unchecked() }
     }
 }
-#[cfg(test)]
 impl Eq for CassDataType {}
 
 unsafe impl Sync for CassDataType {}
@@ -373,6 +377,75 @@ impl CassDataTypeInner {
         }
     }
 
+    /// Renders the type the way it is written in CQL, e.g. `frozen<map<text, list<int>>>`.
+    fn write_cql(&self, out: &mut String) {
+        fn write_sub_types<'a>(
+            out: &mut String,
+            name: &str,
+            sub_types: impl IntoIterator<Item = &'a Arc<CassDataType>>,
+        ) {
+            out.push_str(name);
+            let mut sub_types = sub_types.into_iter().peekable();
+            // Untyped collections and tuples are rendered without the sub types.
+            if sub_types.peek().is_none() {
+                return;
+            }
+            out.push('<');
+            for (i, sub_type) in sub_types.enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                unsafe { sub_type.get_unchecked() }.write_cql(out);
+            }
+            out.push('>');
+        }
+
+        let frozen = match self {
+            CassDataTypeInner::UDT(udt) => udt.frozen,
+            CassDataTypeInner::List { frozen, .. }
+            | CassDataTypeInner::Set { frozen, .. }
+            | CassDataTypeInner::Map { frozen, .. } => *frozen,
+            _ => false,
+        };
+        if frozen {
+            out.push_str("frozen<");
+        }
+
+        match self {
+            CassDataTypeInner::Value(value_type) => out.push_str(value_type_cql_name(*value_type)),
+            CassDataTypeInner::UDT(udt) => {
+                if !udt.keyspace.is_empty() {
+                    out.push_str(&udt.keyspace);
+                    out.push('.');
+                }
+                out.push_str(&udt.name);
+            }
+            CassDataTypeInner::List { typ, .. } => write_sub_types(out, "list", typ),
+            CassDataTypeInner::Set { typ, .. } => write_sub_types(out, "set", typ),
+            CassDataTypeInner::Map { typ, .. } => match typ {
+                MapDataType::Untyped => write_sub_types(out, "map", []),
+                MapDataType::Key(k) => write_sub_types(out, "map", [k]),
+                MapDataType::KeyAndValue(k, v) => write_sub_types(out, "map", [k, v]),
+            },
+            CassDataTypeInner::Tuple(types) => write_sub_types(out, "tuple", types),
+            CassDataTypeInner::Custom(class_name) => {
+                out.push('\'');
+                out.push_str(class_name);
+                out.push('\'');
+            }
+        }
+
+        if frozen {
+            out.push('>');
+        }
+    }
+
+    pub(crate) fn to_cql_string(&self) -> String {
+        let mut out = String::new();
+        self.write_cql(&mut out);
+        out
+    }
+
     pub fn get_value_type(&self) -> CassValueType {
         match &self {
             CassDataTypeInner::Value(value_data_type) => *value_data_type,
@@ -386,7 +459,98 @@ impl CassDataTypeInner {
     }
 }
 
-pub fn get_column_type(column_type: &ColumnType) -> CassDataType {
+fn value_type_cql_name(value_type: CassValueType) -> &'static str {
+    match value_type {
+        CassValueType::CASS_VALUE_TYPE_ASCII => "ascii",
+        CassValueType::CASS_VALUE_TYPE_BIGINT => "bigint",
+        CassValueType::CASS_VALUE_TYPE_BLOB => "blob",
+        CassValueType::CASS_VALUE_TYPE_BOOLEAN => "boolean",
+        CassValueType::CASS_VALUE_TYPE_COUNTER => "counter",
+        CassValueType::CASS_VALUE_TYPE_DECIMAL => "decimal",
+        CassValueType::CASS_VALUE_TYPE_DOUBLE => "double",
+        CassValueType::CASS_VALUE_TYPE_FLOAT => "float",
+        CassValueType::CASS_VALUE_TYPE_INT => "int",
+        CassValueType::CASS_VALUE_TYPE_TEXT => "text",
+        CassValueType::CASS_VALUE_TYPE_TIMESTAMP => "timestamp",
+        CassValueType::CASS_VALUE_TYPE_UUID => "uuid",
+        CassValueType::CASS_VALUE_TYPE_VARCHAR => "varchar",
+        CassValueType::CASS_VALUE_TYPE_VARINT => "varint",
+        CassValueType::CASS_VALUE_TYPE_TIMEUUID => "timeuuid",
+        CassValueType::CASS_VALUE_TYPE_INET => "inet",
+        CassValueType::CASS_VALUE_TYPE_DATE => "date",
+        CassValueType::CASS_VALUE_TYPE_TIME => "time",
+        CassValueType::CASS_VALUE_TYPE_SMALL_INT => "smallint",
+        CassValueType::CASS_VALUE_TYPE_TINY_INT => "tinyint",
+        CassValueType::CASS_VALUE_TYPE_DURATION => "duration",
+        CassValueType::CASS_VALUE_TYPE_LIST => "list",
+        CassValueType::CASS_VALUE_TYPE_SET => "set",
+        CassValueType::CASS_VALUE_TYPE_MAP => "map",
+        CassValueType::CASS_VALUE_TYPE_TUPLE => "tuple",
+        _ => "unknown",
+    }
+}
+
+/// Native types and collections of native types. Data types of these are shared
+/// between all results and metadata, as there are only a few of them.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum SimpleColumnType {
+    Native(CassValueType),
+    List(CassValueType, bool),
+    Set(CassValueType, bool),
+    Map(CassValueType, CassValueType, bool),
+}
+
+impl SimpleColumnType {
+    fn from_column_type(column_type: &ColumnType) -> Option<Self> {
+        use CollectionType::*;
+        use ColumnType::*;
+        let native = |column_type: &ColumnType| match column_type {
+            Native(native) => Some(native_type_to_cass_value_type(native)),
+            _ => None,
+        };
+        match column_type {
+            Native(native) => Some(Self::Native(native_type_to_cass_value_type(native))),
+            Collection {
+                typ: List(typ),
+                frozen,
+            } => Some(Self::List(native(typ)?, *frozen)),
+            Collection {
+                typ: Set(typ),
+                frozen,
+            } => Some(Self::Set(native(typ)?, *frozen)),
+            Collection {
+                typ: Map(key, value),
+                frozen,
+            } => Some(Self::Map(native(key)?, native(value)?, *frozen)),
+            _ => None,
+        }
+    }
+}
+
+static SIMPLE_DATA_TYPES: LazyLock<RwLock<HashMap<SimpleColumnType, Arc<CassDataType>>>> =
+    LazyLock::new(Default::default);
+
+/// Converts rust-driver's column type to data type exposed to the user.
+///
+/// Data types of simple types (see [`SimpleColumnType`]) are deduplicated, i.e. the same
+/// instance is returned for identical types. It is safe, since the data types
+/// returned from results and metadata are immutable.
+pub fn get_column_type(column_type: &ColumnType) -> Arc<CassDataType> {
+    let Some(simple_type) = SimpleColumnType::from_column_type(column_type) else {
+        return Arc::new(build_column_type(column_type));
+    };
+
+    if let Some(data_type) = SIMPLE_DATA_TYPES.read().unwrap().get(&simple_type) {
+        return Arc::clone(data_type);
+    }
+    let mut simple_data_types = SIMPLE_DATA_TYPES.write().unwrap();
+    let data_type = simple_data_types
+        .entry(simple_type)
+        .or_insert_with(|| Arc::new(build_column_type(column_type)));
+    Arc::clone(data_type)
+}
+
+fn build_column_type(column_type: &ColumnType) -> CassDataType {
     use CollectionType::*;
     use ColumnType::*;
     let inner = match column_type {
@@ -395,7 +559,7 @@ pub fn get_column_type(column_type: &ColumnType) -> CassDataType {
             typ: List(boxed_type),
             frozen,
         } => CassDataTypeInner::List {
-            typ: Some(Arc::new(get_column_type(boxed_type.as_ref()))),
+            typ: Some(get_column_type(boxed_type.as_ref())),
             frozen: *frozen,
         },
         Collection {
@@ -403,8 +567,8 @@ pub fn get_column_type(column_type: &ColumnType) -> CassDataType {
             frozen,
         } => CassDataTypeInner::Map {
             typ: MapDataType::KeyAndValue(
-                Arc::new(get_column_type(key.as_ref())),
-                Arc::new(get_column_type(value.as_ref())),
+                get_column_type(key.as_ref()),
+                get_column_type(value.as_ref()),
             ),
             frozen: *frozen,
         },
@@ -412,29 +576,20 @@ pub fn get_column_type(column_type: &ColumnType) -> CassDataType {
             typ: Set(boxed_type),
             frozen,
         } => CassDataTypeInner::Set {
-            typ: Some(Arc::new(get_column_type(boxed_type.as_ref()))),
+            typ: Some(get_column_type(boxed_type.as_ref())),
             frozen: *frozen,
         },
         UserDefinedType { definition, frozen } => CassDataTypeInner::UDT(UDTDataType {
             field_types: definition
                 .field_types
                 .iter()
-                .map(|(name, col_type)| {
-                    (
-                        name.clone().into_owned(),
-                        Arc::new(get_column_type(col_type)),
-                    )
-                })
+                .map(|(name, col_type)| (name.clone().into_owned(), get_column_type(col_type)))
                 .collect(),
             keyspace: definition.keyspace.clone().into_owned(),
             name: definition.name.clone().into_owned(),
             frozen: *frozen,
         }),
-        Tuple(v) => CassDataTypeInner::Tuple(
-            v.iter()
-                .map(|col_type| Arc::new(get_column_type(col_type)))
-                .collect(),
-        ),
+        Tuple(v) => CassDataTypeInner::Tuple(v.iter().map(get_column_type).collect()),
 
         // ColumnType is non_exhaustive.
         _ => CassDataTypeInner::Value(CassValueType::CASS_VALUE_TYPE_UNKNOWN),
@@ -539,6 +694,54 @@ pub unsafe extern "C" fn cass_data_type_is_frozen(
     is_frozen as cass_bool_t
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_data_type_equals(
+    data_type: CassBorrowedSharedPtr<CassDataType, CConst>,
+    other_data_type: CassBorrowedSharedPtr<CassDataType, CConst>,
+) -> cass_bool_t {
+    let (Some(data_type), Some(other_data_type)) =
+        (ArcFFI::as_ref(data_type), ArcFFI::as_ref(other_data_type))
+    else {
+        tracing::error!("Provided null data type pointer to cass_data_type_equals!");
+        return cass_false;
+    };
+
+    (data_type == other_data_type) as cass_bool_t
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_data_type_hash(
+    data_type: CassBorrowedSharedPtr<CassDataType, CConst>,
+) -> cass_uint64_t {
+    let Some(data_type) = ArcFFI::as_ref(data_type) else {
+        tracing::error!("Provided null data type pointer to cass_data_type_hash!");
+        return 0;
+    };
+
+    // The CQL representation is the same for all equal data types. The UDTs differing only
+    // in the fields have the same hash, which is allowed.
+    let mut hasher = DefaultHasher::new();
+    unsafe { data_type.get_unchecked() }
+        .to_cql_string()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_data_type_to_string(
+    data_type: CassBorrowedSharedPtr<CassDataType, CConst>,
+    buffer: *mut c_char,
+    buffer_size: size_t,
+) -> size_t {
+    let Some(data_type) = ArcFFI::as_ref(data_type) else {
+        tracing::error!("Provided null data type pointer to cass_data_type_to_string!");
+        return 0;
+    };
+
+    let rendered = unsafe { data_type.get_unchecked() }.to_cql_string();
+    unsafe { write_str_to_c_buffer(&rendered, buffer, buffer_size) }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_data_type_type_name(
     data_type: CassBorrowedSharedPtr<CassDataType, CConst>,
@@ -935,6 +1138,15 @@ impl TryFrom<CassConsistency> for SerialConsistency {
     }
 }
 
+impl From<SerialConsistency> for CassConsistency {
+    fn from(serial: SerialConsistency) -> CassConsistency {
+        match serial {
+            SerialConsistency::Serial => CassConsistency::CASS_CONSISTENCY_SERIAL,
+            SerialConsistency::LocalSerial => CassConsistency::CASS_CONSISTENCY_LOCAL_SERIAL,
+        }
+    }
+}
+
 pub fn make_batch_type(type_: CassBatchType) -> Option<BatchType> {
     match type_ {
         CassBatchType::CASS_BATCH_TYPE_LOGGED => Some(BatchType::Logged),
@@ -943,3 +1155,118 @@ pub fn make_batch_type(type_: CassBatchType) -> Option<BatchType> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scylla::cluster::metadata::UserDefinedType;
+
+    #[test]
+    fn test_simple_data_types_are_deduplicated() {
+        let list_of_ints = || ColumnType::Collection {
+            frozen: false,
+            typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Int))),
+        };
+        assert!(Arc::ptr_eq(
+            &get_column_type(&ColumnType::Native(NativeType::Text)),
+            &get_column_type(&ColumnType::Native(NativeType::Text))
+        ));
+        assert!(Arc::ptr_eq(
+            &get_column_type(&list_of_ints()),
+            &get_column_type(&list_of_ints())
+        ));
+        // Element type of the collection is shared too.
+        let CassDataTypeInner::List { typ: Some(typ), .. } =
+            (unsafe { get_column_type(&list_of_ints()).get_unchecked() })
+        else {
+            panic!("Expected list data type");
+        };
+        assert!(Arc::ptr_eq(
+            typ,
+            &get_column_type(&ColumnType::Native(NativeType::Int))
+        ));
+
+        // Frozen-ness is taken into account.
+        let frozen_list_of_ints = ColumnType::Collection {
+            frozen: true,
+            typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Int))),
+        };
+        assert!(!Arc::ptr_eq(
+            &get_column_type(&list_of_ints()),
+            &get_column_type(&frozen_list_of_ints)
+        ));
+
+        // UDTs are not deduplicated.
+        let udt = ColumnType::UserDefinedType {
+            frozen: false,
+            definition: Arc::new(UserDefinedType {
+                name: "udt".into(),
+                keyspace: "ks".into(),
+                field_types: vec![("a".into(), ColumnType::Native(NativeType::Int))],
+            }),
+        };
+        assert!(!Arc::ptr_eq(&get_column_type(&udt), &get_column_type(&udt)));
+    }
+
+    #[test]
+    fn test_data_type_cql_string_equality_and_hash() {
+        let map_type = || ColumnType::Collection {
+            frozen: true,
+            typ: CollectionType::Map(
+                Box::new(ColumnType::Native(NativeType::Text)),
+                Box::new(ColumnType::Collection {
+                    frozen: false,
+                    typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Int))),
+                }),
+            ),
+        };
+        let map_data_type = get_column_type(&map_type());
+        assert_eq!(
+            unsafe { map_data_type.get_unchecked() }.to_cql_string(),
+            "frozen<map<text, list<int>>>"
+        );
+
+        let untyped_tuple = CassDataType::new_arced(CassDataTypeInner::Tuple(Vec::new()));
+        assert_eq!(
+            unsafe { untyped_tuple.get_unchecked() }.to_cql_string(),
+            "tuple"
+        );
+
+        unsafe {
+            let other_map_data_type = get_column_type(&map_type());
+            assert_eq!(
+                cass_data_type_equals(
+                    ArcFFI::as_ptr(&map_data_type),
+                    ArcFFI::as_ptr(&other_map_data_type)
+                ),
+                cass_true
+            );
+            assert_eq!(
+                cass_data_type_hash(ArcFFI::as_ptr(&map_data_type)),
+                cass_data_type_hash(ArcFFI::as_ptr(&other_map_data_type))
+            );
+            assert_eq!(
+                cass_data_type_equals(
+                    ArcFFI::as_ptr(&map_data_type),
+                    ArcFFI::as_ptr(&untyped_tuple)
+                ),
+                cass_false
+            );
+
+            // The output is truncated, like with snprintf.
+            let mut buffer = [0 as c_char; 7];
+            assert_eq!(
+                cass_data_type_to_string(
+                    ArcFFI::as_ptr(&map_data_type),
+                    buffer.as_mut_ptr(),
+                    buffer.len() as size_t
+                ),
+                28
+            );
+            assert_eq!(
+                std::ffi::CStr::from_ptr(buffer.as_ptr()).to_str().unwrap(),
+                "frozen"
+            );
+        }
+    }
+}
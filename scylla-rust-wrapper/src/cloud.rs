@@ -0,0 +1,27 @@
+use scylla::client::session_builder::CloudSessionBuilder;
+use scylla::cloud::CloudTlsProvider;
+
+#[cfg(feature = "rustls")]
+const CLOUD_TLS_PROVIDER: CloudTlsProvider = CloudTlsProvider::Rustls023;
+#[cfg(not(feature = "rustls"))]
+const CLOUD_TLS_PROVIDER: CloudTlsProvider = CloudTlsProvider::OpenSsl010;
+
+/// Loads the ScyllaDB Cloud (serverless) connection profile - a YAML file defining
+/// the datacenters (with their SNI proxies and server names), authentication info
+/// and TLS material. The TLS setup and the nodes to connect to are taken from it,
+/// so neither contact points nor SSL need to be configured by the user.
+///
+/// Returns `None` (and logs the reason) if the profile could not be loaded.
+pub(crate) fn load_connection_profile(path: &str) -> Option<CloudSessionBuilder> {
+    match CloudSessionBuilder::new(path, CLOUD_TLS_PROVIDER) {
+        Ok(builder) => Some(builder),
+        Err(err) => {
+            tracing::error!(
+                "Failed to load the cloud connection profile {}: {}",
+                path,
+                err
+            );
+            None
+        }
+    }
+}
@@ -0,0 +1,545 @@
+//! Creating the cluster from a configuration file and environment variables.
+//!
+//! The configuration file consists of `key = value` lines. Values may be quoted.
+//! Lines starting with `#` are comments. Execution profiles are defined in
+//! `[profile.<name>]` sections - keys that follow belong to the profile.
+//!
+//! ```text
+//! contact_points = "127.0.0.1,127.0.0.2"
+//! port = 9042
+//! consistency = LOCAL_QUORUM
+//! local_dc = dc1
+//!
+//! [profile.analytics]
+//! request_timeout_ms = 60000
+//! consistency = ONE
+//! local_dc = dc2
+//! ```
+//!
+//! Settings outside of the profiles can be overridden with `CASS_<KEY>`
+//! environment variables, e.g. `CASS_CONTACT_POINTS` or `CASS_PASSWORD`.
+
+use crate::argconv::*;
+use crate::cass_error::CassError;
+use crate::cass_types::CassConsistency;
+use crate::cluster::*;
+use crate::exec_profile::*;
+use crate::ssl::*;
+use crate::types::*;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int, c_uint};
+
+const ENV_PREFIX: &str = "CASS_";
+
+/// Settings of the cluster that are not part of any profile.
+const CLUSTER_KEYS: &[&str] = &[
+    "contact_points",
+    "contact_points_srv",
+    "port",
+    "username",
+    "password",
+    "connect_timeout_ms",
+    "request_timeout_ms",
+    "consistency",
+    "serial_consistency",
+    "local_dc",
+    "ssl_trusted_cert",
+    "ssl_cert",
+    "ssl_private_key",
+    "ssl_private_key_password",
+    "ssl_verify_flags",
+];
+
+const PROFILE_KEYS: &[&str] = &[
+    "request_timeout_ms",
+    "consistency",
+    "serial_consistency",
+    "local_dc",
+];
+
+#[derive(Debug, Default, PartialEq)]
+struct ClusterConfig {
+    settings: Vec<(String, String)>,
+    profiles: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl ClusterConfig {
+    fn get(&self, key: &str) -> Option<&str> {
+        get_setting(&self.settings, key)
+    }
+
+    fn set(&mut self, key: &str, value: String) {
+        self.settings.retain(|(k, _)| k != key);
+        self.settings.push((key.to_owned(), value));
+    }
+}
+
+fn get_setting<'a>(settings: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    settings
+        .iter()
+        .rev()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+}
+
+fn parse_config(contents: &str) -> Result<ClusterConfig, String> {
+    let mut config = ClusterConfig::default();
+    // Index of the profile the subsequent settings belong to.
+    let mut current_profile: Option<usize> = None;
+
+    for (line_idx, line) in contents.lines().enumerate() {
+        let line_no = line_idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let Some(name) = section.trim().strip_prefix("profile.") else {
+                return Err(format!("Line {line_no}: unknown section [{section}]"));
+            };
+            if name.is_empty() {
+                return Err(format!("Line {line_no}: profile name is empty"));
+            }
+            config.profiles.push((name.to_owned(), Vec::new()));
+            current_profile = Some(config.profiles.len() - 1);
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("Line {line_no}: expected `key = value`"));
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value)
+            .to_owned();
+
+        match current_profile {
+            Some(idx) => {
+                if !PROFILE_KEYS.contains(&key.as_str()) {
+                    return Err(format!("Line {line_no}: unknown profile setting `{key}`"));
+                }
+                config.profiles[idx].1.push((key, value));
+            }
+            None => {
+                if !CLUSTER_KEYS.contains(&key.as_str()) {
+                    return Err(format!("Line {line_no}: unknown setting `{key}`"));
+                }
+                config.settings.push((key, value));
+            }
+        }
+    }
+
+    Ok(config)
+}
+
+fn apply_env_overrides(config: &mut ClusterConfig, env: impl Fn(&str) -> Option<String>) {
+    for key in CLUSTER_KEYS {
+        let env_name = format!("{}{}", ENV_PREFIX, key.to_ascii_uppercase());
+        if let Some(value) = env(&env_name) {
+            config.set(key, value);
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value of `{key}`: {value}"))
+}
+
+fn parse_consistency(key: &str, value: &str) -> Result<CassConsistency, String> {
+    const CONSISTENCIES: &[CassConsistency] = &[
+        CassConsistency::CASS_CONSISTENCY_ANY,
+        CassConsistency::CASS_CONSISTENCY_ONE,
+        CassConsistency::CASS_CONSISTENCY_TWO,
+        CassConsistency::CASS_CONSISTENCY_THREE,
+        CassConsistency::CASS_CONSISTENCY_QUORUM,
+        CassConsistency::CASS_CONSISTENCY_ALL,
+        CassConsistency::CASS_CONSISTENCY_LOCAL_QUORUM,
+        CassConsistency::CASS_CONSISTENCY_EACH_QUORUM,
+        CassConsistency::CASS_CONSISTENCY_SERIAL,
+        CassConsistency::CASS_CONSISTENCY_LOCAL_SERIAL,
+        CassConsistency::CASS_CONSISTENCY_LOCAL_ONE,
+    ];
+
+    CONSISTENCIES
+        .iter()
+        .copied()
+        .find(|c| {
+            c.as_cstr()
+                .to_bytes()
+                .eq_ignore_ascii_case(value.as_bytes())
+        })
+        .ok_or_else(|| format!("Invalid value of `{key}`: {value}"))
+}
+
+fn parse_verify_flags(value: &str) -> Result<i32, String> {
+    value
+        .split('|')
+        .try_fold(CASS_SSL_VERIFY_NONE, |flags, flag| {
+            let flag = match flag.trim().to_ascii_lowercase().as_str() {
+                "none" => CASS_SSL_VERIFY_NONE,
+                "peer_cert" => CASS_SSL_VERIFY_PEER_CERT,
+                "peer_identity" => CASS_SSL_VERIFY_PEER_IDENTITY,
+                "peer_identity_dns" => CASS_SSL_VERIFY_PEER_IDENTITY_DNS,
+                _ => return Err(format!("Invalid value of `ssl_verify_flags`: {value}")),
+            };
+            Ok(flags | flag)
+        })
+}
+
+fn check(key: &str, err: CassError) -> Result<(), String> {
+    match err {
+        CassError::CASS_OK => Ok(()),
+        err => Err(format!("Failed to apply `{key}`: {err:?}")),
+    }
+}
+
+fn read_file(key: &str, path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|err| format!("Failed to read `{key}` {path}: {err}"))
+}
+
+unsafe fn apply_ssl(
+    cluster: CassBorrowedExclusivePtr<CassCluster, CMut>,
+    config: &ClusterConfig,
+) -> Result<(), String> {
+    let ssl = unsafe { cass_ssl_new() };
+    let result = (|| {
+        if let Some(path) = config.get("ssl_trusted_cert") {
+            let cert = read_file("ssl_trusted_cert", path)?;
+            check("ssl_trusted_cert", unsafe {
+                cass_ssl_add_trusted_cert_n(
+                    ssl.borrow(),
+                    cert.as_ptr() as *const c_char,
+                    cert.len() as size_t,
+                )
+            })?;
+        }
+        if let Some(path) = config.get("ssl_cert") {
+            let cert = read_file("ssl_cert", path)?;
+            check("ssl_cert", unsafe {
+                cass_ssl_set_cert_n(
+                    ssl.borrow(),
+                    cert.as_ptr() as *const c_char,
+                    cert.len() as size_t,
+                )
+            })?;
+        }
+        if let Some(path) = config.get("ssl_private_key") {
+            let key = read_file("ssl_private_key", path)?;
+            let password = CString::new(config.get("ssl_private_key_password").unwrap_or(""))
+                .map_err(|_| "Invalid value of `ssl_private_key_password`".to_owned())?;
+            check("ssl_private_key", unsafe {
+                cass_ssl_set_private_key_n(
+                    ssl.borrow(),
+                    key.as_ptr() as *const c_char,
+                    key.len() as size_t,
+                    password.as_ptr() as *mut c_char,
+                    password.as_bytes().len() as size_t,
+                )
+            })?;
+        }
+        if let Some(flags) = config.get("ssl_verify_flags") {
+            unsafe { cass_ssl_set_verify_flags(ssl.borrow(), parse_verify_flags(flags)?) };
+        }
+        Ok(())
+    })();
+
+    if result.is_ok() {
+        unsafe { cass_cluster_set_ssl(cluster, ssl.borrow()) };
+    }
+    unsafe { cass_ssl_free(ssl) };
+
+    result
+}
+
+unsafe fn apply_profile(
+    mut cluster: CassBorrowedExclusivePtr<CassCluster, CMut>,
+    name: &str,
+    settings: &[(String, String)],
+) -> Result<(), String> {
+    let mut profile = unsafe { cass_execution_profile_new() };
+    let result = settings.iter().try_for_each(|(key, value)| {
+        let err = match key.as_str() {
+            "request_timeout_ms" => unsafe {
+                cass_execution_profile_set_request_timeout(
+                    profile.borrow_mut(),
+                    parse_number::<cass_uint64_t>(key, value)?,
+                )
+            },
+            "consistency" => unsafe {
+                cass_execution_profile_set_consistency(
+                    profile.borrow_mut(),
+                    parse_consistency(key, value)?,
+                )
+            },
+            "serial_consistency" => unsafe {
+                cass_execution_profile_set_serial_consistency(
+                    profile.borrow_mut(),
+                    parse_consistency(key, value)?,
+                )
+            },
+            "local_dc" => unsafe {
+                cass_execution_profile_set_load_balance_dc_aware_n(
+                    profile.borrow_mut(),
+                    value.as_ptr() as *const c_char,
+                    value.len() as size_t,
+                    0,
+                    cass_false,
+                )
+            },
+            // Keys are validated when the config is parsed.
+            _ => unreachable!(),
+        };
+        check(key, err)
+    });
+
+    let result = result.and_then(|()| {
+        check(name, unsafe {
+            cass_cluster_set_execution_profile_n(
+                cluster.borrow_mut(),
+                name.as_ptr() as *const c_char,
+                name.len() as size_t,
+                profile.borrow_mut(),
+            )
+        })
+    });
+    unsafe { cass_execution_profile_free(profile) };
+
+    result
+}
+
+unsafe fn apply_config(
+    mut cluster: CassBorrowedExclusivePtr<CassCluster, CMut>,
+    config: &ClusterConfig,
+) -> Result<(), String> {
+    if let Some(contact_points) = config.get("contact_points") {
+        check("contact_points", unsafe {
+            cass_cluster_set_contact_points_n(
+                cluster.borrow_mut(),
+                contact_points.as_ptr() as *const c_char,
+                contact_points.len() as size_t,
+            )
+        })?;
+    }
+    if let Some(name) = config.get("contact_points_srv") {
+        check("contact_points_srv", unsafe {
+            cass_cluster_set_contact_points_srv_n(
+                cluster.borrow_mut(),
+                name.as_ptr() as *const c_char,
+                name.len() as size_t,
+            )
+        })?;
+    }
+    if let Some(port) = config.get("port") {
+        check("port", unsafe {
+            cass_cluster_set_port(cluster.borrow_mut(), parse_number::<c_int>("port", port)?)
+        })?;
+    }
+    let username = config.get("username");
+    let password = config.get("password");
+    if username.is_some() || password.is_some() {
+        let username = username.unwrap_or("");
+        let password = password.unwrap_or("");
+        unsafe {
+            cass_cluster_set_credentials_n(
+                cluster.borrow_mut(),
+                username.as_ptr() as *const c_char,
+                username.len() as size_t,
+                password.as_ptr() as *const c_char,
+                password.len() as size_t,
+            )
+        };
+    }
+    if let Some(timeout) = config.get("connect_timeout_ms") {
+        let timeout = parse_number::<c_uint>("connect_timeout_ms", timeout)?;
+        unsafe { cass_cluster_set_connect_timeout(cluster.borrow_mut(), timeout) };
+    }
+    if let Some(timeout) = config.get("request_timeout_ms") {
+        let timeout = parse_number::<c_uint>("request_timeout_ms", timeout)?;
+        unsafe { cass_cluster_set_request_timeout(cluster.borrow_mut(), timeout) };
+    }
+    if let Some(consistency) = config.get("consistency") {
+        check("consistency", unsafe {
+            cass_cluster_set_consistency(
+                cluster.borrow_mut(),
+                parse_consistency("consistency", consistency)?,
+            )
+        })?;
+    }
+    if let Some(consistency) = config.get("serial_consistency") {
+        check("serial_consistency", unsafe {
+            cass_cluster_set_serial_consistency(
+                cluster.borrow_mut(),
+                parse_consistency("serial_consistency", consistency)?,
+            )
+        })?;
+    }
+    if let Some(local_dc) = config.get("local_dc") {
+        check("local_dc", unsafe {
+            cass_cluster_set_load_balance_dc_aware_n(
+                cluster.borrow_mut(),
+                local_dc.as_ptr() as *const c_char,
+                local_dc.len() as size_t,
+                0,
+                cass_false,
+            )
+        })?;
+    }
+    if config
+        .settings
+        .iter()
+        .any(|(key, _)| key.starts_with("ssl_"))
+    {
+        unsafe { apply_ssl(cluster.borrow_mut(), config) }?;
+    }
+    for (name, settings) in &config.profiles {
+        unsafe { apply_profile(cluster.borrow_mut(), name, settings) }?;
+    }
+
+    Ok(())
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_cluster_new_from_config(
+    path: *const c_char,
+) -> CassOwnedExclusivePtr<CassCluster, CMut> {
+    unsafe { cass_cluster_new_from_config_n(path, strlen(path)) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_cluster_new_from_config_n(
+    path: *const c_char,
+    path_length: size_t,
+) -> CassOwnedExclusivePtr<CassCluster, CMut> {
+    let mut config = match unsafe { ptr_to_cstr_n(path, path_length) } {
+        // No file - configuration is read from the environment only.
+        None | Some("") => ClusterConfig::default(),
+        Some(path) => {
+            let config = std::fs::read_to_string(path)
+                .map_err(|err| format!("Failed to read the config file {path}: {err}"))
+                .and_then(|contents| parse_config(&contents));
+            match config {
+                Ok(config) => config,
+                Err(err) => {
+                    tracing::error!("{}", err);
+                    return BoxFFI::null_mut();
+                }
+            }
+        }
+    };
+    apply_env_overrides(&mut config, |name| std::env::var(name).ok());
+
+    let mut cluster = unsafe { cass_cluster_new() };
+    if let Err(err) = unsafe { apply_config(cluster.borrow_mut(), &config) } {
+        tracing::error!("Failed to configure the cluster: {}", err);
+        unsafe { cass_cluster_free(cluster) };
+        return BoxFFI::null_mut();
+    }
+
+    cluster
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec_profile::ExecProfileName;
+    use crate::load_balancing::LoadBalancingKind;
+    use assert_matches::assert_matches;
+
+    fn owned(settings: &[(&str, &str)]) -> Vec<(String, String)> {
+        settings
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let contents = r#"
+            # Comment
+            contact_points = "127.0.0.1, 127.0.0.2"
+            PORT=9043
+            consistency = local_quorum
+
+            [profile.analytics]
+            request_timeout_ms = 60000
+            consistency = ONE
+        "#;
+
+        assert_eq!(
+            parse_config(contents).unwrap(),
+            ClusterConfig {
+                settings: owned(&[
+                    ("contact_points", "127.0.0.1, 127.0.0.2"),
+                    ("port", "9043"),
+                    ("consistency", "local_quorum"),
+                ]),
+                profiles: vec![(
+                    "analytics".to_owned(),
+                    owned(&[("request_timeout_ms", "60000"), ("consistency", "ONE")])
+                )],
+            }
+        );
+
+        assert!(parse_config("unknown = 1").is_err());
+        assert!(parse_config("port").is_err());
+        assert!(parse_config("[profile.]").is_err());
+        assert!(parse_config("[section]").is_err());
+        assert!(parse_config("[profile.p]\nport = 9042").is_err());
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let mut config = parse_config("port = 9043\nusername = user").unwrap();
+        apply_env_overrides(&mut config, |name| match name {
+            "CASS_PORT" => Some("9044".to_owned()),
+            "CASS_PASSWORD" => Some("pass".to_owned()),
+            _ => None,
+        });
+
+        assert_eq!(config.get("port"), Some("9044"));
+        assert_eq!(config.get("username"), Some("user"));
+        assert_eq!(config.get("password"), Some("pass"));
+    }
+
+    #[test]
+    fn test_apply_config() {
+        unsafe {
+            let config = parse_config(
+                "contact_points = 127.0.0.1\n\
+                 port = 9043\n\
+                 consistency = LOCAL_ONE\n\
+                 local_dc = dc1\n\
+                 [profile.p]\n\
+                 serial_consistency = LOCAL_SERIAL\n\
+                 local_dc = dc2",
+            )
+            .unwrap();
+            let mut cluster_raw = cass_cluster_new();
+            apply_config(cluster_raw.borrow_mut(), &config).unwrap();
+            {
+                let cluster = BoxFFI::as_ref(cluster_raw.borrow()).unwrap();
+                let profile = cluster
+                    .execution_profile_map()
+                    .get(&ExecProfileName::try_from("p".to_owned()).unwrap())
+                    .unwrap();
+                assert_matches!(
+                    &profile.load_balancing_config.load_balancing_kind,
+                    Some(LoadBalancingKind::DcAware { local_dc }) if local_dc == "dc2"
+                );
+            }
+
+            let config = parse_config("consistency = SOMETIMES").unwrap();
+            assert!(apply_config(cluster_raw.borrow_mut(), &config).is_err());
+            let config = parse_config("port = 100000").unwrap();
+            assert!(apply_config(cluster_raw.borrow_mut(), &config).is_err());
+
+            cass_cluster_free(cluster_raw);
+        }
+    }
+}
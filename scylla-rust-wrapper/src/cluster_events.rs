@@ -0,0 +1,422 @@
+use crate::RUNTIME;
+use crate::cass_cluster_event_types::{CassClusterEventType, CassHostListenerEvent};
+use crate::inet::CassInet;
+use crate::session::CassSession;
+use crate::types::cass_int64_t;
+use scylla::cluster::ClusterState;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::os::raw::c_void;
+use std::sync::{Weak, mpsc};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+
+// rust-driver does not expose the server events (TOPOLOGY_CHANGE/STATUS_CHANGE)
+// it receives on the control connection. Instead, we periodically inspect the
+// cluster state (which rust-driver refreshes upon such events) and compute the
+// difference between consecutive snapshots.
+const CLUSTER_EVENT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub type CassClusterEventCallback = Option<
+    unsafe extern "C" fn(
+        event: CassClusterEventType,
+        address: CassInet,
+        timestamp_ms: cass_int64_t,
+        data: *mut c_void,
+    ),
+>;
+
+pub type CassHostListenerCallback = Option<
+    unsafe extern "C" fn(event: CassHostListenerEvent, address: CassInet, data: *mut c_void),
+>;
+
+#[derive(Clone, Copy)]
+enum ListenerCallback {
+    ClusterEvent(
+        unsafe extern "C" fn(
+            event: CassClusterEventType,
+            address: CassInet,
+            timestamp_ms: cass_int64_t,
+            data: *mut c_void,
+        ),
+    ),
+    HostListener(
+        unsafe extern "C" fn(event: CassHostListenerEvent, address: CassInet, data: *mut c_void),
+    ),
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct ClusterEventListener {
+    cb: ListenerCallback,
+    data: *mut c_void,
+}
+
+// The `data` pointer is owned by the user, who is responsible for making it
+// safe to be accessed from the driver's threads (the same contract as for `CassLogCallback`).
+unsafe impl Send for ClusterEventListener {}
+unsafe impl Sync for ClusterEventListener {}
+
+impl ClusterEventListener {
+    /// Returns `None` if user provided a NULL callback.
+    pub(crate) fn new(cb: CassClusterEventCallback, data: *mut c_void) -> Option<Self> {
+        cb.map(|cb| ClusterEventListener {
+            cb: ListenerCallback::ClusterEvent(cb),
+            data,
+        })
+    }
+
+    /// Listener for `cass_cluster_set_host_listener_callback`.
+    /// Returns `None` if user provided a NULL callback.
+    pub(crate) fn new_host_listener(
+        cb: CassHostListenerCallback,
+        data: *mut c_void,
+    ) -> Option<Self> {
+        cb.map(|cb| ClusterEventListener {
+            cb: ListenerCallback::HostListener(cb),
+            data,
+        })
+    }
+
+    fn invoke(&self, event: &ClusterEvent, timestamp_ms: cass_int64_t) {
+        match self.cb {
+            ListenerCallback::ClusterEvent(cb) => unsafe {
+                cb(event.kind, event.address.into(), timestamp_ms, self.data)
+            },
+            ListenerCallback::HostListener(cb) => unsafe {
+                cb(event.host_listener_event(), event.address.into(), self.data)
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ClusterEvent {
+    pub(crate) kind: CassClusterEventType,
+    pub(crate) address: IpAddr,
+}
+
+impl ClusterEvent {
+    fn host_listener_event(&self) -> CassHostListenerEvent {
+        match self.kind {
+            CassClusterEventType::CASS_CLUSTER_EVENT_NEW_NODE => {
+                CassHostListenerEvent::CASS_HOST_LISTENER_EVENT_ADD
+            }
+            CassClusterEventType::CASS_CLUSTER_EVENT_REMOVED_NODE => {
+                CassHostListenerEvent::CASS_HOST_LISTENER_EVENT_REMOVE
+            }
+            CassClusterEventType::CASS_CLUSTER_EVENT_UP => {
+                CassHostListenerEvent::CASS_HOST_LISTENER_EVENT_UP
+            }
+            CassClusterEventType::CASS_CLUSTER_EVENT_DOWN => {
+                CassHostListenerEvent::CASS_HOST_LISTENER_EVENT_DOWN
+            }
+            // The events are computed by the driver.
+            _ => unreachable!("Unknown cluster event type: {}", self.kind.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NodeSnapshot {
+    address: IpAddr,
+    is_up: bool,
+}
+
+/// A view of the nodes in the cluster, keyed by host id.
+#[derive(Debug, Default)]
+pub(crate) struct ClusterSnapshot {
+    nodes: HashMap<uuid::Uuid, NodeSnapshot>,
+}
+
+impl ClusterSnapshot {
+    pub(crate) fn from_cluster_state(cluster_state: &ClusterState) -> Self {
+        let nodes = cluster_state
+            .get_nodes_info()
+            .iter()
+            .map(|node| {
+                (
+                    node.host_id,
+                    NodeSnapshot {
+                        address: node.address.ip(),
+                        is_up: !node.is_down(),
+                    },
+                )
+            })
+            .collect();
+
+        ClusterSnapshot { nodes }
+    }
+
+    /// Computes the events that lead from `self` to `newer` snapshot.
+    pub(crate) fn diff(&self, newer: &ClusterSnapshot) -> Vec<ClusterEvent> {
+        let mut events = Vec::new();
+
+        for (host_id, old) in &self.nodes {
+            match newer.nodes.get(host_id) {
+                None => events.push(ClusterEvent {
+                    kind: CassClusterEventType::CASS_CLUSTER_EVENT_REMOVED_NODE,
+                    address: old.address,
+                }),
+                // The node changed its address - report it as a replacement.
+                Some(new) if new.address != old.address => {
+                    events.push(ClusterEvent {
+                        kind: CassClusterEventType::CASS_CLUSTER_EVENT_REMOVED_NODE,
+                        address: old.address,
+                    });
+                    events.push(ClusterEvent {
+                        kind: CassClusterEventType::CASS_CLUSTER_EVENT_NEW_NODE,
+                        address: new.address,
+                    });
+                }
+                Some(new) if new.is_up != old.is_up => events.push(ClusterEvent {
+                    kind: if new.is_up {
+                        CassClusterEventType::CASS_CLUSTER_EVENT_UP
+                    } else {
+                        CassClusterEventType::CASS_CLUSTER_EVENT_DOWN
+                    },
+                    address: new.address,
+                }),
+                Some(_) => (),
+            }
+        }
+
+        for (host_id, new) in &newer.nodes {
+            if !self.nodes.contains_key(host_id) {
+                events.push(ClusterEvent {
+                    kind: CassClusterEventType::CASS_CLUSTER_EVENT_NEW_NODE,
+                    address: new.address,
+                });
+            }
+        }
+
+        events
+    }
+}
+
+fn now_ms() -> cass_int64_t {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Current time is before UNIX_EPOCH")
+        .as_millis() as cass_int64_t
+}
+
+/// Handle to the background task detecting cluster events.
+/// The task is aborted once the handle is dropped (i.e. when the session is closed),
+/// which also stops the thread invoking the callbacks.
+pub(crate) struct ClusterEventWatcher(JoinHandle<()>);
+
+impl Drop for ClusterEventWatcher {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Spawns the thread invoking the listeners with the events sent over the returned channel.
+/// The callbacks run off the tokio runtime, so they may call the blocking driver functions
+/// (e.g. `cass_session_get_metrics`), and a slow callback does not stall the runtime.
+/// The thread exits once the sender is dropped.
+fn spawn_callback_thread(
+    listeners: Vec<ClusterEventListener>,
+) -> mpsc::Sender<(Vec<ClusterEvent>, cass_int64_t)> {
+    let (sender, receiver) = mpsc::channel::<(Vec<ClusterEvent>, cass_int64_t)>();
+    std::thread::Builder::new()
+        .name("cluster-events".to_owned())
+        .spawn(move || {
+            for (events, timestamp_ms) in receiver {
+                for event in &events {
+                    for listener in &listeners {
+                        listener.invoke(event, timestamp_ms);
+                    }
+                }
+            }
+        })
+        .expect("Failed to spawn the cluster events thread");
+
+    sender
+}
+
+impl ClusterEventWatcher {
+    pub(crate) fn spawn(
+        session: Weak<CassSession>,
+        initial_snapshot: ClusterSnapshot,
+        listeners: Vec<ClusterEventListener>,
+    ) -> Self {
+        let sender = spawn_callback_thread(listeners);
+        let join_handle = RUNTIME.spawn(async move {
+            let mut snapshot = initial_snapshot;
+            let mut interval = tokio::time::interval(CLUSTER_EVENT_POLL_INTERVAL);
+            // The first tick completes immediately.
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                let Some(session) = session.upgrade() else {
+                    return;
+                };
+                let current = {
+                    let session_guard = session.read().await;
+                    let Some(session_inner) = session_guard.as_ref() else {
+                        return;
+                    };
+                    ClusterSnapshot::from_cluster_state(&session_inner.cluster_state())
+                };
+
+                let events = snapshot.diff(&current);
+                if !events.is_empty() && sender.send((events, now_ms())).is_err() {
+                    return;
+                }
+                snapshot = current;
+            }
+        });
+
+        ClusterEventWatcher(join_handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn snapshot(nodes: &[(u128, [u8; 4], bool)]) -> ClusterSnapshot {
+        ClusterSnapshot {
+            nodes: nodes
+                .iter()
+                .map(|&(id, ip, is_up)| {
+                    (
+                        uuid::Uuid::from_u128(id),
+                        NodeSnapshot {
+                            address: IpAddr::V4(Ipv4Addr::from(ip)),
+                            is_up,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn event(kind: CassClusterEventType, ip: [u8; 4]) -> ClusterEvent {
+        ClusterEvent {
+            kind,
+            address: IpAddr::V4(Ipv4Addr::from(ip)),
+        }
+    }
+
+    #[test]
+    fn test_cluster_snapshot_diff() {
+        let initial = snapshot(&[(1, [127, 0, 0, 1], true), (2, [127, 0, 0, 2], true)]);
+
+        // No changes.
+        assert!(initial.diff(&initial).is_empty());
+
+        // Node went down.
+        let node_down = snapshot(&[(1, [127, 0, 0, 1], true), (2, [127, 0, 0, 2], false)]);
+        assert_eq!(
+            initial.diff(&node_down),
+            vec![event(
+                CassClusterEventType::CASS_CLUSTER_EVENT_DOWN,
+                [127, 0, 0, 2]
+            )]
+        );
+
+        // ...and back up.
+        assert_eq!(
+            node_down.diff(&initial),
+            vec![event(
+                CassClusterEventType::CASS_CLUSTER_EVENT_UP,
+                [127, 0, 0, 2]
+            )]
+        );
+
+        // Node added and another one removed.
+        let replaced = snapshot(&[(1, [127, 0, 0, 1], true), (3, [127, 0, 0, 3], true)]);
+        assert_eq!(
+            initial.diff(&replaced),
+            vec![
+                event(
+                    CassClusterEventType::CASS_CLUSTER_EVENT_REMOVED_NODE,
+                    [127, 0, 0, 2]
+                ),
+                event(
+                    CassClusterEventType::CASS_CLUSTER_EVENT_NEW_NODE,
+                    [127, 0, 0, 3]
+                ),
+            ]
+        );
+
+        // Node changed its address.
+        let moved = snapshot(&[(1, [127, 0, 0, 1], true), (2, [127, 0, 0, 4], true)]);
+        assert_eq!(
+            initial.diff(&moved),
+            vec![
+                event(
+                    CassClusterEventType::CASS_CLUSTER_EVENT_REMOVED_NODE,
+                    [127, 0, 0, 2]
+                ),
+                event(
+                    CassClusterEventType::CASS_CLUSTER_EVENT_NEW_NODE,
+                    [127, 0, 0, 4]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_host_listener_event() {
+        let host_listener_event = |kind| event(kind, [127, 0, 0, 1]).host_listener_event();
+
+        assert_eq!(
+            host_listener_event(CassClusterEventType::CASS_CLUSTER_EVENT_NEW_NODE),
+            CassHostListenerEvent::CASS_HOST_LISTENER_EVENT_ADD
+        );
+        assert_eq!(
+            host_listener_event(CassClusterEventType::CASS_CLUSTER_EVENT_REMOVED_NODE),
+            CassHostListenerEvent::CASS_HOST_LISTENER_EVENT_REMOVE
+        );
+        assert_eq!(
+            host_listener_event(CassClusterEventType::CASS_CLUSTER_EVENT_UP),
+            CassHostListenerEvent::CASS_HOST_LISTENER_EVENT_UP
+        );
+        assert_eq!(
+            host_listener_event(CassClusterEventType::CASS_CLUSTER_EVENT_DOWN),
+            CassHostListenerEvent::CASS_HOST_LISTENER_EVENT_DOWN
+        );
+    }
+
+    #[test]
+    fn test_callbacks_invoked_off_runtime() {
+        unsafe extern "C" fn callback(
+            _event: CassClusterEventType,
+            _address: CassInet,
+            _timestamp_ms: cass_int64_t,
+            data: *mut c_void,
+        ) {
+            let sender = unsafe { &*(data as *const mpsc::Sender<bool>) };
+            sender
+                .send(tokio::runtime::Handle::try_current().is_err())
+                .unwrap();
+        }
+
+        let (off_runtime_sender, off_runtime_receiver) = mpsc::channel();
+        let listener = ClusterEventListener::new(
+            Some(callback),
+            &off_runtime_sender as *const mpsc::Sender<bool> as *mut c_void,
+        )
+        .unwrap();
+
+        let sender = spawn_callback_thread(vec![listener]);
+        let events = vec![event(
+            CassClusterEventType::CASS_CLUSTER_EVENT_UP,
+            [127, 0, 0, 1],
+        )];
+        sender.send((events, now_ms())).unwrap();
+
+        assert!(
+            off_runtime_receiver
+                .recv_timeout(Duration::from_secs(1))
+                .unwrap()
+        );
+        drop(sender);
+    }
+}
@@ -1,10 +1,14 @@
+use crate::argconv::*;
 use crate::cass_collection_types::CassCollectionType;
 use crate::cass_error::CassError;
 use crate::cass_types::{CassDataType, CassDataTypeInner, MapDataType};
+use crate::inet::CassInet;
 use crate::types::*;
-use crate::value::CassCqlValue;
-use crate::{argconv::*, value};
+use crate::uuid::CassUuid;
+use crate::value::{CassCqlValue, SerializedItems};
 use std::convert::TryFrom;
+use std::net::IpAddr;
+use std::os::raw::c_char;
 use std::sync::Arc;
 use std::sync::LazyLock;
 
@@ -32,8 +36,121 @@ static UNTYPED_MAP_TYPE: LazyLock<Arc<CassDataType>> = LazyLock::new(|| {
 pub struct CassCollection {
     pub collection_type: CassCollectionType,
     pub data_type: Option<Arc<CassDataType>>,
-    pub capacity: usize,
-    pub items: Vec<CassCqlValue>,
+    items: CollectionItems,
+}
+
+/// The items are serialized when they are appended, so converting the collection
+/// to the [`CassCqlValue`] does not need to copy the values one by one.
+#[derive(Clone, Default)]
+struct CollectionItems {
+    // Number of the appended items. For maps, keys and values are counted separately -
+    // keys are appended at even, and values at odd positions.
+    count: usize,
+    bytes: Vec<u8>,
+    // See `SerializedItems`.
+    samples: Vec<CassCqlValue>,
+    value_samples: Vec<CassCqlValue>,
+}
+
+// Estimated size of a serialized item (with its length prefix), used to turn
+// the item count provided by user into the capacity of the serialized items.
+const ESTIMATED_SERIALIZED_ITEM_SIZE: usize = 16;
+// The item count is only a hint - don't let a bogus one allocate too much upfront.
+const MAX_RESERVED_BYTES: usize = 1 << 20;
+
+impl CollectionItems {
+    fn with_capacity(item_count: usize) -> Self {
+        let reserved_bytes = item_count
+            .saturating_mul(ESTIMATED_SERIALIZED_ITEM_SIZE)
+            .min(MAX_RESERVED_BYTES);
+        CollectionItems {
+            bytes: Vec::with_capacity(reserved_bytes),
+            ..Default::default()
+        }
+    }
+
+    fn add_sample(samples: &mut Vec<CassCqlValue>, value: &CassCqlValue) {
+        if !samples.iter().any(|sample| have_same_type(sample, value)) {
+            samples.push(value.clone());
+        }
+    }
+
+    /// Iterates over the serialized items, each with its length prefix.
+    fn serialized_items(&self) -> impl Iterator<Item = &[u8]> {
+        let mut rest = self.bytes.as_slice();
+        std::iter::from_fn(move || {
+            let (len, _) = rest.split_first_chunk::<4>()?;
+            // Null items can't be appended, so the length is never negative.
+            let item_len = 4 + i32::from_be_bytes(*len).max(0) as usize;
+            let (item, tail) = rest.split_at(item_len);
+            rest = tail;
+            Some(item)
+        })
+    }
+}
+
+/// Whether the values are certainly typechecked the same way, so only one
+/// of them needs to be kept as a sample.
+fn have_same_type(a: &CassCqlValue, b: &CassCqlValue) -> bool {
+    match (a, b) {
+        (
+            CassCqlValue::Custom { class_name: a, .. },
+            CassCqlValue::Custom { class_name: b, .. },
+        ) => a == b,
+        (
+            CassCqlValue::Serialized { data_type: a, .. },
+            CassCqlValue::Serialized { data_type: b, .. },
+        )
+        | (
+            CassCqlValue::UserDefinedType { data_type: a, .. },
+            CassCqlValue::UserDefinedType { data_type: b, .. },
+        )
+        | (
+            CassCqlValue::Tuple {
+                data_type: Some(a), ..
+            },
+            CassCqlValue::Tuple {
+                data_type: Some(b), ..
+            },
+        )
+        | (
+            CassCqlValue::List {
+                data_type: Some(a), ..
+            },
+            CassCqlValue::List {
+                data_type: Some(b), ..
+            },
+        )
+        | (
+            CassCqlValue::Set {
+                data_type: Some(a), ..
+            },
+            CassCqlValue::Set {
+                data_type: Some(b), ..
+            },
+        )
+        | (
+            CassCqlValue::Map {
+                data_type: Some(a), ..
+            },
+            CassCqlValue::Map {
+                data_type: Some(b), ..
+            },
+        ) => Arc::ptr_eq(a, b),
+        // Untyped values are checked item by item.
+        (
+            CassCqlValue::Custom { .. }
+            | CassCqlValue::Serialized { .. }
+            | CassCqlValue::UserDefinedType { .. }
+            | CassCqlValue::Tuple { .. }
+            | CassCqlValue::List { .. }
+            | CassCqlValue::Set { .. }
+            | CassCqlValue::Map { .. },
+            _,
+        ) => false,
+        // Simple values are checked by their kind only.
+        _ => std::mem::discriminant(a) == std::mem::discriminant(b),
+    }
 }
 
 impl FFI for CassCollection {
@@ -41,10 +158,25 @@ impl FFI for CassCollection {
 }
 
 impl CassCollection {
-    fn typecheck_on_append(&self, value: &Option<CassCqlValue>) -> CassError {
-        // See https://github.com/scylladb/cpp-driver/blob/master/src/collection.hpp#L100.
-        let index = self.items.len();
+    fn new(
+        collection_type: CassCollectionType,
+        data_type: Option<Arc<CassDataType>>,
+        item_count: usize,
+    ) -> Self {
+        CassCollection {
+            collection_type,
+            data_type,
+            items: CollectionItems::with_capacity(item_count),
+        }
+    }
+
+    /// For maps, keys are appended at even, and values at odd positions.
+    fn is_next_item_map_key(&self) -> bool {
+        self.is_map() && self.items.count % 2 == 0
+    }
 
+    fn typecheck_on_append(&self, value: &CassCqlValue, is_map_key: bool) -> CassError {
+        // See https://github.com/scylladb/cpp-driver/blob/master/src/collection.hpp#L100.
         // Do validation only if it's a typed collection.
         if let Some(data_type) = &self
             .data_type
@@ -55,7 +187,7 @@ impl CassCollection {
                 CassDataTypeInner::List { typ: subtype, .. }
                 | CassDataTypeInner::Set { typ: subtype, .. } => {
                     if let Some(subtype) = subtype {
-                        if !value::is_type_compatible(value, subtype) {
+                        if !value.is_type_compatible(subtype) {
                             return CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE;
                         }
                     }
@@ -67,15 +199,15 @@ impl CassCollection {
                     // We will do the typecheck if just the key type is defined as well (half-typed maps).
                     match typ {
                         MapDataType::Key(k_typ) => {
-                            if index % 2 == 0 && !value::is_type_compatible(value, k_typ) {
+                            if is_map_key && !value.is_type_compatible(k_typ) {
                                 return CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE;
                             }
                         }
                         MapDataType::KeyAndValue(k_typ, v_typ) => {
-                            if index % 2 == 0 && !value::is_type_compatible(value, k_typ) {
+                            if is_map_key && !value.is_type_compatible(k_typ) {
                                 return CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE;
                             }
-                            if index % 2 != 0 && !value::is_type_compatible(value, v_typ) {
+                            if !is_map_key && !value.is_type_compatible(v_typ) {
                                 return CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE;
                             }
                         }
@@ -91,12 +223,54 @@ impl CassCollection {
     }
 
     pub fn append_cql_value(&mut self, value: Option<CassCqlValue>) -> CassError {
-        let err = self.typecheck_on_append(&value);
+        // There is no API to append null, so unwrap is safe
+        let value = value.unwrap();
+        let is_map_key = self.is_next_item_map_key();
+        let err = self.typecheck_on_append(&value, is_map_key);
         if err != CassError::CASS_OK {
             return err;
         }
-        // There is no API to append null, so unwrap is safe
-        self.items.push(value.unwrap());
+        if value.serialize_item(&mut self.items.bytes).is_err() {
+            return CassError::CASS_ERROR_LIB_INVALID_DATA;
+        }
+        let samples = if self.is_map() && !is_map_key {
+            &mut self.items.value_samples
+        } else {
+            &mut self.items.samples
+        };
+        CollectionItems::add_sample(samples, &value);
+        self.items.count += 1;
+        CassError::CASS_OK
+    }
+
+    fn is_map(&self) -> bool {
+        self.collection_type == CassCollectionType::CASS_COLLECTION_TYPE_MAP
+    }
+
+    /// Appends all the values, or none of them if any value is rejected.
+    fn append_cql_values(
+        &mut self,
+        values: impl IntoIterator<Item = Result<CassCqlValue, CassError>>,
+    ) -> CassError {
+        let count = self.items.count;
+        let bytes_len = self.items.bytes.len();
+        let samples_len = self.items.samples.len();
+        let value_samples_len = self.items.value_samples.len();
+
+        for value in values {
+            let err = match value {
+                Ok(value) => self.append_cql_value(Some(value)),
+                Err(err) => err,
+            };
+            if err != CassError::CASS_OK {
+                // Roll back the values appended so far.
+                self.items.count = count;
+                self.items.bytes.truncate(bytes_len);
+                self.items.samples.truncate(samples_len);
+                self.items.value_samples.truncate(value_samples_len);
+                return err;
+            }
+        }
         CassError::CASS_OK
     }
 }
@@ -104,32 +278,30 @@ impl CassCollection {
 impl TryFrom<&CassCollection> for CassCqlValue {
     type Error = ();
     fn try_from(collection: &CassCollection) -> Result<Self, Self::Error> {
-        // FIXME: validate that collection items are correct
         let data_type = collection.data_type.clone();
+        let items = &collection.items;
+        let values = |count| SerializedItems {
+            count,
+            bytes: items.bytes.as_slice().into(),
+            samples: items.samples.as_slice().into(),
+            value_samples: items.value_samples.as_slice().into(),
+        };
         match collection.collection_type {
             CassCollectionType::CASS_COLLECTION_TYPE_LIST => Ok(CassCqlValue::List {
                 data_type,
-                values: collection.items.clone(),
+                values: values(items.count),
             }),
-            CassCollectionType::CASS_COLLECTION_TYPE_MAP => {
-                let mut grouped_items = Vec::new();
-                // FIXME: validate even number of items
-                for i in (0..collection.items.len()).step_by(2) {
-                    let key = collection.items[i].clone();
-                    let value = collection.items[i + 1].clone();
-
-                    grouped_items.push((key, value));
-                }
-
-                Ok(CassCqlValue::Map {
-                    data_type,
-                    values: grouped_items,
-                })
-            }
             CassCollectionType::CASS_COLLECTION_TYPE_SET => Ok(CassCqlValue::Set {
                 data_type,
-                values: collection.items.clone(),
+                values: values(items.count),
+            }),
+            // A key without its value.
+            CassCollectionType::CASS_COLLECTION_TYPE_MAP if items.count % 2 == 1 => Err(()),
+            CassCollectionType::CASS_COLLECTION_TYPE_MAP => Ok(CassCqlValue::Map {
+                data_type,
+                values: values(items.count / 2),
             }),
+            // Invalid collection type.
             _ => Err(()),
         }
     }
@@ -140,19 +312,11 @@ pub unsafe extern "C" fn cass_collection_new(
     collection_type: CassCollectionType,
     item_count: size_t,
 ) -> CassOwnedExclusivePtr<CassCollection, CMut> {
-    let capacity = match collection_type {
-        // Maps consist of a key and a value, so twice
-        // the number of CassCqlValue will be stored.
-        CassCollectionType::CASS_COLLECTION_TYPE_MAP => item_count * 2,
-        _ => item_count,
-    } as usize;
-
-    BoxFFI::into_ptr(Box::new(CassCollection {
+    BoxFFI::into_ptr(Box::new(CassCollection::new(
         collection_type,
-        data_type: None,
-        capacity,
-        items: Vec::with_capacity(capacity),
-    }))
+        None,
+        item_count as usize,
+    )))
 }
 
 #[unsafe(no_mangle)]
@@ -165,26 +329,18 @@ unsafe extern "C" fn cass_collection_new_from_data_type(
         return BoxFFI::null_mut();
     };
 
-    let (capacity, collection_type) = match unsafe { data_type.get_unchecked() } {
-        CassDataTypeInner::List { .. } => {
-            (item_count, CassCollectionType::CASS_COLLECTION_TYPE_LIST)
-        }
-        CassDataTypeInner::Set { .. } => (item_count, CassCollectionType::CASS_COLLECTION_TYPE_SET),
-        // Maps consist of a key and a value, so twice
-        // the number of CassCqlValue will be stored.
-        CassDataTypeInner::Map { .. } => {
-            (item_count * 2, CassCollectionType::CASS_COLLECTION_TYPE_MAP)
-        }
+    let collection_type = match unsafe { data_type.get_unchecked() } {
+        CassDataTypeInner::List { .. } => CassCollectionType::CASS_COLLECTION_TYPE_LIST,
+        CassDataTypeInner::Set { .. } => CassCollectionType::CASS_COLLECTION_TYPE_SET,
+        CassDataTypeInner::Map { .. } => CassCollectionType::CASS_COLLECTION_TYPE_MAP,
         _ => return BoxFFI::null_mut(),
     };
-    let capacity = capacity as usize;
 
-    BoxFFI::into_ptr(Box::new(CassCollection {
+    BoxFFI::into_ptr(Box::new(CassCollection::new(
         collection_type,
-        data_type: Some(data_type),
-        capacity,
-        items: Vec::with_capacity(capacity),
-    }))
+        Some(data_type),
+        item_count as usize,
+    )))
 }
 
 #[unsafe(no_mangle)]
@@ -219,6 +375,159 @@ pub unsafe extern "C" fn cass_collection_free(
 }
 
 prepare_binders_macro!(@append CassCollection, |collection: &mut CassCollection, v| collection.append_cql_value(v));
+
+/// Declares a function appending all elements of a C array to the collection.
+macro_rules! make_array_appender {
+    ($fn_append_array:ident, $t:ty, $to_value:expr) => {
+        #[unsafe(no_mangle)]
+        #[allow(clippy::redundant_closure_call)]
+        pub unsafe extern "C" fn $fn_append_array(
+            collection: CassBorrowedExclusivePtr<CassCollection, CMut>,
+            values: *const $t,
+            count: size_t,
+        ) -> CassError {
+            let Some((collection, _guard)) = BoxFFI::as_mut_ref_guarded(collection) else {
+                tracing::error!(
+                    "Provided null collection pointer to {}!",
+                    stringify!($fn_append_array)
+                );
+                return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+            };
+            if count == 0 {
+                return CassError::CASS_OK;
+            }
+            if values.is_null() {
+                tracing::error!(
+                    "Provided null values pointer to {}!",
+                    stringify!($fn_append_array)
+                );
+                return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+            }
+
+            let values = unsafe { std::slice::from_raw_parts(values, count as usize) };
+            collection.append_cql_values(values.iter().map(|&v| ($to_value)(v)))
+        }
+    };
+}
+
+make_array_appender!(cass_collection_append_int8_array, cass_int8_t, |v| Ok(
+    CassCqlValue::TinyInt(v)
+));
+make_array_appender!(cass_collection_append_int16_array, cass_int16_t, |v| Ok(
+    CassCqlValue::SmallInt(v)
+));
+make_array_appender!(cass_collection_append_int32_array, cass_int32_t, |v| Ok(
+    CassCqlValue::Int(v)
+));
+make_array_appender!(cass_collection_append_uint32_array, cass_uint32_t, |v| Ok(
+    CassCqlValue::Date(scylla::value::CqlDate(v))
+));
+make_array_appender!(cass_collection_append_int64_array, cass_int64_t, |v| Ok(
+    CassCqlValue::BigInt(v)
+));
+make_array_appender!(cass_collection_append_float_array, cass_float_t, |v| Ok(
+    CassCqlValue::Float(v)
+));
+make_array_appender!(cass_collection_append_double_array, cass_double_t, |v| Ok(
+    CassCqlValue::Double(v)
+));
+make_array_appender!(cass_collection_append_bool_array, cass_bool_t, |v| Ok(
+    CassCqlValue::Boolean(v != 0)
+));
+make_array_appender!(
+    cass_collection_append_string_array,
+    *const c_char,
+    |v: *const c_char| {
+        if v.is_null() {
+            return Err(CassError::CASS_ERROR_LIB_BAD_PARAMS);
+        }
+        match unsafe { ptr_to_cstr(v) } {
+            Some(v) => Ok(CassCqlValue::Text(v.into())),
+            None => Err(CassError::CASS_ERROR_LIB_BAD_PARAMS),
+        }
+    }
+);
+make_array_appender!(
+    cass_collection_append_uuid_array,
+    CassUuid,
+    |v: CassUuid| Ok(CassCqlValue::Uuid(v.into()))
+);
+make_array_appender!(
+    cass_collection_append_inet_array,
+    CassInet,
+    |v: CassInet| {
+        // See the `inet` binder - addresses of invalid length are rejected.
+        match IpAddr::try_from(v) {
+            Ok(v) => Ok(CassCqlValue::Inet(v)),
+            Err(_) => Err(CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE),
+        }
+    }
+);
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_collection_append_map_entries(
+    collection: CassBorrowedExclusivePtr<CassCollection, CMut>,
+    keys: CassBorrowedSharedPtr<CassCollection, CConst>,
+    values: CassBorrowedSharedPtr<CassCollection, CConst>,
+) -> CassError {
+    // Checked before the pointers are converted to references, which must not alias.
+    if collection.is_same_pointee(&keys) || collection.is_same_pointee(&values) {
+        tracing::error!(
+            "Provided the same collection as the target and the source to cass_collection_append_map_entries!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    }
+
+    let Some((collection, _guard)) = BoxFFI::as_mut_ref_guarded(collection) else {
+        tracing::error!("Provided null collection pointer to cass_collection_append_map_entries!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+    let Some(keys) = BoxFFI::as_ref(keys) else {
+        tracing::error!("Provided null keys pointer to cass_collection_append_map_entries!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+    let Some(values) = BoxFFI::as_ref(values) else {
+        tracing::error!("Provided null values pointer to cass_collection_append_map_entries!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    // A key appended on its own would pair the entries' keys with each other.
+    if !collection.is_next_item_map_key() || keys.is_map() || values.is_map() {
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    }
+    if keys.items.count != values.items.count {
+        return CassError::CASS_ERROR_LIB_INVALID_ITEM_COUNT;
+    }
+
+    // The samples stand for all the items of their collection, so it's enough
+    // to typecheck the samples.
+    let samples = keys.items.samples.iter().map(|key| (key, true));
+    let samples = samples.chain(values.items.samples.iter().map(|value| (value, false)));
+    for (sample, is_map_key) in samples {
+        let err = collection.typecheck_on_append(sample, is_map_key);
+        if err != CassError::CASS_OK {
+            return err;
+        }
+    }
+
+    let items = &mut collection.items;
+    for (key, value) in keys
+        .items
+        .serialized_items()
+        .zip(values.items.serialized_items())
+    {
+        items.bytes.extend_from_slice(key);
+        items.bytes.extend_from_slice(value);
+    }
+    items.count += 2 * keys.items.count;
+    for key in &keys.items.samples {
+        CollectionItems::add_sample(&mut items.samples, key);
+    }
+    for value in &values.items.samples {
+        CollectionItems::add_sample(&mut items.value_samples, value);
+    }
+    CassError::CASS_OK
+}
 make_binders!(int8, cass_collection_append_int8);
 make_binders!(int16, cass_collection_append_int16);
 make_binders!(int32, cass_collection_append_int32);
@@ -230,6 +539,9 @@ make_binders!(bool, cass_collection_append_bool);
 make_binders!(string, cass_collection_append_string);
 make_binders!(string_n, cass_collection_append_string_n);
 make_binders!(bytes, cass_collection_append_bytes);
+make_binders!(custom, cass_collection_append_custom);
+make_binders!(custom_n, cass_collection_append_custom_n);
+make_binders!(serialized, cass_collection_append_serialized);
 make_binders!(uuid, cass_collection_append_uuid);
 make_binders!(inet, cass_collection_append_inet);
 make_binders!(duration, cass_collection_append_duration);
@@ -241,18 +553,24 @@ make_binders!(user_type, cass_collection_append_user_type);
 #[cfg(test)]
 mod tests {
     use crate::{
-        argconv::ArcFFI,
+        argconv::{ArcFFI, BoxFFI, CConst, CassBorrowedSharedPtr},
         cass_error::CassError,
         cass_types::{
             CassDataType, CassDataTypeInner, CassValueType, MapDataType,
             cass_data_type_add_sub_type, cass_data_type_free, cass_data_type_new,
         },
         collection::{
-            cass_collection_append_double, cass_collection_append_float, cass_collection_free,
+            cass_collection_append_bytes, cass_collection_append_collection,
+            cass_collection_append_custom, cass_collection_append_custom_n,
+            cass_collection_append_double, cass_collection_append_float,
+            cass_collection_append_int32, cass_collection_append_serialized, cass_collection_free,
         },
         testing::assert_cass_error_eq,
+        types::size_t,
     };
 
+    use std::os::raw::c_char;
+
     use super::{
         CassCollectionType, cass_bool_t, cass_collection_append_bool, cass_collection_append_int16,
         cass_collection_data_type, cass_collection_new, cass_collection_new_from_data_type,
@@ -520,6 +838,411 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_typecheck_on_append_of_untyped_nested_collection() {
+        unsafe {
+            // list<list<int>>
+            let dt = CassDataType::new_arced(CassDataTypeInner::List {
+                typ: Some(CassDataType::new_arced(CassDataTypeInner::List {
+                    typ: Some(CassDataType::new_arced(CassDataTypeInner::Value(
+                        CassValueType::CASS_VALUE_TYPE_INT,
+                    ))),
+                    frozen: true,
+                })),
+                frozen: false,
+            });
+            let dt_ptr = ArcFFI::into_ptr(dt);
+            let mut nested_list = cass_collection_new_from_data_type(dt_ptr.borrow(), 2);
+
+            // The items of untyped nested list are checked against the item type.
+            let mut int_list =
+                cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_LIST, 1);
+            assert_cass_error_eq!(
+                cass_collection_append_int32(int_list.borrow_mut(), 42),
+                CassError::CASS_OK
+            );
+            assert_cass_error_eq!(
+                cass_collection_append_collection(
+                    nested_list.borrow_mut(),
+                    int_list.borrow().into_c_const()
+                ),
+                CassError::CASS_OK
+            );
+
+            let mut bool_list =
+                cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_LIST, 1);
+            assert_cass_error_eq!(
+                cass_collection_append_bool(bool_list.borrow_mut(), true as cass_bool_t),
+                CassError::CASS_OK
+            );
+            assert_cass_error_eq!(
+                cass_collection_append_collection(
+                    nested_list.borrow_mut(),
+                    bool_list.borrow().into_c_const()
+                ),
+                CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+            );
+
+            // Untyped nested set is not a list.
+            let set = cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_SET, 0);
+            assert_cass_error_eq!(
+                cass_collection_append_collection(
+                    nested_list.borrow_mut(),
+                    set.borrow().into_c_const()
+                ),
+                CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+            );
+
+            cass_collection_free(set);
+            cass_collection_free(bool_list);
+            cass_collection_free(int_list);
+            cass_collection_free(nested_list);
+            ArcFFI::free(dt_ptr);
+        }
+    }
+
+    #[test]
+    fn test_append_custom_to_collection() {
+        const CLASS_NAME: &str = "com.example.CustomType";
+        let value = [0x01, 0x02, 0x03];
+
+        unsafe {
+            let dt = CassDataType::new_arced(CassDataTypeInner::List {
+                typ: Some(CassDataType::new_arced(CassDataTypeInner::Custom(
+                    CLASS_NAME.to_owned(),
+                ))),
+                frozen: false,
+            });
+            let dt_ptr = ArcFFI::into_ptr(dt);
+            let mut custom_list = cass_collection_new_from_data_type(dt_ptr.borrow(), 3);
+
+            assert_cass_error_eq!(
+                cass_collection_append_custom_n(
+                    custom_list.borrow_mut(),
+                    CLASS_NAME.as_ptr() as *const c_char,
+                    CLASS_NAME.len() as size_t,
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_OK
+            );
+            // Class name mismatch.
+            assert_cass_error_eq!(
+                cass_collection_append_custom(
+                    custom_list.borrow_mut(),
+                    c"com.example.OtherType".as_ptr(),
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+            );
+            assert_cass_error_eq!(
+                cass_collection_append_bytes(
+                    custom_list.borrow_mut(),
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+            );
+
+            // Untyped collection accepts any custom value.
+            let mut untyped_list =
+                cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_LIST, 1);
+            assert_cass_error_eq!(
+                cass_collection_append_custom(
+                    untyped_list.borrow_mut(),
+                    c"com.example.OtherType".as_ptr(),
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_OK
+            );
+
+            cass_collection_free(untyped_list);
+            cass_collection_free(custom_list);
+            ArcFFI::free(dt_ptr);
+        }
+    }
+
+    #[test]
+    fn test_append_serialized_to_collection() {
+        // Serialized list<int>: [42].
+        let value = [0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 42];
+
+        unsafe {
+            let int_list_dt = CassDataType::new_arced(CassDataTypeInner::List {
+                typ: Some(CassDataType::new_arced(CassDataTypeInner::Value(
+                    CassValueType::CASS_VALUE_TYPE_INT,
+                ))),
+                frozen: true,
+            });
+            let text_list_dt = CassDataType::new_arced(CassDataTypeInner::List {
+                typ: Some(CassDataType::new_arced(CassDataTypeInner::Value(
+                    CassValueType::CASS_VALUE_TYPE_TEXT,
+                ))),
+                frozen: true,
+            });
+            let dt = CassDataType::new_arced(CassDataTypeInner::List {
+                typ: Some(int_list_dt.clone()),
+                frozen: false,
+            });
+            let int_list_dt_ptr = ArcFFI::into_ptr(int_list_dt);
+            let text_list_dt_ptr = ArcFFI::into_ptr(text_list_dt);
+            let dt_ptr = ArcFFI::into_ptr(dt);
+            let mut nested_list = cass_collection_new_from_data_type(dt_ptr.borrow(), 2);
+
+            assert_cass_error_eq!(
+                cass_collection_append_serialized(
+                    nested_list.borrow_mut(),
+                    int_list_dt_ptr.borrow(),
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_OK
+            );
+            // Data type mismatch.
+            assert_cass_error_eq!(
+                cass_collection_append_serialized(
+                    nested_list.borrow_mut(),
+                    text_list_dt_ptr.borrow(),
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+            );
+            // Missing data type.
+            assert_cass_error_eq!(
+                cass_collection_append_serialized(
+                    nested_list.borrow_mut(),
+                    ArcFFI::null(),
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_ERROR_LIB_BAD_PARAMS
+            );
+
+            cass_collection_free(nested_list);
+            ArcFFI::free(dt_ptr);
+            ArcFFI::free(text_list_dt_ptr);
+            ArcFFI::free(int_list_dt_ptr);
+        }
+    }
+
+    #[test]
+    fn test_append_map_with_missing_value() {
+        unsafe {
+            let mut map = cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_MAP, 1);
+            let mut list = cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_LIST, 1);
+
+            // The key is missing its value.
+            assert_cass_error_eq!(
+                cass_collection_append_int32(map.borrow_mut(), 42),
+                CassError::CASS_OK
+            );
+            assert_cass_error_eq!(
+                cass_collection_append_collection(list.borrow_mut(), map.borrow().into_c_const()),
+                CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+            );
+
+            assert_cass_error_eq!(
+                cass_collection_append_int32(map.borrow_mut(), 43),
+                CassError::CASS_OK
+            );
+            assert_cass_error_eq!(
+                cass_collection_append_collection(list.borrow_mut(), map.borrow().into_c_const()),
+                CassError::CASS_OK
+            );
+
+            cass_collection_free(list);
+            cass_collection_free(map);
+        }
+    }
+
+    #[test]
+    fn test_bulk_append() {
+        use super::{
+            cass_collection_append_int32_array, cass_collection_append_int64_array,
+            cass_collection_append_map_entries, cass_collection_append_string_array,
+        };
+
+        let item_count = |collection: &super::CassCollection| match collection.collection_type {
+            CassCollectionType::CASS_COLLECTION_TYPE_MAP => collection.items.count / 2,
+            _ => collection.items.count,
+        };
+
+        unsafe {
+            let int_list_dt = CassDataType::new_arced(CassDataTypeInner::List {
+                typ: Some(CassDataType::new_arced(CassDataTypeInner::Value(
+                    CassValueType::CASS_VALUE_TYPE_INT,
+                ))),
+                frozen: false,
+            });
+            let int_list_dt_ptr = ArcFFI::into_ptr(int_list_dt);
+            let mut int_list = cass_collection_new_from_data_type(int_list_dt_ptr.borrow(), 3);
+
+            let ints = [1, 2, 3];
+            assert_cass_error_eq!(
+                cass_collection_append_int32_array(int_list.borrow_mut(), ints.as_ptr(), 3),
+                CassError::CASS_OK
+            );
+            assert_eq!(item_count(BoxFFI::as_ref(int_list.borrow()).unwrap()), 3);
+
+            // None of the values is appended if they are rejected.
+            let bigints = [4i64, 5];
+            assert_cass_error_eq!(
+                cass_collection_append_int64_array(int_list.borrow_mut(), bigints.as_ptr(), 2),
+                CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+            );
+            assert_eq!(item_count(BoxFFI::as_ref(int_list.borrow()).unwrap()), 3);
+            assert_cass_error_eq!(
+                cass_collection_append_int32_array(int_list.borrow_mut(), std::ptr::null(), 0),
+                CassError::CASS_OK
+            );
+
+            let mut text_list =
+                cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_LIST, 3);
+            let texts = [c"a".as_ptr(), c"b".as_ptr(), c"c".as_ptr()];
+            assert_cass_error_eq!(
+                cass_collection_append_string_array(text_list.borrow_mut(), texts.as_ptr(), 3),
+                CassError::CASS_OK
+            );
+
+            let mut map = cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_MAP, 3);
+            assert_cass_error_eq!(
+                cass_collection_append_map_entries(
+                    map.borrow_mut(),
+                    int_list.borrow().into_c_const(),
+                    text_list.borrow().into_c_const()
+                ),
+                CassError::CASS_OK
+            );
+            assert_eq!(item_count(BoxFFI::as_ref(map.borrow()).unwrap()), 3);
+
+            // Keys and values must be paired.
+            assert_cass_error_eq!(
+                cass_collection_append_string_array(text_list.borrow_mut(), texts.as_ptr(), 1),
+                CassError::CASS_OK
+            );
+            assert_cass_error_eq!(
+                cass_collection_append_map_entries(
+                    map.borrow_mut(),
+                    int_list.borrow().into_c_const(),
+                    text_list.borrow().into_c_const()
+                ),
+                CassError::CASS_ERROR_LIB_INVALID_ITEM_COUNT
+            );
+            assert_cass_error_eq!(
+                cass_collection_append_map_entries(
+                    int_list.borrow_mut(),
+                    text_list.borrow().into_c_const(),
+                    text_list.borrow().into_c_const()
+                ),
+                CassError::CASS_ERROR_LIB_BAD_PARAMS
+            );
+
+            // The map can't be the source of its own entries.
+            let map_alias: CassBorrowedSharedPtr<super::CassCollection, CConst> =
+                std::mem::transmute_copy(&map);
+            assert_cass_error_eq!(
+                cass_collection_append_map_entries(
+                    map.borrow_mut(),
+                    map_alias,
+                    text_list.borrow().into_c_const()
+                ),
+                CassError::CASS_ERROR_LIB_BAD_PARAMS
+            );
+
+            cass_collection_free(map);
+            cass_collection_free(text_list);
+            cass_collection_free(int_list);
+            ArcFFI::free(int_list_dt_ptr);
+        }
+    }
+
+    #[test]
+    fn test_collection_item_count_reserves_capacity() {
+        use super::{ESTIMATED_SERIALIZED_ITEM_SIZE, MAX_RESERVED_BYTES, cass_collection_new};
+
+        unsafe {
+            let list = cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_LIST, 10);
+            {
+                let list = BoxFFI::as_ref(list.borrow()).unwrap();
+                assert!(list.items.bytes.capacity() >= 10 * ESTIMATED_SERIALIZED_ITEM_SIZE);
+            }
+            cass_collection_free(list);
+
+            // A bogus item count does not allocate too much upfront.
+            let list =
+                cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_LIST, size_t::MAX);
+            {
+                let list = BoxFFI::as_ref(list.borrow()).unwrap();
+                assert!(list.items.bytes.capacity() <= MAX_RESERVED_BYTES);
+            }
+            cass_collection_free(list);
+        }
+    }
+
+    #[test]
+    fn test_collection_items_are_serialized_on_append() {
+        use super::{CassCqlValue, cass_collection_append_map_entries};
+
+        let serialize = |collection: &super::CassCollection| {
+            let value = CassCqlValue::try_from(collection).unwrap();
+            let mut buf = Vec::new();
+            value.serialize_item(&mut buf).unwrap();
+            buf
+        };
+
+        unsafe {
+            let mut keys = cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_LIST, 2);
+            let mut values = cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_SET, 2);
+            for (key, value) in [(1, 2), (3, 4)] {
+                assert_cass_error_eq!(
+                    cass_collection_append_int32(keys.borrow_mut(), key),
+                    CassError::CASS_OK
+                );
+                assert_cass_error_eq!(
+                    cass_collection_append_int16(values.borrow_mut(), value),
+                    CassError::CASS_OK
+                );
+            }
+            assert_eq!(
+                serialize(BoxFFI::as_ref(keys.borrow()).unwrap()),
+                [
+                    0, 0, 0, 20, // value length
+                    0, 0, 0, 2, // item count
+                    0, 0, 0, 4, 0, 0, 0, 1, // 1
+                    0, 0, 0, 4, 0, 0, 0, 3, // 3
+                ]
+            );
+
+            let mut map = cass_collection_new(CassCollectionType::CASS_COLLECTION_TYPE_MAP, 2);
+            assert_cass_error_eq!(
+                cass_collection_append_map_entries(
+                    map.borrow_mut(),
+                    keys.borrow().into_c_const(),
+                    values.borrow().into_c_const()
+                ),
+                CassError::CASS_OK
+            );
+            assert_eq!(
+                serialize(BoxFFI::as_ref(map.borrow()).unwrap()),
+                [
+                    0, 0, 0, 32, // value length
+                    0, 0, 0, 2, // entry count
+                    0, 0, 0, 4, 0, 0, 0, 1, // 1
+                    0, 0, 0, 2, 0, 2, // 2
+                    0, 0, 0, 4, 0, 0, 0, 3, // 3
+                    0, 0, 0, 2, 0, 4, // 4
+                ]
+            );
+
+            cass_collection_free(map);
+            cass_collection_free(values);
+            cass_collection_free(keys);
+        }
+    }
+
     #[test]
     fn regression_empty_collection_data_type_test() {
         // This is a regression test that checks whether collections return
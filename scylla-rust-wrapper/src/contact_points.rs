@@ -0,0 +1,304 @@
+use crate::RUNTIME;
+use crate::address_family::{AddressFamily, ResolvedContactPoint};
+use crate::session::CassSession;
+use hickory_resolver::TokioAsyncResolver;
+use scylla::client::session::Session;
+use scylla::client::session_builder::SessionBuilder;
+use scylla::cluster::ClusterState;
+use std::net::Ipv6Addr;
+use std::sync::Weak;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Splits the contact point into the host and the port, if the latter is specified:
+/// `host:port`, or `[ipv6]:port` for IPv6 addresses. Bare IPv6 addresses
+/// (with or without brackets) are hosts without a port.
+///
+/// Returns `None` if the contact point is malformed (e.g. has an invalid port).
+pub(crate) fn split_host_port(contact_point: &str) -> Option<(&str, Option<u16>)> {
+    if let Some(bracketed) = contact_point.strip_prefix('[') {
+        let (host, rest) = bracketed.split_once(']')?;
+        host.parse::<Ipv6Addr>().ok()?;
+        return match rest {
+            "" => Some((host, None)),
+            _ => Some((host, Some(rest.strip_prefix(':')?.parse().ok()?))),
+        };
+    }
+
+    match contact_point.split_once(':') {
+        None => Some((contact_point, None)),
+        // More than one colon - it can only be a bare IPv6 address.
+        Some((_, port)) if port.contains(':') => {
+            contact_point.parse::<Ipv6Addr>().ok()?;
+            Some((contact_point, None))
+        }
+        Some((host, port)) if !host.is_empty() => Some((host, Some(port.parse().ok()?))),
+        Some(_) => None,
+    }
+}
+
+/// Formats the host and the port as an endpoint, enclosing IPv6 addresses in brackets.
+pub(crate) fn format_endpoint(host: &str, port: u16) -> String {
+    if host.contains(':') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    }
+}
+
+/// Discovers the contact points from the DNS SRV record with given name.
+/// Returns the targets (hostnames) with their ports, ordered by priority
+/// (and by weight, within the same priority).
+///
+/// Returns the error message if the lookup fails.
+pub(crate) async fn resolve_srv(
+    name: &str,
+    timeout: Option<Duration>,
+) -> Result<Vec<(String, u16)>, String> {
+    let resolver = TokioAsyncResolver::tokio_from_system_conf()
+        .map_err(|err| format!("Failed to create the DNS resolver: {}", err))?;
+
+    let lookup = resolver.srv_lookup(name);
+    let result = match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, lookup)
+            .await
+            .map_err(|_| format!("DNS SRV lookup of {} timed out", name))?,
+        None => lookup.await,
+    };
+    let srv_lookup = result.map_err(|err| format!("DNS SRV lookup of {} failed: {}", name, err))?;
+
+    let mut records: Vec<_> = srv_lookup.iter().collect();
+    records.sort_by_key(|srv| (srv.priority(), std::cmp::Reverse(srv.weight())));
+    Ok(records
+        .into_iter()
+        .map(|srv| {
+            let target = srv.target().to_utf8();
+            // Strip the root label of the fully qualified name.
+            let target = target.strip_suffix('.').unwrap_or(&target).to_owned();
+            (target, srv.port())
+        })
+        .collect())
+}
+
+/// Adds the contact points to the known nodes, resolving their hostnames
+/// according to the address family preference.
+pub(crate) async fn add_known_nodes(
+    session_builder: SessionBuilder,
+    address_family: AddressFamily,
+    contact_points: &[(String, u16)],
+) -> SessionBuilder {
+    match address_family {
+        // Let rust-driver resolve the hostnames (and re-resolve them if needed).
+        AddressFamily::PreferIpv4 => session_builder.known_nodes(
+            contact_points
+                .iter()
+                .map(|(host, port)| format_endpoint(host, *port)),
+        ),
+        _ => {
+            let resolution_timeout = session_builder.config.hostname_resolution_timeout;
+            let known_nodes = address_family
+                .resolve_contact_points(contact_points, resolution_timeout)
+                .await;
+            known_nodes
+                .into_iter()
+                .fold(
+                    session_builder,
+                    |session_builder, known_node| match known_node {
+                        ResolvedContactPoint::Hostname(hostname) => {
+                            session_builder.known_node(hostname)
+                        }
+                        ResolvedContactPoint::Address(addr) => {
+                            session_builder.known_node_addr(addr)
+                        }
+                    },
+                )
+        }
+    }
+}
+
+/// Contact points discovered from a DNS SRV record.
+pub(crate) struct SrvContactPoints {
+    pub(crate) name: String,
+    /// Targets discovered so far.
+    pub(crate) targets: Vec<(String, u16)>,
+    pub(crate) address_family: AddressFamily,
+}
+
+/// Re-queries the DNS SRV record the contact points were discovered from.
+///
+/// rust-driver does not allow changing the known nodes of a running session,
+/// so a new session is built once the record lists new targets.
+pub(crate) struct SrvReconnector {
+    srv: SrvContactPoints,
+    // The builder the current session was built with.
+    session_builder: SessionBuilder,
+}
+
+impl SrvReconnector {
+    pub(crate) fn new(srv: SrvContactPoints, session_builder: SessionBuilder) -> Self {
+        Self {
+            srv,
+            session_builder,
+        }
+    }
+
+    /// Re-queries the SRV record. If it lists targets which are not known yet,
+    /// returns a new session, which additionally contacts the new targets.
+    async fn reconnect_to_new_targets(&mut self) -> Option<Session> {
+        let resolution_timeout = self.session_builder.config.hostname_resolution_timeout;
+        let targets = match resolve_srv(&self.srv.name, resolution_timeout).await {
+            Ok(targets) => targets,
+            Err(err) => {
+                tracing::debug!("Failed to re-query the SRV record: {}", err);
+                return None;
+            }
+        };
+        let new_targets: Vec<_> = targets
+            .into_iter()
+            .filter(|target| !self.srv.targets.contains(target))
+            .collect();
+        if new_targets.is_empty() {
+            return None;
+        }
+
+        tracing::debug!(
+            "SRV record {} lists new targets: {:?}. Reconnecting the session.",
+            self.srv.name,
+            new_targets
+        );
+        let session_builder = add_known_nodes(
+            self.session_builder.clone(),
+            self.srv.address_family,
+            &new_targets,
+        )
+        .await;
+        match session_builder.build().await {
+            Ok(session) => {
+                self.srv.targets.extend(new_targets);
+                self.session_builder = session_builder;
+                Some(session)
+            }
+            Err(err) => {
+                tracing::debug!("Failed to reconnect to the new SRV targets: {}", err);
+                None
+            }
+        }
+    }
+}
+
+// rust-driver falls back to the initial contact points (resolving their hostnames again)
+// when it fails to fetch the metadata from all known nodes. However, it does so only
+// upon a metadata refresh, which happens rarely (every 60s by default). When all known
+// nodes are down, we trigger the refresh periodically, so the driver can quickly recover
+// after the nodes have been replaced (and the hostnames point to the new addresses).
+
+fn all_nodes_down(cluster_state: &ClusterState) -> bool {
+    cluster_state
+        .get_nodes_info()
+        .iter()
+        .all(|node| node.is_down())
+}
+
+/// Handle to the background task re-resolving the contact points.
+/// The task is aborted once the handle is dropped (i.e. when the session is closed).
+pub(crate) struct ContactPointsReResolver(JoinHandle<()>);
+
+impl Drop for ContactPointsReResolver {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl ContactPointsReResolver {
+    pub(crate) fn spawn(
+        session: Weak<CassSession>,
+        interval: Duration,
+        mut srv_reconnector: Option<SrvReconnector>,
+    ) -> Self {
+        let join_handle = RUNTIME.spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            // The first tick completes immediately.
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                let Some(session) = session.upgrade() else {
+                    return;
+                };
+                let rust_session = {
+                    let session_guard = session.read().await;
+                    let Some(session_inner) = session_guard.as_ref() else {
+                        return;
+                    };
+                    if !all_nodes_down(&session_inner.cluster_state()) {
+                        continue;
+                    }
+                    // Don't hold the lock during the refresh - it may take a while.
+                    session_inner.rust_session()
+                };
+
+                tracing::debug!("All known nodes are down. Re-resolving the contact points.");
+                // Targets added to the SRV record may replace the nodes that are down.
+                if let Some(srv_reconnector) = &mut srv_reconnector {
+                    if let Some(new_session) = srv_reconnector.reconnect_to_new_targets().await {
+                        if let Some(session_inner) = session.write().await.as_mut() {
+                            session_inner.replace_rust_session(new_session);
+                        }
+                        continue;
+                    }
+                }
+                if let Err(err) = rust_session.refresh_metadata().await {
+                    tracing::debug!("Failed to re-resolve the contact points: {}", err);
+                }
+            }
+        });
+
+        ContactPointsReResolver(join_handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_host_port() {
+        // Without the port.
+        assert_eq!(split_host_port("127.0.0.1"), Some(("127.0.0.1", None)));
+        assert_eq!(split_host_port("localhost"), Some(("localhost", None)));
+        assert_eq!(split_host_port("::1"), Some(("::1", None)));
+        assert_eq!(split_host_port("[::1]"), Some(("::1", None)));
+
+        // With the port.
+        assert_eq!(
+            split_host_port("127.0.0.1:9043"),
+            Some(("127.0.0.1", Some(9043)))
+        );
+        assert_eq!(
+            split_host_port("server1.domain.com:19042"),
+            Some(("server1.domain.com", Some(19042)))
+        );
+        assert_eq!(
+            split_host_port("[2001:db8::1]:9043"),
+            Some(("2001:db8::1", Some(9043)))
+        );
+
+        // Malformed.
+        assert_eq!(split_host_port("127.0.0.1:"), None);
+        assert_eq!(split_host_port("127.0.0.1:foo"), None);
+        assert_eq!(split_host_port("127.0.0.1:65536"), None);
+        assert_eq!(split_host_port(":9042"), None);
+        assert_eq!(split_host_port("foo:bar:baz"), None);
+        assert_eq!(split_host_port("[::1]9042"), None);
+        assert_eq!(split_host_port("[localhost]:9042"), None);
+        assert_eq!(split_host_port("[::1"), None);
+    }
+
+    #[test]
+    fn test_format_endpoint() {
+        assert_eq!(format_endpoint("127.0.0.1", 9042), "127.0.0.1:9042");
+        assert_eq!(format_endpoint("localhost", 9042), "localhost:9042");
+        assert_eq!(format_endpoint("::1", 9042), "[::1]:9042");
+    }
+}
@@ -0,0 +1,181 @@
+use crate::argconv::*;
+use crate::cass_error::CassError;
+use crate::cluster::{PlainTextAuthenticatorSession, PlainTextAuthenticatorWithAuthorizationId};
+use crate::types::size_t;
+use async_trait::async_trait;
+use scylla::authentication::{AuthError, AuthenticatorProvider, AuthenticatorSession};
+use std::os::raw::{c_char, c_void};
+
+pub type CassCredentialsCallback = Option<
+    unsafe extern "C" fn(
+        credentials: CassBorrowedExclusivePtr<CassCredentials, CMut>,
+        data: *mut c_void,
+    ),
+>;
+
+/// Credentials filled by the user in [`CassCredentialsCallback`].
+#[derive(Default)]
+pub struct CassCredentials {
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl FFI for CassCredentials {
+    type Origin = FromBox;
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct CredentialsProvider {
+    cb: unsafe extern "C" fn(
+        credentials: CassBorrowedExclusivePtr<CassCredentials, CMut>,
+        data: *mut c_void,
+    ),
+    data: *mut c_void,
+}
+
+// The `data` pointer is owned by the user, who is responsible for making it
+// safe to be accessed from the driver's threads (the same contract as for `CassLogCallback`).
+unsafe impl Send for CredentialsProvider {}
+unsafe impl Sync for CredentialsProvider {}
+
+impl CredentialsProvider {
+    /// Returns `None` if user provided a NULL callback.
+    pub(crate) fn new(cb: CassCredentialsCallback, data: *mut c_void) -> Option<Self> {
+        cb.map(|cb| CredentialsProvider { cb, data })
+    }
+
+    fn fetch(&self) -> CassCredentials {
+        let mut credentials = BoxFFI::into_ptr::<CMut>(Box::new(CassCredentials::default()));
+        unsafe { (self.cb)(credentials.borrow_mut(), self.data) };
+        *BoxFFI::from_ptr(credentials).unwrap()
+    }
+}
+
+#[async_trait]
+impl AuthenticatorProvider for CredentialsProvider {
+    async fn start_authentication_session(
+        &self,
+        _authenticator_name: &str,
+    ) -> Result<(Option<Vec<u8>>, Box<dyn AuthenticatorSession>), AuthError> {
+        // The callback may block (e.g. when it fetches a token from an external service),
+        // so we don't want to call it on the runtime's worker threads.
+        let provider = *self;
+        let credentials = tokio::task::spawn_blocking(move || provider.fetch())
+            .await
+            .map_err(|e| format!("Credentials callback panicked: {e}"))?;
+
+        let (Some(username), Some(password)) = (credentials.username, credentials.password) else {
+            return Err("Credentials callback did not provide the credentials".to_string());
+        };
+
+        let authenticator = PlainTextAuthenticatorWithAuthorizationId {
+            authorization_id: String::new(),
+            username,
+            password,
+        };
+        Ok((
+            Some(authenticator.initial_response()),
+            Box::new(PlainTextAuthenticatorSession),
+        ))
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_credentials_set(
+    credentials: CassBorrowedExclusivePtr<CassCredentials, CMut>,
+    username: *const c_char,
+    password: *const c_char,
+) -> CassError {
+    unsafe {
+        cass_credentials_set_n(
+            credentials,
+            username,
+            strlen(username),
+            password,
+            strlen(password),
+        )
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_credentials_set_n(
+    credentials_raw: CassBorrowedExclusivePtr<CassCredentials, CMut>,
+    username_raw: *const c_char,
+    username_length: size_t,
+    password_raw: *const c_char,
+    password_length: size_t,
+) -> CassError {
+    let Some(credentials) = BoxFFI::as_mut_ref(credentials_raw) else {
+        tracing::error!("Provided null credentials pointer to cass_credentials_set_n!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+    let (Some(username), Some(password)) = (
+        unsafe { ptr_to_cstr_n(username_raw, username_length) },
+        unsafe { ptr_to_cstr_n(password_raw, password_length) },
+    ) else {
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    credentials.username = Some(username.to_string());
+    credentials.password = Some(password.to_string());
+
+    CassError::CASS_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_cass_error_eq;
+
+    unsafe extern "C" fn rotating_credentials(
+        credentials: CassBorrowedExclusivePtr<CassCredentials, CMut>,
+        data: *mut c_void,
+    ) {
+        let counter = unsafe { &mut *(data as *mut u32) };
+        *counter += 1;
+        let password = format!("token-{counter}\0");
+        unsafe {
+            assert_cass_error_eq!(
+                cass_credentials_set(
+                    credentials,
+                    c"service".as_ptr(),
+                    password.as_ptr() as *const c_char
+                ),
+                CassError::CASS_OK
+            );
+        }
+    }
+
+    unsafe extern "C" fn no_credentials(
+        _credentials: CassBorrowedExclusivePtr<CassCredentials, CMut>,
+        _data: *mut c_void,
+    ) {
+    }
+
+    #[tokio::test]
+    async fn test_credentials_callback_fetches_fresh_credentials() {
+        let mut counter: u32 = 0;
+        let provider = CredentialsProvider::new(
+            Some(rotating_credentials),
+            &mut counter as *mut u32 as *mut c_void,
+        )
+        .unwrap();
+
+        for expected in [&b"\0service\0token-1"[..], &b"\0service\0token-2"[..]] {
+            let (response, _) = provider
+                .start_authentication_session("PasswordAuthenticator")
+                .await
+                .unwrap();
+            assert_eq!(response.as_deref(), Some(expected));
+        }
+
+        let provider =
+            CredentialsProvider::new(Some(no_credentials), std::ptr::null_mut()).unwrap();
+        assert!(
+            provider
+                .start_authentication_session("PasswordAuthenticator")
+                .await
+                .is_err()
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use std::convert::{TryFrom, TryInto};
-use std::ffi::c_char;
+use std::ffi::{c_char, c_int};
 use std::future::Future;
 use std::net::IpAddr;
 use std::ops::Deref;
@@ -10,14 +10,13 @@ use std::time::Duration;
 use scylla::client::execution_profile::{
     ExecutionProfile, ExecutionProfileBuilder, ExecutionProfileHandle,
 };
-use scylla::policies::load_balancing::LatencyAwarenessBuilder;
 use scylla::policies::retry::RetryPolicy;
 use scylla::policies::speculative_execution::SimpleSpeculativeExecutionPolicy;
-use scylla::statement::Consistency;
+use scylla::statement::{Consistency, SerialConsistency};
 
 use crate::argconv::{
-    ArcFFI, BoxFFI, CMut, CassBorrowedExclusivePtr, CassBorrowedSharedPtr, CassOwnedExclusivePtr,
-    FFI, FromBox, ptr_to_cstr_n, strlen,
+    ArcFFI, BoxFFI, CConst, CMut, CassBorrowedExclusivePtr, CassBorrowedSharedPtr,
+    CassOwnedExclusivePtr, FFI, FromBox, ptr_to_cstr_n, strlen, write_str_to_c,
 };
 use crate::batch::CassBatch;
 use crate::cass_error::CassError;
@@ -25,7 +24,7 @@ use crate::cass_types::CassConsistency;
 use crate::cluster::{
     set_load_balance_dc_aware_n, set_load_balance_rack_aware_n, update_comma_delimited_list,
 };
-use crate::load_balancing::{LoadBalancingConfig, LoadBalancingKind};
+use crate::load_balancing::{LoadBalancingConfig, LoadBalancingKind, latency_awareness_builder};
 use crate::retry_policy::CassRetryPolicy;
 use crate::retry_policy::RetryPolicy::{
     DefaultRetryPolicy, DowngradingConsistencyRetryPolicy, FallthroughRetryPolicy,
@@ -33,13 +32,25 @@ use crate::retry_policy::RetryPolicy::{
 use crate::session::CassSessionInner;
 use crate::statement::CassStatement;
 use crate::types::{
-    cass_bool_t, cass_double_t, cass_int32_t, cass_int64_t, cass_uint32_t, cass_uint64_t, size_t,
+    cass_bool_t, cass_double_t, cass_false, cass_int32_t, cass_int64_t, cass_uint32_t,
+    cass_uint64_t, size_t,
 };
 
 #[derive(Clone, Debug)]
 pub struct CassExecProfile {
     inner: ExecutionProfileBuilder,
     pub(crate) load_balancing_config: LoadBalancingConfig,
+    // Consistencies not set explicitly on the profile are inherited from
+    // the cluster's default profile, as in cpp-driver.
+    consistency: Option<Consistency>,
+    serial_consistency: Option<Option<SerialConsistency>>,
+    // Kept for the getters, as the builder does not expose its settings.
+    request_timeout_ms: Option<cass_uint64_t>,
+    retry_policy: Option<Arc<CassRetryPolicy>>,
+    // Page size of the statements that don't set it explicitly - None if not set
+    // (the cluster's one is used), Some(None) if paging is disabled.
+    // Not a part of rust-driver's profile - applied by the wrapper upon execution.
+    pub(crate) page_size: Option<Option<c_int>>,
 }
 
 impl FFI for CassExecProfile {
@@ -51,6 +62,11 @@ impl CassExecProfile {
         Self {
             inner: ExecutionProfile::builder(),
             load_balancing_config: Default::default(),
+            consistency: None,
+            serial_consistency: None,
+            request_timeout_ms: None,
+            retry_policy: None,
+            page_size: None,
         }
     }
 
@@ -62,11 +78,25 @@ impl CassExecProfile {
             self.load_balancing_config.build().await
         } else {
             // If load balancing config does not have LB kind defined,
-            // we make use of cluster's LBP.
-            cluster_default_profile.get_load_balancing_policy().clone()
+            // we make use of cluster's LBP. The profile's filtering
+            // (if any) is still applied on top of it.
+            self.load_balancing_config
+                .filtering
+                .apply_to(cluster_default_profile.get_load_balancing_policy().clone())
         };
 
-        self.inner.load_balancing_policy(load_balacing).build()
+        let consistency = self
+            .consistency
+            .unwrap_or_else(|| cluster_default_profile.get_consistency());
+        let serial_consistency = self
+            .serial_consistency
+            .unwrap_or_else(|| cluster_default_profile.get_serial_consistency());
+
+        self.inner
+            .load_balancing_policy(load_balacing)
+            .consistency(consistency)
+            .serial_consistency(serial_consistency)
+            .build()
     }
 }
 
@@ -100,13 +130,22 @@ impl Deref for ExecProfileName {
 // The resolved or not yet resolved execution profile that is specific
 // for a particular statement or batch.
 #[derive(Debug, Clone)]
-pub(crate) struct PerStatementExecProfile(Arc<RwLock<PerStatementExecProfileInner>>);
+pub(crate) struct PerStatementExecProfile {
+    // Kept after the resolution, so the name can be retrieved by the user.
+    name: ExecProfileName,
+    inner: Arc<RwLock<PerStatementExecProfileInner>>,
+}
 
 impl PerStatementExecProfile {
     pub(crate) fn new_unresolved(name: ExecProfileName) -> Self {
-        Self(Arc::new(RwLock::new(
-            PerStatementExecProfileInner::Unresolved(name),
-        )))
+        Self {
+            name: name.clone(),
+            inner: Arc::new(RwLock::new(PerStatementExecProfileInner::Unresolved(name))),
+        }
+    }
+
+    pub(crate) fn name(&self) -> &ExecProfileName {
+        &self.name
     }
 
     // Clippy claims it is possible to make this `async fn`, but it's terribly wrong,
@@ -119,7 +158,7 @@ impl PerStatementExecProfile {
     ) -> impl Future<Output = Result<ExecutionProfileHandle, (CassError, String)>> + 'a {
         async move {
             let already_resolved = {
-                let read_guard = self.0.read().unwrap();
+                let read_guard = self.inner.read().unwrap();
                 match read_guard.deref() {
                     PerStatementExecProfileInner::Unresolved(_) => None,
                     PerStatementExecProfileInner::Resolved(handle) => Some(handle.clone()),
@@ -129,7 +168,7 @@ impl PerStatementExecProfile {
             let handle = if let Some(handle) = already_resolved {
                 handle
             } else {
-                let inner = &mut *self.0.write().unwrap();
+                let inner = &mut *self.inner.write().unwrap();
                 match &*inner {
                     PerStatementExecProfileInner::Unresolved(name) => {
                         let handle = cass_session_inner.resolve_exec_profile(name)?;
@@ -218,6 +257,26 @@ pub unsafe extern "C" fn cass_statement_set_execution_profile_n(
     CassError::CASS_OK
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_statement_get_execution_profile(
+    statement: CassBorrowedSharedPtr<CassStatement, CConst>,
+    name: *mut *const c_char,
+    name_length: *mut size_t,
+) -> CassError {
+    let Some(statement) = BoxFFI::as_ref(statement) else {
+        tracing::error!("Provided null statement pointer to cass_statement_get_execution_profile!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    match &statement.exec_profile {
+        Some(exec_profile) => {
+            unsafe { write_str_to_c(exec_profile.name(), name, name_length) };
+            CassError::CASS_OK
+        }
+        None => CassError::CASS_ERROR_LIB_NULL_VALUE,
+    }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_batch_set_execution_profile(
     batch: CassBorrowedExclusivePtr<CassBatch, CMut>,
@@ -279,7 +338,7 @@ pub unsafe extern "C" fn cass_execution_profile_set_consistency(
         Err(_) => return CassError::CASS_ERROR_LIB_BAD_PARAMS,
     };
 
-    profile_builder.modify_in_place(|builder| builder.consistency(consistency));
+    profile_builder.consistency = Some(consistency);
 
     CassError::CASS_OK
 }
@@ -351,7 +410,7 @@ pub unsafe extern "C" fn cass_execution_profile_set_latency_aware_routing(
 pub unsafe extern "C" fn cass_execution_profile_set_latency_aware_routing_settings(
     profile: CassBorrowedExclusivePtr<CassExecProfile, CMut>,
     exclusion_threshold: cass_double_t,
-    _scale_ms: cass_uint64_t, // Currently ignored, TODO: add this parameter to Rust driver
+    scale_ms: cass_uint64_t,
     retry_period_ms: cass_uint64_t,
     update_rate_ms: cass_uint64_t,
     min_measured: cass_uint64_t,
@@ -365,11 +424,13 @@ pub unsafe extern "C" fn cass_execution_profile_set_latency_aware_routing_settin
 
     profile_builder
         .load_balancing_config
-        .latency_awareness_builder = LatencyAwarenessBuilder::new()
-        .exclusion_threshold(exclusion_threshold)
-        .retry_period(Duration::from_millis(retry_period_ms))
-        .update_rate(Duration::from_millis(update_rate_ms))
-        .minimum_measurements(min_measured as usize);
+        .latency_awareness_builder = latency_awareness_builder(
+        exclusion_threshold,
+        scale_ms,
+        retry_period_ms,
+        update_rate_ms,
+        min_measured,
+    );
 }
 
 #[unsafe(no_mangle)]
@@ -654,6 +715,23 @@ pub unsafe extern "C" fn cass_execution_profile_set_request_timeout(
     profile_builder.modify_in_place(|builder| {
         builder.request_timeout(Some(std::time::Duration::from_millis(timeout_ms)))
     });
+    profile_builder.request_timeout_ms = Some(timeout_ms);
+
+    CassError::CASS_OK
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_profile_set_paging_size(
+    profile: CassBorrowedExclusivePtr<CassExecProfile, CMut>,
+    page_size: c_int,
+) -> CassError {
+    let Some(profile_builder) = BoxFFI::as_mut_ref(profile) else {
+        tracing::error!("Provided null profile pointer to cass_execution_profile_set_paging_size!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    // Non-positive page size disables paging, as for statements.
+    profile_builder.page_size = Some((page_size > 0).then_some(page_size));
 
     CassError::CASS_OK
 }
@@ -669,19 +747,20 @@ pub unsafe extern "C" fn cass_execution_profile_set_retry_policy(
         );
         return CassError::CASS_ERROR_LIB_BAD_PARAMS;
     };
-    let retry_policy: Arc<dyn RetryPolicy> = match ArcFFI::as_ref(retry_policy) {
-        Some(DefaultRetryPolicy(default)) => Arc::clone(default) as _,
-        Some(FallthroughRetryPolicy(fallthrough)) => Arc::clone(fallthrough) as _,
-        Some(DowngradingConsistencyRetryPolicy(downgrading)) => Arc::clone(downgrading) as _,
-        None => {
-            tracing::error!(
-                "Provided null retry policy pointer to cass_execution_profile_set_retry_policy!"
-            );
-            return CassError::CASS_ERROR_LIB_BAD_PARAMS;
-        }
+    let Some(cass_retry_policy) = ArcFFI::cloned_from_ptr(retry_policy) else {
+        tracing::error!(
+            "Provided null retry policy pointer to cass_execution_profile_set_retry_policy!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+    let retry_policy: Arc<dyn RetryPolicy> = match cass_retry_policy.as_ref() {
+        DefaultRetryPolicy(default) => Arc::clone(default) as _,
+        FallthroughRetryPolicy(fallthrough) => Arc::clone(fallthrough) as _,
+        DowngradingConsistencyRetryPolicy(downgrading) => Arc::clone(downgrading) as _,
     };
 
     profile_builder.modify_in_place(|builder| builder.retry_policy(retry_policy));
+    profile_builder.retry_policy = Some(cass_retry_policy);
 
     CassError::CASS_OK
 }
@@ -707,7 +786,7 @@ pub unsafe extern "C" fn cass_execution_profile_set_serial_consistency(
                 Err(_) => return CassError::CASS_ERROR_LIB_BAD_PARAMS,
             }
         };
-    profile_builder.modify_in_place(|builder| builder.serial_consistency(maybe_serial_consistency));
+    profile_builder.serial_consistency = Some(maybe_serial_consistency);
 
     CassError::CASS_OK
 }
@@ -750,6 +829,173 @@ pub unsafe extern "C" fn cass_execution_profile_set_token_aware_routing_shuffle_
     CassError::CASS_OK
 }
 
+/* Config options getters */
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_profile_get_consistency(
+    profile: CassBorrowedSharedPtr<CassExecProfile, CConst>,
+) -> CassConsistency {
+    let Some(profile) = BoxFFI::as_ref(profile) else {
+        tracing::error!("Provided null profile pointer to cass_execution_profile_get_consistency!");
+        return CassConsistency::CASS_CONSISTENCY_UNKNOWN;
+    };
+
+    profile
+        .consistency
+        .map(CassConsistency::from)
+        .unwrap_or(CassConsistency::CASS_CONSISTENCY_UNKNOWN)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_profile_get_serial_consistency(
+    profile: CassBorrowedSharedPtr<CassExecProfile, CConst>,
+) -> CassConsistency {
+    let Some(profile) = BoxFFI::as_ref(profile) else {
+        tracing::error!(
+            "Provided null profile pointer to cass_execution_profile_get_serial_consistency!"
+        );
+        return CassConsistency::CASS_CONSISTENCY_UNKNOWN;
+    };
+
+    profile
+        .serial_consistency
+        .flatten()
+        .map(CassConsistency::from)
+        .unwrap_or(CassConsistency::CASS_CONSISTENCY_UNKNOWN)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_profile_get_request_timeout(
+    profile: CassBorrowedSharedPtr<CassExecProfile, CConst>,
+) -> cass_uint64_t {
+    let Some(profile) = BoxFFI::as_ref(profile) else {
+        tracing::error!(
+            "Provided null profile pointer to cass_execution_profile_get_request_timeout!"
+        );
+        return cass_uint64_t::MAX;
+    };
+
+    // CASS_UINT64_MAX means that the cluster-level request timeout is used.
+    profile.request_timeout_ms.unwrap_or(cass_uint64_t::MAX)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_profile_get_paging_size(
+    profile: CassBorrowedSharedPtr<CassExecProfile, CConst>,
+) -> c_int {
+    let Some(profile) = BoxFFI::as_ref(profile) else {
+        tracing::error!("Provided null profile pointer to cass_execution_profile_get_paging_size!");
+        return -1;
+    };
+
+    profile.page_size.flatten().unwrap_or(-1)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_profile_get_retry_policy(
+    profile: CassBorrowedSharedPtr<CassExecProfile, CConst>,
+) -> CassBorrowedSharedPtr<CassRetryPolicy, CConst> {
+    let Some(profile) = BoxFFI::as_ref(profile) else {
+        tracing::error!(
+            "Provided null profile pointer to cass_execution_profile_get_retry_policy!"
+        );
+        return ArcFFI::null();
+    };
+
+    match &profile.retry_policy {
+        // Non-owning pointer, as for the other borrowed getters.
+        Some(retry_policy) => ArcFFI::as_ptr(retry_policy),
+        None => ArcFFI::null(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_profile_get_token_aware_routing(
+    profile: CassBorrowedSharedPtr<CassExecProfile, CConst>,
+) -> cass_bool_t {
+    let Some(profile) = BoxFFI::as_ref(profile) else {
+        tracing::error!(
+            "Provided null profile pointer to cass_execution_profile_get_token_aware_routing!"
+        );
+        return cass_false;
+    };
+
+    profile.load_balancing_config.token_awareness_enabled as cass_bool_t
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_profile_get_latency_aware_routing(
+    profile: CassBorrowedSharedPtr<CassExecProfile, CConst>,
+) -> cass_bool_t {
+    let Some(profile) = BoxFFI::as_ref(profile) else {
+        tracing::error!(
+            "Provided null profile pointer to cass_execution_profile_get_latency_aware_routing!"
+        );
+        return cass_false;
+    };
+
+    profile.load_balancing_config.latency_awareness_enabled as cass_bool_t
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_profile_get_load_balance_local_dc(
+    profile: CassBorrowedSharedPtr<CassExecProfile, CConst>,
+    local_dc: *mut *const c_char,
+    local_dc_length: *mut size_t,
+) -> CassError {
+    let Some(profile) = BoxFFI::as_ref(profile) else {
+        tracing::error!(
+            "Provided null profile pointer to cass_execution_profile_get_load_balance_local_dc!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+    if local_dc.is_null() || local_dc_length.is_null() {
+        tracing::error!(
+            "Provided null output pointer to cass_execution_profile_get_load_balance_local_dc!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    }
+
+    match &profile.load_balancing_config.load_balancing_kind {
+        Some(LoadBalancingKind::DcAware { local_dc: dc })
+        | Some(LoadBalancingKind::RackAware { local_dc: dc, .. }) => {
+            unsafe { write_str_to_c(dc, local_dc, local_dc_length) };
+            CassError::CASS_OK
+        }
+        Some(LoadBalancingKind::RoundRobin) | None => CassError::CASS_ERROR_LIB_NULL_VALUE,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_profile_get_load_balance_local_rack(
+    profile: CassBorrowedSharedPtr<CassExecProfile, CConst>,
+    local_rack: *mut *const c_char,
+    local_rack_length: *mut size_t,
+) -> CassError {
+    let Some(profile) = BoxFFI::as_ref(profile) else {
+        tracing::error!(
+            "Provided null profile pointer to cass_execution_profile_get_load_balance_local_rack!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+    if local_rack.is_null() || local_rack_length.is_null() {
+        tracing::error!(
+            "Provided null output pointer to cass_execution_profile_get_load_balance_local_rack!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    }
+
+    match &profile.load_balancing_config.load_balancing_kind {
+        Some(LoadBalancingKind::RackAware {
+            local_rack: rack, ..
+        }) => {
+            unsafe { write_str_to_c(rack, local_rack, local_rack_length) };
+            CassError::CASS_OK
+        }
+        _ => CassError::CASS_ERROR_LIB_NULL_VALUE,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::Ipv4Addr;
@@ -761,7 +1007,9 @@ mod tests {
         argconv::{make_c_str, str_to_c_str_n},
         batch::{cass_batch_add_statement, cass_batch_free, cass_batch_new},
         cass_types::CassBatchType,
-        statement::{cass_statement_free, cass_statement_new},
+        statement::{
+            cass_statement_free, cass_statement_new, cass_statement_set_serial_consistency,
+        },
     };
 
     use assert_matches::assert_matches;
@@ -991,7 +1239,7 @@ mod tests {
 
     impl PerStatementExecProfile {
         pub(crate) fn inner(&self) -> &Arc<RwLock<PerStatementExecProfileInner>> {
-            &self.0
+            &self.inner
         }
     }
 
@@ -1170,4 +1418,441 @@ mod tests {
             cass_batch_free(batch_raw);
         }
     }
+
+    #[test]
+    fn test_consistency_inherited_from_cluster() {
+        let cluster_default_profile = ExecutionProfile::builder()
+            .consistency(Consistency::Two)
+            .serial_consistency(Some(SerialConsistency::LocalSerial))
+            .build();
+
+        unsafe {
+            let mut profile_raw = cass_execution_profile_new();
+
+            // Not set on the profile - inherited from the cluster.
+            {
+                let profile = BoxFFI::as_ref(profile_raw.borrow()).unwrap().clone();
+                let built = crate::RUNTIME.block_on(profile.build(&cluster_default_profile));
+                assert_eq!(built.get_consistency(), Consistency::Two);
+                assert_eq!(
+                    built.get_serial_consistency(),
+                    Some(SerialConsistency::LocalSerial)
+                );
+            }
+
+            // Set on the profile - takes precedence over the cluster.
+            {
+                assert_cass_error_eq!(
+                    cass_execution_profile_set_consistency(
+                        profile_raw.borrow_mut(),
+                        CassConsistency::CASS_CONSISTENCY_QUORUM
+                    ),
+                    CassError::CASS_OK
+                );
+                assert_cass_error_eq!(
+                    cass_execution_profile_set_serial_consistency(
+                        profile_raw.borrow_mut(),
+                        CassConsistency::CASS_CONSISTENCY_UNKNOWN
+                    ),
+                    CassError::CASS_OK
+                );
+
+                let profile = BoxFFI::as_ref(profile_raw.borrow()).unwrap().clone();
+                let built = crate::RUNTIME.block_on(profile.build(&cluster_default_profile));
+                assert_eq!(built.get_consistency(), Consistency::Quorum);
+                assert_eq!(built.get_serial_consistency(), None);
+            }
+
+            cass_execution_profile_free(profile_raw);
+        }
+    }
+
+    #[test]
+    fn test_filtering_applied_on_top_of_cluster_policy() {
+        let cluster_default_profile = ExecutionProfile::builder().build();
+        let cluster_policy_name = cluster_default_profile.get_load_balancing_policy().name();
+
+        unsafe {
+            let mut profile_raw = cass_execution_profile_new();
+
+            // No filtering - cluster's policy is used as is.
+            {
+                let profile = BoxFFI::as_ref(profile_raw.borrow()).unwrap().clone();
+                let built = crate::RUNTIME.block_on(profile.build(&cluster_default_profile));
+                assert_eq!(
+                    built.get_load_balancing_policy().name(),
+                    cluster_policy_name
+                );
+            }
+
+            // Filtering without profile's LB kind - cluster's policy is filtered.
+            {
+                assert_cass_error_eq!(
+                    cass_execution_profile_set_blacklist_dc_filtering(
+                        profile_raw.borrow_mut(),
+                        c"dc2".as_ptr()
+                    ),
+                    CassError::CASS_OK
+                );
+
+                let profile = BoxFFI::as_ref(profile_raw.borrow()).unwrap().clone();
+                let built = crate::RUNTIME.block_on(profile.build(&cluster_default_profile));
+                assert_eq!(
+                    built.get_load_balancing_policy().name(),
+                    format!("FilteringLoadBalancingPolicy({})", cluster_policy_name)
+                );
+            }
+
+            cass_execution_profile_free(profile_raw);
+        }
+    }
+
+    #[test]
+    fn test_statement_serial_consistency_overrides_exec_profile() {
+        let cluster_default_profile = ExecutionProfile::builder().build();
+
+        unsafe {
+            let mut profile_raw = cass_execution_profile_new();
+            let mut statement_raw = cass_statement_new(make_c_str!("SELECT 1"), 0);
+
+            assert_cass_error_eq!(
+                cass_execution_profile_set_serial_consistency(
+                    profile_raw.borrow_mut(),
+                    CassConsistency::CASS_CONSISTENCY_LOCAL_SERIAL
+                ),
+                CassError::CASS_OK
+            );
+            let profile = BoxFFI::as_ref(profile_raw.borrow()).unwrap().clone();
+            let built = crate::RUNTIME.block_on(profile.build(&cluster_default_profile));
+            assert_eq!(
+                built.get_serial_consistency(),
+                Some(SerialConsistency::LocalSerial)
+            );
+
+            let effective_serial_consistency =
+                |statement_raw: CassBorrowedSharedPtr<CassStatement, CMut>| {
+                    BoxFFI::as_ref(statement_raw)
+                        .unwrap()
+                        .statement
+                        .effective_serial_consistency(&built)
+                };
+
+            // Not set on the statement - the profile's one applies.
+            assert_eq!(
+                effective_serial_consistency(statement_raw.borrow()),
+                Some(SerialConsistency::LocalSerial)
+            );
+
+            // Set on the statement - takes precedence over the profile.
+            assert_cass_error_eq!(
+                cass_statement_set_serial_consistency(
+                    statement_raw.borrow_mut(),
+                    CassConsistency::CASS_CONSISTENCY_SERIAL
+                ),
+                CassError::CASS_OK
+            );
+            assert_eq!(
+                effective_serial_consistency(statement_raw.borrow()),
+                Some(SerialConsistency::Serial)
+            );
+
+            cass_statement_free(statement_raw);
+            cass_execution_profile_free(profile_raw);
+        }
+    }
+
+    #[test]
+    fn test_unknown_serial_consistency_disables_cluster_setting() {
+        for cluster_serial_consistency in [
+            None,
+            Some(SerialConsistency::Serial),
+            Some(SerialConsistency::LocalSerial),
+        ] {
+            let cluster_default_profile = ExecutionProfile::builder()
+                .serial_consistency(cluster_serial_consistency)
+                .build();
+
+            unsafe {
+                let mut profile_raw = cass_execution_profile_new();
+
+                // Only serial consistencies (and UNKNOWN) are accepted.
+                assert_cass_error_eq!(
+                    cass_execution_profile_set_serial_consistency(
+                        profile_raw.borrow_mut(),
+                        CassConsistency::CASS_CONSISTENCY_QUORUM
+                    ),
+                    CassError::CASS_ERROR_LIB_BAD_PARAMS
+                );
+
+                assert_cass_error_eq!(
+                    cass_execution_profile_set_serial_consistency(
+                        profile_raw.borrow_mut(),
+                        CassConsistency::CASS_CONSISTENCY_UNKNOWN
+                    ),
+                    CassError::CASS_OK
+                );
+                let profile = BoxFFI::as_ref(profile_raw.borrow()).unwrap().clone();
+                let built = crate::RUNTIME.block_on(profile.build(&cluster_default_profile));
+                assert_eq!(built.get_serial_consistency(), None);
+
+                cass_execution_profile_free(profile_raw);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rack_aware_load_balancing() {
+        let cluster_default_profile = ExecutionProfile::builder().build();
+        let cluster_policy_name = cluster_default_profile.get_load_balancing_policy().name();
+
+        unsafe {
+            let mut profile_raw = cass_execution_profile_new();
+
+            // Both dc and rack must be non-empty.
+            assert_cass_error_eq!(
+                cass_execution_profile_set_load_balance_rack_aware(
+                    profile_raw.borrow_mut(),
+                    c"eu".as_ptr(),
+                    c"".as_ptr()
+                ),
+                CassError::CASS_ERROR_LIB_BAD_PARAMS
+            );
+            assert_cass_error_eq!(
+                cass_execution_profile_set_load_balance_rack_aware(
+                    profile_raw.borrow_mut(),
+                    std::ptr::null(),
+                    c"rack1".as_ptr()
+                ),
+                CassError::CASS_ERROR_LIB_BAD_PARAMS
+            );
+            {
+                let profile = BoxFFI::as_ref(profile_raw.borrow()).unwrap();
+                assert_matches!(profile.load_balancing_config.load_balancing_kind, None);
+            }
+
+            assert_cass_error_eq!(
+                cass_execution_profile_set_load_balance_rack_aware(
+                    profile_raw.borrow_mut(),
+                    c"eu".as_ptr(),
+                    c"rack1".as_ptr()
+                ),
+                CassError::CASS_OK
+            );
+            {
+                let profile = BoxFFI::as_ref(profile_raw.borrow()).unwrap();
+                assert_matches!(
+                    &profile.load_balancing_config.load_balancing_kind,
+                    Some(LoadBalancingKind::RackAware { local_dc, local_rack })
+                        if local_dc == "eu" && local_rack == "rack1"
+                );
+
+                // The profile uses its own policy, not the cluster's one.
+                let built =
+                    crate::RUNTIME.block_on(profile.clone().build(&cluster_default_profile));
+                assert_ne!(
+                    built.get_load_balancing_policy().name(),
+                    cluster_policy_name
+                );
+            }
+
+            cass_execution_profile_free(profile_raw);
+        }
+    }
+
+    #[test]
+    fn test_exec_profile_getters() {
+        unsafe {
+            let mut profile_raw = cass_execution_profile_new();
+            let mut name: *const c_char = std::ptr::null();
+            let mut name_len: size_t = 0;
+
+            // Defaults
+            {
+                let profile = profile_raw.borrow().into_c_const();
+                assert_eq!(
+                    cass_execution_profile_get_consistency(profile.borrow()),
+                    CassConsistency::CASS_CONSISTENCY_UNKNOWN
+                );
+                assert_eq!(
+                    cass_execution_profile_get_serial_consistency(profile.borrow()),
+                    CassConsistency::CASS_CONSISTENCY_UNKNOWN
+                );
+                assert_eq!(
+                    cass_execution_profile_get_request_timeout(profile.borrow()),
+                    cass_uint64_t::MAX
+                );
+                assert!(cass_execution_profile_get_retry_policy(profile.borrow()).is_null());
+                assert_eq!(
+                    cass_execution_profile_get_token_aware_routing(profile.borrow()),
+                    1
+                );
+                assert_eq!(
+                    cass_execution_profile_get_latency_aware_routing(profile.borrow()),
+                    0
+                );
+                assert_cass_error_eq!(
+                    cass_execution_profile_get_load_balance_local_dc(
+                        profile.borrow(),
+                        &mut name,
+                        &mut name_len
+                    ),
+                    CassError::CASS_ERROR_LIB_NULL_VALUE
+                );
+            }
+
+            cass_execution_profile_set_consistency(
+                profile_raw.borrow_mut(),
+                CassConsistency::CASS_CONSISTENCY_LOCAL_QUORUM,
+            );
+            cass_execution_profile_set_serial_consistency(
+                profile_raw.borrow_mut(),
+                CassConsistency::CASS_CONSISTENCY_LOCAL_SERIAL,
+            );
+            cass_execution_profile_set_request_timeout(profile_raw.borrow_mut(), 5000);
+            let retry_policy_raw = crate::retry_policy::cass_retry_policy_fallthrough_new();
+            cass_execution_profile_set_retry_policy(
+                profile_raw.borrow_mut(),
+                retry_policy_raw.borrow(),
+            );
+            cass_execution_profile_set_token_aware_routing(profile_raw.borrow_mut(), 0);
+            cass_execution_profile_set_load_balance_rack_aware(
+                profile_raw.borrow_mut(),
+                c"dc1".as_ptr(),
+                c"rack1".as_ptr(),
+            );
+            // The profile keeps its own reference to the retry policy.
+            crate::retry_policy::cass_retry_policy_free(retry_policy_raw);
+
+            {
+                let profile = profile_raw.borrow().into_c_const();
+                assert_eq!(
+                    cass_execution_profile_get_consistency(profile.borrow()),
+                    CassConsistency::CASS_CONSISTENCY_LOCAL_QUORUM
+                );
+                assert_eq!(
+                    cass_execution_profile_get_serial_consistency(profile.borrow()),
+                    CassConsistency::CASS_CONSISTENCY_LOCAL_SERIAL
+                );
+                assert_eq!(
+                    cass_execution_profile_get_request_timeout(profile.borrow()),
+                    5000
+                );
+                assert_matches!(
+                    ArcFFI::as_ref(cass_execution_profile_get_retry_policy(profile.borrow())),
+                    Some(FallthroughRetryPolicy(_))
+                );
+                assert_eq!(
+                    cass_execution_profile_get_token_aware_routing(profile.borrow()),
+                    0
+                );
+                assert_cass_error_eq!(
+                    cass_execution_profile_get_load_balance_local_dc(
+                        profile.borrow(),
+                        &mut name,
+                        &mut name_len
+                    ),
+                    CassError::CASS_OK
+                );
+                assert_eq!(
+                    std::str::from_utf8(std::slice::from_raw_parts(
+                        name as *const u8,
+                        name_len as usize
+                    ))
+                    .unwrap(),
+                    "dc1"
+                );
+                assert_cass_error_eq!(
+                    cass_execution_profile_get_load_balance_local_rack(
+                        profile.borrow(),
+                        &mut name,
+                        &mut name_len
+                    ),
+                    CassError::CASS_OK
+                );
+                assert_eq!(
+                    std::str::from_utf8(std::slice::from_raw_parts(
+                        name as *const u8,
+                        name_len as usize
+                    ))
+                    .unwrap(),
+                    "rack1"
+                );
+            }
+
+            cass_execution_profile_free(profile_raw);
+        }
+    }
+
+    #[test]
+    fn test_exec_profile_paging_size() {
+        unsafe {
+            let mut profile_raw = cass_execution_profile_new();
+            assert_eq!(
+                cass_execution_profile_get_paging_size(profile_raw.borrow().into_c_const()),
+                -1
+            );
+
+            assert_cass_error_eq!(
+                cass_execution_profile_set_paging_size(profile_raw.borrow_mut(), 1000),
+                CassError::CASS_OK
+            );
+            assert_eq!(
+                cass_execution_profile_get_paging_size(profile_raw.borrow().into_c_const()),
+                1000
+            );
+            {
+                let profile = BoxFFI::as_ref(profile_raw.borrow()).unwrap();
+                assert_eq!(profile.page_size, Some(Some(1000)));
+            }
+
+            // Non-positive page size disables paging.
+            assert_cass_error_eq!(
+                cass_execution_profile_set_paging_size(profile_raw.borrow_mut(), 0),
+                CassError::CASS_OK
+            );
+            assert_eq!(
+                cass_execution_profile_get_paging_size(profile_raw.borrow().into_c_const()),
+                -1
+            );
+
+            cass_execution_profile_free(profile_raw);
+        }
+    }
+
+    #[test]
+    fn test_exec_profile_local_dc_and_rack_getters_reject_null_output() {
+        unsafe {
+            let mut profile_raw = cass_execution_profile_new();
+            assert_cass_error_eq!(
+                cass_execution_profile_set_load_balance_rack_aware(
+                    profile_raw.borrow_mut(),
+                    c"dc1".as_ptr(),
+                    c"rack1".as_ptr()
+                ),
+                CassError::CASS_OK
+            );
+
+            let profile = profile_raw.borrow().into_c_const();
+            let mut length: size_t = 0;
+            let mut value: *const c_char = std::ptr::null();
+            assert_cass_error_eq!(
+                cass_execution_profile_get_load_balance_local_dc(
+                    profile.borrow(),
+                    std::ptr::null_mut(),
+                    &mut length
+                ),
+                CassError::CASS_ERROR_LIB_BAD_PARAMS
+            );
+            assert_cass_error_eq!(
+                cass_execution_profile_get_load_balance_local_rack(
+                    profile.borrow(),
+                    &mut value,
+                    std::ptr::null_mut()
+                ),
+                CassError::CASS_ERROR_LIB_BAD_PARAMS
+            );
+
+            cass_execution_profile_free(profile_raw);
+        }
+    }
 }
@@ -0,0 +1,114 @@
+use crate::argconv::*;
+use crate::cass_error::CassError;
+use crate::cass_types::CassConsistency;
+use crate::inet::CassInet;
+use crate::types::*;
+use scylla::statement::Consistency;
+use std::net::SocketAddr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CassAttemptInfo {
+    pub(crate) host: SocketAddr,
+    // CASS_OK for successful attempt.
+    pub(crate) error: CassError,
+}
+
+/// Information about the execution of a single request (statement or batch).
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CassExecutionInfo {
+    pub(crate) attempts: Vec<CassAttemptInfo>,
+    // Consistency of the successful attempt. None, if the request failed.
+    pub(crate) achieved_consistency: Option<Consistency>,
+    pub(crate) speculative_execution_used: bool,
+}
+
+// Borrowed from CassResult in cass_result_execution_info,
+// or from CassFuture in cass_future_execution_info.
+impl FFI for CassExecutionInfo {
+    type Origin = FromRef;
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_info_attempt_count(
+    execution_info: CassBorrowedSharedPtr<CassExecutionInfo, CConst>,
+) -> size_t {
+    let Some(execution_info) = RefFFI::as_ref(execution_info) else {
+        tracing::error!(
+            "Provided null execution info pointer to cass_execution_info_attempt_count!"
+        );
+        return 0;
+    };
+
+    execution_info.attempts.len() as size_t
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_info_attempt_host(
+    execution_info: CassBorrowedSharedPtr<CassExecutionInfo, CConst>,
+    index: size_t,
+    host: *mut CassInet,
+) -> CassError {
+    let Some(execution_info) = RefFFI::as_ref(execution_info) else {
+        tracing::error!(
+            "Provided null execution info pointer to cass_execution_info_attempt_host!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    let Some(attempt) = execution_info.attempts.get(index as usize) else {
+        return CassError::CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS;
+    };
+
+    unsafe { *host = attempt.host.ip().into() };
+    CassError::CASS_OK
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_info_attempt_error(
+    execution_info: CassBorrowedSharedPtr<CassExecutionInfo, CConst>,
+    index: size_t,
+) -> CassError {
+    let Some(execution_info) = RefFFI::as_ref(execution_info) else {
+        tracing::error!(
+            "Provided null execution info pointer to cass_execution_info_attempt_error!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    execution_info
+        .attempts
+        .get(index as usize)
+        .map(|attempt| attempt.error)
+        .unwrap_or(CassError::CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_info_achieved_consistency(
+    execution_info: CassBorrowedSharedPtr<CassExecutionInfo, CConst>,
+) -> CassConsistency {
+    let Some(execution_info) = RefFFI::as_ref(execution_info) else {
+        tracing::error!(
+            "Provided null execution info pointer to cass_execution_info_achieved_consistency!"
+        );
+        return CassConsistency::CASS_CONSISTENCY_UNKNOWN;
+    };
+
+    execution_info
+        .achieved_consistency
+        .map(CassConsistency::from)
+        .unwrap_or(CassConsistency::CASS_CONSISTENCY_UNKNOWN)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_execution_info_speculative_execution_used(
+    execution_info: CassBorrowedSharedPtr<CassExecutionInfo, CConst>,
+) -> cass_bool_t {
+    let Some(execution_info) = RefFFI::as_ref(execution_info) else {
+        tracing::error!(
+            "Provided null execution info pointer to cass_execution_info_speculative_execution_used!"
+        );
+        return cass_false;
+    };
+
+    execution_info.speculative_execution_used as cass_bool_t
+}
@@ -43,6 +43,8 @@ pub unsafe extern "C" fn cass_error_desc(error: CassError) -> *const c_char {
             c"Invalid execution profile specified"
         }
         CassError::CASS_ERROR_LIB_NO_TRACING_ID => c"No tracing ID",
+        CassError::CASS_ERROR_LIB_REQUEST_CANCELLED => c"Request cancelled",
+        CassError::CASS_ERROR_LIB_RESULT_MEMORY_LIMIT_EXCEEDED => c"Result memory limit exceeded",
         CassError::CASS_ERROR_SERVER_SERVER_ERROR => c"Server error",
         CassError::CASS_ERROR_SERVER_PROTOCOL_ERROR => c"Protocol error",
         CassError::CASS_ERROR_SERVER_BAD_CREDENTIALS => c"Bad credentials",
@@ -4,17 +4,18 @@ use crate::cass_error::CassError;
 use crate::cass_error::CassErrorMessage;
 use crate::cass_error::ToCassError;
 use crate::execution_error::CassErrorResult;
+use crate::execution_info::CassExecutionInfo;
 use crate::prepared::CassPrepared;
 use crate::query_result::{CassNode, CassResult};
+use crate::request_tracker::TrackedRequestSlot;
 use crate::types::*;
 use crate::uuid::CassUuid;
-use futures::future;
 use std::future::Future;
 use std::mem;
 use std::os::raw::c_void;
 use std::sync::{Arc, Condvar, Mutex, OnceLock};
-use tokio::task::JoinHandle;
-use tokio::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::task::AbortHandle;
 
 #[derive(Debug)]
 pub enum CassResultValue {
@@ -53,13 +54,16 @@ impl BoundCallback {
 struct CassFutureState {
     err_string: Option<String>,
     callback: Option<BoundCallback>,
-    join_handle: Option<JoinHandle<()>>,
+    // Handle to the task driving the future. Used to abort the task on cancellation.
+    abort_handle: Option<AbortHandle>,
 }
 
 pub struct CassFuture {
     state: Mutex<CassFutureState>,
     result: OnceLock<CassFutureResult>,
     wait_for_value: Condvar,
+    // Set only for futures of executed statements and batches.
+    tracked_request: Option<TrackedRequestSlot>,
 }
 
 impl FFI for CassFuture {
@@ -72,10 +76,6 @@ enum FutureError {
     InvalidDuration,
 }
 
-/// The timeout appeared when we tried to await `JoinHandle`.
-/// This errors contains the original handle, so it can be awaited later again.
-struct JoinHandleTimeout(JoinHandle<()>);
-
 impl CassFuture {
     pub fn make_raw(
         fut: impl Future<Output = CassFutureResult> + Send + 'static,
@@ -83,39 +83,76 @@ impl CassFuture {
         Self::new_from_future(fut).into_raw()
     }
 
+    /// Same as [`CassFuture::make_raw`], but the future additionally exposes
+    /// the execution info of the request provided via `tracked_request`.
+    pub(crate) fn make_raw_tracked(
+        fut: impl Future<Output = CassFutureResult> + Send + 'static,
+        tracked_request: TrackedRequestSlot,
+    ) -> CassOwnedSharedPtr<CassFuture, CMut> {
+        Self::new_from_future_with_tracked_request(fut, Some(tracked_request)).into_raw()
+    }
+
     pub fn new_from_future(
         fut: impl Future<Output = CassFutureResult> + Send + 'static,
+    ) -> Arc<CassFuture> {
+        Self::new_from_future_with_tracked_request(fut, None)
+    }
+
+    fn new_from_future_with_tracked_request(
+        fut: impl Future<Output = CassFutureResult> + Send + 'static,
+        tracked_request: Option<TrackedRequestSlot>,
     ) -> Arc<CassFuture> {
         let cass_fut = Arc::new(CassFuture {
             state: Mutex::new(Default::default()),
             result: OnceLock::new(),
             wait_for_value: Condvar::new(),
+            tracked_request,
         });
         let cass_fut_clone = Arc::clone(&cass_fut);
+        // The task is detached - it's driven to completion by the runtime,
+        // and the waiting threads are notified once the result is set.
         let join_handle = RUNTIME.spawn(async move {
             let r = fut.await;
-            let maybe_cb = {
-                let mut guard = cass_fut_clone.state.lock().unwrap();
-                cass_fut_clone
-                    .result
-                    .set(r)
-                    .expect("Tried to resolve future result twice!");
-                // Take the callback and call it after releasing the lock
-                guard.callback.take()
-            };
-            if let Some(bound_cb) = maybe_cb {
-                let fut_ptr = ArcFFI::as_ptr::<CMut>(&cass_fut_clone);
-                // Safety: pointer is valid, because we get it from arc allocation.
-                bound_cb.invoke(fut_ptr);
+            cass_fut_clone.resolve(r);
+        });
+        cass_fut.state.lock().unwrap().abort_handle = Some(join_handle.abort_handle());
+        cass_fut
+    }
+
+    /// Sets the result, invokes the callback (if any) and wakes up the waiting threads.
+    /// Returns `false` if the future had already been resolved.
+    fn resolve(self: &Arc<Self>, r: CassFutureResult) -> bool {
+        let maybe_cb = {
+            let mut guard = self.state.lock().unwrap();
+            if self.result.set(r).is_err() {
+                // The future has been cancelled in the meantime.
+                return false;
             }
+            guard.abort_handle = None;
+            // Take the callback and call it after releasing the lock
+            guard.callback.take()
+        };
+        if let Some(bound_cb) = maybe_cb {
+            let fut_ptr = ArcFFI::as_ptr::<CMut>(self);
+            // Safety: pointer is valid, because we get it from arc allocation.
+            bound_cb.invoke(fut_ptr);
+        }
 
-            cass_fut_clone.wait_for_value.notify_all();
-        });
-        {
-            let mut lock = cass_fut.state.lock().unwrap();
-            lock.join_handle = Some(join_handle);
+        self.wait_for_value.notify_all();
+        true
+    }
+
+    /// Aborts the task driving the future (dropping the in-flight request along with it)
+    /// and resolves the future with `CASS_ERROR_LIB_REQUEST_CANCELLED`.
+    /// Returns `false` if the future had already been resolved.
+    fn cancel(self: &Arc<Self>) -> bool {
+        if let Some(abort_handle) = self.state.lock().unwrap().abort_handle.take() {
+            abort_handle.abort();
         }
-        cass_fut
+        self.resolve(Err((
+            CassError::CASS_ERROR_LIB_REQUEST_CANCELLED,
+            "Request cancelled".to_string(),
+        )))
     }
 
     pub fn new_ready(r: CassFutureResult) -> Arc<Self> {
@@ -123,6 +160,7 @@ impl CassFuture {
             state: Mutex::new(CassFutureState::default()),
             result: OnceLock::from(r),
             wait_for_value: Condvar::new(),
+            tracked_request: None,
         })
     }
 
@@ -135,44 +173,18 @@ impl CassFuture {
 
     /// Awaits the future until completion.
     ///
-    /// There are two possible cases:
-    /// - noone is currently working on the future -> we take the ownership
-    ///   of JoinHandle (future) and we poll it until completion.
-    /// - some other thread is working on the future -> we wait on the condition
-    ///   variable to get an access to the future's state. Once we are notified,
-    ///   there are two cases:
-    ///     - JoinHandle is consumed -> some other thread already resolved the future.
-    ///       We can return.
-    ///     - JoinHandle is Some -> some other thread was working on the future, but
-    ///       timed out (see [CassFuture::with_waited_state_timed]). We need to
-    ///       take the ownership of the handle, and complete the work.
+    /// The future is driven to completion by the runtime, so the calling thread
+    /// only parks on the condition variable until the result is set.
+    /// This way, no matter how many threads wait for the futures, none of them
+    /// occupies the runtime (as opposed to polling the future with `block_on`).
     fn with_waited_state<T>(&self, f: impl FnOnce(&mut CassFutureState) -> T) -> T {
-        let mut guard = self.state.lock().unwrap();
-        loop {
-            let handle = guard.join_handle.take();
-            if let Some(handle) = handle {
-                mem::drop(guard);
-                // unwrap: JoinError appears only when future either panic'ed or canceled.
-                RUNTIME.block_on(handle).unwrap();
-                guard = self.state.lock().unwrap();
-            } else {
-                guard = self
-                    .wait_for_value
-                    .wait_while(guard, |state| {
-                        self.result.get().is_none() && state.join_handle.is_none()
-                    })
-                    // unwrap: Error appears only when mutex is poisoned.
-                    .unwrap();
-                if guard.join_handle.is_some() {
-                    // join_handle was none, and now it isn't - some other thread must
-                    // have timed out and returned the handle. We need to take over
-                    // the work of completing the future. To do that, we go into
-                    // another iteration so that we land in the branch with block_on.
-                    continue;
-                }
-            }
-            return f(&mut guard);
-        }
+        let guard = self.state.lock().unwrap();
+        let mut guard = self
+            .wait_for_value
+            .wait_while(guard, |_| self.result.get().is_none())
+            // unwrap: Error appears only when mutex is poisoned.
+            .unwrap();
+        f(&mut guard)
     }
 
     fn with_waited_result_timed<T>(
@@ -185,88 +197,31 @@ impl CassFuture {
 
     /// Tries to await the future with a given timeout.
     ///
-    /// There are two possible cases:
-    /// - noone is currently working on the future -> we take the ownership
-    ///   of JoinHandle (future) and we try to poll it with given timeout.
-    ///   If we timed out, we need to return the unfinished JoinHandle, so
-    ///   some other thread can complete the future later.
-    /// - some other thread is working on the future -> we wait on the condition
-    ///   variable to get an access to the future's state.
-    ///   Once we are notified (before the timeout), there are two cases.
-    ///     - JoinHandle is consumed -> some other thread already resolved the future.
-    ///       We can return.
-    ///     - JoinHandle is Some -> some other thread was working on the future, but
-    ///       timed out (see [CassFuture::with_waited_state_timed]). We need to
-    ///       take the ownership of the handle, and continue the work.
+    /// Same as [CassFuture::with_waited_state], the calling thread parks on
+    /// the condition variable until either the result is set or the timeout elapses.
+    /// If we timed out, the runtime keeps working on the future, so it can be
+    /// awaited again later.
     fn with_waited_state_timed<T>(
         &self,
         f: impl FnOnce(&mut CassFutureState) -> T,
         timeout_duration: Duration,
     ) -> Result<T, FutureError> {
-        let mut guard = self.state.lock().unwrap();
-        let deadline = tokio::time::Instant::now()
+        // Make sure that the deadline is representable.
+        Instant::now()
             .checked_add(timeout_duration)
             .ok_or(FutureError::InvalidDuration)?;
 
-        loop {
-            let handle = guard.join_handle.take();
-            if let Some(handle) = handle {
-                mem::drop(guard);
-                // Need to wrap it with async{} block, so the timeout is lazily executed inside the runtime.
-                // See mention about panics: https://docs.rs/tokio/latest/tokio/time/fn.timeout.html.
-                let timed = async {
-                    let sleep_future = tokio::time::sleep_until(deadline);
-                    tokio::pin!(sleep_future);
-                    let value = future::select(handle, sleep_future).await;
-                    match value {
-                        future::Either::Left((result, _)) => Ok(result),
-                        future::Either::Right((_, handle)) => Err(JoinHandleTimeout(handle)),
-                    }
-                };
-                match RUNTIME.block_on(timed) {
-                    Err(JoinHandleTimeout(returned_handle)) => {
-                        // We timed out. so we can't finish waiting for the future.
-                        // The problem is that if current thread executor is used,
-                        // then no one will run this future - other threads will
-                        // go into the branch with condvar and wait there.
-                        // To fix that:
-                        //  - Return the join handle, so that next thread can take it
-                        //  - Signal one thread, so that if all other consumers are
-                        //    already waiting on condvar, one of them wakes up and
-                        //    picks up the work.
-                        guard = self.state.lock().unwrap();
-                        guard.join_handle = Some(returned_handle);
-                        self.wait_for_value.notify_one();
-                        return Err(FutureError::TimeoutError);
-                    }
-                    // unwrap: JoinError appears only when future either panic'ed or canceled.
-                    Ok(result) => result.unwrap(),
-                };
-                guard = self.state.lock().unwrap();
-            } else {
-                let remaining_timeout = deadline.duration_since(tokio::time::Instant::now());
-                let (guard_result, timeout_result) = self
-                    .wait_for_value
-                    .wait_timeout_while(guard, remaining_timeout, |state| {
-                        self.result.get().is_none() && state.join_handle.is_none()
-                    })
-                    // unwrap: Error appears only when mutex is poisoned.
-                    .unwrap();
-                if timeout_result.timed_out() {
-                    return Err(FutureError::TimeoutError);
-                }
-                guard = guard_result;
-                if guard.join_handle.is_some() {
-                    // join_handle was none, and now it isn't - some other thread must
-                    // have timed out and returned the handle. We need to take over
-                    // the work of completing the future. To do that, we go into
-                    // another iteration so that we land in the branch with block_on.
-                    continue;
-                }
-            }
-
-            return Ok(f(&mut guard));
+        let guard = self.state.lock().unwrap();
+        let (mut guard, timeout_result) = self
+            .wait_for_value
+            .wait_timeout_while(guard, timeout_duration, |_| self.result.get().is_none())
+            // unwrap: Error appears only when mutex is poisoned.
+            .unwrap();
+        if timeout_result.timed_out() {
+            return Err(FutureError::TimeoutError);
         }
+
+        Ok(f(&mut guard))
     }
 
     pub unsafe fn set_callback(
@@ -397,6 +352,22 @@ pub unsafe extern "C" fn cass_future_error_message(
     });
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_future_cancel(
+    future_raw: CassBorrowedSharedPtr<CassFuture, CMut>,
+) -> CassError {
+    let Some(future) = ArcFFI::cloned_from_ptr(future_raw) else {
+        tracing::error!("Provided null future pointer to cass_future_cancel!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    if future.cancel() {
+        CassError::CASS_OK
+    } else {
+        CassError::CASS_ERROR_LIB_INVALID_STATE
+    }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_future_free(future_raw: CassOwnedSharedPtr<CassFuture, CMut>) {
     ArcFFI::free(future_raw);
@@ -499,6 +470,26 @@ pub unsafe extern "C" fn cass_future_coordinator(
     })
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_future_execution_info(
+    future_raw: CassBorrowedSharedPtr<CassFuture, CMut>,
+) -> CassBorrowedSharedPtr<CassExecutionInfo, CConst> {
+    let Some(future) = ArcFFI::as_ref(future_raw) else {
+        tracing::error!("Provided null future to cass_future_execution_info!");
+        return RefFFI::null();
+    };
+
+    future.with_waited_result(|_| {
+        // The slot is empty if request failed before it was sent (e.g. session not connected).
+        future
+            .tracked_request
+            .as_ref()
+            .and_then(|slot| slot.get())
+            .map(|tracked_request| RefFFI::as_ptr(tracked_request.execution_info().as_ref()))
+            .unwrap_or(RefFFI::null())
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::testing::{assert_cass_error_eq, assert_cass_future_error_message_eq};
@@ -577,6 +568,39 @@ mod tests {
         }
     }
 
+    // This test makes sure that the waiting threads do not occupy the runtime.
+    // There are much more waiting threads than runtime workers, and each future
+    // depends on a task spawned on the runtime.
+    #[test]
+    #[ntest::timeout(1000)]
+    fn cass_future_wait_does_not_starve_runtime() {
+        const ERROR_MSG: &str = "NOBODY EXPECTED SPANISH INQUISITION";
+        let waiters_count = 16 * thread::available_parallelism().map_or(1, |n| n.get());
+
+        let waiters: Vec<_> = (0..waiters_count)
+            .map(|_| {
+                thread::spawn(|| {
+                    let fut = async {
+                        tokio::spawn(tokio::time::sleep(Duration::from_millis(10)))
+                            .await
+                            .unwrap();
+                        Err((CassError::CASS_OK, ERROR_MSG.into()))
+                    };
+                    let cass_fut = CassFuture::make_raw(fut);
+                    unsafe {
+                        cass_future_wait(cass_fut.borrow());
+                        assert_cass_future_error_message_eq!(cass_fut, Some(ERROR_MSG));
+                        cass_future_free(cass_fut);
+                    }
+                })
+            })
+            .collect();
+
+        for waiter in waiters {
+            waiter.join().unwrap();
+        }
+    }
+
     // This test checks whether the future callback is executed correctly when:
     // - a future is awaited indefinitely
     // - a future is awaited, after the timeout appeared (_wait_timed)
@@ -687,4 +711,43 @@ mod tests {
             let _ = unsafe { Box::from_raw(flag_ptr) };
         }
     }
+
+    #[test]
+    #[ntest::timeout(200)]
+    fn cass_future_cancel_aborts_task() {
+        struct DropFlag(Arc<std::sync::atomic::AtomicBool>);
+        impl Drop for DropFlag {
+            fn drop(&mut self) {
+                self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let drop_flag = DropFlag(Arc::clone(&dropped));
+        let fut = async move {
+            let _drop_flag = drop_flag;
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(CassResultValue::Empty)
+        };
+        let cass_fut = CassFuture::make_raw(fut);
+
+        unsafe {
+            assert_cass_error_eq!(cass_future_cancel(cass_fut.borrow()), CassError::CASS_OK);
+            assert_cass_error_eq!(
+                cass_future_error_code(cass_fut.borrow()),
+                CassError::CASS_ERROR_LIB_REQUEST_CANCELLED
+            );
+            // Already resolved.
+            assert_cass_error_eq!(
+                cass_future_cancel(cass_fut.borrow()),
+                CassError::CASS_ERROR_LIB_INVALID_STATE
+            );
+            cass_future_free(cass_fut);
+        }
+
+        // The task is dropped asynchronously by the runtime.
+        while !dropped.load(std::sync::atomic::Ordering::SeqCst) {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
 }
@@ -1,28 +1,39 @@
+use std::collections::HashMap;
 use std::ffi::{CString, c_char};
-use std::net::SocketAddr;
-use std::sync::Arc;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use scylla::errors::{RequestAttemptError, RequestError};
+use scylla::errors::{DbError, RequestAttemptError, RequestError, WriteType};
 use scylla::observability::history::{AttemptId, HistoryListener, RequestId, SpeculativeId};
 use scylla::policies::retry::RetryDecision;
+use scylla::statement::Consistency;
+use scylla_proxy::{
+    Condition, Node, Proxy, Reaction, RequestOpcode, RequestReaction, RequestRule, RunningProxy,
+    ShardAwareness,
+};
 
+use crate::RUNTIME;
 use crate::argconv::{
     ArcFFI, BoxFFI, CConst, CMut, CassBorrowedExclusivePtr, CassBorrowedSharedPtr,
+    CassOwnedExclusivePtr, FFI, FromBox, ptr_to_cstr, strlen,
 };
 use crate::batch::CassBatch;
-use crate::cluster::CassCluster;
+use crate::cass_error::CassError;
+use crate::cluster::{CassCluster, update_comma_delimited_list};
 use crate::future::{CassFuture, CassResultValue};
 use crate::statement::{BoundStatement, CassStatement};
-use crate::types::{cass_int32_t, cass_uint16_t, cass_uint64_t, size_t};
+use crate::types::{
+    cass_bool_t, cass_byte_t, cass_int32_t, cass_uint8_t, cass_uint32_t, cass_uint64_t, size_t,
+};
 
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn testing_cluster_get_connect_timeout(
     cluster_raw: CassBorrowedExclusivePtr<CassCluster, CMut>,
-) -> cass_uint16_t {
+) -> cass_uint32_t {
     let cluster = BoxFFI::as_ref(cluster_raw).unwrap();
 
-    cluster.get_session_config().connect_timeout.as_millis() as cass_uint16_t
+    cluster.get_session_config().connect_timeout.as_millis() as cass_uint32_t
 }
 
 #[unsafe(no_mangle)]
@@ -174,3 +185,682 @@ pub unsafe extern "C" fn testing_batch_set_sleeping_history_listener(
         .batch
         .set_history_listener(history_listener)
 }
+
+/// A fault simulated by the proxy on the connections to a single node.
+#[derive(Clone, Copy, Debug, Default)]
+enum NodeFault {
+    #[default]
+    None,
+    /// Connections are closed upon the first frame sent on them, so the node is effectively down.
+    CloseConnections,
+    /// New connections are closed during the handshake. Established connections keep working.
+    RejectNewConnections,
+    /// The next `count` requests are responded to with `error`.
+    FailRequests { count: usize, error: CassError },
+}
+
+/// Condition matching the frames carrying the requests (as opposed to e.g. handshake).
+fn request_condition() -> Condition {
+    Condition::RequestOpcode(RequestOpcode::Query)
+        .or(Condition::RequestOpcode(RequestOpcode::Prepare))
+        .or(Condition::RequestOpcode(RequestOpcode::Execute))
+        .or(Condition::RequestOpcode(RequestOpcode::Batch))
+}
+
+impl NodeFault {
+    fn rules(self) -> Vec<RequestRule> {
+        let handshake = Condition::RequestOpcode(RequestOpcode::Options)
+            .or(Condition::RequestOpcode(RequestOpcode::Startup));
+
+        match self {
+            NodeFault::None => Vec::new(),
+            NodeFault::CloseConnections => vec![RequestRule(
+                Condition::True,
+                RequestReaction::drop_connection(),
+            )],
+            NodeFault::RejectNewConnections => {
+                vec![RequestRule(handshake, RequestReaction::drop_connection())]
+            }
+            NodeFault::FailRequests { count, error } => {
+                // unwrap: The error is validated when the fault is injected.
+                let reaction = forged_error_reaction(error).unwrap();
+                vec![RequestRule(
+                    request_condition().and(Condition::TrueForLimitedTimes(count)),
+                    reaction,
+                )]
+            }
+        }
+    }
+}
+
+/// Artificial latency of the requests sent to a single node.
+/// The latency of each request is drawn uniformly from `[latency, latency + jitter]`.
+#[derive(Clone, Copy, Debug)]
+struct InjectedLatency {
+    latency: Duration,
+    jitter: Duration,
+}
+
+impl InjectedLatency {
+    // The proxy can't draw the delay of each request itself, so the jitter range
+    // is split into buckets, and each request is delayed by one of them at random.
+    const JITTER_BUCKETS: u32 = 10;
+
+    fn rules(self) -> Vec<RequestRule> {
+        if self.jitter.is_zero() {
+            return vec![RequestRule(
+                request_condition(),
+                RequestReaction::delay(self.latency),
+            )];
+        }
+
+        (0..Self::JITTER_BUCKETS)
+            .map(|bucket| {
+                let delay = self.latency + self.jitter * bucket / (Self::JITTER_BUCKETS - 1);
+                // Bucket is chosen only if none of the previous ones was,
+                // so each one is chosen with the same probability.
+                let probability = 1.0 / f64::from(Self::JITTER_BUCKETS - bucket);
+                RequestRule(
+                    request_condition().and(Condition::RandomWithProbability(probability)),
+                    RequestReaction::delay(delay),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Returns a reaction responding with a server error corresponding to `error`.
+/// Returns None if `error` is not a server error that can be simulated.
+fn forged_error_reaction(error: CassError) -> Option<RequestReaction> {
+    let db_error = match error {
+        CassError::CASS_ERROR_SERVER_SERVER_ERROR => DbError::ServerError,
+        CassError::CASS_ERROR_SERVER_PROTOCOL_ERROR => DbError::ProtocolError,
+        CassError::CASS_ERROR_SERVER_BAD_CREDENTIALS => DbError::AuthenticationError,
+        CassError::CASS_ERROR_SERVER_UNAVAILABLE => DbError::Unavailable {
+            consistency: Consistency::Quorum,
+            required: 2,
+            alive: 1,
+        },
+        CassError::CASS_ERROR_SERVER_OVERLOADED => DbError::Overloaded,
+        CassError::CASS_ERROR_SERVER_IS_BOOTSTRAPPING => DbError::IsBootstrapping,
+        CassError::CASS_ERROR_SERVER_TRUNCATE_ERROR => DbError::TruncateError,
+        CassError::CASS_ERROR_SERVER_WRITE_TIMEOUT => DbError::WriteTimeout {
+            consistency: Consistency::Quorum,
+            received: 1,
+            required: 2,
+            write_type: WriteType::Simple,
+        },
+        CassError::CASS_ERROR_SERVER_READ_TIMEOUT => DbError::ReadTimeout {
+            consistency: Consistency::Quorum,
+            received: 1,
+            required: 2,
+            data_present: false,
+        },
+        CassError::CASS_ERROR_SERVER_READ_FAILURE => DbError::ReadFailure {
+            consistency: Consistency::Quorum,
+            received: 1,
+            required: 2,
+            numfailures: 1,
+            data_present: false,
+        },
+        CassError::CASS_ERROR_SERVER_WRITE_FAILURE => DbError::WriteFailure {
+            consistency: Consistency::Quorum,
+            received: 1,
+            required: 2,
+            numfailures: 1,
+            write_type: WriteType::Simple,
+        },
+        CassError::CASS_ERROR_SERVER_SYNTAX_ERROR => DbError::SyntaxError,
+        CassError::CASS_ERROR_SERVER_UNAUTHORIZED => DbError::Unauthorized,
+        CassError::CASS_ERROR_SERVER_INVALID_QUERY => DbError::Invalid,
+        CassError::CASS_ERROR_SERVER_CONFIG_ERROR => DbError::ConfigError,
+        _ => return None,
+    };
+
+    Some(RequestReaction::forge_with_error(db_error))
+}
+
+/// Condition of the request interception rule programmed by the user.
+///
+/// It's a description of the proxy's [`Condition`] - rules are rebuilt
+/// each time the rules of the node are reinstalled.
+#[derive(Clone, Debug)]
+pub enum TestingProxyCondition {
+    True,
+    RequestOpcode(u8),
+    BodyContains {
+        bytes: Box<[u8]>,
+        case_sensitive: bool,
+    },
+    TrueForLimitedTimes(usize),
+    Not(Box<TestingProxyCondition>),
+    And(Box<TestingProxyCondition>, Box<TestingProxyCondition>),
+    Or(Box<TestingProxyCondition>, Box<TestingProxyCondition>),
+}
+
+impl FFI for TestingProxyCondition {
+    type Origin = FromBox;
+}
+
+/// Maps the opcode defined by the CQL protocol to the proxy's [`RequestOpcode`].
+fn request_opcode(opcode: u8) -> Option<RequestOpcode> {
+    match opcode {
+        0x01 => Some(RequestOpcode::Startup),
+        0x05 => Some(RequestOpcode::Options),
+        0x07 => Some(RequestOpcode::Query),
+        0x09 => Some(RequestOpcode::Prepare),
+        0x0A => Some(RequestOpcode::Execute),
+        0x0B => Some(RequestOpcode::Register),
+        0x0D => Some(RequestOpcode::Batch),
+        0x0F => Some(RequestOpcode::AuthResponse),
+        _ => None,
+    }
+}
+
+impl TestingProxyCondition {
+    fn build(&self) -> Condition {
+        match self {
+            TestingProxyCondition::True => Condition::True,
+            // unwrap: The opcode is validated when the condition is created.
+            TestingProxyCondition::RequestOpcode(opcode) => {
+                Condition::RequestOpcode(request_opcode(*opcode).unwrap())
+            }
+            TestingProxyCondition::BodyContains {
+                bytes,
+                case_sensitive: true,
+            } => Condition::BodyContainsCaseSensitive(bytes.clone()),
+            TestingProxyCondition::BodyContains {
+                bytes,
+                case_sensitive: false,
+            } => Condition::BodyContainsCaseInsensitive(bytes.clone()),
+            TestingProxyCondition::TrueForLimitedTimes(times) => {
+                Condition::TrueForLimitedTimes(*times)
+            }
+            TestingProxyCondition::Not(c) => Condition::Not(Box::new(c.build())),
+            TestingProxyCondition::And(c1, c2) => c1.build().and(c2.build()),
+            TestingProxyCondition::Or(c1, c2) => c1.build().or(c2.build()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ReactionKind {
+    Noop,
+    DropFrame,
+    DropConnection,
+    ForgeError(CassError),
+}
+
+/// Reaction of the request interception rule programmed by the user.
+#[derive(Clone, Copy, Debug)]
+pub struct TestingProxyReaction {
+    kind: ReactionKind,
+    delay: Option<Duration>,
+}
+
+impl FFI for TestingProxyReaction {
+    type Origin = FromBox;
+}
+
+impl TestingProxyReaction {
+    fn build(&self) -> RequestReaction {
+        let reaction = match self.kind {
+            ReactionKind::Noop => RequestReaction::noop(),
+            ReactionKind::DropFrame => RequestReaction::drop_frame(),
+            ReactionKind::DropConnection => RequestReaction::drop_connection(),
+            // unwrap: The error is validated when the reaction is created.
+            ReactionKind::ForgeError(error) => forged_error_reaction(error).unwrap(),
+        };
+        match self.delay {
+            Some(delay) => reaction.with_time_to_reaction(delay),
+            None => reaction,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ProxiedNode {
+    real_address: SocketAddr,
+    proxy_address: SocketAddr,
+    fault: NodeFault,
+    // Rules programmed by the user.
+    user_rules: Vec<(TestingProxyCondition, TestingProxyReaction)>,
+    latency: Option<InjectedLatency>,
+}
+
+impl ProxiedNode {
+    fn rules(&self) -> Vec<RequestRule> {
+        // Faulty requests and the requests handled by the user rules are not delayed.
+        let mut rules = self.fault.rules();
+        rules.extend(
+            self.user_rules
+                .iter()
+                .map(|(condition, reaction)| RequestRule(condition.build(), reaction.build())),
+        );
+        if let Some(latency) = self.latency {
+            rules.extend(latency.rules());
+        }
+        rules
+    }
+}
+
+/// A proxy placed between the driver and the cluster nodes.
+/// It allows the tests to simulate node failures and increased latency.
+pub struct TestingProxy {
+    // Indexed the same way as `running_proxy.running_nodes`.
+    nodes: Mutex<Vec<ProxiedNode>>,
+    running_proxy: Mutex<Option<RunningProxy>>,
+}
+
+impl FFI for TestingProxy {
+    type Origin = FromBox;
+}
+
+impl TestingProxy {
+    /// Modifies the node with the provided address, and reinstalls its rules.
+    fn modify_node(&self, host: IpAddr, modify: impl FnOnce(&mut ProxiedNode)) -> CassError {
+        let mut nodes = self.nodes.lock().unwrap();
+        let Some(idx) = nodes.iter().position(|node| node.real_address.ip() == host) else {
+            tracing::error!("Host {} is not proxied!", host);
+            return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+        };
+        let node = &mut nodes[idx];
+        modify(node);
+
+        let mut running_proxy = self.running_proxy.lock().unwrap();
+        // unwrap: The proxy is stopped only when it's freed.
+        running_proxy.as_mut().unwrap().running_nodes[idx].change_request_rules(Some(node.rules()));
+
+        CassError::CASS_OK
+    }
+
+    /// Replaces the fault simulated on the node with the provided address.
+    fn set_fault(&self, host: IpAddr, fault: NodeFault) -> CassError {
+        self.modify_node(host, |node| node.fault = fault)
+    }
+}
+
+unsafe fn parse_host(host: *const c_char) -> Option<IpAddr> {
+    let host = unsafe { ptr_to_cstr(host) }?;
+    match host.parse() {
+        Ok(ip) => Some(ip),
+        Err(_) => {
+            tracing::error!("Provided invalid host address: {}!", host);
+            None
+        }
+    }
+}
+
+/// Starts a proxy in front of the nodes from the comma-delimited list of `hosts`,
+/// listening on `port`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_new(
+    hosts: *const c_char,
+    port: cass_int32_t,
+) -> CassOwnedExclusivePtr<TestingProxy, CMut> {
+    let Ok(port) = u16::try_from(port) else {
+        tracing::error!("Provided invalid port {} to testing_proxy_new!", port);
+        return BoxFFI::null_mut();
+    };
+
+    let mut real_addresses = Vec::new();
+    let hosts_length = unsafe { strlen(hosts) };
+    if let Err(err) = unsafe {
+        update_comma_delimited_list(&mut real_addresses, hosts, hosts_length, |s| {
+            s.parse::<IpAddr>().ok()
+        })
+    } {
+        tracing::error!("Provided invalid hosts to testing_proxy_new: {:?}!", err);
+        return BoxFFI::null_mut();
+    }
+
+    let nodes = real_addresses
+        .into_iter()
+        .map(|ip| ProxiedNode {
+            real_address: SocketAddr::new(ip, port),
+            proxy_address: SocketAddr::new(scylla_proxy::get_exclusive_local_address(), port),
+            fault: NodeFault::None,
+            user_rules: Vec::new(),
+            latency: None,
+        })
+        .collect::<Vec<_>>();
+
+    let proxy = nodes
+        .iter()
+        .fold(Proxy::builder(), |builder, node| {
+            builder.with_node(
+                Node::builder()
+                    .real_address(node.real_address)
+                    .proxy_address(node.proxy_address)
+                    .shard_awareness(ShardAwareness::Unaware)
+                    .build(),
+            )
+        })
+        .build();
+
+    let running_proxy = match RUNTIME.block_on(proxy.run()) {
+        Ok(running_proxy) => running_proxy,
+        Err(err) => {
+            tracing::error!("Failed to start the proxy: {}", err);
+            return BoxFFI::null_mut();
+        }
+    };
+
+    BoxFFI::into_ptr(Box::new(TestingProxy {
+        nodes: Mutex::new(nodes),
+        running_proxy: Mutex::new(Some(running_proxy)),
+    }))
+}
+
+/// Stops the proxy and frees it.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_free(proxy_raw: CassOwnedExclusivePtr<TestingProxy, CMut>) {
+    let Some(proxy) = BoxFFI::from_ptr(proxy_raw) else {
+        return;
+    };
+
+    if let Some(running_proxy) = proxy.running_proxy.lock().unwrap().take() {
+        let _ = RUNTIME.block_on(running_proxy.finish());
+    }
+}
+
+/// Makes the cluster connect to the nodes through the proxy.
+///
+/// Contact points are replaced with the proxy addresses, and the addresses
+/// of the nodes discovered by the driver are translated to the proxy addresses.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_cluster_set_proxy(
+    cluster_raw: CassBorrowedExclusivePtr<CassCluster, CMut>,
+    proxy_raw: CassBorrowedSharedPtr<TestingProxy, CConst>,
+) {
+    let cluster = BoxFFI::as_mut_ref(cluster_raw).unwrap();
+    let proxy = BoxFFI::as_ref(proxy_raw).unwrap();
+
+    let nodes = proxy.nodes.lock().unwrap();
+    let translation_map = nodes
+        .iter()
+        .map(|node| (node.real_address, node.proxy_address))
+        .collect::<HashMap<_, _>>();
+    let contact_points = nodes
+        .iter()
+        .map(|node| node.proxy_address.ip().to_string())
+        .collect();
+
+    cluster.set_contact_points(contact_points);
+    cluster.set_address_translator(Arc::new(translation_map));
+}
+
+/// Closes the connections to the host upon their next use, and rejects the new ones,
+/// so the host is perceived as down.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_close_connections(
+    proxy_raw: CassBorrowedSharedPtr<TestingProxy, CConst>,
+    host: *const c_char,
+) -> CassError {
+    let proxy = BoxFFI::as_ref(proxy_raw).unwrap();
+    let Some(host) = (unsafe { parse_host(host) }) else {
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    proxy.set_fault(host, NodeFault::CloseConnections)
+}
+
+/// Rejects new connections to the host. Established connections keep working.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_reject_new_connections(
+    proxy_raw: CassBorrowedSharedPtr<TestingProxy, CConst>,
+    host: *const c_char,
+) -> CassError {
+    let proxy = BoxFFI::as_ref(proxy_raw).unwrap();
+    let Some(host) = (unsafe { parse_host(host) }) else {
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    proxy.set_fault(host, NodeFault::RejectNewConnections)
+}
+
+/// Responds to the next `count` requests sent to the host with `error`.
+/// Only server errors (`CASS_ERROR_SERVER_*`) can be simulated.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_fail_next_requests(
+    proxy_raw: CassBorrowedSharedPtr<TestingProxy, CConst>,
+    host: *const c_char,
+    count: size_t,
+    error: CassError,
+) -> CassError {
+    let proxy = BoxFFI::as_ref(proxy_raw).unwrap();
+    let Some(host) = (unsafe { parse_host(host) }) else {
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+    if forged_error_reaction(error).is_none() {
+        tracing::error!("Error {:?} cannot be simulated by the proxy!", error);
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    }
+
+    proxy.set_fault(
+        host,
+        NodeFault::FailRequests {
+            count: count as usize,
+            error,
+        },
+    )
+}
+
+/// Stops simulating the failure of the host.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_heal_host(
+    proxy_raw: CassBorrowedSharedPtr<TestingProxy, CConst>,
+    host: *const c_char,
+) -> CassError {
+    let proxy = BoxFFI::as_ref(proxy_raw).unwrap();
+    let Some(host) = (unsafe { parse_host(host) }) else {
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    proxy.set_fault(host, NodeFault::None)
+}
+
+/// Delays the requests sent to the host by `latency_ms`, plus a random jitter
+/// of up to `jitter_ms`. Zero latency and jitter stop delaying the requests.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_set_host_latency(
+    proxy_raw: CassBorrowedSharedPtr<TestingProxy, CConst>,
+    host: *const c_char,
+    latency_ms: cass_uint64_t,
+    jitter_ms: cass_uint64_t,
+) -> CassError {
+    let proxy = BoxFFI::as_ref(proxy_raw).unwrap();
+    let Some(host) = (unsafe { parse_host(host) }) else {
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    let latency = (latency_ms != 0 || jitter_ms != 0).then(|| InjectedLatency {
+        latency: Duration::from_millis(latency_ms),
+        jitter: Duration::from_millis(jitter_ms),
+    });
+
+    proxy.modify_node(host, |node| node.latency = latency)
+}
+
+fn new_condition(
+    condition: TestingProxyCondition,
+) -> CassOwnedExclusivePtr<TestingProxyCondition, CMut> {
+    BoxFFI::into_ptr(Box::new(condition))
+}
+
+/// Creates a condition which is always true.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_condition_true()
+-> CassOwnedExclusivePtr<TestingProxyCondition, CMut> {
+    new_condition(TestingProxyCondition::True)
+}
+
+/// Creates a condition which is true for the requests with the provided CQL protocol opcode.
+/// Returns null if the opcode does not denote a request.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_condition_request_opcode(
+    opcode: cass_uint8_t,
+) -> CassOwnedExclusivePtr<TestingProxyCondition, CMut> {
+    if request_opcode(opcode).is_none() {
+        tracing::error!("Provided invalid request opcode {:#04x}!", opcode);
+        return BoxFFI::null_mut();
+    }
+
+    new_condition(TestingProxyCondition::RequestOpcode(opcode))
+}
+
+/// Creates a condition which is true for the requests whose body contains the provided bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_condition_body_contains(
+    bytes: *const cass_byte_t,
+    bytes_size: size_t,
+    case_sensitive: cass_bool_t,
+) -> CassOwnedExclusivePtr<TestingProxyCondition, CMut> {
+    let bytes = unsafe { std::slice::from_raw_parts(bytes, bytes_size as usize) };
+
+    new_condition(TestingProxyCondition::BodyContains {
+        bytes: bytes.into(),
+        case_sensitive: case_sensitive != 0,
+    })
+}
+
+/// Creates a condition which is true for the first `times` requests it is evaluated for.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_condition_true_for_limited_times(
+    times: size_t,
+) -> CassOwnedExclusivePtr<TestingProxyCondition, CMut> {
+    new_condition(TestingProxyCondition::TrueForLimitedTimes(times as usize))
+}
+
+/// Negates the condition. Takes ownership of the provided condition.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_condition_not(
+    condition_raw: CassOwnedExclusivePtr<TestingProxyCondition, CMut>,
+) -> CassOwnedExclusivePtr<TestingProxyCondition, CMut> {
+    let condition = BoxFFI::from_ptr(condition_raw).unwrap();
+
+    new_condition(TestingProxyCondition::Not(condition))
+}
+
+/// Creates a conjunction of the conditions. Takes ownership of the provided conditions.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_condition_and(
+    condition1_raw: CassOwnedExclusivePtr<TestingProxyCondition, CMut>,
+    condition2_raw: CassOwnedExclusivePtr<TestingProxyCondition, CMut>,
+) -> CassOwnedExclusivePtr<TestingProxyCondition, CMut> {
+    let condition1 = BoxFFI::from_ptr(condition1_raw).unwrap();
+    let condition2 = BoxFFI::from_ptr(condition2_raw).unwrap();
+
+    new_condition(TestingProxyCondition::And(condition1, condition2))
+}
+
+/// Creates a disjunction of the conditions. Takes ownership of the provided conditions.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_condition_or(
+    condition1_raw: CassOwnedExclusivePtr<TestingProxyCondition, CMut>,
+    condition2_raw: CassOwnedExclusivePtr<TestingProxyCondition, CMut>,
+) -> CassOwnedExclusivePtr<TestingProxyCondition, CMut> {
+    let condition1 = BoxFFI::from_ptr(condition1_raw).unwrap();
+    let condition2 = BoxFFI::from_ptr(condition2_raw).unwrap();
+
+    new_condition(TestingProxyCondition::Or(condition1, condition2))
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_condition_free(
+    condition_raw: CassOwnedExclusivePtr<TestingProxyCondition, CMut>,
+) {
+    BoxFFI::free(condition_raw);
+}
+
+fn new_reaction(kind: ReactionKind) -> CassOwnedExclusivePtr<TestingProxyReaction, CMut> {
+    BoxFFI::into_ptr(Box::new(TestingProxyReaction { kind, delay: None }))
+}
+
+/// Creates a reaction which passes the request to the node.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_reaction_noop()
+-> CassOwnedExclusivePtr<TestingProxyReaction, CMut> {
+    new_reaction(ReactionKind::Noop)
+}
+
+/// Creates a reaction which drops the request, so it's never responded to.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_reaction_drop_frame()
+-> CassOwnedExclusivePtr<TestingProxyReaction, CMut> {
+    new_reaction(ReactionKind::DropFrame)
+}
+
+/// Creates a reaction which closes the connection the request was sent on.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_reaction_drop_connection()
+-> CassOwnedExclusivePtr<TestingProxyReaction, CMut> {
+    new_reaction(ReactionKind::DropConnection)
+}
+
+/// Creates a reaction which responds to the request with `error`, instead of passing it to the node.
+/// Only server errors (`CASS_ERROR_SERVER_*`) can be forged. Returns null for other errors.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_reaction_forge_error(
+    error: CassError,
+) -> CassOwnedExclusivePtr<TestingProxyReaction, CMut> {
+    if forged_error_reaction(error).is_none() {
+        tracing::error!("Error {:?} cannot be forged by the proxy!", error);
+        return BoxFFI::null_mut();
+    }
+
+    new_reaction(ReactionKind::ForgeError(error))
+}
+
+/// Delays the reaction by `delay_ms`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_reaction_set_delay(
+    reaction_raw: CassBorrowedExclusivePtr<TestingProxyReaction, CMut>,
+    delay_ms: cass_uint64_t,
+) {
+    let reaction = BoxFFI::as_mut_ref(reaction_raw).unwrap();
+
+    reaction.delay = Some(Duration::from_millis(delay_ms));
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_reaction_free(
+    reaction_raw: CassOwnedExclusivePtr<TestingProxyReaction, CMut>,
+) {
+    BoxFFI::free(reaction_raw);
+}
+
+/// Appends the rule to the request interception rules of the host.
+/// The reaction of the first rule whose condition is true for a request is applied.
+///
+/// Takes ownership of the provided condition and reaction.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_add_rule(
+    proxy_raw: CassBorrowedSharedPtr<TestingProxy, CConst>,
+    host: *const c_char,
+    condition_raw: CassOwnedExclusivePtr<TestingProxyCondition, CMut>,
+    reaction_raw: CassOwnedExclusivePtr<TestingProxyReaction, CMut>,
+) -> CassError {
+    let proxy = BoxFFI::as_ref(proxy_raw).unwrap();
+    let condition = BoxFFI::from_ptr(condition_raw).unwrap();
+    let reaction = BoxFFI::from_ptr(reaction_raw).unwrap();
+    let Some(host) = (unsafe { parse_host(host) }) else {
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    proxy.modify_node(host, |node| node.user_rules.push((*condition, *reaction)))
+}
+
+/// Removes all the request interception rules of the host added with `testing_proxy_add_rule`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn testing_proxy_clear_rules(
+    proxy_raw: CassBorrowedSharedPtr<TestingProxy, CConst>,
+    host: *const c_char,
+) -> CassError {
+    let proxy = BoxFFI::as_ref(proxy_raw).unwrap();
+    let Some(host) = (unsafe { parse_host(host) }) else {
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    proxy.modify_node(host, |node| node.user_rules.clear())
+}
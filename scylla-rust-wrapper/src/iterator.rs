@@ -19,12 +19,15 @@ use crate::types::{cass_bool_t, cass_false, size_t};
 
 pub use crate::cass_iterator_types::CassIteratorType;
 
+use std::collections::HashMap;
+use std::collections::hash_map;
 use std::os::raw::c_char;
 use std::sync::Arc;
 
 pub struct CassRowsResultIterator<'result> {
     iterator: TypedRowIterator<'result, 'result, CassRawRow<'result, 'result>>,
     result_metadata: &'result CassResultMetadata,
+    numeric_widening: bool,
     current_row: Option<CassRow<'result>>,
 }
 
@@ -39,25 +42,30 @@ impl CassResultIterator<'_> {
             return false;
         };
 
-        let new_row =
-            rows_result_iterator
-                .iterator
-                .next()
-                .and_then(|raw_row_res: Result<CassRawRow, _>| {
-                    raw_row_res
-                        .and_then(|raw_row| {
-                            CassRow::from_raw_row_and_metadata(
-                                raw_row,
-                                rows_result_iterator.result_metadata,
-                            )
-                        })
-                        .inspect_err(|e| {
-                            // We have no way to propagate the error (return type is bool).
-                            // Let's at least log the deserialization error.
-                            tracing::error!("Failed to deserialize next row: {e}");
-                        })
-                        .ok()
-                });
+        // Only one row is exposed at a time - reuse the columns buffer of the previous row.
+        let mut row = rows_result_iterator
+            .current_row
+            .take()
+            .unwrap_or_else(|| CassRow {
+                columns: Vec::new(),
+                result_metadata: rows_result_iterator.result_metadata,
+                numeric_widening: rows_result_iterator.numeric_widening,
+            });
+
+        let new_row = rows_result_iterator
+            .iterator
+            .next()
+            .and_then(|raw_row_res: Result<CassRawRow, _>| {
+                raw_row_res
+                    .and_then(|raw_row| row.refill_from_raw_row(raw_row))
+                    .inspect_err(|e| {
+                        // We have no way to propagate the error (return type is bool).
+                        // Let's at least log the deserialization error.
+                        tracing::error!("Failed to deserialize next row: {e}");
+                    })
+                    .ok()
+            })
+            .map(|()| row);
 
         rows_result_iterator.current_row = new_row;
 
@@ -84,6 +92,7 @@ impl CassRowIterator<'_> {
 pub struct CassListlikeIterator<'result> {
     iterator: ListlikeIterator<'result, 'result, CassRawValue<'result, 'result>>,
     value_data_type: &'result Arc<CassDataType>,
+    numeric_widening: bool,
     current_value: Option<CassValue<'result>>,
 }
 
@@ -109,6 +118,7 @@ impl<'result> CassListlikeIterator<'result> {
         Ok(Self {
             iterator: listlike_iterator,
             value_data_type: item_type,
+            numeric_widening: value.numeric_widening,
             current_value: None,
         })
     }
@@ -118,6 +128,7 @@ impl<'result> CassListlikeIterator<'result> {
             Ok(value) => Some(CassValue {
                 value,
                 value_type: self.value_data_type,
+                numeric_widening: self.numeric_widening,
             }),
             Err(e) => {
                 tracing::error!("Failed to deserialize next listlike entry: {e}");
@@ -333,6 +344,7 @@ mod tuple_iterator {
 pub struct CassTupleIterator<'result> {
     iterator: tuple_iterator::TupleIterator<'result, 'result>,
     metadata: &'result [Arc<CassDataType>],
+    numeric_widening: bool,
     current_entry: Option<CassTupleIteratorEntry<'result>>,
 }
 
@@ -356,6 +368,7 @@ impl<'result> CassTupleIterator<'result> {
         Ok(Self {
             iterator: tuple_iterator,
             metadata,
+            numeric_widening: value.numeric_widening,
             current_entry: None,
         })
     }
@@ -392,6 +405,7 @@ impl<'result> CassTupleIterator<'result> {
         let new_value = CassValue {
             value: raw_value,
             value_type: &self.metadata[new_metadata_types_index],
+            numeric_widening: self.numeric_widening,
         };
 
         self.current_entry = Some(CassTupleIteratorEntry {
@@ -413,6 +427,7 @@ pub struct CassMapIterator<'result> {
         CassRawValue<'result, 'result>,
     >,
     key_value_types: (&'result Arc<CassDataType>, &'result Arc<CassDataType>),
+    numeric_widening: bool,
     current_entry: Option<(CassValue<'result>, CassValue<'result>)>,
 }
 
@@ -436,6 +451,7 @@ impl<'result> CassMapIterator<'result> {
         Ok(Self {
             iterator: map_iterator,
             key_value_types,
+            numeric_widening: value.numeric_widening,
             current_entry: None,
         })
     }
@@ -456,10 +472,12 @@ impl<'result> CassMapIterator<'result> {
                     CassValue {
                         value: key,
                         value_type: self.key_value_types.0,
+                        numeric_widening: self.numeric_widening,
                     },
                     CassValue {
                         value,
                         value_type: self.key_value_types.1,
+                        numeric_widening: self.numeric_widening,
                     },
                 )
             });
@@ -473,6 +491,7 @@ impl<'result> CassMapIterator<'result> {
 pub struct CassUdtIterator<'result> {
     iterator: UdtIterator<'result, 'result>,
     metadata: &'result [(String, Arc<CassDataType>)],
+    numeric_widening: bool,
     current_entry: Option<CassUdtIteratorEntry<'result>>,
 }
 
@@ -496,6 +515,7 @@ impl<'result> CassUdtIterator<'result> {
         Ok(Self {
             iterator: udt_iterator,
             metadata,
+            numeric_widening: value.numeric_widening,
             current_entry: None,
         })
     }
@@ -538,6 +558,7 @@ impl<'result> CassUdtIterator<'result> {
         let new_value = CassValue {
             value: raw_value,
             value_type: &self.metadata[new_metadata_types_index].1,
+            numeric_widening: self.numeric_widening,
         };
 
         self.current_entry = Some(CassUdtIteratorEntry {
@@ -549,88 +570,28 @@ impl<'result> CassUdtIterator<'result> {
     }
 }
 
-pub struct CassSchemaMetaIterator<'schema> {
-    value: &'schema CassSchemaMeta,
-    count: usize,
-    position: Option<usize>,
-}
-
-impl CassSchemaMetaIterator<'_> {
-    fn next(&mut self) -> bool {
-        let new_pos: usize = self.position.map_or(0, |prev_pos| prev_pos + 1);
-
-        self.position = Some(new_pos);
-
-        new_pos < self.count
-    }
+/// An iterator over the entries of schema metadata collection.
+/// Keeps the live iterator over the collection, so advancing it is O(1).
+pub struct CassSchemaEntriesIterator<'schema, T> {
+    entries: hash_map::Values<'schema, Arc<str>, T>,
+    current: Option<&'schema T>,
 }
 
-pub struct CassKeyspaceMetaIterator<'schema> {
-    value: &'schema CassKeyspaceMeta,
-    count: usize,
-    position: Option<usize>,
-}
-
-impl CassKeyspaceMetaIterator<'_> {
-    fn next(&mut self) -> bool {
-        let new_pos: usize = self.position.map_or(0, |prev_pos| prev_pos + 1);
-
-        self.position = Some(new_pos);
-
-        new_pos < self.count
-    }
-}
-
-pub struct CassTableMetaIterator<'schema> {
-    value: &'schema CassTableMeta,
-    count: usize,
-    position: Option<usize>,
-}
-
-impl CassTableMetaIterator<'_> {
-    fn next(&mut self) -> bool {
-        let new_pos: usize = self.position.map_or(0, |prev_pos| prev_pos + 1);
-
-        self.position = Some(new_pos);
-
-        new_pos < self.count
+impl<'schema, T> CassSchemaEntriesIterator<'schema, T> {
+    fn new(entries: &'schema HashMap<Arc<str>, T>) -> Self {
+        Self {
+            entries: entries.values(),
+            current: None,
+        }
     }
-}
 
-pub struct CassViewMetaIterator<'schema> {
-    value: &'schema CassMaterializedViewMeta,
-    count: usize,
-    position: Option<usize>,
-}
-
-impl CassViewMetaIterator<'_> {
     fn next(&mut self) -> bool {
-        let new_pos: usize = self.position.map_or(0, |prev_pos| prev_pos + 1);
+        self.current = self.entries.next();
 
-        self.position = Some(new_pos);
-
-        new_pos < self.count
+        self.current.is_some()
     }
 }
 
-/// An iterator over columns metadata.
-/// Can be constructed from either table ([`cass_iterator_columns_from_table_meta()`])
-/// or view metadata ([`cass_iterator_columns_from_materialized_view_meta()`]).
-/// To be used by [`cass_iterator_get_column_meta()`].
-pub enum CassColumnsMetaIterator<'schema> {
-    FromTable(CassTableMetaIterator<'schema>),
-    FromView(CassViewMetaIterator<'schema>),
-}
-
-/// An iterator over materialized views.
-/// Can be constructed from either keyspace ([`cass_iterator_materialized_views_from_keyspace_meta()`])
-/// or table ([`cass_iterator_materialized_views_from_table_meta()`]) metadata.
-/// To be used by [`cass_iterator_get_materialized_view_meta()`].
-pub enum CassMaterializedViewsMetaIterator<'schema> {
-    FromKeyspace(CassKeyspaceMetaIterator<'schema>),
-    FromTable(CassTableMetaIterator<'schema>),
-}
-
 pub enum CassIterator<'result_or_schema> {
     // Iterators derived from CassResult.
     // Naming convention of the variants: the name of the collection.
@@ -650,15 +611,17 @@ pub enum CassIterator<'result_or_schema> {
     // Iterators derived from CassSchemaMeta.
     // Naming convention of the variants: name of item in the collection (plural).
     /// Iterator over keyspaces in schema metadata.
-    KeyspacesMeta(CassSchemaMetaIterator<'result_or_schema>),
+    KeyspacesMeta(CassSchemaEntriesIterator<'result_or_schema, CassKeyspaceMeta>),
     /// Iterator over tables in keyspace metadata.
-    TablesMeta(CassKeyspaceMetaIterator<'result_or_schema>),
+    TablesMeta(CassSchemaEntriesIterator<'result_or_schema, Arc<CassTableMeta>>),
     /// Iterator over UDTs in keyspace metadata.
-    UserTypes(CassKeyspaceMetaIterator<'result_or_schema>),
+    UserTypes(CassSchemaEntriesIterator<'result_or_schema, Arc<CassDataType>>),
     /// Iterator over materialized views in either keyspace or table metadata.
-    MaterializedViewsMeta(CassMaterializedViewsMetaIterator<'result_or_schema>),
+    MaterializedViewsMeta(
+        CassSchemaEntriesIterator<'result_or_schema, Arc<CassMaterializedViewMeta>>,
+    ),
     /// Iterator over columns metadata in either table or view metadata.
-    ColumnsMeta(CassColumnsMetaIterator<'result_or_schema>),
+    ColumnsMeta(CassSchemaEntriesIterator<'result_or_schema, CassColumnMeta>),
 }
 
 impl FFI for CassIterator<'_> {
@@ -702,7 +665,7 @@ pub unsafe extern "C" fn cass_iterator_type(
 pub unsafe extern "C" fn cass_iterator_next(
     iterator: CassBorrowedExclusivePtr<CassIterator, CMut>,
 ) -> cass_bool_t {
-    let Some(mut iter) = BoxFFI::as_mut_ref(iterator) else {
+    let Some((mut iter, _guard)) = BoxFFI::as_mut_ref_guarded(iterator) else {
         tracing::error!("Provided null iterator pointer to cass_iterator_next!");
         return cass_false;
     };
@@ -714,21 +677,11 @@ pub unsafe extern "C" fn cass_iterator_next(
         CassIterator::Tuple(tuple_iterator) => tuple_iterator.next(),
         CassIterator::Map(map_iterator) => map_iterator.next(),
         CassIterator::Udt(udt_iterator) => udt_iterator.next(),
-        CassIterator::KeyspacesMeta(schema_meta_iterator) => schema_meta_iterator.next(),
-        CassIterator::TablesMeta(keyspace_meta_iterator)
-        | CassIterator::UserTypes(keyspace_meta_iterator)
-        | CassIterator::MaterializedViewsMeta(CassMaterializedViewsMetaIterator::FromKeyspace(
-            keyspace_meta_iterator,
-        )) => keyspace_meta_iterator.next(),
-        CassIterator::MaterializedViewsMeta(CassMaterializedViewsMetaIterator::FromTable(
-            table_iterator,
-        ))
-        | CassIterator::ColumnsMeta(CassColumnsMetaIterator::FromTable(table_iterator)) => {
-            table_iterator.next()
-        }
-        CassIterator::ColumnsMeta(CassColumnsMetaIterator::FromView(view_iterator)) => {
-            view_iterator.next()
-        }
+        CassIterator::KeyspacesMeta(keyspaces_iterator) => keyspaces_iterator.next(),
+        CassIterator::TablesMeta(tables_iterator) => tables_iterator.next(),
+        CassIterator::UserTypes(user_types_iterator) => user_types_iterator.next(),
+        CassIterator::MaterializedViewsMeta(views_iterator) => views_iterator.next(),
+        CassIterator::ColumnsMeta(columns_iterator) => columns_iterator.next(),
     };
 
     result as cass_bool_t
@@ -926,25 +879,14 @@ pub unsafe extern "C" fn cass_iterator_get_keyspace_meta<'schema>(
         return RefFFI::null();
     };
 
-    if let CassIterator::KeyspacesMeta(schema_meta_iterator) = iter {
-        let iter_position = match schema_meta_iterator.position {
-            Some(pos) => pos,
-            None => return RefFFI::null(),
-        };
-
-        let schema_meta_entry_opt = &schema_meta_iterator
-            .value
-            .keyspaces
-            .iter()
-            .nth(iter_position);
-
-        return match schema_meta_entry_opt {
-            Some(schema_meta_entry) => RefFFI::as_ptr(schema_meta_entry.1),
-            None => RefFFI::null(),
-        };
-    }
+    let CassIterator::KeyspacesMeta(keyspaces_iterator) = iter else {
+        return RefFFI::null();
+    };
 
-    RefFFI::null()
+    keyspaces_iterator
+        .current
+        .map(RefFFI::as_ptr)
+        .unwrap_or(RefFFI::null())
 }
 
 #[unsafe(no_mangle)]
@@ -956,25 +898,14 @@ pub unsafe extern "C" fn cass_iterator_get_table_meta<'schema>(
         return RefFFI::null();
     };
 
-    if let CassIterator::TablesMeta(keyspace_meta_iterator) = iter {
-        let iter_position = match keyspace_meta_iterator.position {
-            Some(pos) => pos,
-            None => return RefFFI::null(),
-        };
-
-        let table_meta_entry_opt = keyspace_meta_iterator
-            .value
-            .tables
-            .iter()
-            .nth(iter_position);
-
-        return match table_meta_entry_opt {
-            Some(table_meta_entry) => RefFFI::as_ptr(table_meta_entry.1.as_ref()),
-            None => RefFFI::null(),
-        };
-    }
+    let CassIterator::TablesMeta(tables_iterator) = iter else {
+        return RefFFI::null();
+    };
 
-    RefFFI::null()
+    tables_iterator
+        .current
+        .map(|table_meta| RefFFI::as_ptr(table_meta.as_ref()))
+        .unwrap_or(RefFFI::null())
 }
 
 #[unsafe(no_mangle)]
@@ -986,25 +917,14 @@ pub unsafe extern "C" fn cass_iterator_get_user_type<'schema>(
         return ArcFFI::null();
     };
 
-    if let CassIterator::UserTypes(keyspace_meta_iterator) = iter {
-        let iter_position = match keyspace_meta_iterator.position {
-            Some(pos) => pos,
-            None => return ArcFFI::null(),
-        };
-
-        let udt_to_type_entry_opt = keyspace_meta_iterator
-            .value
-            .user_defined_type_data_type
-            .iter()
-            .nth(iter_position);
-
-        return match udt_to_type_entry_opt {
-            Some(udt_to_type_entry) => ArcFFI::as_ptr(udt_to_type_entry.1),
-            None => ArcFFI::null(),
-        };
-    }
+    let CassIterator::UserTypes(user_types_iterator) = iter else {
+        return ArcFFI::null();
+    };
 
-    ArcFFI::null()
+    user_types_iterator
+        .current
+        .map(ArcFFI::as_ptr)
+        .unwrap_or(ArcFFI::null())
 }
 
 #[unsafe(no_mangle)]
@@ -1016,44 +936,14 @@ pub unsafe extern "C" fn cass_iterator_get_column_meta<'schema>(
         return RefFFI::null();
     };
 
-    match iter {
-        CassIterator::ColumnsMeta(CassColumnsMetaIterator::FromTable(table_meta_iterator)) => {
-            let iter_position = match table_meta_iterator.position {
-                Some(pos) => pos,
-                None => return RefFFI::null(),
-            };
-
-            let column_meta_entry_opt = table_meta_iterator
-                .value
-                .columns_metadata
-                .iter()
-                .nth(iter_position);
-
-            match column_meta_entry_opt {
-                Some(column_meta_entry) => RefFFI::as_ptr(column_meta_entry.1),
-                None => RefFFI::null(),
-            }
-        }
-        CassIterator::ColumnsMeta(CassColumnsMetaIterator::FromView(view_meta_iterator)) => {
-            let iter_position = match view_meta_iterator.position {
-                Some(pos) => pos,
-                None => return RefFFI::null(),
-            };
-
-            let column_meta_entry_opt = view_meta_iterator
-                .value
-                .view_metadata
-                .columns_metadata
-                .iter()
-                .nth(iter_position);
+    let CassIterator::ColumnsMeta(columns_iterator) = iter else {
+        return RefFFI::null();
+    };
 
-            match column_meta_entry_opt {
-                Some(column_meta_entry) => RefFFI::as_ptr(column_meta_entry.1),
-                None => RefFFI::null(),
-            }
-        }
-        _ => RefFFI::null(),
-    }
+    columns_iterator
+        .current
+        .map(RefFFI::as_ptr)
+        .unwrap_or(RefFFI::null())
 }
 
 #[unsafe(no_mangle)]
@@ -1067,39 +957,14 @@ pub unsafe extern "C" fn cass_iterator_get_materialized_view_meta<'schema>(
         return RefFFI::null();
     };
 
-    match iter {
-        CassIterator::MaterializedViewsMeta(CassMaterializedViewsMetaIterator::FromKeyspace(
-            keyspace_meta_iterator,
-        )) => {
-            let iter_position = match keyspace_meta_iterator.position {
-                Some(pos) => pos,
-                None => return RefFFI::null(),
-            };
-
-            let view_meta_entry_opt = keyspace_meta_iterator.value.views.iter().nth(iter_position);
-
-            match view_meta_entry_opt {
-                Some(view_meta_entry) => RefFFI::as_ptr(view_meta_entry.1.as_ref()),
-                None => RefFFI::null(),
-            }
-        }
-        CassIterator::MaterializedViewsMeta(CassMaterializedViewsMetaIterator::FromTable(
-            table_meta_iterator,
-        )) => {
-            let iter_position = match table_meta_iterator.position {
-                Some(pos) => pos,
-                None => return RefFFI::null(),
-            };
-
-            let view_meta_entry_opt = table_meta_iterator.value.views.iter().nth(iter_position);
+    let CassIterator::MaterializedViewsMeta(views_iterator) = iter else {
+        return RefFFI::null();
+    };
 
-            match view_meta_entry_opt {
-                Some(view_meta_entry) => RefFFI::as_ptr(view_meta_entry.1.as_ref()),
-                None => RefFFI::null(),
-            }
-        }
-        _ => RefFFI::null(),
-    }
+    views_iterator
+        .current
+        .map(|view_meta| RefFFI::as_ptr(view_meta.as_ref()))
+        .unwrap_or(RefFFI::null())
 }
 
 #[unsafe(no_mangle)]
@@ -1123,6 +988,7 @@ pub unsafe extern "C" fn cass_iterator_from_result<'result>(
                     .rows_iter::<CassRawRow>()
                     .unwrap(),
                 result_metadata: &cass_rows_result.shared_data.metadata,
+                numeric_widening: cass_rows_result.shared_data.numeric_widening,
                 current_row: None,
             })
         }
@@ -1257,11 +1123,7 @@ pub unsafe extern "C" fn cass_iterator_keyspaces_from_schema_meta<'schema>(
         return BoxFFI::null_mut();
     };
 
-    let iterator = CassSchemaMetaIterator {
-        value: metadata,
-        count: metadata.keyspaces.len(),
-        position: None,
-    };
+    let iterator = CassSchemaEntriesIterator::new(&metadata.keyspaces);
 
     BoxFFI::into_ptr(Box::new(CassIterator::KeyspacesMeta(iterator)))
 }
@@ -1278,11 +1140,7 @@ pub unsafe extern "C" fn cass_iterator_tables_from_keyspace_meta<'schema>(
         return BoxFFI::null_mut();
     };
 
-    let iterator = CassKeyspaceMetaIterator {
-        value: metadata,
-        count: metadata.tables.len(),
-        position: None,
-    };
+    let iterator = CassSchemaEntriesIterator::new(&metadata.tables);
 
     BoxFFI::into_ptr(Box::new(CassIterator::TablesMeta(iterator)))
 }
@@ -1299,15 +1157,9 @@ pub unsafe extern "C" fn cass_iterator_materialized_views_from_keyspace_meta<'sc
         return BoxFFI::null_mut();
     };
 
-    let iterator = CassKeyspaceMetaIterator {
-        value: metadata,
-        count: metadata.views.len(),
-        position: None,
-    };
+    let iterator = CassSchemaEntriesIterator::new(&metadata.views);
 
-    BoxFFI::into_ptr(Box::new(CassIterator::MaterializedViewsMeta(
-        CassMaterializedViewsMetaIterator::FromKeyspace(iterator),
-    )))
+    BoxFFI::into_ptr(Box::new(CassIterator::MaterializedViewsMeta(iterator)))
 }
 
 #[unsafe(no_mangle)]
@@ -1322,11 +1174,7 @@ pub unsafe extern "C" fn cass_iterator_user_types_from_keyspace_meta<'schema>(
         return BoxFFI::null_mut();
     };
 
-    let iterator = CassKeyspaceMetaIterator {
-        value: metadata,
-        count: metadata.user_defined_type_data_type.len(),
-        position: None,
-    };
+    let iterator = CassSchemaEntriesIterator::new(&metadata.user_defined_type_data_type);
 
     BoxFFI::into_ptr(Box::new(CassIterator::UserTypes(iterator)))
 }
@@ -1343,15 +1191,9 @@ pub unsafe extern "C" fn cass_iterator_columns_from_table_meta<'schema>(
         return BoxFFI::null_mut();
     };
 
-    let iterator = CassTableMetaIterator {
-        value: metadata,
-        count: metadata.columns_metadata.len(),
-        position: None,
-    };
+    let iterator = CassSchemaEntriesIterator::new(&metadata.columns_metadata);
 
-    BoxFFI::into_ptr(Box::new(CassIterator::ColumnsMeta(
-        CassColumnsMetaIterator::FromTable(iterator),
-    )))
+    BoxFFI::into_ptr(Box::new(CassIterator::ColumnsMeta(iterator)))
 }
 
 #[unsafe(no_mangle)]
@@ -1366,15 +1208,9 @@ pub unsafe extern "C" fn cass_iterator_materialized_views_from_table_meta<'schem
         return BoxFFI::null_mut();
     };
 
-    let iterator = CassTableMetaIterator {
-        value: metadata,
-        count: metadata.views.len(),
-        position: None,
-    };
+    let iterator = CassSchemaEntriesIterator::new(&metadata.views);
 
-    BoxFFI::into_ptr(Box::new(CassIterator::MaterializedViewsMeta(
-        CassMaterializedViewsMetaIterator::FromTable(iterator),
-    )))
+    BoxFFI::into_ptr(Box::new(CassIterator::MaterializedViewsMeta(iterator)))
 }
 
 #[unsafe(no_mangle)]
@@ -1389,13 +1225,7 @@ pub unsafe extern "C" fn cass_iterator_columns_from_materialized_view_meta<'sche
         return BoxFFI::null_mut();
     };
 
-    let iterator = CassViewMetaIterator {
-        value: metadata,
-        count: metadata.view_metadata.columns_metadata.len(),
-        position: None,
-    };
+    let iterator = CassSchemaEntriesIterator::new(&metadata.view_metadata.columns_metadata);
 
-    BoxFFI::into_ptr(Box::new(CassIterator::ColumnsMeta(
-        CassColumnsMetaIterator::FromView(iterator),
-    )))
+    BoxFFI::into_ptr(Box::new(CassIterator::ColumnsMeta(iterator)))
 }
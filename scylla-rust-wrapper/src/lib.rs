@@ -6,18 +6,28 @@ use std::sync::LazyLock;
 use std::sync::RwLock;
 use tokio::runtime::Runtime;
 
+#[cfg(not(any(feature = "openssl", feature = "rustls")))]
+compile_error!("Either the `openssl` or the `rustls` feature must be enabled.");
+
 #[macro_use]
 mod binding;
+mod address_family;
 // pub, because doctests defined in `argconv` module need to access it.
 pub mod argconv;
 pub mod batch;
 pub mod cass_error;
 pub mod cass_types;
+mod cloud;
 pub mod cluster;
+mod cluster_config;
+mod cluster_events;
 pub mod collection;
+mod contact_points;
+pub mod credentials;
 pub mod date_time;
 pub mod exec_profile;
 pub mod execution_error;
+pub mod execution_info;
 mod external;
 pub mod future;
 pub mod inet;
@@ -26,24 +36,44 @@ pub mod integration_testing;
 pub mod iterator;
 mod load_balancing;
 mod logging;
+mod memory;
 pub mod metadata;
+mod metrics;
 pub mod misc;
+pub mod paging_state_token;
 pub mod prepared;
+mod prometheus;
+mod ptr_validation;
+pub mod query_plan;
 pub mod query_result;
+mod request_queue;
+mod request_tracker;
+mod result_json;
+mod result_memory;
 pub mod retry_policy;
 #[cfg(test)]
 mod ser_de_tests;
 pub mod session;
+#[cfg(not(feature = "rustls"))]
 pub mod ssl;
+#[cfg(feature = "rustls")]
+mod ssl_rustls;
+#[cfg(feature = "rustls")]
+pub use ssl_rustls as ssl;
 pub mod statement;
 #[cfg(test)]
 pub mod testing;
 pub mod timestamp_generator;
+mod trace_context;
 pub mod tuple;
 pub mod user_type;
 pub mod uuid;
 pub mod value;
 
+#[cfg(feature = "custom-allocator")]
+#[global_allocator]
+static GLOBAL_ALLOCATOR: memory::CassAllocator = memory::CassAllocator;
+
 /// Includes a file generated by bindgen called `filename`.
 macro_rules! include_bindgen_generated {
     ($filename:expr) => {
@@ -129,6 +159,21 @@ pub mod cass_metrics_types {
     include_bindgen_generated!("cppdriver_metrics_types.rs");
 }
 
+/// CassClusterEventType, CassHostListenerEvent
+pub mod cass_cluster_event_types {
+    include_bindgen_generated!("cppdriver_cluster_event_types.rs");
+}
+
+/// CassRequestPriority
+pub mod cass_request_priority_types {
+    include_bindgen_generated!("cppdriver_request_priority_types.rs");
+}
+
+/// CassAddressFamily
+pub mod cass_address_family_types {
+    include_bindgen_generated!("cppdriver_address_family_types.rs");
+}
+
 pub static RUNTIME: LazyLock<Runtime> = LazyLock::new(|| Runtime::new().unwrap());
 pub static LOGGER: LazyLock<RwLock<Logger>> = LazyLock::new(|| {
     RwLock::new(Logger {
@@ -1,7 +1,10 @@
 use std::net::IpAddr;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::metrics::PerHostMetrics;
+use scylla::client::execution_profile::ExecutionProfile;
 use scylla::cluster::metadata::Peer;
 use scylla::cluster::{ClusterState, NodeRef};
 use scylla::errors::RequestAttemptError;
@@ -19,6 +22,29 @@ pub(crate) struct FilteringConfig {
 }
 
 impl FilteringConfig {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.whitelist_hosts.is_empty()
+            && self.blacklist_hosts.is_empty()
+            && self.whitelist_dc.is_empty()
+            && self.blacklist_dc.is_empty()
+    }
+
+    /// Applies the filtering on top of the provided policy.
+    /// If there are no filtering rules, the policy is returned as is.
+    pub(crate) fn apply_to(
+        self,
+        child_policy: Arc<dyn LoadBalancingPolicy>,
+    ) -> Arc<dyn LoadBalancingPolicy> {
+        if self.is_empty() {
+            return child_policy;
+        }
+
+        Arc::new(FilteringLoadBalancingPolicy {
+            filtering: self.into_filtering_info(),
+            child_policy,
+        })
+    }
+
     /// Maps each white/blacklist into `Option<Vec<_>>`.
     /// If the list is empty, it is not going to be used for filtering (None).
     fn into_filtering_info(self) -> FilteringInfo {
@@ -138,6 +164,62 @@ impl LoadBalancingConfig {
     }
 }
 
+impl LoadBalancingConfig {
+    /// Checks the settings for conflicts, which would exclude the hosts
+    /// (or the local datacenter) from the query plans unintentionally.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        let filtering = &self.filtering;
+        if let Some(host) = filtering
+            .whitelist_hosts
+            .iter()
+            .find(|host| filtering.blacklist_hosts.contains(host))
+        {
+            return Err(format!("Host {} is both whitelisted and blacklisted", host));
+        }
+        if let Some(dc) = filtering
+            .whitelist_dc
+            .iter()
+            .find(|dc| filtering.blacklist_dc.contains(dc))
+        {
+            return Err(format!(
+                "Datacenter {} is both whitelisted and blacklisted",
+                dc
+            ));
+        }
+
+        let local_dc = match &self.load_balancing_kind {
+            Some(LoadBalancingKind::DcAware { local_dc })
+            | Some(LoadBalancingKind::RackAware { local_dc, .. }) => local_dc,
+            Some(LoadBalancingKind::RoundRobin) | None => return Ok(()),
+        };
+        if filtering.blacklist_dc.contains(local_dc) {
+            return Err(format!("Local datacenter {} is blacklisted", local_dc));
+        }
+        if !filtering.whitelist_dc.is_empty() && !filtering.whitelist_dc.contains(local_dc) {
+            return Err(format!("Local datacenter {} is not whitelisted", local_dc));
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds the latency awareness settings from the arguments of
+/// `cass_cluster_set_latency_aware_routing_settings` (and its execution profile counterpart).
+pub(crate) fn latency_awareness_builder(
+    exclusion_threshold: f64,
+    scale_ms: u64,
+    retry_period_ms: u64,
+    update_rate_ms: u64,
+    min_measured: u64,
+) -> LatencyAwarenessBuilder {
+    LatencyAwarenessBuilder::new()
+        .exclusion_threshold(exclusion_threshold)
+        .scale(Duration::from_millis(scale_ms))
+        .retry_period(Duration::from_millis(retry_period_ms))
+        .update_rate(Duration::from_millis(update_rate_ms))
+        .minimum_measurements(min_measured as usize)
+}
+
 impl Default for LoadBalancingConfig {
     fn default() -> Self {
         Self {
@@ -225,6 +307,84 @@ impl LoadBalancingPolicy for FilteringLoadBalancingPolicy {
     }
 }
 
+/// Skips the hosts that already have the maximum number of requests in flight,
+/// so a single slow host can't absorb all the requests of the session.
+/// Requests are diverted to the other hosts of the plan - if all of them are
+/// saturated, the plan is empty and the request fails fast.
+#[derive(Debug)]
+pub(crate) struct HostInFlightLimitingPolicy {
+    max_in_flight: u64,
+    host_metrics: Arc<PerHostMetrics>,
+    child_policy: Arc<dyn LoadBalancingPolicy>,
+}
+
+impl HostInFlightLimitingPolicy {
+    /// Wraps the load balancing policy of the profile.
+    pub(crate) fn apply(
+        profile: ExecutionProfile,
+        max_in_flight: NonZeroUsize,
+        host_metrics: &Arc<PerHostMetrics>,
+    ) -> ExecutionProfile {
+        let policy = HostInFlightLimitingPolicy {
+            max_in_flight: max_in_flight.get() as u64,
+            host_metrics: Arc::clone(host_metrics),
+            child_policy: Arc::clone(profile.get_load_balancing_policy()),
+        };
+        profile
+            .to_builder()
+            .load_balancing_policy(Arc::new(policy))
+            .build()
+    }
+
+    fn is_saturated(&self, node: NodeRef<'_>) -> bool {
+        self.host_metrics.in_flight(node.address.ip()) >= self.max_in_flight
+    }
+}
+
+impl LoadBalancingPolicy for HostInFlightLimitingPolicy {
+    fn pick<'a>(
+        &'a self,
+        request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> Option<(scylla::cluster::NodeRef<'a>, Option<scylla::routing::Shard>)> {
+        // If the picked host is saturated, the fallback plan is used instead.
+        self.child_policy
+            .pick(request, cluster)
+            .filter(|(node, _shard)| !self.is_saturated(node))
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> FallbackPlan<'a> {
+        Box::new(
+            self.child_policy
+                .fallback(request, cluster)
+                .filter(|(node, _shard)| !self.is_saturated(node)),
+        )
+    }
+
+    fn on_request_success(&self, request: &RoutingInfo, latency: Duration, node: NodeRef<'_>) {
+        self.child_policy.on_request_success(request, latency, node);
+    }
+
+    fn on_request_failure(
+        &self,
+        request: &RoutingInfo,
+        latency: Duration,
+        node: NodeRef<'_>,
+        error: &RequestAttemptError,
+    ) {
+        self.child_policy
+            .on_request_failure(request, latency, node, error);
+    }
+
+    fn name(&self) -> String {
+        format!("HostInFlightLimitingPolicy({})", self.child_policy.name())
+    }
+}
+
 /// A host filter used by cpp-rust-driver. It's constructed based on the
 /// filtering configuration provided by the user.
 pub(crate) struct CassHostFilter {
@@ -299,6 +459,14 @@ impl CassHostFilter {
     pub(crate) fn new_from_lbp_configs<'a>(
         configs: impl Iterator<Item = &'a LoadBalancingConfig> + Clone,
     ) -> Arc<dyn HostFilter> {
+        Arc::new(Self {
+            filtering: Self::filtering_from_lbp_configs(configs),
+        })
+    }
+
+    fn filtering_from_lbp_configs<'a>(
+        configs: impl Iterator<Item = &'a LoadBalancingConfig> + Clone,
+    ) -> FilteringInfo {
         let whitelist_hosts = nonempty_union(
             configs
                 .clone()
@@ -320,19 +488,129 @@ impl CassHostFilter {
         let blacklist_dc =
             nonempty_intersection(configs.map(|lbp_config| &lbp_config.filtering.blacklist_dc));
 
-        Arc::new(Self {
-            filtering: FilteringInfo {
-                whitelist_hosts,
-                blacklist_hosts,
-                whitelist_dc,
-                blacklist_dc,
-            },
-        })
+        FilteringInfo {
+            whitelist_hosts,
+            blacklist_hosts,
+            whitelist_dc,
+            blacklist_dc,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn filtering_info(config: FilteringConfig) -> FilteringInfo {
+        config.into_filtering_info()
+    }
+
+    fn no_filtering() -> FilteringConfig {
+        LoadBalancingConfig::default().filtering
+    }
+
+    #[test]
+    fn test_whitelist_hosts_filtering() {
+        // No whitelist - every host is allowed.
+        let filtering = filtering_info(no_filtering());
+        assert!(filtering.is_host_allowed(&ip("127.0.0.1"), Some("dc1")));
+        assert!(filtering.is_host_allowed(&ip("::1"), None));
+
+        let filtering = filtering_info(FilteringConfig {
+            whitelist_hosts: vec![ip("127.0.0.1"), ip("::1")],
+            ..no_filtering()
+        });
+        assert!(filtering.is_host_allowed(&ip("127.0.0.1"), Some("dc1")));
+        assert!(filtering.is_host_allowed(&ip("::1"), None));
+        assert!(!filtering.is_host_allowed(&ip("127.0.0.2"), Some("dc1")));
+    }
+
+    #[test]
+    fn test_blacklist_hosts_filtering() {
+        let cluster_config = LoadBalancingConfig {
+            filtering: FilteringConfig {
+                blacklist_hosts: vec![ip("127.0.0.1")],
+                ..no_filtering()
+            },
+            ..Default::default()
+        };
+        let profile_config = LoadBalancingConfig {
+            load_balancing_kind: Some(LoadBalancingKind::DcAware {
+                local_dc: "dc1".to_owned(),
+            }),
+            filtering: FilteringConfig {
+                blacklist_hosts: vec![ip("127.0.0.1"), ip("127.0.0.2")],
+                ..no_filtering()
+            },
+            ..Default::default()
+        };
+
+        let filtering = filtering_info(profile_config.filtering.clone());
+        assert!(!filtering.is_host_allowed(&ip("127.0.0.1"), Some("dc1")));
+        assert!(!filtering.is_host_allowed(&ip("127.0.0.2"), Some("dc1")));
+        assert!(filtering.is_host_allowed(&ip("127.0.0.3"), Some("dc1")));
+
+        // The connections are not opened only to the hosts rejected by all policies.
+        let host_filtering = CassHostFilter::filtering_from_lbp_configs(
+            [&cluster_config, &profile_config].into_iter(),
+        );
+        assert!(!host_filtering.is_host_allowed(&ip("127.0.0.1"), Some("dc1")));
+        assert!(host_filtering.is_host_allowed(&ip("127.0.0.2"), Some("dc1")));
+    }
+
+    #[test]
+    fn test_whitelist_dc_filtering() {
+        let filtering = filtering_info(FilteringConfig {
+            whitelist_dc: vec!["dc1".to_owned(), "dc2".to_owned()],
+            ..no_filtering()
+        });
+        assert!(filtering.is_host_allowed(&ip("127.0.0.1"), Some("dc1")));
+        assert!(filtering.is_host_allowed(&ip("127.0.0.2"), Some("dc2")));
+        assert!(!filtering.is_host_allowed(&ip("127.0.0.3"), Some("dc3")));
+        // Hosts of unknown DC are rejected.
+        assert!(!filtering.is_host_allowed(&ip("127.0.0.4"), None));
+
+        // The union of the whitelists is taken for the host filter.
+        let configs = [vec!["dc1".to_owned()], vec!["dc3".to_owned()]].map(|whitelist_dc| {
+            LoadBalancingConfig {
+                filtering: FilteringConfig {
+                    whitelist_dc,
+                    ..no_filtering()
+                },
+                ..Default::default()
+            }
+        });
+        let host_filtering = CassHostFilter::filtering_from_lbp_configs(configs.iter());
+        assert!(host_filtering.is_host_allowed(&ip("127.0.0.1"), Some("dc1")));
+        assert!(!host_filtering.is_host_allowed(&ip("127.0.0.2"), Some("dc2")));
+        assert!(host_filtering.is_host_allowed(&ip("127.0.0.3"), Some("dc3")));
+    }
+
+    #[test]
+    fn test_blacklist_dc_filtering() {
+        let filtering = filtering_info(FilteringConfig {
+            blacklist_dc: vec!["dc2".to_owned()],
+            ..no_filtering()
+        });
+        assert!(filtering.is_host_allowed(&ip("127.0.0.1"), Some("dc1")));
+        assert!(!filtering.is_host_allowed(&ip("127.0.0.2"), Some("dc2")));
+        // Hosts of unknown DC are not blacklisted.
+        assert!(filtering.is_host_allowed(&ip("127.0.0.3"), None));
+
+        // Blacklists are applied on top of the whitelists.
+        let filtering = filtering_info(FilteringConfig {
+            whitelist_hosts: vec![ip("127.0.0.1"), ip("127.0.0.2")],
+            blacklist_dc: vec!["dc2".to_owned()],
+            ..no_filtering()
+        });
+        assert!(filtering.is_host_allowed(&ip("127.0.0.1"), Some("dc1")));
+        assert!(!filtering.is_host_allowed(&ip("127.0.0.2"), Some("dc2")));
+    }
+
     #[test]
     fn test_union_and_intersection() {
         struct TestCase {
@@ -402,4 +680,42 @@ mod tests {
             assert_eq!(intersection, test.expected_intersection);
         }
     }
+
+    #[tokio::test]
+    #[ntest::timeout(5000)]
+    async fn test_host_in_flight_limiting_policy() {
+        use crate::testing::{MockLoadBalancingPolicy, with_proxy_cluster_state};
+
+        with_proxy_cluster_state(2, |cluster_state| {
+            let saturated = cluster_state.get_nodes_info()[0].address.ip();
+            let available = cluster_state.get_nodes_info()[1].address.ip();
+
+            let host_metrics = Arc::new(PerHostMetrics::new(None));
+            // The saturated host is at the limit, the other one is just below it.
+            for _ in 0..2 {
+                host_metrics.host(saturated).request_started();
+            }
+            host_metrics.host(available).request_started();
+
+            let policy = HostInFlightLimitingPolicy {
+                max_in_flight: 2,
+                host_metrics,
+                child_policy: Arc::new(MockLoadBalancingPolicy {
+                    picked: Some((0, None)),
+                    fallback: vec![(0, None), (1, None)],
+                }),
+            };
+            let routing_info = RoutingInfo::default();
+
+            // The saturated host is not picked...
+            assert!(policy.pick(&routing_info, cluster_state).is_none());
+            // ...and is filtered out of the fallback.
+            let fallback: Vec<_> = policy
+                .fallback(&routing_info, cluster_state)
+                .map(|(node, _shard)| node.address.ip())
+                .collect();
+            assert_eq!(fallback, vec![available]);
+        })
+        .await;
+    }
 }
@@ -0,0 +1,179 @@
+use crate::types::size_t;
+use std::os::raw::c_void;
+
+pub type CassMallocFunction = Option<unsafe extern "C" fn(size: size_t) -> *mut c_void>;
+pub type CassReallocFunction =
+    Option<unsafe extern "C" fn(ptr: *mut c_void, size: size_t) -> *mut c_void>;
+pub type CassFreeFunction = Option<unsafe extern "C" fn(ptr: *mut c_void)>;
+
+#[cfg(feature = "custom-allocator")]
+pub(crate) use custom_allocator::CassAllocator;
+
+#[cfg(feature = "custom-allocator")]
+mod custom_allocator {
+    use super::*;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::OnceLock;
+    use std::sync::atomic::{AtomicU8, Ordering};
+
+    // The alignment that every malloc implementation is expected to provide.
+    // Allocations requiring stricter alignment are always served by the system allocator.
+    const MAX_CUSTOM_ALIGN: usize = 8;
+
+    struct AllocFunctions {
+        malloc: unsafe extern "C" fn(size: size_t) -> *mut c_void,
+        realloc: unsafe extern "C" fn(ptr: *mut c_void, size: size_t) -> *mut c_void,
+        free: unsafe extern "C" fn(ptr: *mut c_void),
+    }
+
+    static ALLOC_FUNCTIONS: OnceLock<AllocFunctions> = OnceLock::new();
+
+    // Which allocator serves the allocations. It's decided once - either by the first allocation
+    // (system allocator), or by `cass_alloc_set_functions` called before it (user's functions) -
+    // and never changes afterwards. This way, memory is always freed by the allocator
+    // that allocated it.
+    const ALLOCATOR_UNDECIDED: u8 = 0;
+    const ALLOCATOR_SYSTEM: u8 = 1;
+    const ALLOCATOR_CUSTOM: u8 = 2;
+    static ALLOCATOR: AtomicU8 = AtomicU8::new(ALLOCATOR_UNDECIDED);
+
+    /// Global allocator of the driver. It routes all allocations through
+    /// the functions provided with `cass_alloc_set_functions`, if any.
+    ///
+    /// It's installed only if the `custom-allocator` feature is enabled, which is meant
+    /// for the builds of the C library (cdylib/staticlib) - Rust crates depending on
+    /// the driver should keep their own global allocator.
+    pub(crate) struct CassAllocator;
+
+    impl CassAllocator {
+        fn custom_functions(layout: Layout) -> Option<&'static AllocFunctions> {
+            if layout.align() > MAX_CUSTOM_ALIGN {
+                return None;
+            }
+            let mut allocator = ALLOCATOR.load(Ordering::Acquire);
+            if allocator == ALLOCATOR_UNDECIDED {
+                // Races with `cass_alloc_set_functions` - whichever comes first decides.
+                allocator = match ALLOCATOR.compare_exchange(
+                    ALLOCATOR_UNDECIDED,
+                    ALLOCATOR_SYSTEM,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => ALLOCATOR_SYSTEM,
+                    Err(decided) => decided,
+                };
+            }
+            // The functions are set before the allocator is switched to them.
+            (allocator == ALLOCATOR_CUSTOM).then(|| ALLOC_FUNCTIONS.get().unwrap())
+        }
+    }
+
+    unsafe impl GlobalAlloc for CassAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            match Self::custom_functions(layout) {
+                Some(functions) => unsafe {
+                    (functions.malloc)(layout.size() as size_t) as *mut u8
+                },
+                None => unsafe { System.alloc(layout) },
+            }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            match Self::custom_functions(layout) {
+                Some(functions) => unsafe { (functions.free)(ptr as *mut c_void) },
+                None => unsafe { System.dealloc(ptr, layout) },
+            }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            match Self::custom_functions(layout) {
+                Some(functions) => unsafe {
+                    (functions.realloc)(ptr as *mut c_void, new_size as size_t) as *mut u8
+                },
+                None => unsafe { System.realloc(ptr, layout, new_size) },
+            }
+        }
+    }
+
+    #[unsafe(no_mangle)]
+    pub unsafe extern "C" fn cass_alloc_set_functions(
+        malloc_func: CassMallocFunction,
+        realloc_func: CassReallocFunction,
+        free_func: CassFreeFunction,
+    ) {
+        let (Some(malloc), Some(realloc), Some(free)) = (malloc_func, realloc_func, free_func)
+        else {
+            tracing::error!("Provided null allocation function to cass_alloc_set_functions!");
+            return;
+        };
+
+        if ALLOC_FUNCTIONS
+            .set(AllocFunctions {
+                malloc,
+                realloc,
+                free,
+            })
+            .is_err()
+        {
+            tracing::error!(
+                "Allocation functions have already been set. They will not be changed."
+            );
+            return;
+        }
+
+        if ALLOCATOR
+            .compare_exchange(
+                ALLOCATOR_UNDECIDED,
+                ALLOCATOR_CUSTOM,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            )
+            .is_err()
+        {
+            tracing::error!(
+                "cass_alloc_set_functions must be called before any other driver function! \
+                The allocation functions will not be changed."
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        unsafe extern "C" fn test_malloc(size: size_t) -> *mut c_void {
+            unsafe { libc::malloc(size as usize) }
+        }
+
+        unsafe extern "C" fn test_realloc(ptr: *mut c_void, size: size_t) -> *mut c_void {
+            unsafe { libc::realloc(ptr, size as usize) }
+        }
+
+        unsafe extern "C" fn test_free(ptr: *mut c_void) {
+            unsafe { libc::free(ptr) }
+        }
+
+        #[test]
+        fn test_alloc_functions_are_not_replaced_after_allocation() {
+            // The test harness has certainly allocated some memory by now.
+            let _allocated = vec![0_u8; 16];
+            unsafe {
+                cass_alloc_set_functions(Some(test_malloc), Some(test_realloc), Some(test_free));
+            }
+            assert_eq!(ALLOCATOR.load(Ordering::Acquire), ALLOCATOR_SYSTEM);
+        }
+    }
+}
+
+#[cfg(not(feature = "custom-allocator"))]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_alloc_set_functions(
+    _malloc_func: CassMallocFunction,
+    _realloc_func: CassReallocFunction,
+    _free_func: CassFreeFunction,
+) {
+    tracing::error!(
+        "cass_alloc_set_functions is not supported - the driver was built without \
+        the custom-allocator feature. The allocation functions will not be changed."
+    );
+}
@@ -3,14 +3,64 @@ use crate::cass_column_types::CassColumnType;
 use crate::cass_types::CassDataType;
 use crate::cass_types::get_column_type;
 use crate::types::*;
-use scylla::cluster::metadata::{ColumnKind, Table};
-use std::collections::HashMap;
+use scylla::cluster::ClusterState;
+use scylla::cluster::metadata::{ColumnKind, ColumnType, Keyspace, Table};
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroUsize;
 use std::os::raw::c_char;
-use std::sync::Arc;
 use std::sync::Weak;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::thread;
+
+/// Pool of interned names of schema objects (keyspaces, tables, columns etc.).
+///
+/// The same names are repeated many times - on each schema metadata rebuild, and in
+/// the metadata of each result. Interning lets them share a single allocation.
+struct NamePool {
+    names: HashSet<Arc<str>>,
+    // Names that are no longer referenced are removed once the pool reaches this size.
+    prune_threshold: usize,
+}
+
+const MIN_NAME_POOL_PRUNE_THRESHOLD: usize = 1024;
+
+impl NamePool {
+    fn new() -> Self {
+        NamePool {
+            names: HashSet::new(),
+            prune_threshold: MIN_NAME_POOL_PRUNE_THRESHOLD,
+        }
+    }
+
+    fn intern(&mut self, name: &str) -> Arc<str> {
+        if let Some(interned) = self.names.get(name) {
+            return Arc::clone(interned);
+        }
+
+        // Names of dropped schema objects would otherwise stay in the pool forever.
+        if self.names.len() >= self.prune_threshold {
+            self.names.retain(|name| Arc::strong_count(name) > 1);
+            self.prune_threshold = (2 * self.names.len()).max(MIN_NAME_POOL_PRUNE_THRESHOLD);
+        }
+
+        let interned: Arc<str> = Arc::from(name);
+        self.names.insert(Arc::clone(&interned));
+        interned
+    }
+}
+
+static NAME_POOL: LazyLock<RwLock<NamePool>> = LazyLock::new(|| RwLock::new(NamePool::new()));
+
+/// Returns the interned instance of the schema object's name.
+pub(crate) fn intern_name(name: &str) -> Arc<str> {
+    if let Some(interned) = NAME_POOL.read().unwrap().names.get(name) {
+        return Arc::clone(interned);
+    }
+    NAME_POOL.write().unwrap().intern(name)
+}
 
 pub struct CassSchemaMeta {
-    pub keyspaces: HashMap<String, CassKeyspaceMeta>,
+    pub keyspaces: HashMap<Arc<str>, CassKeyspaceMeta>,
 }
 
 impl FFI for CassSchemaMeta {
@@ -18,12 +68,12 @@ impl FFI for CassSchemaMeta {
 }
 
 pub struct CassKeyspaceMeta {
-    pub name: String,
+    pub name: Arc<str>,
 
     // User defined type name to type
-    pub user_defined_type_data_type: HashMap<String, Arc<CassDataType>>,
-    pub tables: HashMap<String, Arc<CassTableMeta>>,
-    pub views: HashMap<String, Arc<CassMaterializedViewMeta>>,
+    pub user_defined_type_data_type: HashMap<Arc<str>, Arc<CassDataType>>,
+    pub tables: HashMap<Arc<str>, Arc<CassTableMeta>>,
+    pub views: HashMap<Arc<str>, Arc<CassMaterializedViewMeta>>,
 }
 
 // Owned by CassSchemaMeta
@@ -32,13 +82,15 @@ impl FFI for CassKeyspaceMeta {
 }
 
 pub struct CassTableMeta {
-    pub name: String,
-    pub columns_metadata: HashMap<String, CassColumnMeta>,
-    pub partition_keys: Vec<String>,
-    pub clustering_keys: Vec<String>,
+    pub name: Arc<str>,
+    pub columns_metadata: HashMap<Arc<str>, CassColumnMeta>,
+    pub partition_keys: Vec<Arc<str>>,
+    pub clustering_keys: Vec<Arc<str>>,
     /// Non-key columns sorted alphabetically by name.
-    pub non_key_sorted_columns: Vec<String>,
-    pub views: HashMap<String, Arc<CassMaterializedViewMeta>>,
+    pub non_key_sorted_columns: Vec<Arc<str>>,
+    pub views: HashMap<Arc<str>, Arc<CassMaterializedViewMeta>>,
+    /// Name of the table's CDC log table, if CDC is enabled for the table.
+    pub cdc_log_table_name: Option<Arc<str>>,
 }
 
 // Either:
@@ -49,7 +101,7 @@ impl FFI for CassTableMeta {
 }
 
 pub struct CassMaterializedViewMeta {
-    pub name: String,
+    pub name: Arc<str>,
     pub view_metadata: CassTableMeta,
     pub base_table: Weak<CassTableMeta>,
 }
@@ -60,7 +112,7 @@ impl FFI for CassMaterializedViewMeta {
 }
 
 pub struct CassColumnMeta {
-    pub name: String,
+    pub name: Arc<str>,
     pub column_type: Arc<CassDataType>,
     pub column_kind: CassColumnType,
 }
@@ -70,15 +122,32 @@ impl FFI for CassColumnMeta {
     type Origin = FromRef;
 }
 
+/// ScyllaDB names the CDC log table after its base table, with this suffix appended.
+const CDC_LOG_TABLE_SUFFIX: &str = "_scylla_cdc_log";
+/// Column of the CDC log table holding the id of the stream.
+const CDC_STREAM_ID_COLUMN: &str = "cdc$stream_id";
+
+fn cdc_log_table_name(base_table_name: &str) -> String {
+    format!("{}{}", base_table_name, CDC_LOG_TABLE_SUFFIX)
+}
+
+impl CassTableMeta {
+    fn is_cdc_log(&self) -> bool {
+        self.name.ends_with(CDC_LOG_TABLE_SUFFIX)
+            && self.columns_metadata.contains_key(CDC_STREAM_ID_COLUMN)
+    }
+}
+
 pub fn create_table_metadata(table_name: &str, table_metadata: &Table) -> CassTableMeta {
     let mut columns_metadata = HashMap::new();
     table_metadata
         .columns
         .iter()
         .for_each(|(column_name, column_metadata)| {
+            let column_name = intern_name(column_name);
             let cass_column_meta = CassColumnMeta {
-                name: column_name.clone(),
-                column_type: Arc::new(get_column_type(&column_metadata.typ)),
+                name: Arc::clone(&column_name),
+                column_type: get_column_type(&column_metadata.typ),
                 column_kind: match column_metadata.kind {
                     ColumnKind::Regular => CassColumnType::CASS_COLUMN_TYPE_REGULAR,
                     ColumnKind::Static => CassColumnType::CASS_COLUMN_TYPE_STATIC,
@@ -90,7 +159,7 @@ pub fn create_table_metadata(table_name: &str, table_metadata: &Table) -> CassTa
                 },
             };
 
-            columns_metadata.insert(column_name.clone(), cass_column_meta);
+            columns_metadata.insert(column_name, cass_column_meta);
         });
 
     let mut non_key_sorted_columns = columns_metadata
@@ -102,20 +171,137 @@ pub fn create_table_metadata(table_name: &str, table_metadata: &Table) -> CassTa
                     | CassColumnType::CASS_COLUMN_TYPE_CLUSTERING_KEY,
             )
         })
-        .map(|(name, _column)| name.to_owned())
+        .map(|(name, _column)| Arc::clone(name))
         .collect::<Vec<_>>();
     non_key_sorted_columns.sort_unstable();
 
     CassTableMeta {
-        name: table_name.to_owned(),
+        name: intern_name(table_name),
         columns_metadata,
-        partition_keys: table_metadata.partition_key.clone(),
-        clustering_keys: table_metadata.clustering_key.clone(),
+        partition_keys: table_metadata
+            .partition_key
+            .iter()
+            .map(|name| intern_name(name))
+            .collect(),
+        clustering_keys: table_metadata
+            .clustering_key
+            .iter()
+            .map(|name| intern_name(name))
+            .collect(),
         non_key_sorted_columns,
         views: HashMap::new(),
+        cdc_log_table_name: None,
+    }
+}
+
+pub fn create_keyspace_metadata(keyspace_name: &str, keyspace: &Keyspace) -> CassKeyspaceMeta {
+    let mut user_defined_type_data_type = HashMap::new();
+    let mut tables = HashMap::new();
+    let mut views = HashMap::new();
+
+    for (udt_name, udt) in keyspace.user_defined_types.iter() {
+        user_defined_type_data_type.insert(
+            intern_name(udt_name),
+            get_column_type(&ColumnType::UserDefinedType {
+                definition: Arc::clone(udt),
+                frozen: false,
+            }),
+        );
+    }
+
+    for (table_name, table_metadata) in &keyspace.tables {
+        let cass_table_meta_arced = Arc::new_cyclic(|weak_cass_table_meta| {
+            let mut cass_table_meta = create_table_metadata(table_name, table_metadata);
+
+            let mut table_views = HashMap::new();
+            for (view_name, view_metadata) in &keyspace.views {
+                let cass_view_table_meta =
+                    create_table_metadata(view_name, &view_metadata.view_metadata);
+                let view_name = Arc::clone(&cass_view_table_meta.name);
+                let cass_view_meta = CassMaterializedViewMeta {
+                    name: Arc::clone(&view_name),
+                    view_metadata: cass_view_table_meta,
+                    base_table: weak_cass_table_meta.clone(),
+                };
+                let cass_view_meta_arced = Arc::new(cass_view_meta);
+                table_views.insert(Arc::clone(&view_name), cass_view_meta_arced.clone());
+
+                views.insert(view_name, cass_view_meta_arced);
+            }
+
+            cass_table_meta.views = table_views;
+
+            // The log table is created (and dropped) together with enabling (disabling) CDC.
+            let log_table_name = cdc_log_table_name(table_name);
+            if keyspace.tables.contains_key(&log_table_name) {
+                cass_table_meta.cdc_log_table_name = Some(intern_name(&log_table_name));
+            }
+
+            cass_table_meta
+        });
+
+        tables.insert(
+            Arc::clone(&cass_table_meta_arced.name),
+            cass_table_meta_arced,
+        );
+    }
+
+    CassKeyspaceMeta {
+        name: intern_name(keyspace_name),
+        user_defined_type_data_type,
+        tables,
+        views,
     }
 }
 
+/// Upper bound of threads used to build the schema metadata.
+const MAX_SCHEMA_METADATA_PARALLELISM: usize = 8;
+/// Keyspaces are not distributed among the threads in smaller batches,
+/// as spawning a thread would cost more than building their metadata.
+const MIN_KEYSPACES_PER_THREAD: usize = 16;
+
+/// Builds the metadata of each keyspace in `cluster_state`.
+///
+/// On clusters with many keyspaces, the keyspaces are split between
+/// a bounded number of threads, and their metadata is built concurrently.
+pub fn create_schema_metadata(cluster_state: &ClusterState) -> CassSchemaMeta {
+    let keyspaces: Vec<(&str, &Keyspace)> = cluster_state.keyspaces_iter().collect();
+
+    let parallelism = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(MAX_SCHEMA_METADATA_PARALLELISM)
+        .min(keyspaces.len().div_ceil(MIN_KEYSPACES_PER_THREAD));
+
+    let create_keyspaces_metadata = |keyspaces: &[(&str, &Keyspace)]| {
+        keyspaces
+            .iter()
+            .map(|(keyspace_name, keyspace)| {
+                let cass_keyspace_meta = create_keyspace_metadata(keyspace_name, keyspace);
+                (Arc::clone(&cass_keyspace_meta.name), cass_keyspace_meta)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let keyspaces = if parallelism <= 1 {
+        create_keyspaces_metadata(&keyspaces).into_iter().collect()
+    } else {
+        let chunk_size = keyspaces.len().div_ceil(parallelism);
+        thread::scope(|scope| {
+            let workers: Vec<_> = keyspaces
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || create_keyspaces_metadata(chunk)))
+                .collect();
+            workers
+                .into_iter()
+                // unwrap: building the metadata does not panic.
+                .flat_map(|worker| worker.join().unwrap())
+                .collect()
+        })
+    };
+
+    CassSchemaMeta { keyspaces }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_schema_meta_free(
     schema_meta: CassOwnedExclusivePtr<CassSchemaMeta, CConst>,
@@ -170,7 +356,7 @@ pub unsafe extern "C" fn cass_keyspace_meta_name(
         return;
     };
 
-    unsafe { write_str_to_c(keyspace_meta.name.as_str(), name, name_length) }
+    unsafe { write_str_to_c(&keyspace_meta.name, name, name_length) }
 }
 
 #[unsafe(no_mangle)]
@@ -242,6 +428,44 @@ pub unsafe extern "C" fn cass_keyspace_meta_table_by_name_n(
     }
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_keyspace_meta_cdc_log_table_by_name(
+    keyspace_meta: CassBorrowedSharedPtr<CassKeyspaceMeta, CConst>,
+    table: *const c_char,
+) -> CassBorrowedSharedPtr<CassTableMeta, CConst> {
+    unsafe { cass_keyspace_meta_cdc_log_table_by_name_n(keyspace_meta, table, strlen(table)) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_keyspace_meta_cdc_log_table_by_name_n(
+    keyspace_meta: CassBorrowedSharedPtr<CassKeyspaceMeta, CConst>,
+    table: *const c_char,
+    table_length: size_t,
+) -> CassBorrowedSharedPtr<CassTableMeta, CConst> {
+    let Some(keyspace_meta) = RefFFI::as_ref(keyspace_meta) else {
+        tracing::error!(
+            "Provided null keyspace metadata pointer to cass_keyspace_meta_cdc_log_table_by_name_n!"
+        );
+        return RefFFI::null();
+    };
+    if table.is_null() {
+        return RefFFI::null();
+    }
+
+    let table_name = unsafe { ptr_to_cstr_n(table, table_length) }.unwrap();
+
+    let log_table_meta = keyspace_meta
+        .tables
+        .get(table_name)
+        .and_then(|table_meta| table_meta.cdc_log_table_name.as_ref())
+        .and_then(|log_table_name| keyspace_meta.tables.get(log_table_name));
+
+    match log_table_meta {
+        Some(meta) => RefFFI::as_ptr(meta),
+        None => RefFFI::null(),
+    }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_table_meta_name(
     table_meta: CassBorrowedSharedPtr<CassTableMeta, CConst>,
@@ -253,7 +477,31 @@ pub unsafe extern "C" fn cass_table_meta_name(
         return;
     };
 
-    unsafe { write_str_to_c(table_meta.name.as_str(), name, name_length) }
+    unsafe { write_str_to_c(&table_meta.name, name, name_length) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_table_meta_is_cdc_enabled(
+    table_meta: CassBorrowedSharedPtr<CassTableMeta, CConst>,
+) -> cass_bool_t {
+    let Some(table_meta) = RefFFI::as_ref(table_meta) else {
+        tracing::error!("Provided null table metadata pointer to cass_table_meta_is_cdc_enabled!");
+        return cass_false;
+    };
+
+    table_meta.cdc_log_table_name.is_some() as cass_bool_t
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_table_meta_is_cdc_log(
+    table_meta: CassBorrowedSharedPtr<CassTableMeta, CConst>,
+) -> cass_bool_t {
+    let Some(table_meta) = RefFFI::as_ref(table_meta) else {
+        tracing::error!("Provided null table metadata pointer to cass_table_meta_is_cdc_log!");
+        return cass_false;
+    };
+
+    table_meta.is_cdc_log() as cass_bool_t
 }
 
 #[unsafe(no_mangle)]
@@ -433,7 +681,7 @@ pub unsafe extern "C" fn cass_column_meta_name(
         return;
     };
 
-    unsafe { write_str_to_c(column_meta.name.as_str(), name, name_length) }
+    unsafe { write_str_to_c(&column_meta.name, name, name_length) }
 }
 
 #[unsafe(no_mangle)]
@@ -602,7 +850,7 @@ pub unsafe extern "C" fn cass_materialized_view_meta_name(
         return;
     };
 
-    unsafe { write_str_to_c(view_meta.name.as_str(), name, name_length) }
+    unsafe { write_str_to_c(&view_meta.name, name, name_length) }
 }
 
 #[unsafe(no_mangle)]
@@ -723,3 +971,55 @@ pub unsafe extern "C" fn cass_materialized_view_meta_clustering_key(
         None => RefFFI::null(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cass_types::{CassDataTypeInner, CassValueType};
+
+    #[test]
+    fn test_name_pool() {
+        let mut pool = NamePool::new();
+        let name = pool.intern("ks");
+        assert!(Arc::ptr_eq(&name, &pool.intern("ks")));
+        assert!(!Arc::ptr_eq(&name, &pool.intern("KS")));
+
+        // Unreferenced names are pruned once the pool grows.
+        for i in 0..MIN_NAME_POOL_PRUNE_THRESHOLD {
+            pool.intern(&format!("table_{i}"));
+        }
+        assert!(pool.names.len() < MIN_NAME_POOL_PRUNE_THRESHOLD);
+        assert!(Arc::ptr_eq(&name, &pool.intern("ks")));
+    }
+
+    #[test]
+    fn test_cdc_log_table_detection() {
+        let table_meta = |name: &str, columns: &[&str]| CassTableMeta {
+            name: intern_name(name),
+            columns_metadata: columns
+                .iter()
+                .map(|column| {
+                    let column_meta = CassColumnMeta {
+                        name: intern_name(column),
+                        column_type: CassDataType::new_arced(CassDataTypeInner::Value(
+                            CassValueType::CASS_VALUE_TYPE_BLOB,
+                        )),
+                        column_kind: CassColumnType::CASS_COLUMN_TYPE_REGULAR,
+                    };
+                    (intern_name(column), column_meta)
+                })
+                .collect(),
+            partition_keys: vec![],
+            clustering_keys: vec![],
+            non_key_sorted_columns: vec![],
+            views: HashMap::new(),
+            cdc_log_table_name: None,
+        };
+
+        assert_eq!(cdc_log_table_name("orders"), "orders_scylla_cdc_log");
+        assert!(table_meta("orders_scylla_cdc_log", &["cdc$stream_id", "pk"]).is_cdc_log());
+        // A user table that happens to be named like a log table.
+        assert!(!table_meta("orders_scylla_cdc_log", &["pk"]).is_cdc_log());
+        assert!(!table_meta("orders", &["cdc$stream_id"]).is_cdc_log());
+    }
+}
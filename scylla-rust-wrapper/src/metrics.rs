@@ -0,0 +1,736 @@
+use crate::inet::CassInet;
+use crate::types::cass_uint64_t;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+// The histogram is log-linear (similar to HdrHistogram): values below `LINEAR_BUCKETS`
+// are recorded exactly, and every further power of two is split into `SUB_BUCKETS`
+// buckets of equal width. This bounds the relative error of the reported values by 1/SUB_BUCKETS.
+const SUB_BUCKET_BITS: u32 = 7;
+const SUB_BUCKETS: usize = 1 << SUB_BUCKET_BITS;
+const LINEAR_BUCKETS: usize = 2 * SUB_BUCKETS;
+// Latencies are recorded in microseconds. Larger values (~12.7 days) are clamped.
+const MAX_VALUE_BITS: u32 = 40;
+const MAX_VALUE: u64 = (1 << MAX_VALUE_BITS) - 1;
+const BUCKET_COUNT: usize =
+    LINEAR_BUCKETS + (MAX_VALUE_BITS - SUB_BUCKET_BITS - 1) as usize * SUB_BUCKETS;
+
+fn bucket_index(value: u64) -> usize {
+    let value = value.min(MAX_VALUE);
+    if value < LINEAR_BUCKETS as u64 {
+        return value as usize;
+    }
+    // Position of the highest set bit. At least SUB_BUCKET_BITS + 1.
+    let exp = u64::BITS - 1 - value.leading_zeros();
+    let shift = exp - SUB_BUCKET_BITS;
+    let sub_bucket = (value >> shift) as usize - SUB_BUCKETS;
+    LINEAR_BUCKETS + (exp - SUB_BUCKET_BITS - 1) as usize * SUB_BUCKETS + sub_bucket
+}
+
+/// Returns the range `[lower, lower + width)` of values recorded in the bucket.
+fn bucket_range(index: usize) -> (u64, u64) {
+    if index < LINEAR_BUCKETS {
+        return (index as u64, 1);
+    }
+    let group = (index - LINEAR_BUCKETS) / SUB_BUCKETS;
+    let sub_bucket = (index - LINEAR_BUCKETS) % SUB_BUCKETS;
+    let shift = group as u32 + 1;
+    (((SUB_BUCKETS + sub_bucket) as u64) << shift, 1 << shift)
+}
+
+/// Value representing all of the values recorded in the bucket.
+fn bucket_value(index: usize) -> u64 {
+    let (lower, width) = bucket_range(index);
+    lower + width / 2
+}
+
+/// Latency statistics, in microseconds.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LatencySnapshot {
+    pub(crate) min: u64,
+    pub(crate) max: u64,
+    pub(crate) mean: u64,
+    pub(crate) stddev: u64,
+    pub(crate) median: u64,
+    pub(crate) percentile_75th: u64,
+    pub(crate) percentile_95th: u64,
+    pub(crate) percentile_98th: u64,
+    pub(crate) percentile_99th: u64,
+    pub(crate) percentile_999th: u64,
+}
+
+/// Lock-free histogram of latencies.
+pub(crate) struct LatencyHistogram {
+    buckets: Box<[AtomicU64]>,
+    sum: AtomicU64,
+    min: AtomicU64,
+    max: AtomicU64,
+}
+
+impl LatencyHistogram {
+    pub(crate) fn new() -> Self {
+        LatencyHistogram {
+            buckets: (0..BUCKET_COUNT).map(|_| AtomicU64::new(0)).collect(),
+            sum: AtomicU64::new(0),
+            min: AtomicU64::new(u64::MAX),
+            max: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn record(&self, value_us: u64) {
+        let value_us = value_us.min(MAX_VALUE);
+        self.buckets[bucket_index(value_us)].fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(value_us, Ordering::Relaxed);
+        self.min.fetch_min(value_us, Ordering::Relaxed);
+        self.max.fetch_max(value_us, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> LatencySnapshot {
+        let counts: Vec<u64> = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect();
+        Self::compute_snapshot(
+            &counts,
+            self.sum.load(Ordering::Relaxed),
+            self.min.load(Ordering::Relaxed),
+            self.max.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Takes the snapshot, and clears the histogram.
+    ///
+    /// Values recorded concurrently are either included in the snapshot,
+    /// or remain in the histogram (possibly with slightly inaccurate sum/min/max).
+    pub(crate) fn snapshot_and_reset(&self) -> LatencySnapshot {
+        let counts: Vec<u64> = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.swap(0, Ordering::Relaxed))
+            .collect();
+        Self::compute_snapshot(
+            &counts,
+            self.sum.swap(0, Ordering::Relaxed),
+            self.min.swap(u64::MAX, Ordering::Relaxed),
+            self.max.swap(0, Ordering::Relaxed),
+        )
+    }
+
+    fn compute_snapshot(counts: &[u64], sum: u64, min: u64, max: u64) -> LatencySnapshot {
+        let count: u64 = counts.iter().sum();
+        if count == 0 {
+            return LatencySnapshot::default();
+        }
+
+        let mean = sum as f64 / count as f64;
+        let variance = counts
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c > 0)
+            .map(|(index, c)| {
+                let diff = bucket_value(index) as f64 - mean;
+                diff * diff * *c as f64
+            })
+            .sum::<f64>()
+            / count as f64;
+
+        let percentile = |p: f64| -> u64 {
+            let rank = ((p * count as f64).ceil() as u64).max(1);
+            let mut seen = 0;
+            for (index, c) in counts.iter().enumerate() {
+                seen += c;
+                if seen >= rank {
+                    return bucket_value(index).clamp(min, max);
+                }
+            }
+            max
+        };
+
+        LatencySnapshot {
+            min,
+            max,
+            mean: mean.round() as u64,
+            stddev: variance.sqrt().round() as u64,
+            median: percentile(0.5),
+            percentile_75th: percentile(0.75),
+            percentile_95th: percentile(0.95),
+            percentile_98th: percentile(0.98),
+            percentile_99th: percentile(0.99),
+            percentile_999th: percentile(0.999),
+        }
+    }
+}
+
+// Exponentially weighted moving averages are updated every TICK_INTERVAL
+// (the same way as in the original cpp-driver).
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy)]
+struct Ewma {
+    alpha: f64,
+    // Events per second. None until the first tick.
+    rate: Option<f64>,
+}
+
+impl Ewma {
+    fn with_period(period: Duration) -> Self {
+        Ewma {
+            alpha: 1.0 - (-TICK_INTERVAL.as_secs_f64() / period.as_secs_f64()).exp(),
+            rate: None,
+        }
+    }
+
+    /// Updates the average with `count` events that happened during the last tick,
+    /// followed by `idle_ticks` ticks with no events.
+    fn tick(&mut self, count: u64, idle_ticks: u32) {
+        let instant_rate = count as f64 / TICK_INTERVAL.as_secs_f64();
+        let rate = match self.rate {
+            Some(rate) => rate + self.alpha * (instant_rate - rate),
+            None => instant_rate,
+        };
+        self.rate = Some(rate * (1.0 - self.alpha).powi(idle_ticks as i32));
+    }
+}
+
+struct MeterState {
+    last_tick: Instant,
+    // 1, 5 and 15 minute rates.
+    rates: [Ewma; 3],
+}
+
+/// Request rates, in requests per second.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct MeterRates {
+    pub(crate) mean_rate: f64,
+    pub(crate) one_minute_rate: f64,
+    pub(crate) five_minute_rate: f64,
+    pub(crate) fifteen_minute_rate: f64,
+}
+
+/// Measures the rate of events.
+pub(crate) struct Meter {
+    start: Instant,
+    count: AtomicU64,
+    // Events not yet accounted in the moving averages.
+    uncounted: AtomicU64,
+    state: Mutex<MeterState>,
+}
+
+impl Meter {
+    pub(crate) fn new(now: Instant) -> Self {
+        Meter {
+            start: now,
+            count: AtomicU64::new(0),
+            uncounted: AtomicU64::new(0),
+            state: Mutex::new(MeterState {
+                last_tick: now,
+                rates: [
+                    Ewma::with_period(Duration::from_secs(60)),
+                    Ewma::with_period(Duration::from_secs(5 * 60)),
+                    Ewma::with_period(Duration::from_secs(15 * 60)),
+                ],
+            }),
+        }
+    }
+
+    pub(crate) fn mark(&self, now: Instant) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.uncounted.fetch_add(1, Ordering::Relaxed);
+        // Don't contend with other threads - one of them will do the tick.
+        if let Ok(mut state) = self.state.try_lock() {
+            self.tick_if_necessary(&mut state, now);
+        }
+    }
+
+    fn tick_if_necessary(&self, state: &mut MeterState, now: Instant) {
+        let ticks = (now.saturating_duration_since(state.last_tick).as_nanos()
+            / TICK_INTERVAL.as_nanos()) as u32;
+        if ticks == 0 {
+            return;
+        }
+        state.last_tick += TICK_INTERVAL * ticks;
+
+        let count = self.uncounted.swap(0, Ordering::Relaxed);
+        for ewma in state.rates.iter_mut() {
+            ewma.tick(count, ticks - 1);
+        }
+    }
+
+    pub(crate) fn rates(&self, now: Instant) -> MeterRates {
+        let mut state = self.state.lock().unwrap();
+        self.tick_if_necessary(&mut state, now);
+
+        let elapsed = now.saturating_duration_since(self.start).as_secs_f64();
+        let count = self.count.load(Ordering::Relaxed);
+        let [one_minute, five_minute, fifteen_minute] =
+            state.rates.map(|ewma| ewma.rate.unwrap_or(0.0));
+
+        MeterRates {
+            mean_rate: if elapsed > 0.0 {
+                count as f64 / elapsed
+            } else {
+                0.0
+            },
+            one_minute_rate: one_minute,
+            five_minute_rate: five_minute,
+            fifteen_minute_rate: fifteen_minute,
+        }
+    }
+}
+
+/// Latency histogram that is periodically refreshed.
+struct RefreshingHistogram {
+    histogram: LatencyHistogram,
+    // None means that the histogram is never refreshed, i.e. it accumulates
+    // all of the latencies recorded since the session was connected.
+    refresh_interval: Option<Duration>,
+    // Time of the last refresh, and the snapshot of the latencies recorded
+    // during the last complete refresh interval.
+    refreshed: Mutex<(Instant, LatencySnapshot)>,
+}
+
+impl RefreshingHistogram {
+    fn new(refresh_interval: Option<Duration>, now: Instant) -> Self {
+        RefreshingHistogram {
+            histogram: LatencyHistogram::new(),
+            refresh_interval,
+            refreshed: Mutex::new((now, LatencySnapshot::default())),
+        }
+    }
+
+    fn record(&self, latency: Duration) {
+        self.histogram.record(latency.as_micros() as u64);
+    }
+
+    fn snapshot_at(&self, now: Instant) -> LatencySnapshot {
+        let Some(refresh_interval) = self.refresh_interval else {
+            return self.histogram.snapshot();
+        };
+
+        let mut refreshed = self.refreshed.lock().unwrap();
+        let (last_refresh, snapshot) = &mut *refreshed;
+        if now.saturating_duration_since(*last_refresh) >= refresh_interval {
+            *last_refresh = now;
+            *snapshot = self.histogram.snapshot_and_reset();
+        }
+        *snapshot
+    }
+}
+
+/// Request metrics maintained by the session.
+pub(crate) struct RequestMetrics {
+    latencies: RefreshingHistogram,
+    rate: Meter,
+    hosts: Arc<PerHostMetrics>,
+    queue: Arc<QueueMetrics>,
+}
+
+impl RequestMetrics {
+    pub(crate) fn new(refresh_interval: Option<Duration>) -> Self {
+        let now = Instant::now();
+        RequestMetrics {
+            latencies: RefreshingHistogram::new(refresh_interval, now),
+            rate: Meter::new(now),
+            hosts: Arc::new(PerHostMetrics::new(refresh_interval)),
+            queue: Arc::new(QueueMetrics::default()),
+        }
+    }
+
+    /// Records the latency of the request that completed successfully.
+    pub(crate) fn record_request(&self, latency: Duration) {
+        self.latencies.record(latency);
+        self.rate.mark(Instant::now());
+    }
+
+    pub(crate) fn latency_snapshot(&self) -> LatencySnapshot {
+        self.latencies.snapshot_at(Instant::now())
+    }
+
+    pub(crate) fn rates(&self) -> MeterRates {
+        self.rate.rates(Instant::now())
+    }
+
+    pub(crate) fn hosts(&self) -> &Arc<PerHostMetrics> {
+        &self.hosts
+    }
+
+    pub(crate) fn queue(&self) -> &Arc<QueueMetrics> {
+        &self.queue
+    }
+}
+
+/// Metrics of the requests waiting to be sent, and of the requests throttled
+/// due to backpressure. A request is pending from the moment it is executed
+/// by the session, until its first attempt is sent to a host.
+#[derive(Debug, Default)]
+pub(crate) struct QueueMetrics {
+    pending: AtomicU64,
+    throttled: AtomicU64,
+    pending_timeouts: AtomicU64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct QueueMetricsSnapshot {
+    pub(crate) pending_requests: u64,
+    pub(crate) throttled_requests: u64,
+    pub(crate) pending_request_timeouts: u64,
+}
+
+impl QueueMetrics {
+    pub(crate) fn request_queued(&self) {
+        self.pending.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn request_dequeued(&self) {
+        self.pending.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Records an attempt that could not be sent, because the connection had no free stream ids.
+    pub(crate) fn request_throttled(&self) {
+        self.throttled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a request that timed out while still pending.
+    pub(crate) fn pending_request_timed_out(&self) {
+        self.pending_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> QueueMetricsSnapshot {
+        QueueMetricsSnapshot {
+            pending_requests: self.pending.load(Ordering::Relaxed),
+            throttled_requests: self.throttled.load(Ordering::Relaxed),
+            pending_request_timeouts: self.pending_timeouts.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Session-wide metrics, as exposed by `cass_session_get_metrics`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct SessionMetricsSnapshot {
+    pub(crate) latencies: LatencySnapshot,
+    pub(crate) rates: MeterRates,
+    pub(crate) total_connections: u64,
+    pub(crate) connection_timeouts: u64,
+    pub(crate) request_timeouts: u64,
+    pub(crate) queue: QueueMetricsSnapshot,
+}
+
+/// Metrics of the requests sent to a single host.
+/// Each attempt (i.e. retries and speculative executions too) counts as a separate request.
+pub(crate) struct HostMetrics {
+    requests: AtomicU64,
+    errors: AtomicU64,
+    in_flight: AtomicU64,
+    latencies: RefreshingHistogram,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HostMetricsSnapshot {
+    pub(crate) requests: u64,
+    pub(crate) errors: u64,
+    pub(crate) in_flight: u64,
+    pub(crate) latencies: LatencySnapshot,
+}
+
+impl Debug for HostMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HostMetrics")
+            .field("requests", &self.requests)
+            .field("errors", &self.errors)
+            .field("in_flight", &self.in_flight)
+            .finish_non_exhaustive()
+    }
+}
+
+impl HostMetrics {
+    pub(crate) fn request_started(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records the completion of the request. Latency is recorded only for requests
+    /// that got a response from the host (successful or not).
+    pub(crate) fn request_finished(&self, latency: Option<Duration>, failed: bool) {
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+        if failed {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(latency) = latency {
+            self.latencies.record(latency);
+        }
+    }
+
+    fn snapshot_at(&self, now: Instant) -> HostMetricsSnapshot {
+        HostMetricsSnapshot {
+            requests: self.requests.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            latencies: self.latencies.snapshot_at(now),
+        }
+    }
+}
+
+/// Mirrors `CassHostMetrics` from `cassandra.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct CassHostMetrics {
+    pub address: CassInet,
+    pub requests: cass_uint64_t,
+    pub errors: cass_uint64_t,
+    pub in_flight: cass_uint64_t,
+    pub min: cass_uint64_t,
+    pub max: cass_uint64_t,
+    pub mean: cass_uint64_t,
+    pub stddev: cass_uint64_t,
+    pub median: cass_uint64_t,
+    pub percentile_75th: cass_uint64_t,
+    pub percentile_95th: cass_uint64_t,
+    pub percentile_98th: cass_uint64_t,
+    pub percentile_99th: cass_uint64_t,
+    pub percentile_999th: cass_uint64_t,
+}
+
+impl CassHostMetrics {
+    pub(crate) fn new(address: IpAddr, snapshot: &HostMetricsSnapshot) -> Self {
+        let latencies = &snapshot.latencies;
+        CassHostMetrics {
+            address: address.into(),
+            requests: snapshot.requests,
+            errors: snapshot.errors,
+            in_flight: snapshot.in_flight,
+            min: latencies.min,
+            max: latencies.max,
+            mean: latencies.mean,
+            stddev: latencies.stddev,
+            median: latencies.median,
+            percentile_75th: latencies.percentile_75th,
+            percentile_95th: latencies.percentile_95th,
+            percentile_98th: latencies.percentile_98th,
+            percentile_99th: latencies.percentile_99th,
+            percentile_999th: latencies.percentile_999th,
+        }
+    }
+}
+
+/// Metrics of the hosts that the session sent requests to, keyed by host address.
+pub(crate) struct PerHostMetrics {
+    refresh_interval: Option<Duration>,
+    hosts: RwLock<HashMap<IpAddr, Arc<HostMetrics>>>,
+}
+
+impl Debug for PerHostMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PerHostMetrics")
+            .field("refresh_interval", &self.refresh_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PerHostMetrics {
+    pub(crate) fn new(refresh_interval: Option<Duration>) -> Self {
+        PerHostMetrics {
+            refresh_interval,
+            hosts: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn host(&self, address: IpAddr) -> Arc<HostMetrics> {
+        if let Some(host) = self.hosts.read().unwrap().get(&address) {
+            return Arc::clone(host);
+        }
+
+        let mut hosts = self.hosts.write().unwrap();
+        let host = hosts.entry(address).or_insert_with(|| {
+            Arc::new(HostMetrics {
+                requests: AtomicU64::new(0),
+                errors: AtomicU64::new(0),
+                in_flight: AtomicU64::new(0),
+                latencies: RefreshingHistogram::new(self.refresh_interval, Instant::now()),
+            })
+        });
+        Arc::clone(host)
+    }
+
+    /// Number of the requests in flight to the host.
+    pub(crate) fn in_flight(&self, address: IpAddr) -> u64 {
+        self.hosts
+            .read()
+            .unwrap()
+            .get(&address)
+            .map_or(0, |host| host.in_flight.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn host_snapshot(&self, address: IpAddr) -> Option<HostMetricsSnapshot> {
+        let host = self.hosts.read().unwrap().get(&address).cloned()?;
+        Some(host.snapshot_at(Instant::now()))
+    }
+
+    /// Returns the snapshots of all hosts, ordered by the host address.
+    pub(crate) fn snapshot(&self) -> Vec<(IpAddr, HostMetricsSnapshot)> {
+        let now = Instant::now();
+        let mut hosts: Vec<_> = self
+            .hosts
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(address, host)| (*address, Arc::clone(host)))
+            .collect();
+        hosts.sort_unstable_by_key(|(address, _)| *address);
+
+        hosts
+            .into_iter()
+            .map(|(address, host)| (address, host.snapshot_at(now)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_index_and_range() {
+        let mut previous_index = 0;
+        for value in (0..100_000).chain([MAX_VALUE / 3, MAX_VALUE - 1, MAX_VALUE]) {
+            let index = bucket_index(value);
+            assert!(index < BUCKET_COUNT);
+            // Indices are monotonic.
+            assert!(index >= previous_index);
+            previous_index = index;
+
+            let (lower, width) = bucket_range(index);
+            assert!((lower..lower + width).contains(&value), "{}", value);
+            // Relative error is bounded.
+            assert!(
+                width == 1 || width * SUB_BUCKETS as u64 <= lower,
+                "{}",
+                value
+            );
+        }
+        assert_eq!(bucket_index(u64::MAX), BUCKET_COUNT - 1);
+    }
+
+    #[test]
+    fn test_latency_histogram_snapshot() {
+        let histogram = LatencyHistogram::new();
+        assert_eq!(histogram.snapshot(), LatencySnapshot::default());
+
+        // 1..=1000 ms
+        for value in 1..=1000 {
+            histogram.record(value * 1000);
+        }
+        let snapshot = histogram.snapshot();
+
+        let assert_approx = |actual: u64, expected: u64| {
+            let error = actual.abs_diff(expected) as f64 / expected as f64;
+            assert!(
+                error <= 1.0 / SUB_BUCKETS as f64,
+                "actual: {}, expected: {}",
+                actual,
+                expected
+            );
+        };
+        assert_eq!(snapshot.min, 1000);
+        assert_eq!(snapshot.max, 1_000_000);
+        assert_eq!(snapshot.mean, 500_500);
+        assert_approx(snapshot.stddev, 288_675);
+        assert_approx(snapshot.median, 500_000);
+        assert_approx(snapshot.percentile_75th, 750_000);
+        assert_approx(snapshot.percentile_95th, 950_000);
+        assert_approx(snapshot.percentile_98th, 980_000);
+        assert_approx(snapshot.percentile_99th, 990_000);
+        assert_approx(snapshot.percentile_999th, 999_000);
+
+        assert_eq!(histogram.snapshot_and_reset(), snapshot);
+        assert_eq!(histogram.snapshot(), LatencySnapshot::default());
+    }
+
+    #[test]
+    fn test_refreshing_histogram() {
+        let refresh_interval = Duration::from_secs(10);
+        let start = Instant::now();
+        let histogram = RefreshingHistogram::new(Some(refresh_interval), start);
+
+        histogram.record(Duration::from_micros(100));
+        // Refresh interval has not passed yet - values from the previous (empty) interval are reported.
+        assert_eq!(histogram.snapshot_at(start), LatencySnapshot::default());
+
+        let after_refresh = start + refresh_interval;
+        let snapshot = histogram.snapshot_at(after_refresh);
+        assert_eq!((snapshot.min, snapshot.max), (100, 100));
+
+        // Values recorded during the current interval are not reported until the next refresh.
+        histogram.record(Duration::from_micros(200));
+        assert_eq!(histogram.snapshot_at(after_refresh), snapshot);
+        let snapshot = histogram.snapshot_at(after_refresh + refresh_interval);
+        assert_eq!((snapshot.min, snapshot.max), (200, 200));
+
+        // Without refresh interval, the histogram accumulates all values.
+        let histogram = RefreshingHistogram::new(None, start);
+        histogram.record(Duration::from_micros(100));
+        histogram.record(Duration::from_micros(200));
+        let snapshot = histogram.snapshot_at(start);
+        assert_eq!((snapshot.min, snapshot.max), (100, 200));
+        assert_eq!(histogram.snapshot_at(start + refresh_interval), snapshot);
+    }
+
+    #[test]
+    fn test_per_host_metrics() {
+        let metrics = PerHostMetrics::new(None);
+        let host1 = IpAddr::from([127, 0, 0, 1]);
+        let host2 = IpAddr::from([127, 0, 0, 2]);
+        assert!(metrics.snapshot().is_empty());
+        assert_eq!(metrics.host_snapshot(host1), None);
+
+        let host2_metrics = metrics.host(host2);
+        host2_metrics.request_started();
+        host2_metrics.request_started();
+        host2_metrics.request_finished(Some(Duration::from_micros(100)), true);
+        let host1_metrics = metrics.host(host1);
+        host1_metrics.request_started();
+        // The same metrics are returned for the same address.
+        assert!(Arc::ptr_eq(&metrics.host(host2), &host2_metrics));
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(
+            snapshot
+                .iter()
+                .map(|(address, host)| (*address, host.requests, host.errors, host.in_flight))
+                .collect::<Vec<_>>(),
+            vec![(host1, 1, 0, 1), (host2, 2, 1, 1)]
+        );
+        assert_eq!(snapshot[1].1.latencies.max, 100);
+        assert_eq!(metrics.host_snapshot(host2), Some(snapshot[1].1));
+        assert_eq!(metrics.in_flight(host2), 1);
+        assert_eq!(metrics.in_flight(IpAddr::from([127, 0, 0, 3])), 0);
+    }
+
+    #[test]
+    fn test_meter_rates() {
+        let start = Instant::now();
+        let meter = Meter::new(start);
+        assert_eq!(meter.rates(start), MeterRates::default());
+
+        // 10 events per second during the first tick.
+        for _ in 0..50 {
+            meter.mark(start);
+        }
+        let rates = meter.rates(start + TICK_INTERVAL);
+        assert_eq!(rates.one_minute_rate, 10.0);
+        assert_eq!(rates.five_minute_rate, 10.0);
+        assert_eq!(rates.fifteen_minute_rate, 10.0);
+        assert_eq!(rates.mean_rate, 10.0);
+
+        // After a minute with no events, the rates decay - the shorter period, the faster.
+        let rates = meter.rates(start + TICK_INTERVAL + Duration::from_secs(60));
+        assert!(rates.one_minute_rate < rates.five_minute_rate);
+        assert!(rates.five_minute_rate < rates.fifteen_minute_rate);
+        assert!(rates.fifteen_minute_rate < 10.0);
+        assert!((rates.one_minute_rate - 10.0 * (-1.0f64).exp()).abs() < 1e-9);
+    }
+}
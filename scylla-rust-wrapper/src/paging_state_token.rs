@@ -0,0 +1,138 @@
+use ring::hmac;
+
+// Paging state tokens are opaque to the users, but they are not protected in any way -
+// a client may forge a token and make the driver resume an arbitrary query from an arbitrary
+// position. When the signing key is set on the cluster, the tokens returned by
+// `cass_result_paging_state_token` are signed with HMAC-SHA256 (token = raw paging state || MAC),
+// and the tokens passed to `cass_statement_set_paging_state_token` are rejected upon execution
+// unless their MAC is valid for the executed statement.
+
+const MAC_SIZE: usize = 32;
+
+/// Identifies the statement (and the keyspace) that the paging state belongs to.
+/// It's covered by the MAC, so a token can't be used to resume a different statement.
+pub(crate) struct PagingStateBinding(Vec<u8>);
+
+impl PagingStateBinding {
+    pub(crate) fn for_query(contents: &str, keyspace: Option<&str>) -> Self {
+        Self::new(b'Q', contents.as_bytes(), keyspace)
+    }
+
+    pub(crate) fn for_prepared(id: &[u8], keyspace: Option<&str>) -> Self {
+        Self::new(b'P', id, keyspace)
+    }
+
+    fn new(kind: u8, statement: &[u8], keyspace: Option<&str>) -> Self {
+        // Length-prefixed fields, so distinct bindings never serialize to the same bytes.
+        let keyspace = keyspace.unwrap_or_default().as_bytes();
+        let mut binding = Vec::with_capacity(1 + 16 + statement.len() + keyspace.len());
+        binding.push(kind);
+        for field in [statement, keyspace] {
+            binding.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            binding.extend_from_slice(field);
+        }
+        Self(binding)
+    }
+}
+
+pub(crate) struct PagingStateSigner {
+    key: hmac::Key,
+}
+
+impl PagingStateSigner {
+    pub(crate) fn new(key: &[u8]) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, key),
+        }
+    }
+
+    /// The message covered by the MAC.
+    fn message(raw_paging_state: &[u8], binding: &PagingStateBinding) -> Vec<u8> {
+        let mut message = Vec::with_capacity(binding.0.len() + raw_paging_state.len());
+        message.extend_from_slice(&binding.0);
+        message.extend_from_slice(raw_paging_state);
+        message
+    }
+
+    pub(crate) fn sign(&self, raw_paging_state: &[u8], binding: &PagingStateBinding) -> Vec<u8> {
+        let mac = hmac::sign(&self.key, &Self::message(raw_paging_state, binding));
+        let mut token = Vec::with_capacity(raw_paging_state.len() + MAC_SIZE);
+        token.extend_from_slice(raw_paging_state);
+        token.extend_from_slice(mac.as_ref());
+        token
+    }
+
+    /// Returns the raw paging state carried by the token, if the token's MAC is valid
+    /// for the statement.
+    pub(crate) fn verify<'a>(
+        &self,
+        token: &'a [u8],
+        binding: &PagingStateBinding,
+    ) -> Option<&'a [u8]> {
+        let raw_len = token.len().checked_sub(MAC_SIZE)?;
+        let (raw_paging_state, mac) = token.split_at(raw_len);
+        // Compares the MACs in constant time.
+        hmac::verify(&self.key, &Self::message(raw_paging_state, binding), mac)
+            .ok()
+            .map(|()| raw_paging_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paging_state_signer() {
+        let signer = PagingStateSigner::new(b"secret");
+        let binding = PagingStateBinding::for_query("SELECT * FROM t", Some("ks"));
+        let raw = b"raw paging state";
+
+        let token = signer.sign(raw, &binding);
+        assert_eq!(signer.verify(&token, &binding), Some(&raw[..]));
+
+        let mut tampered = token.clone();
+        tampered[0] ^= 1;
+        assert_eq!(signer.verify(&tampered, &binding), None);
+        assert_eq!(signer.verify(raw, &binding), None);
+        assert_eq!(signer.verify(&[], &binding), None);
+
+        let other_signer = PagingStateSigner::new(b"other secret");
+        assert_eq!(other_signer.verify(&token, &binding), None);
+    }
+
+    #[test]
+    fn test_paging_state_token_is_bound_to_statement() {
+        let signer = PagingStateSigner::new(b"secret");
+        let raw = b"raw paging state";
+        let token = signer.sign(
+            raw,
+            &PagingStateBinding::for_query("SELECT * FROM t", Some("ks")),
+        );
+
+        // Other statement.
+        assert_eq!(
+            signer.verify(
+                &token,
+                &PagingStateBinding::for_query("SELECT * FROM secrets", Some("ks"))
+            ),
+            None
+        );
+        // Same statement, other keyspace.
+        assert_eq!(
+            signer.verify(
+                &token,
+                &PagingStateBinding::for_query("SELECT * FROM t", Some("other_ks"))
+            ),
+            None
+        );
+        // Prepared statement with the same bytes as id.
+        assert_eq!(
+            signer.verify(
+                &token,
+                &PagingStateBinding::for_prepared(b"SELECT * FROM t", Some("ks"))
+            ),
+            None
+        );
+    }
+}
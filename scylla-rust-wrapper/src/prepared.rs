@@ -7,8 +7,9 @@ use crate::{
     cass_error::CassError,
     cass_types::{CassDataType, get_column_type},
     query_result::CassResultMetadata,
+    request_queue::RequestPriority,
     statement::{BoundPreparedStatement, BoundStatement, CassStatement},
-    types::size_t,
+    types::{cass_bool_t, cass_false, size_t},
 };
 use scylla::statement::prepared::PreparedStatement;
 
@@ -38,7 +39,7 @@ impl CassPrepared {
         let variable_col_data_types = statement
             .get_variable_col_specs()
             .iter()
-            .map(|col_spec| Arc::new(get_column_type(col_spec.typ())))
+            .map(|col_spec| get_column_type(col_spec.typ()))
             .collect();
 
         let result_metadata = Arc::new(CassResultMetadata::from_column_specs(
@@ -106,13 +107,67 @@ pub unsafe extern "C" fn cass_prepared_bind(
     BoxFFI::into_ptr(Box::new(CassStatement {
         statement,
         paging_state: PagingState::start(),
-        // Cpp driver disables paging by default.
-        paging_enabled: false,
+        paging_state_from_token: false,
+        // Not set - the page size of the execution profile applies. Unless it's
+        // configured, paging is disabled - as in cpp-driver.
+        paging_enabled: None,
         request_timeout_ms: None,
         exec_profile: None,
+        trace_context: None,
+        priority: RequestPriority::default(),
     }))
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_prepared_query_string(
+    prepared_raw: CassBorrowedSharedPtr<CassPrepared, CConst>,
+    query: *mut *const c_char,
+    query_length: *mut size_t,
+) {
+    let Some(prepared) = ArcFFI::as_ref(prepared_raw) else {
+        tracing::error!("Provided null prepared statement pointer to cass_prepared_query_string!");
+        return;
+    };
+
+    unsafe { write_str_to_c(prepared.statement.get_statement(), query, query_length) };
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_prepared_keyspace(
+    prepared_raw: CassBorrowedSharedPtr<CassPrepared, CConst>,
+    keyspace: *mut *const c_char,
+    keyspace_length: *mut size_t,
+) -> CassError {
+    let Some(prepared) = ArcFFI::as_ref(prepared_raw) else {
+        tracing::error!("Provided null prepared statement pointer to cass_prepared_keyspace!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    // The keyspace is known only if the statement has bind markers
+    // (it comes from the metadata of the bind markers).
+    match prepared.statement.get_table_spec() {
+        Some(table_spec) => {
+            unsafe { write_str_to_c(table_spec.ks_name(), keyspace, keyspace_length) };
+            CassError::CASS_OK
+        }
+        None => CassError::CASS_ERROR_LIB_NULL_VALUE,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_prepared_is_lwt(
+    prepared_raw: CassBorrowedSharedPtr<CassPrepared, CConst>,
+) -> cass_bool_t {
+    let Some(prepared) = ArcFFI::as_ref(prepared_raw) else {
+        tracing::error!("Provided null prepared statement pointer to cass_prepared_is_lwt!");
+        return cass_false;
+    };
+
+    // rust-driver routes the confirmed LWT statements to the replicas in a fixed order
+    // (instead of shuffling them), to avoid contention between the coordinators.
+    prepared.statement.is_confirmed_lwt() as cass_bool_t
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_prepared_parameter_name(
     prepared_raw: CassBorrowedSharedPtr<CassPrepared, CConst>,
@@ -140,6 +195,74 @@ pub unsafe extern "C" fn cass_prepared_parameter_name(
     }
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_prepared_parameter_is_partition_key(
+    prepared_raw: CassBorrowedSharedPtr<CassPrepared, CConst>,
+    index: size_t,
+) -> cass_bool_t {
+    let Some(prepared) = ArcFFI::as_ref(prepared_raw) else {
+        tracing::error!(
+            "Provided null prepared statement pointer to cass_prepared_parameter_is_partition_key!"
+        );
+        return cass_false;
+    };
+
+    prepared
+        .statement
+        .get_variable_pk_indexes()
+        .iter()
+        .any(|pk_index| pk_index.index as size_t == index) as cass_bool_t
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_prepared_partition_key_count(
+    prepared_raw: CassBorrowedSharedPtr<CassPrepared, CConst>,
+) -> size_t {
+    let Some(prepared) = ArcFFI::as_ref(prepared_raw) else {
+        tracing::error!(
+            "Provided null prepared statement pointer to cass_prepared_partition_key_count!"
+        );
+        return 0;
+    };
+
+    prepared.statement.get_variable_pk_indexes().len() as size_t
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_prepared_partition_key_parameter_index(
+    prepared_raw: CassBorrowedSharedPtr<CassPrepared, CConst>,
+    position: size_t,
+    index: *mut size_t,
+) -> CassError {
+    let Some(prepared) = ArcFFI::as_ref(prepared_raw) else {
+        tracing::error!(
+            "Provided null prepared statement pointer to cass_prepared_partition_key_parameter_index!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+    if index.is_null() {
+        tracing::error!(
+            "Provided null index pointer to cass_prepared_partition_key_parameter_index!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    }
+
+    // The indexes are sorted by the bind marker index, not by the position
+    // of the column in the partition key.
+    match prepared
+        .statement
+        .get_variable_pk_indexes()
+        .iter()
+        .find(|pk_index| pk_index.sequence as size_t == position)
+    {
+        Some(pk_index) => {
+            unsafe { *index = pk_index.index as size_t };
+            CassError::CASS_OK
+        }
+        None => CassError::CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS,
+    }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_prepared_parameter_data_type(
     prepared_raw: CassBorrowedSharedPtr<CassPrepared, CConst>,
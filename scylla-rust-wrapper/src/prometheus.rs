@@ -0,0 +1,296 @@
+use crate::metrics::{HostMetricsSnapshot, LatencySnapshot, SessionMetricsSnapshot};
+use std::fmt::{Display, Write};
+use std::net::IpAddr;
+
+const PREFIX: &str = "scylla_cpp_driver";
+
+/// Renders metrics in Prometheus text exposition format (version 0.0.4).
+///
+/// See https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format.
+struct PrometheusWriter {
+    out: String,
+}
+
+impl PrometheusWriter {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        writeln!(self.out, "# HELP {PREFIX}_{name} {help}").unwrap();
+        writeln!(self.out, "# TYPE {PREFIX}_{name} {kind}").unwrap();
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &dyn Display)], value: impl Display) {
+        write!(self.out, "{PREFIX}_{name}").unwrap();
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (label, label_value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                // Label values are either numbers or IP addresses - no escaping is needed.
+                write!(self.out, "{label}=\"{label_value}\"").unwrap();
+            }
+            self.out.push('}');
+        }
+        writeln!(self.out, " {value}").unwrap();
+    }
+
+    /// Renders the quantiles as a summary, and the remaining statistics as gauges.
+    /// `latencies` are labeled with the host, if provided.
+    fn latency_families(
+        &mut self,
+        name: &str,
+        help: &str,
+        latencies: &[(Option<IpAddr>, &LatencySnapshot)],
+    ) {
+        self.family(name, "summary", help);
+        for (host, l) in latencies {
+            for (quantile, value) in [
+                ("0.5", l.median),
+                ("0.75", l.percentile_75th),
+                ("0.95", l.percentile_95th),
+                ("0.98", l.percentile_98th),
+                ("0.99", l.percentile_99th),
+                ("0.999", l.percentile_999th),
+            ] {
+                match host {
+                    Some(host) => {
+                        self.sample(name, &[("host", host), ("quantile", &quantile)], value)
+                    }
+                    None => self.sample(name, &[("quantile", &quantile)], value),
+                }
+            }
+        }
+
+        let stats: [(&str, &str, fn(&LatencySnapshot) -> u64); 4] = [
+            ("min", "Minimum of", |l| l.min),
+            ("max", "Maximum of", |l| l.max),
+            ("mean", "Mean of", |l| l.mean),
+            ("stddev", "Standard deviation of", |l| l.stddev),
+        ];
+        for (stat, stat_help, value) in stats {
+            let stat_name = format!("{name}_{stat}");
+            self.family(
+                &stat_name,
+                "gauge",
+                &format!("{stat_help} the {}", help.to_lowercase()),
+            );
+            for (host, l) in latencies {
+                match host {
+                    Some(host) => self.sample(&stat_name, &[("host", host)], value(l)),
+                    None => self.sample(&stat_name, &[], value(l)),
+                }
+            }
+        }
+    }
+}
+
+/// Renders the session-wide and per-host metrics in Prometheus text format.
+pub(crate) fn render_prometheus(
+    session: &SessionMetricsSnapshot,
+    hosts: &[(IpAddr, HostMetricsSnapshot)],
+) -> String {
+    let mut w = PrometheusWriter { out: String::new() };
+
+    w.latency_families(
+        "request_latency_microseconds",
+        "Latency of successfully completed requests, in microseconds.",
+        &[(None, &session.latencies)],
+    );
+
+    w.family(
+        "request_rate",
+        "gauge",
+        "Rate of successfully completed requests, in requests per second.",
+    );
+    for (window, rate) in [
+        ("mean", session.rates.mean_rate),
+        ("1m", session.rates.one_minute_rate),
+        ("5m", session.rates.five_minute_rate),
+        ("15m", session.rates.fifteen_minute_rate),
+    ] {
+        w.sample("request_rate", &[("window", &window)], rate);
+    }
+
+    w.family("connections", "gauge", "The total number of connections.");
+    w.sample("connections", &[], session.total_connections);
+    w.family(
+        "connection_timeouts_total",
+        "counter",
+        "Occurrences of a connection timeout.",
+    );
+    w.sample(
+        "connection_timeouts_total",
+        &[],
+        session.connection_timeouts,
+    );
+    w.family(
+        "request_timeouts_total",
+        "counter",
+        "Occurrences of requests that timed out waiting for a request to finish.",
+    );
+    w.sample("request_timeouts_total", &[], session.request_timeouts);
+
+    w.family(
+        "pending_requests",
+        "gauge",
+        "The number of requests currently waiting to be sent.",
+    );
+    w.sample("pending_requests", &[], session.queue.pending_requests);
+    w.family(
+        "throttled_requests_total",
+        "counter",
+        "Occurrences of attempts rejected, because the connection had no free stream ids.",
+    );
+    w.sample(
+        "throttled_requests_total",
+        &[],
+        session.queue.throttled_requests,
+    );
+    w.family(
+        "pending_request_timeouts_total",
+        "counter",
+        "Occurrences of requests that timed out before being sent.",
+    );
+    w.sample(
+        "pending_request_timeouts_total",
+        &[],
+        session.queue.pending_request_timeouts,
+    );
+
+    if hosts.is_empty() {
+        return w.out;
+    }
+
+    w.family(
+        "host_requests_total",
+        "counter",
+        "The number of requests sent to the host.",
+    );
+    for (host, metrics) in hosts {
+        w.sample("host_requests_total", &[("host", host)], metrics.requests);
+    }
+    w.family(
+        "host_errors_total",
+        "counter",
+        "The number of requests sent to the host that failed.",
+    );
+    for (host, metrics) in hosts {
+        w.sample("host_errors_total", &[("host", host)], metrics.errors);
+    }
+    w.family(
+        "host_in_flight_requests",
+        "gauge",
+        "The number of requests to the host currently in flight.",
+    );
+    for (host, metrics) in hosts {
+        w.sample(
+            "host_in_flight_requests",
+            &[("host", host)],
+            metrics.in_flight,
+        );
+    }
+    let host_latencies: Vec<_> = hosts
+        .iter()
+        .map(|(host, metrics)| (Some(*host), &metrics.latencies))
+        .collect();
+    w.latency_families(
+        "host_request_latency_microseconds",
+        "Latency of requests sent to the host, in microseconds.",
+        &host_latencies,
+    );
+
+    w.out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{MeterRates, QueueMetricsSnapshot};
+
+    #[test]
+    fn test_render_prometheus() {
+        let latencies = LatencySnapshot {
+            min: 1,
+            max: 2,
+            mean: 3,
+            stddev: 4,
+            median: 5,
+            percentile_75th: 6,
+            percentile_95th: 7,
+            percentile_98th: 8,
+            percentile_99th: 9,
+            percentile_999th: 10,
+        };
+        let session = SessionMetricsSnapshot {
+            latencies,
+            rates: MeterRates {
+                mean_rate: 1.5,
+                one_minute_rate: 2.0,
+                five_minute_rate: 0.25,
+                fifteen_minute_rate: 0.0,
+            },
+            total_connections: 3,
+            connection_timeouts: 1,
+            request_timeouts: 2,
+            queue: QueueMetricsSnapshot {
+                pending_requests: 4,
+                throttled_requests: 5,
+                pending_request_timeouts: 6,
+            },
+        };
+
+        let rendered = render_prometheus(&session, &[]);
+        for line in [
+            "# TYPE scylla_cpp_driver_request_latency_microseconds summary",
+            "scylla_cpp_driver_request_latency_microseconds{quantile=\"0.5\"} 5",
+            "scylla_cpp_driver_request_latency_microseconds{quantile=\"0.999\"} 10",
+            "# TYPE scylla_cpp_driver_request_latency_microseconds_max gauge",
+            "scylla_cpp_driver_request_latency_microseconds_max 2",
+            "scylla_cpp_driver_request_latency_microseconds_stddev 4",
+            "scylla_cpp_driver_request_rate{window=\"mean\"} 1.5",
+            "scylla_cpp_driver_request_rate{window=\"5m\"} 0.25",
+            "scylla_cpp_driver_connections 3",
+            "scylla_cpp_driver_connection_timeouts_total 1",
+            "scylla_cpp_driver_request_timeouts_total 2",
+            "scylla_cpp_driver_pending_requests 4",
+            "scylla_cpp_driver_throttled_requests_total 5",
+            "scylla_cpp_driver_pending_request_timeouts_total 6",
+        ] {
+            assert!(rendered.lines().any(|l| l == line), "{}", line);
+        }
+        assert!(!rendered.contains("host"));
+
+        let host = IpAddr::from([127, 0, 0, 1]);
+        let rendered = render_prometheus(
+            &session,
+            &[(
+                host,
+                HostMetricsSnapshot {
+                    requests: 10,
+                    errors: 2,
+                    in_flight: 1,
+                    latencies,
+                },
+            )],
+        );
+        for line in [
+            "scylla_cpp_driver_host_requests_total{host=\"127.0.0.1\"} 10",
+            "scylla_cpp_driver_host_errors_total{host=\"127.0.0.1\"} 2",
+            "scylla_cpp_driver_host_in_flight_requests{host=\"127.0.0.1\"} 1",
+            "scylla_cpp_driver_host_request_latency_microseconds{host=\"127.0.0.1\",quantile=\"0.99\"} 9",
+            "scylla_cpp_driver_host_request_latency_microseconds_min{host=\"127.0.0.1\"} 1",
+        ] {
+            assert!(rendered.lines().any(|l| l == line), "{}", line);
+        }
+
+        // Every sample belongs to a family declared before.
+        let mut declared = Vec::new();
+        for line in rendered.lines() {
+            if let Some(declaration) = line.strip_prefix("# TYPE ") {
+                declared.push(declaration.split(' ').next().unwrap());
+            } else if !line.starts_with('#') {
+                let name = line.split(['{', ' ']).next().unwrap();
+                assert_eq!(declared.last(), Some(&name), "{}", line);
+            }
+        }
+    }
+}
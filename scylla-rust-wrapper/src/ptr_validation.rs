@@ -0,0 +1,304 @@
+//! Debug validation of the pointers passed by the C API user.
+//!
+//! Enabled with the `debug-pointer-validation` cargo feature. The driver keeps a registry
+//! of the owned pointers handed out to the user (see [`BoxFFI::into_ptr`](crate::argconv::BoxFFI::into_ptr)
+//! and [`ArcFFI::into_ptr`](crate::argconv::ArcFFI::into_ptr)), tagged with the pointee's type.
+//! The pointers are validated against the registry when they are dereferenced or freed,
+//! so use-after-free, double free and wrong-type pointer bugs of the application are reported
+//! (and the process is aborted) immediately, instead of resulting in undefined behaviour.
+//!
+//! The validation is best-effort:
+//! - pointers borrowed from Arc or from other objects (see `RefFFI`) are not registered,
+//!   as the driver does not know their lifetime. They are only checked for alignment,
+//!   and for not being an owned pointer of a different type (except for `RefFFI` pointers,
+//!   which may point to the beginning of an enclosing owned object).
+//! - a freed pointer whose memory has been reused for an object of the same type
+//!   is indistinguishable from a valid one.
+//!
+//! Additionally, the API functions mutating the objects that are most commonly shared between
+//! threads by mistake (iterators, statements, collections, tuples and UDTs) mark the object
+//! as in use (see [`BoxFFI::as_mut_ref_guarded`](crate::argconv::BoxFFI::as_mut_ref_guarded)).
+//! Using the object from another thread in the meantime is reported as well.
+//!
+//! Without the feature, all functions of this module are no-ops.
+
+#[cfg(feature = "debug-pointer-validation")]
+mod enabled {
+    use std::collections::HashMap;
+    use std::sync::{LazyLock, Mutex};
+    use std::thread::ThreadId;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(crate) enum Origin {
+        Box,
+        Arc,
+    }
+
+    struct LivePointer {
+        // `std::any::TypeId` requires `'static` types, and some of the FFI types are not.
+        type_name: &'static str,
+        origin: Origin,
+        // Number of owned pointers held by the user. Always 1 for `Box`.
+        owners: usize,
+        // The thread mutating the object, and the number of its (nested) guards.
+        in_use_by: Option<(ThreadId, usize)>,
+    }
+
+    impl LivePointer {
+        fn is_in_use_by_other_thread(&self) -> bool {
+            self.in_use_by
+                .is_some_and(|(thread_id, _)| thread_id != std::thread::current().id())
+        }
+    }
+
+    static LIVE_POINTERS: LazyLock<Mutex<HashMap<usize, LivePointer>>> =
+        LazyLock::new(|| Mutex::new(HashMap::new()));
+
+    #[cold]
+    #[track_caller]
+    fn invalid_pointer<T>(ptr: *const T, reason: &str) -> ! {
+        let msg = format!(
+            "Invalid {} pointer {:p} passed to the driver: {}",
+            std::any::type_name::<T>(),
+            ptr,
+            reason
+        );
+        tracing::error!("{}", msg);
+        panic!("{}", msg);
+    }
+
+    fn check_alignment<T>(ptr: *const T) {
+        if !ptr.is_aligned() {
+            invalid_pointer(ptr, "the pointer is misaligned");
+        }
+    }
+
+    /// Registers the owned pointer handed out to the user.
+    pub(crate) fn register<T>(ptr: *const T, origin: Origin) {
+        // Zero-sized allocations share the (dangling) address.
+        if size_of::<T>() == 0 {
+            return;
+        }
+
+        let mut live_pointers = LIVE_POINTERS.lock().unwrap();
+        let live_pointer = live_pointers.entry(ptr as usize).or_insert(LivePointer {
+            type_name: std::any::type_name::<T>(),
+            origin,
+            owners: 0,
+            in_use_by: None,
+        });
+        live_pointer.owners += 1;
+    }
+
+    /// Validates the owned pointer. If `release` is true, the pointer is released by the user.
+    pub(crate) fn validate_owned<T>(ptr: *const T, origin: Origin, release: bool) {
+        check_alignment(ptr);
+        if size_of::<T>() == 0 {
+            return;
+        }
+
+        let mut live_pointers = LIVE_POINTERS.lock().unwrap();
+        let Some(live_pointer) = live_pointers.get_mut(&(ptr as usize)) else {
+            drop(live_pointers);
+            invalid_pointer(
+                ptr,
+                "the pointer does not point to a live object (it was already freed, or was never allocated by the driver)",
+            );
+        };
+        if live_pointer.type_name != std::any::type_name::<T>() || live_pointer.origin != origin {
+            let type_name = live_pointer.type_name;
+            drop(live_pointers);
+            invalid_pointer(ptr, &format!("the pointer points to {}", type_name));
+        }
+        if live_pointer.is_in_use_by_other_thread() {
+            drop(live_pointers);
+            invalid_pointer(ptr, "the object is concurrently mutated by another thread");
+        }
+
+        if release {
+            live_pointer.owners -= 1;
+            if live_pointer.owners == 0 {
+                live_pointers.remove(&(ptr as usize));
+            }
+        }
+    }
+
+    /// Validates the pointer that may be borrowed (i.e. not registered).
+    /// If `check_type` is true, the pointer can't point to an owned object of a different type.
+    pub(crate) fn validate_borrowed<T>(ptr: *const T, check_type: bool) {
+        check_alignment(ptr);
+        if !check_type || size_of::<T>() == 0 {
+            return;
+        }
+
+        let other_type_name = LIVE_POINTERS
+            .lock()
+            .unwrap()
+            .get(&(ptr as usize))
+            .map(|live_pointer| live_pointer.type_name)
+            .filter(|type_name| *type_name != std::any::type_name::<T>());
+        if let Some(type_name) = other_type_name {
+            invalid_pointer(ptr, &format!("the pointer points to {}", type_name));
+        }
+    }
+
+    /// Marks the object as in use by the current thread until dropped.
+    pub struct ExclusiveUseGuard {
+        // None for zero-sized objects.
+        address: Option<usize>,
+    }
+
+    /// Validates the owned `Box` pointer, and marks the pointee as in use by the current thread.
+    pub(crate) fn enter_exclusive_use<T>(ptr: *const T) -> ExclusiveUseGuard {
+        validate_owned(ptr, Origin::Box, false);
+        if size_of::<T>() == 0 {
+            return ExclusiveUseGuard { address: None };
+        }
+
+        let address = ptr as usize;
+        let mut live_pointers = LIVE_POINTERS.lock().unwrap();
+        // The pointer was validated above, but it could have been freed concurrently.
+        if let Some(live_pointer) = live_pointers.get_mut(&address) {
+            let current_thread = std::thread::current().id();
+            match &mut live_pointer.in_use_by {
+                Some((thread_id, depth)) if *thread_id == current_thread => *depth += 1,
+                Some(_) => {
+                    drop(live_pointers);
+                    invalid_pointer(ptr, "the object is concurrently mutated by another thread");
+                }
+                None => live_pointer.in_use_by = Some((current_thread, 1)),
+            }
+        }
+
+        ExclusiveUseGuard {
+            address: Some(address),
+        }
+    }
+
+    impl Drop for ExclusiveUseGuard {
+        fn drop(&mut self) {
+            let Some(address) = self.address else {
+                return;
+            };
+            let mut live_pointers = LIVE_POINTERS.lock().unwrap();
+            if let Some(live_pointer) = live_pointers.get_mut(&address) {
+                live_pointer.in_use_by = match live_pointer.in_use_by {
+                    Some((thread_id, depth)) if depth > 1 => Some((thread_id, depth - 1)),
+                    _ => None,
+                };
+            }
+        }
+    }
+}
+
+#[cfg(feature = "debug-pointer-validation")]
+pub(crate) use enabled::*;
+
+#[cfg(not(feature = "debug-pointer-validation"))]
+mod disabled {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(crate) enum Origin {
+        Box,
+        Arc,
+    }
+
+    #[inline(always)]
+    pub(crate) fn register<T>(_ptr: *const T, _origin: Origin) {}
+
+    #[inline(always)]
+    pub(crate) fn validate_owned<T>(_ptr: *const T, _origin: Origin, _release: bool) {}
+
+    #[inline(always)]
+    pub(crate) fn validate_borrowed<T>(_ptr: *const T, _check_type: bool) {}
+
+    pub struct ExclusiveUseGuard;
+
+    #[inline(always)]
+    pub(crate) fn enter_exclusive_use<T>(_ptr: *const T) -> ExclusiveUseGuard {
+        ExclusiveUseGuard
+    }
+}
+
+#[cfg(not(feature = "debug-pointer-validation"))]
+pub(crate) use disabled::*;
+
+#[cfg(all(test, feature = "debug-pointer-validation"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owned_pointer_validation() {
+        let boxed = Box::new(42u64);
+        let ptr: *const u64 = &*boxed;
+        register(ptr, Origin::Box);
+        validate_owned(ptr, Origin::Box, false);
+        validate_borrowed(ptr, true);
+
+        // Wrong type.
+        let result =
+            std::panic::catch_unwind(|| validate_owned(ptr as *const i64, Origin::Box, false));
+        assert!(result.is_err());
+        let result = std::panic::catch_unwind(|| validate_borrowed(ptr as *const i64, true));
+        assert!(result.is_err());
+        // RefFFI pointers are not type-checked.
+        validate_borrowed(ptr as *const i64, false);
+
+        // Double free.
+        validate_owned(ptr, Origin::Box, true);
+        let result = std::panic::catch_unwind(|| validate_owned(ptr, Origin::Box, true));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_shared_pointer_validation() {
+        let arc = std::sync::Arc::new(42u32);
+        let ptr = std::sync::Arc::as_ptr(&arc);
+        register(ptr, Origin::Arc);
+        register(ptr, Origin::Arc);
+
+        validate_owned(ptr, Origin::Arc, true);
+        // Still owned by the user once.
+        validate_owned(ptr, Origin::Arc, true);
+        let result = std::panic::catch_unwind(|| validate_owned(ptr, Origin::Arc, true));
+        assert!(result.is_err());
+        // Borrowed pointers are not registered.
+        validate_borrowed(ptr, true);
+    }
+
+    #[test]
+    fn test_exclusive_use_detection() {
+        struct Pointer(*const u64);
+        // SAFETY: the pointer is never dereferenced.
+        unsafe impl Send for Pointer {}
+
+        let boxed = Box::new(42u64);
+        let ptr: *const u64 = &*boxed;
+        register(ptr, Origin::Box);
+
+        {
+            let _guard = enter_exclusive_use(ptr);
+            // Nested use by the same thread is fine.
+            let _nested_guard = enter_exclusive_use(ptr);
+            validate_owned(ptr, Origin::Box, false);
+
+            let other_thread_ptr = Pointer(ptr);
+            let result = std::thread::spawn(move || {
+                let other_thread_ptr = other_thread_ptr;
+                validate_owned(other_thread_ptr.0, Origin::Box, false)
+            })
+            .join();
+            assert!(result.is_err());
+        }
+
+        // The guards are dropped - the object can be used by another thread.
+        let other_thread_ptr = Pointer(ptr);
+        std::thread::spawn(move || {
+            let other_thread_ptr = other_thread_ptr;
+            let _guard = enter_exclusive_use(other_thread_ptr.0);
+        })
+        .join()
+        .unwrap();
+
+        validate_owned(ptr, Origin::Box, true);
+    }
+}
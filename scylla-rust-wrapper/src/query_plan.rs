@@ -0,0 +1,263 @@
+use crate::argconv::*;
+use crate::cass_error::CassError;
+use crate::inet::CassInet;
+use crate::session::{CassSession, CassSessionInner};
+use crate::statement::{BoundStatement, CassStatement};
+use crate::types::{cass_int32_t, size_t};
+use scylla::cluster::{ClusterState, Node};
+use scylla::policies::load_balancing::{LoadBalancingPolicy, RoutingInfo};
+use scylla::routing::Shard;
+use std::net::IpAddr;
+
+/// Ordered list of the hosts (and shards) that the driver would try
+/// when executing the statement.
+pub struct CassQueryPlan {
+    hosts: Vec<(IpAddr, Option<Shard>)>,
+}
+
+impl FFI for CassQueryPlan {
+    type Origin = FromBox;
+}
+
+impl CassQueryPlan {
+    fn new(
+        policy: &dyn LoadBalancingPolicy,
+        routing_info: &RoutingInfo,
+        cluster_state: &ClusterState,
+    ) -> Self {
+        // Mimics the plan of rust-driver: the picked target goes first,
+        // and is not repeated by the fallback.
+        let picked = policy.pick(routing_info, cluster_state);
+        let is_picked = |node: &Node, shard: Option<Shard>| match picked {
+            Some((picked_node, picked_shard)) => {
+                picked_node.host_id == node.host_id && picked_shard == shard
+            }
+            None => false,
+        };
+        let fallback = policy
+            .fallback(routing_info, cluster_state)
+            .filter(|(node, shard)| !is_picked(node, *shard));
+
+        let hosts = picked
+            .into_iter()
+            .chain(fallback)
+            // Disabled nodes (e.g. rejected by the host filter) are never connected to.
+            .filter(|(node, _)| node.is_enabled())
+            .map(|(node, shard)| (node.address.ip(), shard))
+            .collect();
+
+        CassQueryPlan { hosts }
+    }
+}
+
+fn compute_query_plan(
+    session: &CassSessionInner,
+    statement: &CassStatement,
+) -> Result<CassQueryPlan, (CassError, String)> {
+    let exec_profile_handle = futures::executor::block_on(
+        session.get_or_resolve_profile_handle(statement.exec_profile.as_ref()),
+    )?;
+    let rust_session = session.rust_session();
+    let profile = exec_profile_handle
+        .as_ref()
+        .unwrap_or_else(|| rust_session.get_default_execution_profile_handle())
+        .to_profile();
+
+    let mut routing_info = RoutingInfo::default();
+    routing_info.serial_consistency = statement.statement.effective_serial_consistency(&profile);
+    routing_info.consistency = profile.get_consistency();
+    match &statement.statement {
+        // Unprepared statements carry no routing information - the token is unknown.
+        BoundStatement::Simple(query) => {
+            if let Some(consistency) = query.query.get_consistency() {
+                routing_info.consistency = consistency;
+            }
+        }
+        BoundStatement::Prepared(prepared) => {
+            let prepared_statement = &prepared.statement.statement;
+            if let Some(consistency) = prepared_statement.get_consistency() {
+                routing_info.consistency = consistency;
+            }
+            routing_info.token = prepared_statement
+                .calculate_token(&prepared.bound_values)
+                .map_err(|err| (CassError::CASS_ERROR_LIB_INVALID_DATA, err.to_string()))?;
+            routing_info.table = prepared_statement.get_table_spec();
+            routing_info.is_confirmed_lwt = prepared_statement.is_confirmed_lwt();
+        }
+    }
+
+    let cluster_state = session.cluster_state();
+    Ok(CassQueryPlan::new(
+        profile.get_load_balancing_policy().as_ref(),
+        &routing_info,
+        &cluster_state,
+    ))
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_session_get_query_plan(
+    session_raw: CassBorrowedSharedPtr<CassSession, CConst>,
+    statement_raw: CassBorrowedSharedPtr<CassStatement, CConst>,
+) -> CassOwnedExclusivePtr<CassQueryPlan, CMut> {
+    let Some(maybe_session_lock) = ArcFFI::as_ref(session_raw) else {
+        tracing::error!("Provided null session pointer to cass_session_get_query_plan!");
+        return BoxFFI::null_mut();
+    };
+    let Some(statement) = BoxFFI::as_ref(statement_raw) else {
+        tracing::error!("Provided null statement pointer to cass_session_get_query_plan!");
+        return BoxFFI::null_mut();
+    };
+
+    let maybe_session_guard = maybe_session_lock.blocking_read();
+    let Some(session) = maybe_session_guard.as_ref() else {
+        tracing::warn!("Attempted to get query plan before connecting session object");
+        return BoxFFI::null_mut();
+    };
+
+    match compute_query_plan(session, statement) {
+        Ok(plan) => BoxFFI::into_ptr(Box::new(plan)),
+        Err((err, msg)) => {
+            tracing::error!("Failed to compute query plan ({:?}): {}", err, msg);
+            BoxFFI::null_mut()
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_query_plan_host_count(
+    query_plan: CassBorrowedSharedPtr<CassQueryPlan, CConst>,
+) -> size_t {
+    let Some(query_plan) = BoxFFI::as_ref(query_plan) else {
+        tracing::error!("Provided null query plan pointer to cass_query_plan_host_count!");
+        return 0;
+    };
+
+    query_plan.hosts.len() as size_t
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_query_plan_host(
+    query_plan: CassBorrowedSharedPtr<CassQueryPlan, CConst>,
+    index: size_t,
+    host: *mut CassInet,
+    shard: *mut cass_int32_t,
+) -> CassError {
+    let Some(query_plan) = BoxFFI::as_ref(query_plan) else {
+        tracing::error!("Provided null query plan pointer to cass_query_plan_host!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    let Some((address, target_shard)) = query_plan.hosts.get(index as usize) else {
+        return CassError::CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS;
+    };
+
+    if !host.is_null() {
+        unsafe { *host = (*address).into() };
+    }
+    if !shard.is_null() {
+        // -1 means that the request is not targeted at any particular shard.
+        unsafe { *shard = target_shard.map_or(-1, |shard| shard as cass_int32_t) };
+    }
+    CassError::CASS_OK
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_query_plan_free(
+    query_plan: CassOwnedExclusivePtr<CassQueryPlan, CMut>,
+) {
+    BoxFFI::free(query_plan);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MockLoadBalancingPolicy, assert_cass_error_eq, with_proxy_cluster_state};
+
+    fn node_ip(cluster_state: &ClusterState, index: usize) -> IpAddr {
+        cluster_state.get_nodes_info()[index].address.ip()
+    }
+
+    #[tokio::test]
+    #[ntest::timeout(5000)]
+    async fn test_query_plan_order() {
+        with_proxy_cluster_state(3, |cluster_state| {
+            let policy = MockLoadBalancingPolicy {
+                picked: Some((1, Some(2))),
+                fallback: vec![(1, Some(2)), (2, Some(0)), (0, None), (1, Some(3))],
+            };
+            let plan = CassQueryPlan::new(&policy, &RoutingInfo::default(), cluster_state);
+
+            // The picked target is not repeated, and the order of the fallback is preserved.
+            assert_eq!(
+                plan.hosts,
+                vec![
+                    (node_ip(cluster_state, 1), Some(2)),
+                    (node_ip(cluster_state, 2), Some(0)),
+                    (node_ip(cluster_state, 0), None),
+                    (node_ip(cluster_state, 1), Some(3)),
+                ]
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[ntest::timeout(5000)]
+    async fn test_query_plan_host() {
+        with_proxy_cluster_state(2, |cluster_state| {
+            let policy = MockLoadBalancingPolicy {
+                picked: None,
+                fallback: vec![(0, None), (1, Some(5))],
+            };
+            let plan = CassQueryPlan::new(&policy, &RoutingInfo::default(), cluster_state);
+            let plan_raw = BoxFFI::into_ptr(Box::new(plan));
+
+            unsafe {
+                assert_eq!(
+                    cass_query_plan_host_count(plan_raw.borrow().into_c_const()),
+                    2
+                );
+
+                let mut host = CassInet::from(IpAddr::from([0, 0, 0, 0]));
+                let mut shard: cass_int32_t = 0;
+                assert_cass_error_eq!(
+                    cass_query_plan_host(
+                        plan_raw.borrow().into_c_const(),
+                        0,
+                        &mut host,
+                        &mut shard
+                    ),
+                    CassError::CASS_OK
+                );
+                assert_eq!(IpAddr::try_from(host).unwrap(), node_ip(cluster_state, 0));
+                // The policy returned no shard.
+                assert_eq!(shard, -1);
+
+                assert_cass_error_eq!(
+                    cass_query_plan_host(
+                        plan_raw.borrow().into_c_const(),
+                        1,
+                        &mut host,
+                        &mut shard
+                    ),
+                    CassError::CASS_OK
+                );
+                assert_eq!(IpAddr::try_from(host).unwrap(), node_ip(cluster_state, 1));
+                assert_eq!(shard, 5);
+
+                assert_cass_error_eq!(
+                    cass_query_plan_host(
+                        plan_raw.borrow().into_c_const(),
+                        2,
+                        &mut host,
+                        &mut shard
+                    ),
+                    CassError::CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS
+                );
+
+                cass_query_plan_free(plan_raw);
+            }
+        })
+        .await;
+    }
+}
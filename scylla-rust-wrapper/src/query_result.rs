@@ -5,7 +5,11 @@ use crate::cass_types::{
     cass_data_type_type, get_column_type,
 };
 use crate::execution_error::CassErrorResult;
+use crate::execution_info::CassExecutionInfo;
 use crate::inet::CassInet;
+use crate::metadata::intern_name;
+use crate::result_json::render_rows_json;
+use crate::result_memory::ResultMemoryReservation;
 use crate::types::*;
 use crate::uuid::CassUuid;
 use cass_raw_value::CassRawValue;
@@ -44,9 +48,16 @@ pub struct CassRowsResult {
 
 #[derive(Debug)]
 pub(crate) struct CassRowsResultSharedData {
+    // The rows bytes are a part of the response frame, allocated by rust-driver
+    // when the frame is received. Rust-driver does not allow to provide the buffers
+    // for the frames, so the pages cannot be read into pooled (reused) buffers.
+    // The buffer is freed once the last reference to the page (the result,
+    // or a row borrowed from it) is dropped.
     pub(crate) raw_rows: DeserializedMetadataAndRawRows,
     // Arc: shared with CassPrepared
     pub(crate) metadata: Arc<CassResultMetadata>,
+    // See `cass_cluster_set_numeric_widening`.
+    pub(crate) numeric_widening: bool,
 }
 
 pub type CassNode = Coordinator;
@@ -64,6 +75,13 @@ pub struct CassResult {
     // None only for tests - currently no way to mock coordinator in rust-driver.
     // Should be able to do so under "cpp_rust_unstable".
     pub(crate) coordinator: Option<Coordinator>,
+    // None for results that were not obtained by executing a request (e.g. in tests).
+    pub(crate) execution_info: Option<Arc<CassExecutionInfo>>,
+    // Releases the page's bytes from the session's result memory limit on drop.
+    pub(crate) memory_reservation: Option<ResultMemoryReservation>,
+    // Set if the result has more pages and the session signs the paging state tokens
+    // (see `cass_cluster_set_paging_state_token_signing_key`).
+    pub(crate) signed_paging_state_token: Option<Vec<u8>>,
 }
 
 impl CassResult {
@@ -71,10 +89,12 @@ impl CassResult {
     /// - query result
     /// - paging state response
     /// - optional cached result metadata - it's provided for prepared statements
+    /// - whether the values of the result accept numeric widening
     pub fn from_result_payload(
         result: QueryResult,
         paging_state_response: PagingStateResponse,
         maybe_result_metadata: Option<Arc<CassResultMetadata>>,
+        numeric_widening: bool,
     ) -> Result<Self, Arc<CassErrorResult>> {
         match result.into_rows_result() {
             Ok(rows_result) => {
@@ -88,7 +108,11 @@ impl CassResult {
                 });
 
                 let (raw_rows, tracing_id, _, coordinator) = rows_result.into_inner();
-                let shared_data = Arc::new(CassRowsResultSharedData { raw_rows, metadata });
+                let shared_data = Arc::new(CassRowsResultSharedData {
+                    raw_rows,
+                    metadata,
+                    numeric_widening,
+                });
                 let first_row = RowWithSelfBorrowedResultData::first_from_raw_rows_and_metadata(
                     Arc::clone(&shared_data),
                 )?;
@@ -101,6 +125,9 @@ impl CassResult {
                         first_row,
                     }),
                     coordinator,
+                    execution_info: None,
+                    memory_reservation: None,
+                    signed_paging_state_token: None,
                 };
 
                 Ok(cass_result)
@@ -111,6 +138,9 @@ impl CassResult {
                     paging_state_response,
                     kind: CassResultKind::NonRows,
                     coordinator: Some(result.request_coordinator().clone()),
+                    execution_info: None,
+                    memory_reservation: None,
+                    signed_paging_state_token: None,
                 };
 
                 Ok(cass_result)
@@ -120,6 +150,14 @@ impl CassResult {
             }
         }
     }
+
+    /// Size of the serialized rows held by the result.
+    pub(crate) fn rows_bytes_size(&self) -> usize {
+        match &self.kind {
+            CassResultKind::Rows(rows_result) => rows_result.shared_data.raw_rows.rows_bytes_size(),
+            CassResultKind::NonRows => 0,
+        }
+    }
 }
 
 impl FFI for CassResult {
@@ -136,8 +174,8 @@ impl CassResultMetadata {
         let col_specs = col_specs
             .iter()
             .map(|col_spec| {
-                let name = col_spec.name().to_owned();
-                let data_type = Arc::new(get_column_type(col_spec.typ()));
+                let name = intern_name(col_spec.name());
+                let data_type = get_column_type(col_spec.typ());
 
                 CassColumnSpec { name, data_type }
             })
@@ -167,6 +205,7 @@ impl<'frame, 'metadata> DeserializeRow<'frame, 'metadata> for CassRawRow<'frame,
 pub struct CassRow<'result> {
     pub columns: Vec<CassValue<'result>>,
     pub result_metadata: &'result CassResultMetadata,
+    pub(crate) numeric_widening: bool,
 }
 
 impl FFI for CassRow<'_> {
@@ -177,12 +216,30 @@ impl<'result> CassRow<'result> {
     pub(crate) fn from_raw_row_and_metadata(
         row: CassRawRow<'result, 'result>,
         result_metadata: &'result CassResultMetadata,
+        numeric_widening: bool,
     ) -> Result<Self, DeserializationError> {
-        let mut columns = Vec::with_capacity(row.columns.columns_remaining());
+        let mut cass_row = Self {
+            columns: Vec::with_capacity(row.columns.columns_remaining()),
+            result_metadata,
+            numeric_widening,
+        };
+        cass_row.refill_from_raw_row(row)?;
+
+        Ok(cass_row)
+    }
+
+    /// Replaces the columns with the ones deserialized from `row`, reusing the columns buffer.
+    /// On error, the row is left partially filled and should be discarded.
+    pub(crate) fn refill_from_raw_row(
+        &mut self,
+        row: CassRawRow<'result, 'result>,
+    ) -> Result<(), DeserializationError> {
+        self.columns.clear();
+        self.columns.reserve(row.columns.columns_remaining());
 
         let mut raw_columns_with_cass_metadata = row
             .columns
-            .zip(result_metadata.col_specs.iter())
+            .zip(self.result_metadata.col_specs.iter())
             .map(|(raw_column_res, cass_metadata)| {
                 raw_column_res.map(|raw_column| (raw_column, cass_metadata))
             });
@@ -209,14 +266,12 @@ impl<'result> CassRow<'result> {
             let value = CassValue {
                 value: raw_value,
                 value_type,
+                numeric_widening: self.numeric_widening,
             };
-            columns.push(value);
+            self.columns.push(value);
         }
 
-        Ok(Self {
-            columns,
-            result_metadata,
-        })
+        Ok(())
     }
 }
 
@@ -271,7 +326,11 @@ mod row_with_self_borrowed_result_data {
             let yoke_result = Yoke::try_attach_to_cart(
                 raw_rows_and_metadata,
                 |raw_rows_and_metadata_ref| -> Result<_, AttachError> {
-                    let CassRowsResultSharedData { raw_rows, metadata } = raw_rows_and_metadata_ref;
+                    let CassRowsResultSharedData {
+                        raw_rows,
+                        metadata,
+                        numeric_widening,
+                    } = raw_rows_and_metadata_ref;
 
                     let raw_row_result = raw_rows
                         .rows_iter::<CassRawRow>()
@@ -280,8 +339,9 @@ mod row_with_self_borrowed_result_data {
                         .next()
                         .ok_or(AttachError::NoRows)?;
 
-                    let row_result = raw_row_result
-                        .and_then(|raw_row| CassRow::from_raw_row_and_metadata(raw_row, metadata));
+                    let row_result = raw_row_result.and_then(|raw_row| {
+                        CassRow::from_raw_row_and_metadata(raw_row, metadata, *numeric_widening)
+                    });
 
                     let row = row_result
                         .map_err(DeserializationError::into)
@@ -317,8 +377,6 @@ pub(crate) mod cass_raw_value {
     pub(crate) struct CassRawValue<'frame, 'metadata> {
         typ: &'metadata ColumnType<'metadata>,
         slice: Option<FrameSlice<'frame>>,
-        /// Required to implement [`super::cass_value_item_count`].
-        item_count: Option<usize>,
     }
 
     #[derive(Error, Debug)]
@@ -329,13 +387,12 @@ pub(crate) mod cass_raw_value {
         NegativeLength(i32),
     }
 
-    /// Precomputes the `item_count` from raw value.
-    /// It is required to implement [`cass_value_item_count`].
+    /// Computes the `item_count` from raw value.
+    /// It is required to implement [`super::cass_value_item_count`].
     fn determine_value_item_count(
         typ: &ColumnType<'_>,
         slice: Option<FrameSlice<'_>>,
     ) -> Result<Option<usize>, CollectionLengthDeserializationError> {
-        // Pre-compute the item count. Required to implement cass_value_item_count.
         // The cpp-driver semantics:
         // - tuples/UDTs - obtain the value count from type metadata
         // - collections - deserialize the first 4 bytes as collection length
@@ -409,14 +466,10 @@ pub(crate) mod cass_raw_value {
                 (_, slice) => slice,
             };
 
-            let item_count =
-                determine_value_item_count(typ, slice).map_err(DeserializationError::new)?;
-
-            Ok(Self {
-                typ,
-                slice,
-                item_count,
-            })
+            // Nothing more is done here - the value is deserialized only once requested
+            // by the user. This way, reading a row does not pay for the columns
+            // that are never accessed.
+            Ok(Self { typ, slice })
         }
     }
 
@@ -429,16 +482,51 @@ pub(crate) mod cass_raw_value {
             self.slice
         }
 
+        /// Returns `None` for non-collection values, and for malformed collections.
         pub(crate) fn item_count(&self) -> Option<usize> {
-            self.item_count
+            determine_value_item_count(self.typ, self.slice)
+                .inspect_err(|err| tracing::error!("Failed to determine the item count: {err}"))
+                .ok()
+                .flatten()
         }
     }
 }
 
+/// Numeric type that values of narrower numeric types can be losslessly converted to.
+pub trait WidenNumeric: Sized {
+    /// Returns `None` if the type of the value can't be widened to `Self`.
+    fn widen(value: &CassValue) -> Option<Result<Self, NonNullDeserializationError>>;
+}
+
+macro_rules! impl_widen_numeric {
+    ($t:ty: $($narrow:ty => $native_type:ident),*) => {
+        impl WidenNumeric for $t {
+            fn widen(value: &CassValue) -> Option<Result<Self, NonNullDeserializationError>> {
+                match value.value.typ() {
+                    $(
+                        ColumnType::Native(NativeType::$native_type) => {
+                            Some(value.get_non_null::<$narrow>().map(<$t>::from))
+                        }
+                    )*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_widen_numeric!(i16: i8 => TinyInt);
+impl_widen_numeric!(i32: i8 => TinyInt, i16 => SmallInt);
+impl_widen_numeric!(i64: i8 => TinyInt, i16 => SmallInt, i32 => Int);
+impl_widen_numeric!(f32: i8 => TinyInt, i16 => SmallInt);
+impl_widen_numeric!(f64: i8 => TinyInt, i16 => SmallInt, i32 => Int, f32 => Float);
+
 #[derive(Debug)]
 pub struct CassValue<'result> {
     pub(crate) value: CassRawValue<'result, 'result>,
     pub(crate) value_type: &'result Arc<CassDataType>,
+    // Inherited from the result - see `cass_cluster_set_numeric_widening`.
+    pub(crate) numeric_widening: bool,
 }
 
 impl FFI for CassValue<'_> {
@@ -460,6 +548,21 @@ impl<'result> CassValue<'result> {
         Ok(v)
     }
 
+    /// Same as [`CassValue::get_non_null`], but if numeric widening is enabled
+    /// (see `cass_cluster_set_numeric_widening`), values of narrower numeric types
+    /// are accepted as well, and losslessly converted to `T`.
+    pub fn get_numeric_non_null<T>(&'result self) -> Result<T, NonNullDeserializationError>
+    where
+        T: DeserializeValue<'result, 'result> + WidenNumeric,
+    {
+        if self.numeric_widening {
+            if let Some(widened) = T::widen(self) {
+                return widened;
+            }
+        }
+        self.get_non_null()
+    }
+
     pub fn get_bytes_non_null(&self) -> Result<&'result [u8], NonNullDeserializationError> {
         let Some(slice) = self.value.slice() else {
             return Err(NonNullDeserializationError::IsNull);
@@ -566,7 +669,7 @@ pub unsafe extern "C" fn cass_row_get_column_by_name_n<'result>(
         .iter()
         .enumerate()
         .find(|(_, col_spec)| {
-            is_case_sensitive && col_spec.name == name_str
+            is_case_sensitive && *col_spec.name == *name_str
                 || !is_case_sensitive && col_spec.name.eq_ignore_ascii_case(name_str)
         })
         .map(|(index, _)| match row_from_raw.columns.get(index) {
@@ -692,7 +795,7 @@ pub unsafe extern "C" fn cass_value_get_float(
 ) -> CassError {
     let val: &CassValue = val_ptr_to_ref_ensure_non_null!(value, "cass_value_get_float");
 
-    let f: f32 = match val.get_non_null() {
+    let f: f32 = match val.get_numeric_non_null() {
         Ok(v) => v,
         Err(e) => return e.to_cass_error(),
     };
@@ -708,7 +811,7 @@ pub unsafe extern "C" fn cass_value_get_double(
 ) -> CassError {
     let val: &CassValue = val_ptr_to_ref_ensure_non_null!(value, "cass_value_get_double");
 
-    let f: f64 = match val.get_non_null() {
+    let f: f64 = match val.get_numeric_non_null() {
         Ok(v) => v,
         Err(e) => return e.to_cass_error(),
     };
@@ -756,7 +859,7 @@ pub unsafe extern "C" fn cass_value_get_int16(
 ) -> CassError {
     let val: &CassValue = val_ptr_to_ref_ensure_non_null!(value, "cass_value_get_int16");
 
-    let i: i16 = match val.get_non_null() {
+    let i: i16 = match val.get_numeric_non_null() {
         Ok(v) => v,
         Err(e) => return e.to_cass_error(),
     };
@@ -788,7 +891,7 @@ pub unsafe extern "C" fn cass_value_get_int32(
 ) -> CassError {
     let val: &CassValue = val_ptr_to_ref_ensure_non_null!(value, "cass_value_get_int32");
 
-    let i: i32 = match val.get_non_null() {
+    let i: i32 = match val.get_numeric_non_null() {
         Ok(v) => v,
         Err(e) => return e.to_cass_error(),
     };
@@ -833,7 +936,10 @@ pub unsafe extern "C" fn cass_value_get_int64(
             }
             Err(e) => return e.to_cass_error(),
         },
-        _ => return CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE,
+        _ => match val.get_numeric_non_null::<i64>() {
+            Ok(v) => v,
+            Err(e) => return e.to_cass_error(),
+        },
     };
 
     unsafe { std::ptr::write(output, i) };
@@ -1113,6 +1219,33 @@ pub unsafe extern "C" fn cass_result_column_count(
     shared_data.metadata.col_specs.len() as size_t
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_result_to_json(
+    result_raw: CassBorrowedSharedPtr<CassResult, CConst>,
+    buffer: *mut c_char,
+    buffer_size: size_t,
+) -> size_t {
+    let Some(result) = ArcFFI::as_ref(result_raw) else {
+        tracing::error!("Provided null result pointer to cass_result_to_json!");
+        return 0;
+    };
+
+    let rendered = match &result.kind {
+        CassResultKind::Rows(CassRowsResult { shared_data, .. }) => {
+            match render_rows_json(&shared_data.raw_rows, &shared_data.metadata) {
+                Ok(rendered) => rendered,
+                Err(err) => {
+                    tracing::error!("Failed to render the result as JSON: {}", err);
+                    return 0;
+                }
+            }
+        }
+        CassResultKind::NonRows => "[]".to_owned(),
+    };
+
+    unsafe { write_str_to_c_buffer(&rendered, buffer, buffer_size) }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_result_first_row(
     result_raw: CassBorrowedSharedPtr<CassResult, CConst>,
@@ -1150,10 +1283,16 @@ pub unsafe extern "C" fn cass_result_paging_state_token(
 
     match &result_from_raw.paging_state_response {
         PagingStateResponse::HasMorePages { state } => match state.as_bytes_slice() {
-            Some(result_paging_state) => unsafe {
-                *paging_state_size = result_paging_state.len() as u64;
-                *paging_state = result_paging_state.as_ptr() as *const c_char;
-            },
+            Some(raw_paging_state) => {
+                let result_paging_state = match &result_from_raw.signed_paging_state_token {
+                    Some(token) => token.as_slice(),
+                    None => &raw_paging_state[..],
+                };
+                unsafe {
+                    *paging_state_size = result_paging_state.len() as u64;
+                    *paging_state = result_paging_state.as_ptr() as *const c_char;
+                }
+            }
             None => unsafe {
                 *paging_state_size = 0;
                 *paging_state = std::ptr::null();
@@ -1168,6 +1307,22 @@ pub unsafe extern "C" fn cass_result_paging_state_token(
     CassError::CASS_OK
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_result_execution_info(
+    result_raw: CassBorrowedSharedPtr<CassResult, CConst>,
+) -> CassBorrowedSharedPtr<CassExecutionInfo, CConst> {
+    let Some(result) = ArcFFI::as_ref(result_raw) else {
+        tracing::error!("Provided null result pointer to cass_result_execution_info!");
+        return RefFFI::null();
+    };
+
+    result
+        .execution_info
+        .as_deref()
+        .map(RefFFI::as_ptr)
+        .unwrap_or(RefFFI::null())
+}
+
 #[cfg(test)]
 mod tests {
     use scylla::cluster::metadata::{CollectionType, ColumnType, NativeType};
@@ -1214,7 +1369,11 @@ mod tests {
         ])));
 
         let raw_rows = DeserializedMetadataAndRawRows::mock_empty();
-        let shared_data = Arc::new(CassRowsResultSharedData { raw_rows, metadata });
+        let shared_data = Arc::new(CassRowsResultSharedData {
+            raw_rows,
+            metadata,
+            numeric_widening: false,
+        });
         let first_row = RowWithSelfBorrowedResultData::first_from_raw_rows_and_metadata(
             Arc::clone(&shared_data),
         )
@@ -1228,6 +1387,9 @@ mod tests {
                 first_row,
             }),
             coordinator: None,
+            execution_info: None,
+            memory_reservation: None,
+            signed_paging_state_token: None,
         }
     }
 
@@ -1332,6 +1494,9 @@ mod tests {
             paging_state_response: PagingStateResponse::NoMorePages,
             kind: CassResultKind::NonRows,
             coordinator: None,
+            execution_info: None,
+            memory_reservation: None,
+            signed_paging_state_token: None,
         }
     }
 
@@ -0,0 +1,156 @@
+use crate::cass_error::CassError;
+use crate::cass_request_priority_types::CassRequestPriority;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// Priority class of a request. Under backpressure (i.e. when the limit
+/// of concurrent requests is reached), requests of higher priority
+/// are admitted before the requests of lower priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum RequestPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl RequestPriority {
+    const COUNT: usize = 3;
+
+    fn queue_index(self) -> usize {
+        match self {
+            RequestPriority::High => 0,
+            RequestPriority::Normal => 1,
+            RequestPriority::Low => 2,
+        }
+    }
+}
+
+impl TryFrom<CassRequestPriority> for RequestPriority {
+    type Error = CassError;
+
+    fn try_from(priority: CassRequestPriority) -> Result<Self, Self::Error> {
+        match priority {
+            CassRequestPriority::CASS_REQUEST_PRIORITY_LOW => Ok(RequestPriority::Low),
+            CassRequestPriority::CASS_REQUEST_PRIORITY_NORMAL => Ok(RequestPriority::Normal),
+            CassRequestPriority::CASS_REQUEST_PRIORITY_HIGH => Ok(RequestPriority::High),
+            _ => Err(CassError::CASS_ERROR_LIB_BAD_PARAMS),
+        }
+    }
+}
+
+struct QueueState {
+    in_flight: usize,
+    // Waiting requests, one FIFO queue per priority class (highest priority first).
+    waiters: [VecDeque<oneshot::Sender<RequestPermit>>; RequestPriority::COUNT],
+}
+
+/// Limits the number of concurrently executed requests of the session.
+/// Requests exceeding the limit wait for their turn, ordered by their priority.
+pub(crate) struct PriorityRequestQueue {
+    max_in_flight: usize,
+    state: Mutex<QueueState>,
+}
+
+/// Permission to execute a request. The slot is released (or handed over
+/// to the next waiting request) on drop.
+pub(crate) struct RequestPermit {
+    queue: Option<Arc<PriorityRequestQueue>>,
+}
+
+impl Drop for RequestPermit {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.release();
+        }
+    }
+}
+
+impl PriorityRequestQueue {
+    pub(crate) fn new(max_in_flight: usize) -> Arc<Self> {
+        Arc::new(PriorityRequestQueue {
+            max_in_flight,
+            state: Mutex::new(QueueState {
+                in_flight: 0,
+                waiters: Default::default(),
+            }),
+        })
+    }
+
+    pub(crate) async fn acquire(self: &Arc<Self>, priority: RequestPriority) -> RequestPermit {
+        let receiver = {
+            let mut state = self.state.lock().unwrap();
+            if state.in_flight < self.max_in_flight {
+                state.in_flight += 1;
+                return RequestPermit {
+                    queue: Some(Arc::clone(self)),
+                };
+            }
+            let (sender, receiver) = oneshot::channel();
+            state.waiters[priority.queue_index()].push_back(sender);
+            receiver
+        };
+
+        // unwrap: The senders are never dropped without sending - they are
+        // either handed a permit, or stay in the queue.
+        receiver.await.unwrap()
+    }
+
+    fn release(self: &Arc<Self>) {
+        let mut state = self.state.lock().unwrap();
+        for queue_index in 0..RequestPriority::COUNT {
+            while let Some(sender) = state.waiters[queue_index].pop_front() {
+                let permit = RequestPermit {
+                    queue: Some(Arc::clone(self)),
+                };
+                match sender.send(permit) {
+                    // The slot has been handed over to the waiting request.
+                    Ok(()) => return,
+                    // The waiting request has been cancelled. Make sure that dropping
+                    // the returned permit doesn't release the slot (we hold the lock).
+                    Err(mut permit) => permit.queue = None,
+                }
+            }
+        }
+        state.in_flight -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    #[tokio::test]
+    async fn test_priority_request_queue_admits_by_priority() {
+        let queue = PriorityRequestQueue::new(1);
+
+        let permit = queue.acquire(RequestPriority::Normal).await;
+
+        let mut low = Box::pin(queue.acquire(RequestPriority::Low));
+        let mut normal = Box::pin(queue.acquire(RequestPriority::Normal));
+        let mut cancelled_high = Box::pin(queue.acquire(RequestPriority::High));
+        let mut high = Box::pin(queue.acquire(RequestPriority::High));
+        assert!((&mut low).now_or_never().is_none());
+        assert!((&mut normal).now_or_never().is_none());
+        assert!((&mut cancelled_high).now_or_never().is_none());
+        assert!((&mut high).now_or_never().is_none());
+        drop(cancelled_high);
+
+        drop(permit);
+        let permit = (&mut high).now_or_never().unwrap();
+        assert!((&mut low).now_or_never().is_none());
+        assert!((&mut normal).now_or_never().is_none());
+
+        drop(permit);
+        let permit = (&mut normal).now_or_never().unwrap();
+        assert!((&mut low).now_or_never().is_none());
+
+        drop(permit);
+        let permit = (&mut low).now_or_never().unwrap();
+
+        drop(permit);
+        assert_eq!(queue.state.lock().unwrap().in_flight, 0);
+    }
+}
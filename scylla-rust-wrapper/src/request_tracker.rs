@@ -0,0 +1,691 @@
+use crate::cass_error::{CassError, ToCassError};
+use crate::execution_info::{CassAttemptInfo, CassExecutionInfo};
+use crate::inet::CassInet;
+use crate::metrics::{HostMetrics, PerHostMetrics, QueueMetrics};
+use crate::types::{cass_uint32_t, cass_uint64_t};
+use scylla::errors::{RequestAttemptError, RequestError};
+use scylla::observability::history::{AttemptId, HistoryListener, RequestId, SpeculativeId};
+use scylla::policies::retry::RetryDecision;
+use scylla::statement::Consistency;
+use std::fmt::Debug;
+use std::net::SocketAddr;
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+pub type CassRequestStartCallback =
+    Option<unsafe extern "C" fn(request_id: cass_uint64_t, data: *mut c_void)>;
+
+pub type CassRequestAttemptStartCallback = Option<
+    unsafe extern "C" fn(
+        request_id: cass_uint64_t,
+        attempt: cass_uint32_t,
+        host: CassInet,
+        data: *mut c_void,
+    ),
+>;
+
+pub type CassRequestAttemptEndCallback = Option<
+    unsafe extern "C" fn(
+        request_id: cass_uint64_t,
+        attempt: cass_uint32_t,
+        host: CassInet,
+        error: CassError,
+        latency_us: cass_uint64_t,
+        data: *mut c_void,
+    ),
+>;
+
+pub type CassRequestEndCallback = Option<
+    unsafe extern "C" fn(
+        request_id: cass_uint64_t,
+        error: CassError,
+        latency_us: cass_uint64_t,
+        coordinator: CassInet,
+        data: *mut c_void,
+    ),
+>;
+
+/// Mirrors `CassRequestTrackerCallbacks` from `cassandra.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct CassRequestTrackerCallbacks {
+    pub on_request_start: CassRequestStartCallback,
+    pub on_attempt_start: CassRequestAttemptStartCallback,
+    pub on_attempt_end: CassRequestAttemptEndCallback,
+    pub on_request_end: CassRequestEndCallback,
+}
+
+/// User-provided set of request observer callbacks.
+pub(crate) struct RequestTracker {
+    callbacks: CassRequestTrackerCallbacks,
+    data: *mut c_void,
+}
+
+// The `data` pointer is owned by the user, who is responsible for making it
+// safe to be accessed from the driver's threads (the same contract as for `CassLogCallback`).
+unsafe impl Send for RequestTracker {}
+unsafe impl Sync for RequestTracker {}
+
+impl Debug for RequestTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestTracker")
+            .field("data", &self.data)
+            .finish_non_exhaustive()
+    }
+}
+
+// Identifiers reported to the user. They are unique within the process,
+// so the user can correlate the callbacks of requests executed concurrently.
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Address reported when the host is unknown (e.g. request failed before any attempt was made).
+fn unknown_host() -> CassInet {
+    CassInet {
+        address: [0; 16],
+        address_length: 0,
+    }
+}
+
+impl RequestTracker {
+    pub(crate) fn new(callbacks: CassRequestTrackerCallbacks, data: *mut c_void) -> Self {
+        RequestTracker { callbacks, data }
+    }
+}
+
+#[derive(Debug)]
+struct AttemptState {
+    host: SocketAddr,
+    start: Instant,
+    consistency: Consistency,
+    // None until the attempt completes.
+    error: Option<CassError>,
+    // Some while the attempt is in flight.
+    host_metrics: Option<Arc<HostMetrics>>,
+}
+
+impl AttemptState {
+    /// Marks the attempt as no longer in flight.
+    /// `latency` is None if attempt did not get a response.
+    fn finish(&mut self, latency: Option<Duration>, error: Option<CassError>) {
+        if let Some(host_metrics) = self.host_metrics.take() {
+            let failed = error.is_some_and(|error| error != CassError::CASS_OK);
+            host_metrics.request_finished(latency, failed);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TrackedRequestState {
+    start: Option<Instant>,
+    // Indexed by `AttemptId`.
+    attempts: Vec<AttemptState>,
+    // Consistency of the next attempt. It may be changed by the retry policy.
+    consistency: Consistency,
+    speculative_execution_used: bool,
+}
+
+impl TrackedRequestState {
+    fn elapsed_us(&self) -> cass_uint64_t {
+        self.start
+            .map(|start| start.elapsed().as_micros() as cass_uint64_t)
+            .unwrap_or(0)
+    }
+}
+
+/// Allows to access the [`TrackedRequest`] from outside of the request's future.
+/// The request is set once the session is resolved.
+pub(crate) type TrackedRequestSlot = Arc<OnceLock<Arc<TrackedRequest>>>;
+
+/// Observes a single execution of a statement. Collects the [`CassExecutionInfo`]
+/// and bridges rust-driver's [`HistoryListener`] to the user-provided [`RequestTracker`] (if any).
+#[derive(Debug)]
+pub(crate) struct TrackedRequest {
+    tracker: Option<Arc<RequestTracker>>,
+    host_metrics: Arc<PerHostMetrics>,
+    queue_metrics: Arc<QueueMetrics>,
+    // True until the first attempt is started.
+    pending: AtomicBool,
+    request_id: u64,
+    state: Mutex<TrackedRequestState>,
+    execution_info: OnceLock<Arc<CassExecutionInfo>>,
+    // Client span of the request (see `cass_statement_set_trace_context`). Disabled if the
+    // request has no trace context.
+    client_span: tracing::Span,
+}
+
+impl TrackedRequest {
+    /// `consistency` is the consistency the request is initially executed with.
+    /// The request is considered pending until its first attempt is started.
+    pub(crate) fn new(
+        tracker: Option<Arc<RequestTracker>>,
+        host_metrics: Arc<PerHostMetrics>,
+        queue_metrics: Arc<QueueMetrics>,
+        consistency: Consistency,
+    ) -> Self {
+        queue_metrics.request_queued();
+        TrackedRequest {
+            tracker,
+            host_metrics,
+            queue_metrics,
+            pending: AtomicBool::new(true),
+            request_id: NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed),
+            state: Mutex::new(TrackedRequestState {
+                start: None,
+                attempts: Vec::new(),
+                consistency,
+                speculative_execution_used: false,
+            }),
+            execution_info: OnceLock::new(),
+            client_span: tracing::Span::none(),
+        }
+    }
+
+    /// Records the request's attempts in the client span, and tags the span with
+    /// the request id reported to the user's request tracker.
+    pub(crate) fn with_client_span(mut self, client_span: tracing::Span) -> Self {
+        client_span.record("request_id", self.request_id);
+        self.client_span = client_span;
+        self
+    }
+
+    /// Returns the information about the execution of the request.
+    ///
+    /// It is computed upon the first call, so it should be called once the request has completed.
+    /// Attempts that were still in flight at that time (e.g. speculative executions that lost the race,
+    /// or attempts cancelled due to the request timeout) are reported with `CASS_ERROR_LIB_REQUEST_TIMED_OUT`.
+    pub(crate) fn execution_info(&self) -> &Arc<CassExecutionInfo> {
+        self.execution_info.get_or_init(|| {
+            let state = self.state.lock().unwrap();
+            let attempts = state
+                .attempts
+                .iter()
+                .map(|attempt| CassAttemptInfo {
+                    host: attempt.host,
+                    error: attempt
+                        .error
+                        .unwrap_or(CassError::CASS_ERROR_LIB_REQUEST_TIMED_OUT),
+                })
+                .collect();
+            let achieved_consistency = state
+                .attempts
+                .iter()
+                .find(|attempt| attempt.error == Some(CassError::CASS_OK))
+                .map(|attempt| attempt.consistency);
+
+            Arc::new(CassExecutionInfo {
+                attempts,
+                achieved_consistency,
+                speculative_execution_used: state.speculative_execution_used,
+            })
+        })
+    }
+
+    /// Marks the attempts that are still in flight (e.g. speculative executions that lost the race,
+    /// or attempts cancelled due to the request timeout) as no longer in flight.
+    pub(crate) fn abandon_in_flight_attempts(&self) {
+        let mut state = self.state.lock().unwrap();
+        for attempt in state.attempts.iter_mut() {
+            attempt.finish(None, None);
+        }
+    }
+
+    /// Marks the request as no longer pending. Returns true if it was pending.
+    fn dequeue(&self) -> bool {
+        let was_pending = self.pending.swap(false, Ordering::Relaxed);
+        if was_pending {
+            self.queue_metrics.request_dequeued();
+        }
+        was_pending
+    }
+
+    /// Called when the request was cancelled due to the request timeout.
+    pub(crate) fn on_timeout(&self) {
+        if self.dequeue() {
+            self.queue_metrics.pending_request_timed_out();
+        }
+        self.abandon_in_flight_attempts();
+    }
+
+    fn on_request_end(&self, error: CassError) {
+        // The request may fail before any attempt is started.
+        self.dequeue();
+        self.abandon_in_flight_attempts();
+
+        let Some(RequestTracker {
+            callbacks:
+                CassRequestTrackerCallbacks {
+                    on_request_end: Some(cb),
+                    ..
+                },
+            data,
+        }) = self.tracker.as_deref()
+        else {
+            return;
+        };
+
+        let (latency_us, coordinator) = {
+            let state = self.state.lock().unwrap();
+            let coordinator = state
+                .attempts
+                .iter()
+                .rev()
+                // Prefer the host that successfully served the request.
+                // If there is no such host, report the host of the last attempt.
+                .find(|attempt| attempt.error == Some(CassError::CASS_OK))
+                .or_else(|| state.attempts.last())
+                .map(|attempt| attempt.host.ip().into())
+                .unwrap_or_else(unknown_host);
+            (state.elapsed_us(), coordinator)
+        };
+
+        unsafe { cb(self.request_id, error, latency_us, coordinator, *data) }
+    }
+
+    fn on_attempt_end(&self, attempt_id: AttemptId, error: CassError) {
+        let (host, latency_us) = {
+            let mut state = self.state.lock().unwrap();
+            let Some(attempt) = state.attempts.get_mut(attempt_id.0) else {
+                tracing::warn!(
+                    "Attempt {} of request {} not found",
+                    attempt_id.0,
+                    self.request_id
+                );
+                return;
+            };
+            let latency = attempt.start.elapsed();
+            attempt.error = Some(error);
+            attempt.finish(Some(latency), Some(error));
+            if !self.client_span.is_disabled() {
+                tracing::debug!(
+                    parent: &self.client_span,
+                    attempt = attempt_id.0,
+                    host = %attempt.host,
+                    error = ?error,
+                    latency_us = latency.as_micros() as u64,
+                    "Request attempt finished"
+                );
+            }
+            (
+                attempt.host.ip().into(),
+                latency.as_micros() as cass_uint64_t,
+            )
+        };
+
+        if let Some(RequestTracker {
+            callbacks:
+                CassRequestTrackerCallbacks {
+                    on_attempt_end: Some(cb),
+                    ..
+                },
+            data,
+        }) = self.tracker.as_deref()
+        {
+            unsafe {
+                cb(
+                    self.request_id,
+                    attempt_id.0 as cass_uint32_t,
+                    host,
+                    error,
+                    latency_us,
+                    *data,
+                )
+            }
+        }
+    }
+}
+
+impl Drop for TrackedRequest {
+    fn drop(&mut self) {
+        self.dequeue();
+        self.abandon_in_flight_attempts();
+    }
+}
+
+impl HistoryListener for TrackedRequest {
+    fn log_request_start(&self) -> RequestId {
+        self.state.lock().unwrap().start = Some(Instant::now());
+
+        if let Some(RequestTracker {
+            callbacks:
+                CassRequestTrackerCallbacks {
+                    on_request_start: Some(cb),
+                    ..
+                },
+            data,
+        }) = self.tracker.as_deref()
+        {
+            unsafe { cb(self.request_id, *data) }
+        }
+
+        RequestId(self.request_id as usize)
+    }
+
+    fn log_request_success(&self, _request_id: RequestId) {
+        self.on_request_end(CassError::CASS_OK);
+    }
+
+    fn log_request_error(&self, _request_id: RequestId, error: &RequestError) {
+        self.on_request_end(error.to_cass_error());
+    }
+
+    fn log_new_speculative_fiber(&self, _request_id: RequestId) -> SpeculativeId {
+        self.state.lock().unwrap().speculative_execution_used = true;
+        SpeculativeId(0)
+    }
+
+    fn log_attempt_start(
+        &self,
+        _request_id: RequestId,
+        _speculative_id: Option<SpeculativeId>,
+        node_addr: SocketAddr,
+    ) -> AttemptId {
+        self.dequeue();
+        let host_metrics = self.host_metrics.host(node_addr.ip());
+        host_metrics.request_started();
+
+        let attempt_id = {
+            let mut state = self.state.lock().unwrap();
+            let consistency = state.consistency;
+            state.attempts.push(AttemptState {
+                host: node_addr,
+                start: Instant::now(),
+                consistency,
+                error: None,
+                host_metrics: Some(host_metrics),
+            });
+            state.attempts.len() - 1
+        };
+        if !self.client_span.is_disabled() {
+            tracing::debug!(
+                parent: &self.client_span,
+                attempt = attempt_id,
+                host = %node_addr,
+                "Request attempt started"
+            );
+        }
+
+        if let Some(RequestTracker {
+            callbacks:
+                CassRequestTrackerCallbacks {
+                    on_attempt_start: Some(cb),
+                    ..
+                },
+            data,
+        }) = self.tracker.as_deref()
+        {
+            unsafe {
+                cb(
+                    self.request_id,
+                    attempt_id as cass_uint32_t,
+                    node_addr.ip().into(),
+                    *data,
+                )
+            }
+        }
+
+        AttemptId(attempt_id)
+    }
+
+    fn log_attempt_success(&self, attempt_id: AttemptId) {
+        self.on_attempt_end(attempt_id, CassError::CASS_OK);
+    }
+
+    fn log_attempt_error(
+        &self,
+        attempt_id: AttemptId,
+        error: &RequestAttemptError,
+        retry_decision: &RetryDecision,
+    ) {
+        // Retry policy may decide to retry with a different (e.g. downgraded) consistency.
+        if let RetryDecision::RetrySameTarget(Some(consistency))
+        | RetryDecision::RetryNextTarget(Some(consistency)) = retry_decision
+        {
+            self.state.lock().unwrap().consistency = *consistency;
+        }
+        // All stream ids of the connection are in use - the driver is being throttled.
+        if let RequestAttemptError::UnableToAllocStreamId = error {
+            self.queue_metrics.request_throttled();
+        }
+        self.on_attempt_end(attempt_id, error.to_cass_error());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::QueueMetricsSnapshot;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[derive(Default)]
+    struct Recorded {
+        started: Vec<u64>,
+        attempts: Vec<(u64, u32, CassError)>,
+        ended: Vec<(u64, CassError, IpAddr)>,
+    }
+
+    unsafe extern "C" fn on_request_start(request_id: cass_uint64_t, data: *mut c_void) {
+        let recorded = unsafe { &mut *(data as *mut Recorded) };
+        recorded.started.push(request_id);
+    }
+
+    unsafe extern "C" fn on_attempt_end(
+        request_id: cass_uint64_t,
+        attempt: cass_uint32_t,
+        _host: CassInet,
+        error: CassError,
+        _latency_us: cass_uint64_t,
+        data: *mut c_void,
+    ) {
+        let recorded = unsafe { &mut *(data as *mut Recorded) };
+        recorded.attempts.push((request_id, attempt, error));
+    }
+
+    unsafe extern "C" fn on_request_end(
+        request_id: cass_uint64_t,
+        error: CassError,
+        _latency_us: cass_uint64_t,
+        coordinator: CassInet,
+        data: *mut c_void,
+    ) {
+        let recorded = unsafe { &mut *(data as *mut Recorded) };
+        recorded
+            .ended
+            .push((request_id, error, coordinator.try_into().unwrap()));
+    }
+
+    #[test]
+    fn test_tracked_request_reports_attempts_and_coordinator() {
+        let mut recorded = Recorded::default();
+        let tracker = Arc::new(RequestTracker::new(
+            CassRequestTrackerCallbacks {
+                on_request_start: Some(on_request_start),
+                // NULL callbacks should be simply skipped.
+                on_attempt_start: None,
+                on_attempt_end: Some(on_attempt_end),
+                on_request_end: Some(on_request_end),
+            },
+            &mut recorded as *mut Recorded as *mut c_void,
+        ));
+
+        let host1 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 9042);
+        let host2 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 9042);
+
+        let request = TrackedRequest::new(
+            Some(Arc::clone(&tracker)),
+            Arc::new(PerHostMetrics::new(None)),
+            Arc::new(QueueMetrics::default()),
+            Consistency::One,
+        );
+        let request_id = request.log_request_start();
+        let attempt1 = request.log_attempt_start(request_id, None, host1);
+        request.log_attempt_error(
+            attempt1,
+            &RequestAttemptError::UnableToAllocStreamId,
+            &RetryDecision::RetryNextTarget(None),
+        );
+        let attempt2 = request.log_attempt_start(request_id, None, host2);
+        request.log_attempt_success(attempt2);
+        request.log_request_success(request_id);
+
+        let id = request.request_id;
+        assert_eq!(recorded.started, vec![id]);
+        assert_eq!(
+            recorded.attempts,
+            vec![
+                (id, 0, CassError::CASS_ERROR_LIB_NO_STREAMS),
+                (id, 1, CassError::CASS_OK)
+            ]
+        );
+        assert_eq!(recorded.ended, vec![(id, CassError::CASS_OK, host2.ip())]);
+
+        // Requests are assigned distinct identifiers.
+        assert_ne!(
+            TrackedRequest::new(
+                None,
+                Arc::new(PerHostMetrics::new(None)),
+                Arc::new(QueueMetrics::default()),
+                Consistency::One
+            )
+            .request_id,
+            id
+        );
+    }
+
+    #[test]
+    fn test_tracked_request_execution_info() {
+        let host1 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 9042);
+        let host2 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 9042);
+        let host_metrics = Arc::new(PerHostMetrics::new(None));
+
+        // Request is retried with downgraded consistency, and a speculative execution is started.
+        let request = TrackedRequest::new(
+            None,
+            Arc::clone(&host_metrics),
+            Arc::new(QueueMetrics::default()),
+            Consistency::Quorum,
+        );
+        let request_id = request.log_request_start();
+        let attempt1 = request.log_attempt_start(request_id, None, host1);
+        request.log_attempt_error(
+            attempt1,
+            &RequestAttemptError::UnableToAllocStreamId,
+            &RetryDecision::RetrySameTarget(Some(Consistency::One)),
+        );
+        let attempt2 = request.log_attempt_start(request_id, None, host1);
+        let speculative_id = request.log_new_speculative_fiber(request_id);
+        // Speculative attempt does not complete before the request does.
+        request.log_attempt_start(request_id, Some(speculative_id), host2);
+        request.log_attempt_success(attempt2);
+        request.log_request_success(request_id);
+
+        assert_eq!(
+            **request.execution_info(),
+            CassExecutionInfo {
+                attempts: vec![
+                    CassAttemptInfo {
+                        host: host1,
+                        error: CassError::CASS_ERROR_LIB_NO_STREAMS,
+                    },
+                    CassAttemptInfo {
+                        host: host1,
+                        error: CassError::CASS_OK,
+                    },
+                    CassAttemptInfo {
+                        host: host2,
+                        error: CassError::CASS_ERROR_LIB_REQUEST_TIMED_OUT,
+                    },
+                ],
+                achieved_consistency: Some(Consistency::One),
+                speculative_execution_used: true,
+            }
+        );
+
+        // Per-host metrics are updated. Attempts cancelled once the request completed are no longer in flight.
+        assert_eq!(
+            host_metrics
+                .snapshot()
+                .iter()
+                .map(|(address, host)| (*address, host.requests, host.errors, host.in_flight))
+                .collect::<Vec<_>>(),
+            vec![(host1.ip(), 2, 1, 0), (host2.ip(), 1, 0, 0)]
+        );
+
+        // Failed request has no achieved consistency.
+        let request = TrackedRequest::new(
+            None,
+            Arc::clone(&host_metrics),
+            Arc::new(QueueMetrics::default()),
+            Consistency::Quorum,
+        );
+        let request_id = request.log_request_start();
+        let attempt = request.log_attempt_start(request_id, None, host1);
+        request.log_attempt_error(
+            attempt,
+            &RequestAttemptError::UnableToAllocStreamId,
+            &RetryDecision::DontRetry,
+        );
+        assert_eq!(
+            **request.execution_info(),
+            CassExecutionInfo {
+                attempts: vec![CassAttemptInfo {
+                    host: host1,
+                    error: CassError::CASS_ERROR_LIB_NO_STREAMS,
+                }],
+                achieved_consistency: None,
+                speculative_execution_used: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_tracked_request_queue_metrics() {
+        let host = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 9042);
+        let host_metrics = Arc::new(PerHostMetrics::new(None));
+        let queue_metrics = Arc::new(QueueMetrics::default());
+        let new_request = || {
+            TrackedRequest::new(
+                None,
+                Arc::clone(&host_metrics),
+                Arc::clone(&queue_metrics),
+                Consistency::One,
+            )
+        };
+
+        // Request is pending until its first attempt is started.
+        let request = new_request();
+        assert_eq!(queue_metrics.snapshot().pending_requests, 1);
+        let request_id = request.log_request_start();
+        let attempt = request.log_attempt_start(request_id, None, host);
+        assert_eq!(queue_metrics.snapshot().pending_requests, 0);
+        request.log_attempt_error(
+            attempt,
+            &RequestAttemptError::UnableToAllocStreamId,
+            &RetryDecision::RetrySameTarget(None),
+        );
+        let attempt = request.log_attempt_start(request_id, None, host);
+        request.log_attempt_success(attempt);
+        request.log_request_success(request_id);
+
+        // Request timed out before being sent.
+        let timed_out = new_request();
+        timed_out.on_timeout();
+        // Request timed out after being sent.
+        let request = new_request();
+        let request_id = request.log_request_start();
+        request.log_attempt_start(request_id, None, host);
+        request.on_timeout();
+        // Request dropped before being sent.
+        drop(new_request());
+
+        assert_eq!(
+            queue_metrics.snapshot(),
+            QueueMetricsSnapshot {
+                pending_requests: 0,
+                throttled_requests: 1,
+                pending_request_timeouts: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use crate::query_result::CassResultMetadata;
+use scylla::errors::DeserializationError;
+use scylla::frame::response::result::DeserializedMetadataAndRawRows;
+use scylla::value::{CqlValue, Row};
+use std::fmt::Write;
+
+/// Renders the rows of the page as a JSON array of objects (one per row, keyed by column names).
+///
+/// The values are mapped similarly to CQL's `SELECT JSON`: numbers are rendered as JSON numbers
+/// (except for varints and decimals, which may not fit in a double), collections and tuples
+/// as arrays, maps and UDTs as objects, and the remaining types as strings.
+/// Blobs are base64-encoded.
+pub(crate) fn render_rows_json(
+    raw_rows: &DeserializedMetadataAndRawRows,
+    metadata: &CassResultMetadata,
+) -> Result<String, DeserializationError> {
+    let mut writer = JsonWriter { out: String::new() };
+    writer.out.push('[');
+    // unwrap: Row always passes the typecheck.
+    for (i, row) in raw_rows.rows_iter::<Row>().unwrap().enumerate() {
+        if i > 0 {
+            writer.out.push(',');
+        }
+        writer.out.push('{');
+        for (j, (column, col_spec)) in row?.columns.iter().zip(&metadata.col_specs).enumerate() {
+            if j > 0 {
+                writer.out.push(',');
+            }
+            writer.string(&col_spec.name);
+            writer.out.push(':');
+            writer.optional_value(column.as_ref());
+        }
+        writer.out.push('}');
+    }
+    writer.out.push(']');
+
+    Ok(writer.out)
+}
+
+struct JsonWriter {
+    out: String,
+}
+
+impl JsonWriter {
+    fn string(&mut self, s: &str) {
+        self.out.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                '\r' => self.out.push_str("\\r"),
+                '\t' => self.out.push_str("\\t"),
+                c if c.is_control() => write!(self.out, "\\u{:04x}", c as u32).unwrap(),
+                c => self.out.push(c),
+            }
+        }
+        self.out.push('"');
+    }
+
+    fn float(&mut self, value: f64) {
+        // JSON has no representation of NaN and infinities.
+        if value.is_finite() {
+            write!(self.out, "{}", value).unwrap();
+        } else {
+            self.string(&value.to_string());
+        }
+    }
+
+    fn array<'a>(&mut self, values: impl IntoIterator<Item = Option<&'a CqlValue>>) {
+        self.out.push('[');
+        for (i, value) in values.into_iter().enumerate() {
+            if i > 0 {
+                self.out.push(',');
+            }
+            self.optional_value(value);
+        }
+        self.out.push(']');
+    }
+
+    fn optional_value(&mut self, value: Option<&CqlValue>) {
+        match value {
+            Some(value) => self.value(value),
+            None => self.out.push_str("null"),
+        }
+    }
+
+    fn value(&mut self, value: &CqlValue) {
+        match value {
+            CqlValue::Empty => self.out.push_str("null"),
+            CqlValue::Boolean(b) => write!(self.out, "{}", b).unwrap(),
+            CqlValue::TinyInt(v) => write!(self.out, "{}", v).unwrap(),
+            CqlValue::SmallInt(v) => write!(self.out, "{}", v).unwrap(),
+            CqlValue::Int(v) => write!(self.out, "{}", v).unwrap(),
+            CqlValue::BigInt(v) => write!(self.out, "{}", v).unwrap(),
+            CqlValue::Counter(counter) => write!(self.out, "{}", counter.0).unwrap(),
+            CqlValue::Float(v) => self.float(*v as f64),
+            CqlValue::Double(v) => self.float(*v),
+            CqlValue::Varint(varint) => {
+                self.string(&signed_be_bytes_to_decimal(
+                    varint.as_signed_bytes_be_slice(),
+                ));
+            }
+            CqlValue::Decimal(decimal) => {
+                let (bytes, scale) = decimal.as_signed_be_bytes_slice_and_exponent();
+                self.string(&apply_scale(signed_be_bytes_to_decimal(bytes), scale));
+            }
+            CqlValue::Ascii(s) | CqlValue::Text(s) => self.string(s),
+            CqlValue::Blob(bytes) => self.string(&base64_encode(bytes)),
+            CqlValue::Uuid(uuid) => self.string(&uuid.to_string()),
+            CqlValue::Timeuuid(uuid) => self.string(&uuid.to_string()),
+            CqlValue::Inet(inet) => self.string(&inet.to_string()),
+            CqlValue::Timestamp(timestamp) => self.string(&format_timestamp(timestamp.0)),
+            CqlValue::Date(date) => {
+                // Dates are stored as the number of days since the epoch, centered at 2^31.
+                let days = date.0 as i64 - (1 << 31);
+                let (year, month, day) = civil_from_days(days);
+                self.string(&format!("{:04}-{:02}-{:02}", year, month, day));
+            }
+            CqlValue::Time(time) => {
+                let nanos = time.0;
+                let seconds = nanos / 1_000_000_000;
+                self.string(&format!(
+                    "{:02}:{:02}:{:02}.{:09}",
+                    seconds / 3600,
+                    seconds / 60 % 60,
+                    seconds % 60,
+                    nanos % 1_000_000_000
+                ));
+            }
+            CqlValue::Duration(duration) => self.string(&format!(
+                "{}mo{}d{}ns",
+                duration.months, duration.days, duration.nanoseconds
+            )),
+            CqlValue::List(values) | CqlValue::Set(values) | CqlValue::Vector(values) => {
+                self.array(values.iter().map(Some))
+            }
+            CqlValue::Tuple(values) => self.array(values.iter().map(Option::as_ref)),
+            CqlValue::Map(entries) => {
+                self.out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push(',');
+                    }
+                    // JSON object keys must be strings - like CQL's `SELECT JSON`,
+                    // the keys of other types are rendered as JSON strings.
+                    let mut key_writer = JsonWriter { out: String::new() };
+                    key_writer.value(key);
+                    if key_writer.out.starts_with('"') {
+                        self.out.push_str(&key_writer.out);
+                    } else {
+                        self.string(&key_writer.out);
+                    }
+                    self.out.push(':');
+                    self.value(value);
+                }
+                self.out.push('}');
+            }
+            CqlValue::UserDefinedType { fields, .. } => {
+                self.out.push('{');
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push(',');
+                    }
+                    self.string(name);
+                    self.out.push(':');
+                    self.optional_value(value.as_ref());
+                }
+                self.out.push('}');
+            }
+            // CqlValue is non_exhaustive.
+            _ => self.out.push_str("null"),
+        }
+    }
+}
+
+/// Converts a two's complement, big-endian integer of arbitrary length to its decimal representation.
+fn signed_be_bytes_to_decimal(bytes: &[u8]) -> String {
+    let negative = bytes.first().is_some_and(|b| b & 0x80 != 0);
+    // Magnitude of the number, big-endian.
+    let mut magnitude: Vec<u8> = if negative {
+        // Two's complement negation: invert the bits and add one.
+        let mut negated: Vec<u8> = bytes.iter().map(|b| !b).collect();
+        for byte in negated.iter_mut().rev() {
+            let (sum, overflow) = byte.overflowing_add(1);
+            *byte = sum;
+            if !overflow {
+                break;
+            }
+        }
+        negated
+    } else {
+        bytes.to_vec()
+    };
+
+    let mut digits = Vec::new();
+    while magnitude.iter().any(|&b| b != 0) {
+        // Divide the magnitude by 10 in place, collecting the remainder.
+        let mut remainder = 0_u32;
+        for byte in magnitude.iter_mut() {
+            let current = (remainder << 8) | *byte as u32;
+            *byte = (current / 10) as u8;
+            remainder = current % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    if negative {
+        digits.push(b'-');
+    }
+    digits.reverse();
+
+    // unwrap: The digits are ASCII.
+    String::from_utf8(digits).unwrap()
+}
+
+/// Applies the scale of a decimal to the decimal representation of its unscaled value.
+fn apply_scale(unscaled: String, scale: i32) -> String {
+    let (sign, digits) = match unscaled.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", unscaled.as_str()),
+    };
+
+    if scale <= 0 {
+        if digits == "0" {
+            return unscaled;
+        }
+        return format!(
+            "{}{}{}",
+            sign,
+            digits,
+            "0".repeat(scale.unsigned_abs() as usize)
+        );
+    }
+
+    let scale = scale as usize;
+    let digits = if digits.len() <= scale {
+        format!("{}{}", "0".repeat(scale - digits.len() + 1), digits)
+    } else {
+        digits.to_owned()
+    };
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+/// Converts the number of days since the epoch to a (year, month, day) date
+/// in the proleptic Gregorian calendar.
+///
+/// See http://howardhinnant.github.io/date_algorithms.html#civil_from_days.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+/// Formats the number of milliseconds since the epoch the same way as CQL's `SELECT JSON`.
+fn format_timestamp(millis: i64) -> String {
+    let days = millis.div_euclid(86_400_000);
+    let millis_of_day = millis.rem_euclid(86_400_000);
+    let (year, month, day) = civil_from_days(days);
+    let seconds_of_day = millis_of_day / 1000;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+        millis_of_day % 1000
+    )
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let indices = [
+            b[0] >> 2,
+            ((b[0] & 0x03) << 4) | (b[1] >> 4),
+            ((b[1] & 0x0f) << 2) | (b[2] >> 6),
+            b[2] & 0x3f,
+        ];
+        for (i, index) in indices.iter().enumerate() {
+            // A chunk of n bytes is encoded with n + 1 characters, and padded.
+            if i <= chunk.len() {
+                out.push(ALPHABET[*index as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scylla::value::{CqlDate, CqlDecimal, CqlTimestamp, CqlVarint};
+
+    fn render(value: &CqlValue) -> String {
+        let mut writer = JsonWriter { out: String::new() };
+        writer.value(value);
+        writer.out
+    }
+
+    #[test]
+    fn test_render_values_json() {
+        assert_eq!(render(&CqlValue::Int(-42)), "-42");
+        assert_eq!(render(&CqlValue::Double(f64::NAN)), "\"NaN\"");
+        assert_eq!(render(&CqlValue::Text("a\"b\n".into())), r#""a\"b\n""#);
+        assert_eq!(render(&CqlValue::Blob(b"foobar".to_vec())), "\"Zm9vYmFy\"");
+        assert_eq!(render(&CqlValue::Blob(b"fooba".to_vec())), "\"Zm9vYmE=\"");
+        assert_eq!(render(&CqlValue::Blob(b"f".to_vec())), "\"Zg==\"");
+        assert_eq!(
+            render(&CqlValue::Varint(CqlVarint::from_signed_bytes_be(vec![
+                0xff, 0x00
+            ]))),
+            "\"-256\""
+        );
+        assert_eq!(
+            render(&CqlValue::Decimal(
+                CqlDecimal::from_signed_be_bytes_and_exponent(vec![0xfb, 0x2e], 3)
+            )),
+            "\"-1.234\""
+        );
+        assert_eq!(
+            render(&CqlValue::Timestamp(CqlTimestamp(1_700_000_000_123))),
+            "\"2023-11-14 22:13:20.123Z\""
+        );
+        assert_eq!(
+            render(&CqlValue::Date(CqlDate((1 << 31) - 1))),
+            "\"1969-12-31\""
+        );
+        assert_eq!(
+            render(&CqlValue::Map(vec![
+                (
+                    CqlValue::Int(1),
+                    CqlValue::List(vec![CqlValue::Boolean(true)])
+                ),
+                (CqlValue::Text("k".into()), CqlValue::Empty),
+            ])),
+            r#"{"1":[true],"k":null}"#
+        );
+        assert_eq!(
+            render(&CqlValue::Tuple(vec![Some(CqlValue::Int(1)), None])),
+            "[1,null]"
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::cass_error::CassError;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Bounds the total size of the result pages buffered by the session,
+/// i.e. held by the `CassResult`s that have not been freed yet.
+///
+/// The limit is soft: the request is rejected only if the limit is already
+/// reached when it is executed, so the page that crosses the limit is still
+/// delivered to the user.
+#[derive(Debug)]
+pub(crate) struct ResultMemoryLimiter {
+    limit: usize,
+    used: AtomicUsize,
+}
+
+/// Bytes of a result page accounted in the limiter. They are released on drop
+/// (i.e. once the result is freed).
+#[derive(Debug)]
+pub(crate) struct ResultMemoryReservation {
+    limiter: Arc<ResultMemoryLimiter>,
+    bytes: usize,
+}
+
+impl Drop for ResultMemoryReservation {
+    fn drop(&mut self) {
+        self.limiter.used.fetch_sub(self.bytes, Ordering::AcqRel);
+    }
+}
+
+impl ResultMemoryLimiter {
+    pub(crate) fn new(limit: usize) -> Arc<Self> {
+        Arc::new(ResultMemoryLimiter {
+            limit,
+            used: AtomicUsize::new(0),
+        })
+    }
+
+    pub(crate) fn used(&self) -> usize {
+        self.used.load(Ordering::Acquire)
+    }
+
+    /// Checks whether another result page can be fetched.
+    pub(crate) fn check(&self) -> Result<(), (CassError, String)> {
+        let used = self.used();
+        if used >= self.limit {
+            return Err((
+                CassError::CASS_ERROR_LIB_RESULT_MEMORY_LIMIT_EXCEEDED,
+                format!(
+                    "Buffered results occupy {} bytes, which exceeds the limit of {} bytes",
+                    used, self.limit
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Accounts the result page of given size.
+    pub(crate) fn reserve(self: &Arc<Self>, bytes: usize) -> ResultMemoryReservation {
+        self.used.fetch_add(bytes, Ordering::AcqRel);
+        ResultMemoryReservation {
+            limiter: Arc::clone(self),
+            bytes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_cass_error_eq;
+
+    #[test]
+    fn test_result_memory_limiter() {
+        let limiter = ResultMemoryLimiter::new(100);
+        assert!(limiter.check().is_ok());
+
+        let first = limiter.reserve(60);
+        assert!(limiter.check().is_ok());
+
+        // The page crossing the limit is still accepted.
+        let second = limiter.reserve(60);
+        assert_eq!(limiter.used(), 120);
+        let (err, _) = limiter.check().unwrap_err();
+        assert_cass_error_eq!(err, CassError::CASS_ERROR_LIB_RESULT_MEMORY_LIMIT_EXCEEDED);
+
+        drop(first);
+        assert_eq!(limiter.used(), 60);
+        assert!(limiter.check().is_ok());
+
+        drop(second);
+        assert_eq!(limiter.used(), 0);
+    }
+}
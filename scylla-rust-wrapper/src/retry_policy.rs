@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use crate::argconv::{ArcFFI, CMut, CassOwnedSharedPtr, FFI, FromArc};
 
+#[derive(Debug)]
 pub enum RetryPolicy {
     DefaultRetryPolicy(Arc<DefaultRetryPolicy>),
     FallthroughRetryPolicy(Arc<FallthroughRetryPolicy>),
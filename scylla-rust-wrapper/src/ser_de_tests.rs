@@ -234,10 +234,11 @@ where
     F: Fn(CassBorrowedSharedPtr<CassValue, CConst>) -> T,
 {
     let bytes = Bytes::from(do_serialize(&to_serialize_non_null, &typ));
-    let data_type = Arc::new(get_column_type(&typ));
+    let data_type = get_column_type(&typ);
     let cass_value = CassValue {
         value: do_deserialize::<CassRawValue>(&typ, &bytes),
         value_type: &data_type,
+        numeric_widening: false,
     };
     let value_ptr = RefFFI::as_ptr(&cass_value);
 
@@ -386,6 +387,93 @@ fn test_deserialize_value_native() {
     );
 }
 
+#[test]
+fn test_deserialize_malformed_collection_lazily() {
+    let typ = ColumnType::Collection {
+        frozen: false,
+        typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Int))),
+    };
+    let data_type = get_column_type(&typ);
+
+    // The collection length is not read until requested, so malformed
+    // collections can still be obtained - reading them fails later.
+    for malformed in [&[0x00, 0x01][..], &[0xff, 0xff, 0xff, 0xff][..]] {
+        let mut cell = (malformed.len() as i32).to_be_bytes().to_vec();
+        cell.extend_from_slice(malformed);
+        let bytes = Bytes::from(cell);
+        let cass_value = CassValue {
+            value: do_deserialize::<CassRawValue>(&typ, &bytes),
+            value_type: &data_type,
+            numeric_widening: false,
+        };
+        let value_ptr = RefFFI::as_ptr(&cass_value);
+
+        unsafe {
+            assert_eq!(cass_value_is_null(value_ptr.borrow()), 0);
+            assert_eq!(cass_value_item_count(value_ptr.borrow()), 0);
+        }
+    }
+}
+
+#[test]
+fn test_numeric_widening() {
+    let get_int64 = |value_ptr: CassBorrowedSharedPtr<CassValue, CConst>| {
+        let mut output: i64 = 0;
+        let err = unsafe { cass_value_get_int64(value_ptr, addr_of_mut!(output)) };
+        (err, output)
+    };
+    let get_double = |value_ptr: CassBorrowedSharedPtr<CassValue, CConst>| {
+        let mut output: f64 = 0.0;
+        let err = unsafe { cass_value_get_double(value_ptr, addr_of_mut!(output)) };
+        (err, output)
+    };
+
+    let int_typ = ColumnType::Native(NativeType::Int);
+    let int_bytes = Bytes::from(do_serialize(424242_i32, &int_typ));
+    let int_data_type = get_column_type(&int_typ);
+    let int_value = |numeric_widening| CassValue {
+        value: do_deserialize::<CassRawValue>(&int_typ, &int_bytes),
+        value_type: &int_data_type,
+        numeric_widening,
+    };
+    let int_value_no_widening = int_value(false);
+    let int_no_widening_ptr = RefFFI::as_ptr(&int_value_no_widening);
+    let int_value = int_value(true);
+    let int_ptr = RefFFI::as_ptr(&int_value);
+
+    let bigint_typ = ColumnType::Native(NativeType::BigInt);
+    let bigint_bytes = Bytes::from(do_serialize(i64::MAX, &bigint_typ));
+    let bigint_data_type = get_column_type(&bigint_typ);
+    let bigint_value = CassValue {
+        value: do_deserialize::<CassRawValue>(&bigint_typ, &bigint_bytes),
+        value_type: &bigint_data_type,
+        numeric_widening: true,
+    };
+    let bigint_ptr = RefFFI::as_ptr(&bigint_value);
+
+    assert_cass_error_eq!(
+        get_int64(int_no_widening_ptr.borrow()).0,
+        CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+    );
+
+    assert_eq!(get_int64(int_ptr.borrow()), (CassError::CASS_OK, 424242));
+    assert_eq!(get_double(int_ptr.borrow()), (CassError::CASS_OK, 424242.0));
+    assert_eq!(
+        get_int64(bigint_ptr.borrow()),
+        (CassError::CASS_OK, i64::MAX)
+    );
+    // Narrowing, or a lossy conversion, is still rejected.
+    let mut int32_output: i32 = 0;
+    assert_cass_error_eq!(
+        unsafe { cass_value_get_int32(bigint_ptr.borrow(), addr_of_mut!(int32_output)) },
+        CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+    );
+    assert_cass_error_eq!(
+        get_double(bigint_ptr.borrow()).0,
+        CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+    );
+}
+
 #[test]
 fn test_deserialize_tuple_iterator() {
     let typ = ColumnType::Tuple(vec![
@@ -395,10 +483,11 @@ fn test_deserialize_tuple_iterator() {
     ]);
     let to_serialize = (42_i32, String::from("Ala ma kota"), 42.42_f32);
     let bytes = Bytes::from(do_serialize(&to_serialize, &typ));
-    let data_type = Arc::new(get_column_type(&typ));
+    let data_type = get_column_type(&typ);
     let cass_value = CassValue {
         value: do_deserialize::<CassRawValue>(&typ, &bytes),
         value_type: &data_type,
+        numeric_widening: false,
     };
     let value_ptr = RefFFI::as_ptr(&cass_value);
 
@@ -448,10 +537,11 @@ fn test_deserialize_list_iterator() {
     };
     let to_serialize = Vec::<i32>::from([42, 4242, 424242, 42424242]);
     let bytes = Bytes::from(do_serialize(&to_serialize, &typ));
-    let data_type = Arc::new(get_column_type(&typ));
+    let data_type = get_column_type(&typ);
     let cass_value = CassValue {
         value: do_deserialize::<CassRawValue>(&typ, &bytes),
         value_type: &data_type,
+        numeric_widening: false,
     };
     let value_ptr = RefFFI::as_ptr(&cass_value);
 
@@ -489,10 +579,11 @@ fn test_deserialize_set_iterator() {
     };
     let to_serialize = HashSet::<i32>::from([42, 4242, 424242, 42424242]);
     let bytes = Bytes::from(do_serialize(&to_serialize, &typ));
-    let data_type = Arc::new(get_column_type(&typ));
+    let data_type = get_column_type(&typ);
     let cass_value = CassValue {
         value: do_deserialize::<CassRawValue>(&typ, &bytes),
         value_type: &data_type,
+        numeric_widening: false,
     };
     let value_ptr = RefFFI::as_ptr(&cass_value);
 
@@ -560,10 +651,11 @@ fn test_deserialize_map_iterator() {
             ),
         ]);
         let bytes = Bytes::from(do_serialize(&to_serialize, &typ));
-        let data_type = Arc::new(get_column_type(&typ));
+        let data_type = get_column_type(&typ);
         let cass_value = CassValue {
             value: do_deserialize::<CassRawValue>(&typ, &bytes),
             value_type: &data_type,
+            numeric_widening: false,
         };
         let value_ptr = RefFFI::as_ptr(&cass_value);
 
@@ -702,10 +794,11 @@ fn test_deserialize_udt_iterator() {
         ("field3", ColumnType::Native(NativeType::Float)),
         ("field4", ColumnType::Native(NativeType::Boolean)), // Extra field
     ]);
-    let data_type = Arc::new(get_column_type(&deserialize_typ));
+    let data_type = get_column_type(&deserialize_typ);
     let cass_value = CassValue {
         value: do_deserialize::<CassRawValue>(&deserialize_typ, &bytes),
         value_type: &data_type,
+        numeric_widening: false,
     };
     let value_ptr = RefFFI::as_ptr(&cass_value);
 
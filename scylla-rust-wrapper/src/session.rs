@@ -1,45 +1,168 @@
 use crate::argconv::*;
-use crate::batch::CassBatch;
+use crate::batch::{CassBatch, SerializedOnceRow};
 use crate::cass_error::*;
-use crate::cass_metrics_types::CassMetrics;
-use crate::cass_types::get_column_type;
-use crate::cluster::CassCluster;
+use crate::cass_metrics_types::{CassMetrics, CassQueueMetrics};
 use crate::cluster::build_session_builder;
+use crate::cluster::{CassCluster, PROTOCOL_VERSION};
+use crate::cluster_events::{ClusterEventListener, ClusterEventWatcher, ClusterSnapshot};
+use crate::contact_points::{ContactPointsReResolver, SrvContactPoints, SrvReconnector};
 use crate::exec_profile::{CassExecProfile, ExecProfileName, PerStatementExecProfile};
 use crate::future::{CassFuture, CassFutureResult, CassResultValue};
-use crate::metadata::create_table_metadata;
-use crate::metadata::{CassKeyspaceMeta, CassMaterializedViewMeta, CassSchemaMeta};
+use crate::inet::CassInet;
+use crate::load_balancing::HostInFlightLimitingPolicy;
+use crate::metadata::{CassSchemaMeta, create_schema_metadata};
+use crate::metrics::{CassHostMetrics, RequestMetrics, SessionMetricsSnapshot};
+use crate::paging_state_token::{PagingStateBinding, PagingStateSigner};
 use crate::prepared::CassPrepared;
-use crate::query_result::{CassResult, CassResultKind, CassResultMetadata};
+use crate::prometheus::render_prometheus;
+use crate::query_result::{CassNode, CassResult, CassResultKind, CassResultMetadata};
+use crate::request_queue::{PriorityRequestQueue, RequestPermit, RequestPriority};
+use crate::request_tracker::{RequestTracker, TrackedRequest, TrackedRequestSlot};
+use crate::result_memory::{ResultMemoryLimiter, ResultMemoryReservation};
 use crate::statement::{BoundStatement, CassStatement, SimpleQueryRowSerializer};
 use crate::types::{cass_uint64_t, size_t};
 use crate::uuid::CassUuid;
-use scylla::client::execution_profile::ExecutionProfileHandle;
+use scylla::client::execution_profile::{ExecutionProfile, ExecutionProfileHandle};
 use scylla::client::session::Session;
 use scylla::client::session_builder::SessionBuilder;
-use scylla::cluster::metadata::ColumnType;
+use scylla::cluster::ClusterState;
 use scylla::errors::ExecutionError;
 use scylla::frame::types::Consistency;
-use scylla::observability::metrics::MetricsError;
 use scylla::policies::host_filter::HostFilter;
-use scylla::response::PagingStateResponse;
 use scylla::response::query_result::QueryResult;
+use scylla::response::{PagingState, PagingStateResponse};
 use scylla::statement::unprepared::Statement;
 use std::collections::HashMap;
 use std::future::Future;
+use std::num::NonZeroUsize;
 use std::ops::Deref;
-use std::os::raw::c_char;
+use std::os::raw::{c_char, c_int};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tracing::Instrument;
+
+/// Settings of the session that are handled by the wrapper (i.e. not passed to rust-driver).
+struct SessionSettings {
+    client_id: uuid::Uuid,
+    cluster_event_listeners: Vec<ClusterEventListener>,
+    request_tracker: Option<Arc<RequestTracker>>,
+    histogram_refresh_interval: Option<Duration>,
+    max_concurrent_requests: Option<NonZeroUsize>,
+    max_result_memory: Option<NonZeroUsize>,
+    max_in_flight_requests_per_host: Option<NonZeroUsize>,
+    contact_points_reresolution_interval: Option<Duration>,
+    numeric_widening: bool,
+    ssl_error: Option<String>,
+    paging_state_signer: Option<Arc<PagingStateSigner>>,
+    page_size: Option<c_int>,
+}
 
 pub struct CassSessionInner {
-    session: Session,
+    // Shared with the handles returned by `cass_session_get_rust_session`.
+    session: Arc<Session>,
     exec_profile_map: HashMap<ExecProfileName, ExecutionProfileHandle>,
+    // Page sizes of the execution profiles that define one (None, if the profile disables paging).
+    exec_profile_page_sizes: HashMap<ExecProfileName, Option<c_int>>,
+    // Page size of the default execution profile. None disables paging.
+    page_size: Option<c_int>,
     client_id: uuid::Uuid,
+    request_tracker: Option<Arc<RequestTracker>>,
+    metrics: RequestMetrics,
+    // Set if the number of concurrent requests is limited.
+    request_queue: Option<Arc<PriorityRequestQueue>>,
+    // Set if the memory occupied by the buffered results is limited.
+    result_memory_limiter: Option<Arc<ResultMemoryLimiter>>,
+    // See `cass_cluster_set_numeric_widening`.
+    numeric_widening: bool,
+    // Set if the paging state tokens are signed.
+    paging_state_signer: Option<Arc<PagingStateSigner>>,
+    // Aborts the background task on drop.
+    _cluster_event_watcher: Option<ClusterEventWatcher>,
+    // Aborts the background task on drop.
+    _contact_points_reresolver: Option<ContactPointsReResolver>,
 }
 
 impl CassSessionInner {
+    pub(crate) fn cluster_state(&self) -> Arc<ClusterState> {
+        self.session.get_cluster_state()
+    }
+
+    pub(crate) fn rust_session(&self) -> Arc<Session> {
+        Arc::clone(&self.session)
+    }
+
+    /// Replaces the Rust session, once it has been reconnected to the nodes discovered anew.
+    /// The handles returned by `cass_session_get_rust_session` keep the previous session.
+    pub(crate) fn replace_rust_session(&mut self, session: Session) {
+        self.session = Arc::new(session);
+    }
+
+    /// Creates a listener observing a single execution of a request.
+    ///
+    /// `consistency` is the consistency set on the statement (if any), and `exec_profile_handle`
+    /// is the handle of the statement's execution profile (if any).
+    fn new_tracked_request(
+        &self,
+        consistency: Option<Consistency>,
+        exec_profile_handle: Option<&ExecutionProfileHandle>,
+    ) -> Arc<TrackedRequest> {
+        // Statement's consistency takes precedence over the one from the execution profile.
+        let consistency = consistency.unwrap_or_else(|| {
+            exec_profile_handle
+                .unwrap_or_else(|| self.session.get_default_execution_profile_handle())
+                .to_profile()
+                .get_consistency()
+        });
+        // Called within the request's future, which is instrumented with the request's
+        // client span (if user provided the trace context).
+        Arc::new(
+            TrackedRequest::new(
+                self.request_tracker.clone(),
+                Arc::clone(self.metrics.hosts()),
+                Arc::clone(self.metrics.queue()),
+                consistency,
+            )
+            .with_client_span(tracing::Span::current()),
+        )
+    }
+
+    /// Waits until the request is allowed to be executed, if the number
+    /// of concurrent requests is limited.
+    async fn admit_request(&self, priority: RequestPriority) -> Option<RequestPermit> {
+        match &self.request_queue {
+            Some(queue) => Some(queue.acquire(priority).await),
+            None => None,
+        }
+    }
+
+    /// Fails if the results buffered by the user already exceed the memory limit.
+    fn check_result_memory(&self) -> Result<(), (CassError, String)> {
+        match &self.result_memory_limiter {
+            Some(limiter) => limiter.check(),
+            None => Ok(()),
+        }
+    }
+
+    /// Accounts the result in the memory limit, if any.
+    fn reserve_result_memory(&self, result: &CassResult) -> Option<ResultMemoryReservation> {
+        self.result_memory_limiter
+            .as_ref()
+            .map(|limiter| limiter.reserve(result.rows_bytes_size()))
+    }
+
+    fn metrics_snapshot(&self) -> SessionMetricsSnapshot {
+        let rust_metrics = self.session.get_metrics();
+        SessionMetricsSnapshot {
+            latencies: self.metrics.latency_snapshot(),
+            rates: self.metrics.rates(),
+            total_connections: rust_metrics.get_total_connections(),
+            connection_timeouts: rust_metrics.get_connection_timeouts(),
+            request_timeouts: rust_metrics.get_request_timeouts(),
+            queue: self.metrics.queue().snapshot(),
+        }
+    }
+
     pub(crate) fn resolve_exec_profile(
         &self,
         name: &ExecProfileName,
@@ -53,11 +176,22 @@ impl CassSessionInner {
         })
     }
 
+    /// Returns the page size of the statement's execution profile, falling back
+    /// to the default profile's one. None, if paging is disabled.
+    fn exec_profile_page_size(
+        &self,
+        exec_profile: Option<&PerStatementExecProfile>,
+    ) -> Option<c_int> {
+        exec_profile
+            .and_then(|profile| self.exec_profile_page_sizes.get(profile.name()).copied())
+            .unwrap_or(self.page_size)
+    }
+
     // Clippy claims it is possible to make this `async fn`, but it's terribly wrong,
     // because async fn can't have its future bound to a specific lifetime, which is
     // required in this case.
     #[allow(clippy::manual_async_fn)]
-    fn get_or_resolve_profile_handle<'a>(
+    pub(crate) fn get_or_resolve_profile_handle<'a>(
         &'a self,
         exec_profile: Option<&'a PerStatementExecProfile>,
     ) -> impl Future<Output = Result<Option<ExecutionProfileHandle>, (CassError, String)>> + 'a
@@ -80,26 +214,47 @@ impl CassSessionInner {
         let session_builder = build_session_builder(cluster);
         let exec_profile_map = cluster.execution_profile_map().clone();
         let host_filter = cluster.build_host_filter();
+        let settings = SessionSettings {
+            client_id: cluster
+                .get_client_id()
+                // If user did not set a client id, generate a random uuid v4.
+                .unwrap_or_else(uuid::Uuid::new_v4),
+            cluster_event_listeners: cluster
+                .get_cluster_event_listener()
+                .into_iter()
+                .chain(cluster.get_host_listener())
+                .collect(),
+            request_tracker: cluster.get_request_tracker().cloned(),
+            histogram_refresh_interval: cluster.get_histogram_refresh_interval(),
+            max_concurrent_requests: cluster.get_max_concurrent_requests(),
+            max_result_memory: cluster.get_max_result_memory(),
+            max_in_flight_requests_per_host: cluster.get_max_in_flight_requests_per_host(),
+            contact_points_reresolution_interval: cluster
+                .get_contact_points_reresolution_interval(),
+            numeric_widening: cluster.get_numeric_widening(),
+            ssl_error: cluster.get_ssl_error().map(ToOwned::to_owned),
+            paging_state_signer: cluster.get_paging_state_signer().cloned(),
+            page_size: cluster.get_page_size(),
+        };
 
         CassFuture::make_raw(Self::connect_fut(
             session_opt,
             session_builder,
             exec_profile_map,
             host_filter,
-            cluster
-                .get_client_id()
-                // If user did not set a client id, generate a random uuid v4.
-                .unwrap_or_else(uuid::Uuid::new_v4),
+            settings,
             keyspace,
         ))
     }
 
     async fn connect_fut(
         session_opt: Arc<RwLock<Option<CassSessionInner>>>,
-        session_builder_fut: impl Future<Output = SessionBuilder>,
+        session_builder_fut: impl Future<
+            Output = Result<(SessionBuilder, Option<SrvContactPoints>), (CassError, String)>,
+        >,
         exec_profile_builder_map: HashMap<ExecProfileName, CassExecProfile>,
         host_filter: Arc<dyn HostFilter>,
-        client_id: uuid::Uuid,
+        settings: SessionSettings,
         keyspace: Option<String>,
     ) -> CassFutureResult {
         // This can sleep for a long time, but only if someone connects/closes session
@@ -112,31 +267,96 @@ impl CassSessionInner {
             ));
         }
 
-        let mut session_builder = session_builder_fut.await;
+        // Never fall back to an unencrypted connection.
+        if let Some(msg) = settings.ssl_error {
+            return Err((CassError::CASS_ERROR_SSL_INVALID_CERT, msg));
+        }
+
+        // Created before the session, as the per-host metrics are used for load balancing.
+        let metrics = RequestMetrics::new(settings.histogram_refresh_interval);
+        let limit_in_flight_requests_per_host =
+            |profile: ExecutionProfile| match settings.max_in_flight_requests_per_host {
+                Some(max) => HostInFlightLimitingPolicy::apply(profile, max, metrics.hosts()),
+                None => profile,
+            };
+
+        let (mut session_builder, srv_contact_points) = session_builder_fut.await?;
+        // Send the client id (also the generated one) in the STARTUP options,
+        // so the connections can be identified server-side (system.clients).
+        session_builder
+            .config
+            .identity
+            .set_client_id(settings.client_id.to_string());
         let default_profile = session_builder
             .config
             .default_execution_profile_handle
             .to_profile();
 
         let mut exec_profile_map = HashMap::with_capacity(exec_profile_builder_map.len());
+        let mut exec_profile_page_sizes = HashMap::new();
         for (name, builder) in exec_profile_builder_map {
-            exec_profile_map.insert(name, builder.build(&default_profile).await.into_handle());
+            if let Some(page_size) = builder.page_size {
+                exec_profile_page_sizes.insert(name.clone(), page_size);
+            }
+            let profile = builder.build(&default_profile).await;
+            exec_profile_map.insert(
+                name,
+                limit_in_flight_requests_per_host(profile).into_handle(),
+            );
         }
+        session_builder = session_builder.default_execution_profile_handle(
+            limit_in_flight_requests_per_host(default_profile).into_handle(),
+        );
 
         if let Some(keyspace) = keyspace {
             session_builder = session_builder.use_keyspace(keyspace, false);
         }
 
+        let session_builder = session_builder.host_filter(host_filter);
         let session = session_builder
-            .host_filter(host_filter)
             .build()
             .await
             .map_err(|err| (err.to_cass_error(), err.msg()))?;
 
+        let cluster_event_watcher = (!settings.cluster_event_listeners.is_empty()).then(|| {
+            ClusterEventWatcher::spawn(
+                Arc::downgrade(&session_opt),
+                ClusterSnapshot::from_cluster_state(&session.get_cluster_state()),
+                settings.cluster_event_listeners,
+            )
+        });
+
+        let contact_points_reresolver =
+            settings
+                .contact_points_reresolution_interval
+                .map(|interval| {
+                    let srv_reconnector = srv_contact_points
+                        .map(|srv| SrvReconnector::new(srv, session_builder.clone()));
+                    ContactPointsReResolver::spawn(
+                        Arc::downgrade(&session_opt),
+                        interval,
+                        srv_reconnector,
+                    )
+                });
+
         *session_guard = Some(CassSessionInner {
-            session,
+            session: Arc::new(session),
             exec_profile_map,
-            client_id,
+            exec_profile_page_sizes,
+            client_id: settings.client_id,
+            request_tracker: settings.request_tracker,
+            metrics,
+            request_queue: settings
+                .max_concurrent_requests
+                .map(|max| PriorityRequestQueue::new(max.get())),
+            result_memory_limiter: settings
+                .max_result_memory
+                .map(|max| ResultMemoryLimiter::new(max.get())),
+            numeric_widening: settings.numeric_widening,
+            paging_state_signer: settings.paging_state_signer,
+            page_size: settings.page_size,
+            _cluster_event_watcher: cluster_event_watcher,
+            _contact_points_reresolver: contact_points_reresolver,
         });
         Ok(CassResultValue::Empty)
     }
@@ -219,9 +439,13 @@ pub unsafe extern "C" fn cass_session_execute_batch(
 
     // DO NOT refer to `batch_from_raw` inside the async block, as I've done just to face a segfault.
     let batch_exec_profile = batch_from_raw.exec_profile.clone();
+    let priority = batch_from_raw.priority;
+    let trace_context = batch_from_raw.trace_context;
     #[allow(unused, clippy::let_unit_value)]
     let batch_from_raw = (); // Hardening shadow to avoid use-after-free.
 
+    let tracked_request_slot = TrackedRequestSlot::default();
+    let tracked_request_slot_clone = Arc::clone(&tracked_request_slot);
     let future = async move {
         let session_guard = session_opt.read().await;
         if session_guard.is_none() {
@@ -238,39 +462,92 @@ pub unsafe extern "C" fn cass_session_execute_batch(
             .get_or_resolve_profile_handle(batch_exec_profile.as_ref())
             .await?;
 
-        Arc::make_mut(&mut state)
-            .batch
-            .set_execution_profile_handle(handle);
-
-        let query_res = session.batch(&state.batch, &state.bound_values).await;
+        let _permit = cass_session_inner.admit_request(priority).await;
+
+        let batch = &mut Arc::make_mut(&mut state).batch;
+        let tracked_request =
+            cass_session_inner.new_tracked_request(batch.get_consistency(), handle.as_ref());
+        let _ = tracked_request_slot_clone.set(Arc::clone(&tracked_request));
+        batch.set_execution_profile_handle(handle);
+        batch.set_history_listener(tracked_request.clone());
+
+        let start = Instant::now();
+        let bound_values: Vec<_> = state
+            .bound_values
+            .iter()
+            .map(SerializedOnceRow::new)
+            .collect();
+        let query_res = session.batch(&state.batch, &bound_values).await;
         match query_res {
-            Ok(result) => Ok(CassResultValue::QueryResult(Arc::new(CassResult {
-                tracing_id: None,
-                paging_state_response: PagingStateResponse::NoMorePages,
-                kind: CassResultKind::NonRows,
-                coordinator: Some(result.request_coordinator().clone()),
-            }))),
+            Ok(result) => {
+                cass_session_inner.metrics.record_request(start.elapsed());
+                Ok(CassResultValue::QueryResult(Arc::new(CassResult {
+                    tracing_id: None,
+                    paging_state_response: PagingStateResponse::NoMorePages,
+                    kind: CassResultKind::NonRows,
+                    coordinator: Some(result.request_coordinator().clone()),
+                    execution_info: Some(Arc::clone(tracked_request.execution_info())),
+                    memory_reservation: None,
+                    signed_paging_state_token: None,
+                })))
+            }
             Err(err) => Ok(CassResultValue::QueryError(Arc::new(err.into()))),
         }
     };
 
+    // Record the client span of the request, if user provided the trace context.
+    let client_span = trace_context
+        .map(|ctx| ctx.client_span(rand::random()))
+        .unwrap_or_else(tracing::Span::none);
+    let future = future.instrument(client_span);
+
     match request_timeout_ms {
         Some(timeout_ms) => {
-            CassFuture::make_raw(async move { request_with_timeout(timeout_ms, future).await })
+            let tracked_request_slot_clone = Arc::clone(&tracked_request_slot);
+            CassFuture::make_raw_tracked(
+                async move {
+                    request_with_timeout(timeout_ms, future, &tracked_request_slot_clone).await
+                },
+                tracked_request_slot,
+            )
+        }
+        None => CassFuture::make_raw_tracked(future, tracked_request_slot),
+    }
+}
+
+/// Statement's page size takes precedence over the one from the execution profile.
+/// Returns whether the paging is enabled.
+fn apply_page_size(
+    statement: &mut BoundStatement,
+    statement_paging_enabled: Option<bool>,
+    exec_profile_page_size: Option<c_int>,
+) -> bool {
+    match (statement_paging_enabled, exec_profile_page_size) {
+        (Some(enabled), _) => enabled,
+        (None, Some(page_size)) => {
+            statement.set_page_size(page_size);
+            true
         }
-        None => CassFuture::make_raw(future),
+        (None, None) => false,
     }
 }
 
 async fn request_with_timeout(
     request_timeout_ms: cass_uint64_t,
     future: impl Future<Output = Result<CassResultValue, (CassError, String)>>,
+    tracked_request: &TrackedRequestSlot,
 ) -> Result<CassResultValue, (CassError, String)> {
     match tokio::time::timeout(Duration::from_millis(request_timeout_ms), future).await {
         Ok(result) => result,
-        Err(_timeout_err) => Ok(CassResultValue::QueryError(Arc::new(
-            ExecutionError::RequestTimeout(Duration::from_millis(request_timeout_ms)).into(),
-        ))),
+        Err(_timeout_err) => {
+            // The request was cancelled - its attempts will never complete.
+            if let Some(tracked_request) = tracked_request.get() {
+                tracked_request.on_timeout();
+            }
+            Ok(CassResultValue::QueryError(Arc::new(
+                ExecutionError::RequestTimeout(Duration::from_millis(request_timeout_ms)).into(),
+            )))
+        }
     }
 }
 
@@ -291,14 +568,19 @@ pub unsafe extern "C" fn cass_session_execute(
     };
 
     let paging_state = statement_opt.paging_state.clone();
+    let paging_state_from_token = statement_opt.paging_state_from_token;
     let paging_enabled = statement_opt.paging_enabled;
     let request_timeout_ms = statement_opt.request_timeout_ms;
 
     let mut statement = statement_opt.statement.clone();
     let statement_exec_profile = statement_opt.exec_profile.clone();
+    let trace_context = statement_opt.trace_context;
+    let priority = statement_opt.priority;
     #[allow(unused, clippy::let_unit_value)]
     let statement_opt = (); // Hardening shadow to avoid use-after-free.
 
+    let tracked_request_slot = TrackedRequestSlot::default();
+    let tracked_request_slot_clone = Arc::clone(&tracked_request_slot);
     let future = async move {
         let session_guard = session_opt.read().await;
         if session_guard.is_none() {
@@ -314,12 +596,72 @@ pub unsafe extern "C" fn cass_session_execute(
             .get_or_resolve_profile_handle(statement_exec_profile.as_ref())
             .await?;
 
-        match &mut statement {
-            BoundStatement::Simple(query) => query.query.set_execution_profile_handle(handle),
-            BoundStatement::Prepared(prepared) => Arc::make_mut(&mut prepared.statement)
-                .statement
-                .set_execution_profile_handle(handle),
+        let paging_enabled = apply_page_size(
+            &mut statement,
+            paging_enabled,
+            cass_session_inner.exec_profile_page_size(statement_exec_profile.as_ref()),
+        );
+
+        // The statement that the paging state tokens are bound to, if the tokens are signed.
+        let paging_state_signing = cass_session_inner
+            .paging_state_signer
+            .as_ref()
+            .map(|signer| {
+                let binding = match &statement {
+                    BoundStatement::Simple(query) => PagingStateBinding::for_query(
+                        &query.query.contents,
+                        session.get_keyspace().as_deref().map(String::as_str),
+                    ),
+                    BoundStatement::Prepared(prepared) => {
+                        let prepared_statement = &prepared.statement.statement;
+                        PagingStateBinding::for_prepared(
+                            prepared_statement.get_id(),
+                            prepared_statement.get_keyspace_name(),
+                        )
+                    }
+                };
+                (signer, binding)
+            });
+        let paging_state = match &paging_state_signing {
+            Some((signer, binding)) if paging_state_from_token => {
+                let token = paging_state.as_bytes_slice().map_or(&[][..], |s| &s[..]);
+                let Some(raw_paging_state) = signer.verify(token, binding) else {
+                    return Err((
+                        CassError::CASS_ERROR_LIB_INVALID_DATA,
+                        "Provided paging state token with invalid signature".msg(),
+                    ));
+                };
+                PagingState::new_from_raw_bytes(raw_paging_state)
+            }
+            _ => paging_state,
+        };
+
+        // Only the queries fetching rows add to the buffered results - other requests
+        // (e.g. writes, schema changes) are not rejected.
+        if statement.fetches_rows() {
+            cass_session_inner.check_result_memory()?;
         }
+        let _permit = cass_session_inner.admit_request(priority).await;
+
+        let tracked_request = match &mut statement {
+            BoundStatement::Simple(query) => {
+                query.apply_server_timeout();
+                let tracked_request = cass_session_inner
+                    .new_tracked_request(query.query.get_consistency(), handle.as_ref());
+                query.query.set_execution_profile_handle(handle);
+                query.query.set_history_listener(tracked_request.clone());
+                tracked_request
+            }
+            BoundStatement::Prepared(prepared) => {
+                let prepared_statement = &mut Arc::make_mut(&mut prepared.statement).statement;
+                let tracked_request = cass_session_inner
+                    .new_tracked_request(prepared_statement.get_consistency(), handle.as_ref());
+                prepared_statement.set_execution_profile_handle(handle);
+                prepared_statement.set_history_listener(tracked_request.clone());
+                tracked_request
+            }
+        };
+        let _ = tracked_request_slot_clone.set(Arc::clone(&tracked_request));
 
         // Creating a type alias here to fix clippy lints.
         // I want this type to be explicit, so future developers can understand
@@ -336,6 +678,7 @@ pub unsafe extern "C" fn cass_session_execute(
             ),
             ExecutionError,
         >;
+        let start = Instant::now();
         let query_res: QueryRes = match statement {
             BoundStatement::Simple(query) => {
                 // We don't store result metadata for Queries - return None.
@@ -395,12 +738,28 @@ pub unsafe extern "C" fn cass_session_execute(
 
         match query_res {
             Ok((result, paging_state_response, maybe_result_metadata)) => {
+                cass_session_inner.metrics.record_request(start.elapsed());
                 match CassResult::from_result_payload(
                     result,
                     paging_state_response,
                     maybe_result_metadata,
+                    cass_session_inner.numeric_widening,
                 ) {
-                    Ok(result) => Ok(CassResultValue::QueryResult(Arc::new(result))),
+                    Ok(mut result) => {
+                        if let (
+                            Some((signer, binding)),
+                            PagingStateResponse::HasMorePages { state },
+                        ) = (&paging_state_signing, &result.paging_state_response)
+                        {
+                            result.signed_paging_state_token = state
+                                .as_bytes_slice()
+                                .map(|raw_paging_state| signer.sign(raw_paging_state, binding));
+                        }
+                        result.execution_info = Some(Arc::clone(tracked_request.execution_info()));
+                        result.memory_reservation =
+                            cass_session_inner.reserve_result_memory(&result);
+                        Ok(CassResultValue::QueryResult(Arc::new(result)))
+                    }
                     Err(e) => Ok(CassResultValue::QueryError(e)),
                 }
             }
@@ -408,11 +767,23 @@ pub unsafe extern "C" fn cass_session_execute(
         }
     };
 
+    // Record the client span of the request, if user provided the trace context.
+    let client_span = trace_context
+        .map(|ctx| ctx.client_span(rand::random()))
+        .unwrap_or_else(tracing::Span::none);
+    let future = future.instrument(client_span);
+
     match request_timeout_ms {
         Some(timeout_ms) => {
-            CassFuture::make_raw(async move { request_with_timeout(timeout_ms, future).await })
+            let tracked_request_slot_clone = Arc::clone(&tracked_request_slot);
+            CassFuture::make_raw_tracked(
+                async move {
+                    request_with_timeout(timeout_ms, future, &tracked_request_slot_clone).await
+                },
+                tracked_request_slot,
+            )
         }
-        None => CassFuture::make_raw(future),
+        None => CassFuture::make_raw_tracked(future, tracked_request_slot),
     }
 }
 
@@ -433,7 +804,7 @@ pub unsafe extern "C" fn cass_session_prepare_from_existing(
     let statement = cass_statement.statement.clone();
 
     CassFuture::make_raw(async move {
-        let query = match &statement {
+        let mut query = match statement {
             BoundStatement::Simple(q) => q,
             BoundStatement::Prepared(ps) => {
                 return Ok(CassResultValue::Prepared(ps.statement.clone()));
@@ -449,7 +820,10 @@ pub unsafe extern "C" fn cass_session_prepare_from_existing(
         }
         let session = &session_guard.as_ref().unwrap().session;
         let prepared = session
-            .prepare(query.query.clone())
+            .prepare({
+                query.apply_server_timeout();
+                query.query
+            })
             .await
             .map_err(|err| (err.to_cass_error(), err.msg()))?;
 
@@ -539,6 +913,35 @@ pub unsafe extern "C" fn cass_session_close(
     })
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_session_check_schema_agreement(
+    session: CassBorrowedSharedPtr<CassSession, CMut>,
+) -> CassOwnedSharedPtr<CassFuture, CMut> {
+    let Some(session_opt) = ArcFFI::cloned_from_ptr(session) else {
+        tracing::error!("Provided null session pointer to cass_session_check_schema_agreement!");
+        return ArcFFI::null();
+    };
+
+    CassFuture::make_raw(async move {
+        let session_guard = session_opt.read().await;
+        let Some(session) = session_guard.as_ref() else {
+            return Err((
+                CassError::CASS_ERROR_LIB_NO_HOSTS_AVAILABLE,
+                "Session is not connected".msg(),
+            ));
+        };
+
+        // Waits at most `max_schema_wait_time`, polling every `schema_agreement_interval`.
+        session
+            .session
+            .await_schema_agreement()
+            .await
+            .map_err(|err| (err.to_cass_error(), err.msg()))?;
+
+        Ok(CassResultValue::Empty)
+    })
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_session_get_client_id(
     session: CassBorrowedSharedPtr<CassSession, CMut>,
@@ -548,8 +951,86 @@ pub unsafe extern "C" fn cass_session_get_client_id(
         return uuid::Uuid::nil().into();
     };
 
-    let client_id: uuid::Uuid = cass_session.blocking_read().as_ref().unwrap().client_id;
-    client_id.into()
+    let session_guard = cass_session.blocking_read();
+    let Some(session) = session_guard.as_ref() else {
+        tracing::warn!("Attempted to get client id before connecting session object");
+        return uuid::Uuid::nil().into();
+    };
+
+    session.client_id.into()
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_session_get_protocol_version(
+    session_raw: CassBorrowedSharedPtr<CassSession, CConst>,
+) -> c_int {
+    let Some(session_lock) = ArcFFI::as_ref(session_raw) else {
+        tracing::error!("Provided null session pointer to cass_session_get_protocol_version!");
+        return 0;
+    };
+
+    if session_lock.blocking_read().is_none() {
+        tracing::warn!("Attempted to get protocol version before connecting session object");
+        return 0;
+    }
+
+    // The protocol version is the same for all connections - it's not negotiated
+    // (i.e. downgraded) by rust-driver.
+    PROTOCOL_VERSION
+}
+
+/// Handle to the rust-driver's [`Session`] underlying a connected [`CassSession`].
+///
+/// It allows the Rust parts of mixed C/Rust codebases to use the features of rust-driver
+/// that are not exposed through the C API, on the same connection pool.
+/// The handle keeps the session alive, even if the [`CassSession`] is closed or freed.
+pub struct CassRustSession(Arc<Session>);
+
+impl FFI for CassRustSession {
+    type Origin = FromBox;
+}
+
+impl CassRustSession {
+    /// Returns the underlying rust-driver session.
+    pub fn session(&self) -> &Arc<Session> {
+        &self.0
+    }
+
+    /// Converts the pointer returned by `cass_session_get_rust_session` to the reference.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be either null, or a pointer returned by `cass_session_get_rust_session`
+    /// which has not been freed yet with `cass_rust_session_free`, and which
+    /// outlives the returned reference.
+    pub unsafe fn from_raw<'a>(ptr: *const CassRustSession) -> Option<&'a CassRustSession> {
+        unsafe { ptr.as_ref() }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_session_get_rust_session(
+    session: CassBorrowedSharedPtr<CassSession, CConst>,
+) -> CassOwnedExclusivePtr<CassRustSession, CMut> {
+    let Some(cass_session) = ArcFFI::as_ref(session) else {
+        tracing::error!("Provided null session pointer to cass_session_get_rust_session!");
+        return BoxFFI::null_mut();
+    };
+
+    let session_guard = cass_session.blocking_read();
+    let Some(session) = session_guard.as_ref() else {
+        tracing::error!("Attempted to get rust session before connecting session object");
+        return BoxFFI::null_mut();
+    };
+
+    BoxFFI::into_ptr(Box::new(CassRustSession(Arc::clone(&session.session))))
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_rust_session_free(
+    rust_session: CassOwnedExclusivePtr<CassRustSession, CMut>,
+) {
+    BoxFFI::free(rust_session);
 }
 
 #[unsafe(no_mangle)]
@@ -557,69 +1038,14 @@ pub unsafe extern "C" fn cass_session_get_schema_meta(
     session: CassBorrowedSharedPtr<CassSession, CConst>,
 ) -> CassOwnedExclusivePtr<CassSchemaMeta, CConst> {
     let cass_session = ArcFFI::as_ref(session).unwrap();
-    let mut keyspaces: HashMap<String, CassKeyspaceMeta> = HashMap::new();
-
-    for (keyspace_name, keyspace) in cass_session
+    let cluster_state = cass_session
         .blocking_read()
         .as_ref()
         .unwrap()
         .session
-        .get_cluster_state()
-        .keyspaces_iter()
-    {
-        let mut user_defined_type_data_type = HashMap::new();
-        let mut tables = HashMap::new();
-        let mut views = HashMap::new();
-
-        for (udt_name, udt) in keyspace.user_defined_types.iter() {
-            user_defined_type_data_type.insert(
-                udt_name.clone(),
-                Arc::new(get_column_type(&ColumnType::UserDefinedType {
-                    definition: Arc::clone(udt),
-                    frozen: false,
-                })),
-            );
-        }
+        .get_cluster_state();
 
-        for (table_name, table_metadata) in &keyspace.tables {
-            let cass_table_meta_arced = Arc::new_cyclic(|weak_cass_table_meta| {
-                let mut cass_table_meta = create_table_metadata(table_name, table_metadata);
-
-                let mut table_views = HashMap::new();
-                for (view_name, view_metadata) in &keyspace.views {
-                    let cass_view_table_meta =
-                        create_table_metadata(view_name, &view_metadata.view_metadata);
-                    let cass_view_meta = CassMaterializedViewMeta {
-                        name: view_name.clone(),
-                        view_metadata: cass_view_table_meta,
-                        base_table: weak_cass_table_meta.clone(),
-                    };
-                    let cass_view_meta_arced = Arc::new(cass_view_meta);
-                    table_views.insert(view_name.clone(), cass_view_meta_arced.clone());
-
-                    views.insert(view_name.clone(), cass_view_meta_arced);
-                }
-
-                cass_table_meta.views = table_views;
-
-                cass_table_meta
-            });
-
-            tables.insert(table_name.clone(), cass_table_meta_arced);
-        }
-
-        keyspaces.insert(
-            keyspace_name.to_owned(),
-            CassKeyspaceMeta {
-                name: keyspace_name.to_owned(),
-                user_defined_type_data_type,
-                tables,
-                views,
-            },
-        );
-    }
-
-    BoxFFI::into_ptr(Box::new(CassSchemaMeta { keyspaces }))
+    BoxFFI::into_ptr(Box::new(create_schema_metadata(&cluster_state)))
 }
 
 #[unsafe(no_mangle)]
@@ -643,68 +1069,177 @@ pub unsafe extern "C" fn cass_session_get_metrics(
         return;
     };
 
-    let rust_metrics = session.session.get_metrics();
-    // TODO (rust-driver): Add Snapshot::default() or Snapshot::empty() with 0-initialized snapshot.
-    let (
-        min,
-        max,
-        mean,
-        stddev,
-        median,
-        percentile_75,
-        percentile_95,
-        percentile_98,
-        percentile_99,
-        percentile_99_9,
-    ) = match rust_metrics.get_snapshot() {
-        Ok(snapshot) => (
-            snapshot.min,
-            snapshot.max,
-            snapshot.mean,
-            snapshot.stddev,
-            snapshot.median,
-            snapshot.percentile_75,
-            snapshot.percentile_95,
-            snapshot.percentile_98,
-            snapshot.percentile_99,
-            snapshot.percentile_99_9,
-        ),
-        // Histogram is empty, but we don't want to return because there
-        // are other metrics that don't depend on histogram.
-        Err(MetricsError::Empty) => (0, 0, 0, 0, 0, 0, 0, 0, 0, 0),
-        Err(e) => {
-            tracing::error!("Failed to get metrics snapshot: {}", e);
-            return;
-        }
-    };
+    let SessionMetricsSnapshot {
+        latencies,
+        rates,
+        total_connections,
+        connection_timeouts,
+        request_timeouts,
+        queue,
+    } = session.metrics_snapshot();
 
-    const MILLIS_TO_MICROS: u64 = 1000;
     // SAFETY: We assume that user provided valid CassMetrics pointer.
     unsafe {
-        (*metrics).requests.min = min * MILLIS_TO_MICROS;
-        (*metrics).requests.max = max * MILLIS_TO_MICROS;
-        (*metrics).requests.mean = mean * MILLIS_TO_MICROS;
-        (*metrics).requests.stddev = stddev * MILLIS_TO_MICROS;
-        (*metrics).requests.median = median * MILLIS_TO_MICROS;
-        (*metrics).requests.percentile_75th = percentile_75 * MILLIS_TO_MICROS;
-        (*metrics).requests.percentile_95th = percentile_95 * MILLIS_TO_MICROS;
-        (*metrics).requests.percentile_98th = percentile_98 * MILLIS_TO_MICROS;
-        (*metrics).requests.percentile_99th = percentile_99 * MILLIS_TO_MICROS;
-        (*metrics).requests.percentile_999th = percentile_99_9 * MILLIS_TO_MICROS;
-        (*metrics).requests.mean_rate = rust_metrics.get_mean_rate();
-        (*metrics).requests.one_minute_rate = rust_metrics.get_one_minute_rate();
-        (*metrics).requests.five_minute_rate = rust_metrics.get_five_minute_rate();
-        (*metrics).requests.fifteen_minute_rate = rust_metrics.get_fifteen_minute_rate();
-
-        (*metrics).stats.total_connections = rust_metrics.get_total_connections();
+        (*metrics).requests.min = latencies.min;
+        (*metrics).requests.max = latencies.max;
+        (*metrics).requests.mean = latencies.mean;
+        (*metrics).requests.stddev = latencies.stddev;
+        (*metrics).requests.median = latencies.median;
+        (*metrics).requests.percentile_75th = latencies.percentile_75th;
+        (*metrics).requests.percentile_95th = latencies.percentile_95th;
+        (*metrics).requests.percentile_98th = latencies.percentile_98th;
+        (*metrics).requests.percentile_99th = latencies.percentile_99th;
+        (*metrics).requests.percentile_999th = latencies.percentile_999th;
+        (*metrics).requests.mean_rate = rates.mean_rate;
+        (*metrics).requests.one_minute_rate = rates.one_minute_rate;
+        (*metrics).requests.five_minute_rate = rates.five_minute_rate;
+        (*metrics).requests.fifteen_minute_rate = rates.fifteen_minute_rate;
+
+        (*metrics).stats.total_connections = total_connections;
         (*metrics).stats.available_connections = 0; // Deprecated
         (*metrics).stats.exceeded_pending_requests_water_mark = 0; // Deprecated
         (*metrics).stats.exceeded_write_bytes_water_mark = 0; // Deprecated
 
-        (*metrics).errors.connection_timeouts = rust_metrics.get_connection_timeouts();
-        (*metrics).errors.pending_request_timeouts = 0; // Deprecated
-        (*metrics).errors.request_timeouts = rust_metrics.get_request_timeouts();
+        (*metrics).errors.connection_timeouts = connection_timeouts;
+        (*metrics).errors.pending_request_timeouts = queue.pending_request_timeouts;
+        (*metrics).errors.request_timeouts = request_timeouts;
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_session_get_queue_metrics(
+    session_raw: CassBorrowedSharedPtr<CassSession, CConst>,
+    output: *mut CassQueueMetrics,
+) {
+    let Some(maybe_session_lock) = ArcFFI::as_ref(session_raw) else {
+        tracing::error!("Provided null session pointer to cass_session_get_queue_metrics!");
+        return;
+    };
+    if output.is_null() {
+        tracing::error!("Provided null metrics pointer to cass_session_get_queue_metrics!");
+        return;
+    }
+
+    let maybe_session_guard = maybe_session_lock.blocking_read();
+    let Some(session) = maybe_session_guard.as_ref() else {
+        tracing::warn!("Attempted to get queue metrics before connecting session object");
+        return;
+    };
+
+    let queue = session.metrics.queue().snapshot();
+
+    // SAFETY: We assume that user provided valid CassQueueMetrics pointer.
+    unsafe {
+        (*output).pending_requests = queue.pending_requests;
+        (*output).throttled_requests = queue.throttled_requests;
+        (*output).pending_request_timeouts = queue.pending_request_timeouts;
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_session_get_host_metrics(
+    session_raw: CassBorrowedSharedPtr<CassSession, CConst>,
+    output: *mut CassHostMetrics,
+    output_count: size_t,
+) -> size_t {
+    let Some(maybe_session_lock) = ArcFFI::as_ref(session_raw) else {
+        tracing::error!("Provided null session pointer to cass_session_get_host_metrics!");
+        return 0;
+    };
+
+    let maybe_session_guard = maybe_session_lock.blocking_read();
+    let Some(session) = maybe_session_guard.as_ref() else {
+        tracing::warn!("Attempted to get host metrics before connecting session object");
+        return 0;
+    };
+
+    let hosts = session.metrics.hosts().snapshot();
+    if !output.is_null() {
+        for (i, (address, snapshot)) in hosts.iter().take(output_count as usize).enumerate() {
+            // SAFETY: We assume that user provided an array of at least `output_count` elements.
+            unsafe { *output.add(i) = CassHostMetrics::new(*address, snapshot) };
+        }
+    }
+
+    hosts.len() as size_t
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_session_get_host_metrics_by_address(
+    session_raw: CassBorrowedSharedPtr<CassSession, CConst>,
+    address: CassInet,
+    output: *mut CassHostMetrics,
+) -> CassError {
+    let Some(maybe_session_lock) = ArcFFI::as_ref(session_raw) else {
+        tracing::error!(
+            "Provided null session pointer to cass_session_get_host_metrics_by_address!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+    if output.is_null() {
+        tracing::error!(
+            "Provided null metrics pointer to cass_session_get_host_metrics_by_address!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
     }
+    let Ok(address) = address.try_into() else {
+        tracing::error!("Provided invalid address to cass_session_get_host_metrics_by_address!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    let maybe_session_guard = maybe_session_lock.blocking_read();
+    let Some(session) = maybe_session_guard.as_ref() else {
+        tracing::warn!("Attempted to get host metrics before connecting session object");
+        return CassError::CASS_ERROR_LIB_NO_HOSTS_AVAILABLE;
+    };
+
+    let Some(snapshot) = session.metrics.hosts().host_snapshot(address) else {
+        return CassError::CASS_ERROR_LIB_HOST_RESOLUTION;
+    };
+
+    // SAFETY: We assume that user provided valid CassHostMetrics pointer.
+    unsafe { *output = CassHostMetrics::new(address, &snapshot) };
+    CassError::CASS_OK
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_session_get_host_metrics_by_node(
+    session_raw: CassBorrowedSharedPtr<CassSession, CConst>,
+    node_raw: CassBorrowedSharedPtr<CassNode, CConst>,
+    output: *mut CassHostMetrics,
+) -> CassError {
+    let Some(node) = RefFFI::as_ref(node_raw) else {
+        tracing::error!("Provided null node pointer to cass_session_get_host_metrics_by_node!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    let address: CassInet = node.node().address.ip().into();
+    unsafe { cass_session_get_host_metrics_by_address(session_raw, address, output) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_metrics_export_prometheus(
+    session_raw: CassBorrowedSharedPtr<CassSession, CConst>,
+    buffer: *mut c_char,
+    buffer_size: size_t,
+) -> size_t {
+    let Some(maybe_session_lock) = ArcFFI::as_ref(session_raw) else {
+        tracing::error!("Provided null session pointer to cass_metrics_export_prometheus!");
+        return 0;
+    };
+
+    let maybe_session_guard = maybe_session_lock.blocking_read();
+    let Some(session) = maybe_session_guard.as_ref() else {
+        tracing::warn!("Attempted to export metrics before connecting session object");
+        return 0;
+    };
+
+    let rendered = render_prometheus(
+        &session.metrics_snapshot(),
+        &session.metrics.hosts().snapshot(),
+    );
+
+    unsafe { write_str_to_c_buffer(&rendered, buffer, buffer_size) }
 }
 
 #[cfg(test)]
@@ -1660,6 +2195,113 @@ mod tests {
         }
     }
 
+    #[test]
+    #[ntest::timeout(5000)]
+    fn check_schema_agreement_on_disconnected_session() {
+        unsafe {
+            let session_raw = cass_session_new();
+            {
+                let cass_future = cass_session_check_schema_agreement(session_raw.borrow());
+                cass_future_wait(cass_future.borrow());
+                assert_cass_error_eq!(
+                    cass_future_error_code(cass_future.borrow()),
+                    CassError::CASS_ERROR_LIB_NO_HOSTS_AVAILABLE
+                );
+                cass_future_free(cass_future);
+            }
+            cass_session_free(session_raw);
+        }
+    }
+
+    #[test]
+    fn client_id_of_disconnected_session() {
+        unsafe {
+            let session_raw = cass_session_new();
+            let client_id: uuid::Uuid = cass_session_get_client_id(session_raw.borrow()).into();
+            assert!(client_id.is_nil());
+            cass_session_free(session_raw);
+        }
+    }
+
+    #[test]
+    fn host_metrics_by_node_rejects_null_node() {
+        unsafe {
+            let session_raw = cass_session_new();
+            let mut metrics = std::mem::MaybeUninit::<CassHostMetrics>::uninit();
+            assert_cass_error_eq!(
+                cass_session_get_host_metrics_by_node(
+                    session_raw.borrow().into_c_const(),
+                    RefFFI::null(),
+                    metrics.as_mut_ptr()
+                ),
+                CassError::CASS_ERROR_LIB_BAD_PARAMS
+            );
+            cass_session_free(session_raw);
+        }
+    }
+
+    #[test]
+    fn statement_page_size_takes_precedence_over_exec_profile_page_size() {
+        use crate::statement::cass_statement_set_paging_size;
+
+        let page_size = |statement: &BoundStatement| match statement {
+            BoundStatement::Simple(query) => query.query.get_page_size(),
+            BoundStatement::Prepared(_) => unreachable!(),
+        };
+
+        unsafe {
+            let mut statement_raw = cass_statement_new(make_c_str!("SELECT * FROM ks.t"), 0);
+
+            // Page size not set on the statement - the profile's one is used.
+            {
+                let statement = BoxFFI::as_mut_ref(statement_raw.borrow_mut()).unwrap();
+                let mut bound_statement = statement.statement.clone();
+                assert!(apply_page_size(
+                    &mut bound_statement,
+                    statement.paging_enabled,
+                    Some(1000)
+                ));
+                assert_eq!(page_size(&bound_statement), 1000);
+                assert!(!apply_page_size(
+                    &mut statement.statement.clone(),
+                    statement.paging_enabled,
+                    None
+                ));
+            }
+
+            assert_cass_error_eq!(
+                cass_statement_set_paging_size(statement_raw.borrow_mut(), 10),
+                CassError::CASS_OK
+            );
+            {
+                let statement = BoxFFI::as_mut_ref(statement_raw.borrow_mut()).unwrap();
+                let mut bound_statement = statement.statement.clone();
+                assert!(apply_page_size(
+                    &mut bound_statement,
+                    statement.paging_enabled,
+                    Some(1000)
+                ));
+                assert_eq!(page_size(&bound_statement), 10);
+            }
+
+            // Paging disabled on the statement.
+            assert_cass_error_eq!(
+                cass_statement_set_paging_size(statement_raw.borrow_mut(), -1),
+                CassError::CASS_OK
+            );
+            {
+                let statement = BoxFFI::as_mut_ref(statement_raw.borrow_mut()).unwrap();
+                assert!(!apply_page_size(
+                    &mut statement.statement.clone(),
+                    statement.paging_enabled,
+                    Some(1000)
+                ));
+            }
+
+            cass_statement_free(statement_raw);
+        }
+    }
+
     rusty_fork_test! {
         #![rusty_fork(timeout_ms = 1000)]
         #[test]
@@ -2,13 +2,14 @@ use crate::argconv::{ArcFFI, CMut, CassBorrowedSharedPtr, CassOwnedSharedPtr, FF
 use crate::cass_error::CassError;
 use crate::types::size_t;
 use libc::{c_int, strlen};
-use openssl::ssl::SslVerifyMode;
+use openssl::ssl::{SslContextBuilder, SslVerifyMode};
 use openssl_sys::{
     BIO, BIO_free_all, BIO_new_mem_buf, EVP_PKEY_free, PEM_read_bio_PrivateKey, PEM_read_bio_X509,
     SSL_CTX, SSL_CTX_add_extra_chain_cert, SSL_CTX_free, SSL_CTX_new, SSL_CTX_set_cert_store,
-    SSL_CTX_set_verify, SSL_CTX_use_PrivateKey, SSL_CTX_use_certificate, TLS_method, X509_STORE,
-    X509_STORE_add_cert, X509_STORE_new, X509_free,
+    SSL_CTX_set_verify, SSL_CTX_up_ref, SSL_CTX_use_PrivateKey, SSL_CTX_use_certificate,
+    TLS_method, X509_STORE, X509_STORE_add_cert, X509_STORE_new, X509_free,
 };
+use scylla::client::session::SessionConfig;
 use std::convert::TryInto;
 use std::os::raw::c_char;
 use std::os::raw::c_void;
@@ -67,6 +68,18 @@ impl Drop for CassSsl {
     }
 }
 
+impl CassSsl {
+    /// Configures the session to use the TLS settings set by the user.
+    pub(crate) fn apply_to(&self, session_config: &mut SessionConfig) -> Result<(), String> {
+        let ssl_context_builder = unsafe { SslContextBuilder::from_ptr(self.ssl_context) };
+        // Reference count is increased as tokio_openssl will try to free `ssl_context` when calling `SSL_free`.
+        unsafe { SSL_CTX_up_ref(self.ssl_context) };
+
+        session_config.tls_context = Some(ssl_context_builder.build().into());
+        Ok(())
+    }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_ssl_free(ssl: CassOwnedSharedPtr<CassSsl, CMut>) {
     ArcFFI::free(ssl);
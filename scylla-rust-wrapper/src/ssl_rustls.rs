@@ -0,0 +1,419 @@
+//! Implementation of the `cass_ssl_*` API backed by rustls.
+//! It's used instead of OpenSSL when the `rustls` feature is enabled.
+
+use crate::argconv::{ArcFFI, CMut, CassBorrowedSharedPtr, CassOwnedSharedPtr, FFI, FromArc};
+use crate::cass_error::CassError;
+use crate::types::size_t;
+use libc::strlen;
+use rustls::client::WebPkiServerVerifier;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{CryptoProvider, verify_tls12_signature, verify_tls13_signature};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{
+    CertificateError, ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+};
+use scylla::client::session::SessionConfig;
+use std::convert::TryInto;
+use std::os::raw::c_char;
+use std::sync::{Arc, Mutex};
+
+pub struct CassSsl {
+    config: Mutex<SslConfig>,
+}
+
+impl FFI for CassSsl {
+    type Origin = FromArc;
+}
+
+pub const CASS_SSL_VERIFY_NONE: i32 = 0x00;
+pub const CASS_SSL_VERIFY_PEER_CERT: i32 = 0x01;
+pub const CASS_SSL_VERIFY_PEER_IDENTITY: i32 = 0x02;
+pub const CASS_SSL_VERIFY_PEER_IDENTITY_DNS: i32 = 0x04;
+
+/// Configuration gathered from the user. The rustls config is built
+/// from it when it's set on the cluster.
+#[derive(Default)]
+struct SslConfig {
+    trusted_certs: Vec<CertificateDer<'static>>,
+    cert_chain: Vec<CertificateDer<'static>>,
+    private_key: Option<PrivateKeyDer<'static>>,
+    verify_flags: i32,
+}
+
+impl SslConfig {
+    fn build(&self) -> Result<ClientConfig, rustls::Error> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+        let verifier = if self.verify_flags == CASS_SSL_VERIFY_NONE {
+            None
+        } else {
+            if self.verify_flags & CASS_SSL_VERIFY_PEER_IDENTITY_DNS != 0 {
+                tracing::warn!(
+                    "The CASS_SSL_VERIFY_PEER_IDENTITY_DNS is not supported, CASS_SSL_VERIFY_PEER_CERT is set in SSL context."
+                );
+            }
+
+            let mut roots = RootCertStore::empty();
+            for cert in &self.trusted_certs {
+                roots.add(cert.clone())?;
+            }
+            let verifier =
+                WebPkiServerVerifier::builder_with_provider(Arc::new(roots), Arc::clone(&provider))
+                    .build()
+                    .map_err(|err| rustls::Error::General(err.to_string()))?;
+            Some(verifier)
+        };
+
+        let config_builder = ClientConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(CassServerCertVerifier {
+                provider,
+                verifier,
+                verify_identity: self.verify_flags & CASS_SSL_VERIFY_PEER_IDENTITY != 0,
+            }));
+
+        match &self.private_key {
+            Some(private_key) => config_builder
+                .with_client_auth_cert(self.cert_chain.clone(), private_key.clone_key()),
+            None => Ok(config_builder.with_no_client_auth()),
+        }
+    }
+}
+
+/// Verifies the server certificate according to the verify flags set by the user.
+///
+/// rustls always verifies the identity of the peer, so the identity errors
+/// are ignored unless CASS_SSL_VERIFY_PEER_IDENTITY is set.
+#[derive(Debug)]
+struct CassServerCertVerifier {
+    provider: Arc<CryptoProvider>,
+    // None if the server certificate is not verified at all.
+    verifier: Option<Arc<WebPkiServerVerifier>>,
+    verify_identity: bool,
+}
+
+impl ServerCertVerifier for CassServerCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let Some(verifier) = &self.verifier else {
+            return Ok(ServerCertVerified::assertion());
+        };
+
+        match verifier.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        ) {
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. },
+            )) if !self.verify_identity => Ok(ServerCertVerified::assertion()),
+            result => result,
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+impl CassSsl {
+    /// Configures the session to use the TLS settings set by the user.
+    /// Returns an error if the TLS configuration can't be built.
+    pub(crate) fn apply_to(&self, session_config: &mut SessionConfig) -> Result<(), String> {
+        let client_config = self
+            .config
+            .lock()
+            .unwrap()
+            .build()
+            .map_err(|err| format!("Failed to build the TLS configuration: {err}"))?;
+        session_config.tls_context = Some(Arc::new(client_config).into());
+        Ok(())
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_ssl_new() -> CassOwnedSharedPtr<CassSsl, CMut> {
+    unsafe { cass_ssl_new_no_lib_init() }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_ssl_new_no_lib_init() -> CassOwnedSharedPtr<CassSsl, CMut> {
+    ArcFFI::into_ptr(Arc::new(CassSsl {
+        config: Mutex::new(SslConfig::default()),
+    }))
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_ssl_free(ssl: CassOwnedSharedPtr<CassSsl, CMut>) {
+    ArcFFI::free(ssl);
+}
+
+/// Parses all the certificates from the PEM string.
+unsafe fn parse_certs(
+    cert: *const c_char,
+    cert_length: size_t,
+) -> Result<Vec<CertificateDer<'static>>, CassError> {
+    let pem = unsafe { std::slice::from_raw_parts(cert as *const u8, cert_length as usize) };
+
+    let certs = CertificateDer::pem_slice_iter(pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| CassError::CASS_ERROR_SSL_INVALID_CERT)?;
+    if certs.is_empty() {
+        return Err(CassError::CASS_ERROR_SSL_INVALID_CERT);
+    }
+
+    Ok(certs)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_ssl_add_trusted_cert(
+    ssl: CassBorrowedSharedPtr<CassSsl, CMut>,
+    cert: *const c_char,
+) -> CassError {
+    if cert.is_null() {
+        return CassError::CASS_ERROR_SSL_INVALID_CERT;
+    }
+
+    unsafe { cass_ssl_add_trusted_cert_n(ssl, cert, strlen(cert).try_into().unwrap()) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_ssl_add_trusted_cert_n(
+    ssl: CassBorrowedSharedPtr<CassSsl, CMut>,
+    cert: *const c_char,
+    cert_length: size_t,
+) -> CassError {
+    let Some(ssl) = ArcFFI::cloned_from_ptr(ssl) else {
+        tracing::error!("Provided null ssl pointer to cass_ssl_add_trusted_cert_n!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    // Same as OpenSSL's PEM_read_bio_X509, only the first certificate is read.
+    let mut certs = match unsafe { parse_certs(cert, cert_length) } {
+        Ok(certs) => certs,
+        Err(err) => return err,
+    };
+    certs.truncate(1);
+
+    ssl.config.lock().unwrap().trusted_certs.extend(certs);
+
+    CassError::CASS_OK
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_ssl_set_verify_flags(
+    ssl: CassBorrowedSharedPtr<CassSsl, CMut>,
+    flags: i32,
+) {
+    let Some(ssl) = ArcFFI::cloned_from_ptr(ssl) else {
+        tracing::error!("Provided null ssl pointer to cass_ssl_set_verify_flags!");
+        return;
+    };
+
+    ssl.config.lock().unwrap().verify_flags = flags;
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_ssl_set_cert(
+    ssl: CassBorrowedSharedPtr<CassSsl, CMut>,
+    cert: *const c_char,
+) -> CassError {
+    if cert.is_null() {
+        return CassError::CASS_ERROR_SSL_INVALID_CERT;
+    }
+
+    unsafe { cass_ssl_set_cert_n(ssl, cert, strlen(cert).try_into().unwrap()) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_ssl_set_cert_n(
+    ssl: CassBorrowedSharedPtr<CassSsl, CMut>,
+    cert: *const c_char,
+    cert_length: size_t,
+) -> CassError {
+    let Some(ssl) = ArcFFI::cloned_from_ptr(ssl) else {
+        tracing::error!("Provided null ssl pointer to cass_ssl_set_cert_n!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    // The first certificate is the client's certificate, the rest is the chain.
+    let certs = match unsafe { parse_certs(cert, cert_length) } {
+        Ok(certs) => certs,
+        Err(err) => return err,
+    };
+
+    ssl.config.lock().unwrap().cert_chain = certs;
+
+    CassError::CASS_OK
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_ssl_set_private_key(
+    ssl: CassBorrowedSharedPtr<CassSsl, CMut>,
+    key: *const c_char,
+    password: *mut c_char,
+) -> CassError {
+    if key.is_null() || password.is_null() {
+        return CassError::CASS_ERROR_SSL_INVALID_PRIVATE_KEY;
+    }
+
+    unsafe {
+        cass_ssl_set_private_key_n(
+            ssl,
+            key,
+            strlen(key).try_into().unwrap(),
+            password,
+            strlen(password).try_into().unwrap(),
+        )
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_ssl_set_private_key_n(
+    ssl: CassBorrowedSharedPtr<CassSsl, CMut>,
+    key: *const c_char,
+    key_length: size_t,
+    _password: *mut c_char,
+    _password_length: size_t,
+) -> CassError {
+    let Some(ssl) = ArcFFI::cloned_from_ptr(ssl) else {
+        tracing::error!("Provided null ssl pointer to cass_ssl_set_private_key_n!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    let pem = unsafe { std::slice::from_raw_parts(key as *const u8, key_length as usize) };
+
+    // rustls does not support encrypted private keys, so the password is ignored.
+    let Ok(private_key) = PrivateKeyDer::from_pem_slice(pem) else {
+        tracing::error!(
+            "Failed to parse the private key. Note that encrypted keys are not supported."
+        );
+        return CassError::CASS_ERROR_SSL_INVALID_PRIVATE_KEY;
+    };
+
+    ssl.config.lock().unwrap().private_key = Some(private_key);
+
+    CassError::CASS_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::argconv::BoxFFI;
+    use crate::cluster::{
+        cass_cluster_free, cass_cluster_new, cass_cluster_set_contact_points, cass_cluster_set_ssl,
+        cass_cluster_validate,
+    };
+    use crate::testing::assert_cass_error_eq;
+
+    #[test]
+    fn test_invalid_cert_and_key() {
+        unsafe {
+            let ssl = cass_ssl_new();
+
+            assert_cass_error_eq!(
+                cass_ssl_add_trusted_cert(ssl.borrow(), c"not a certificate".as_ptr()),
+                CassError::CASS_ERROR_SSL_INVALID_CERT
+            );
+            assert_cass_error_eq!(
+                cass_ssl_set_cert(ssl.borrow(), c"".as_ptr()),
+                CassError::CASS_ERROR_SSL_INVALID_CERT
+            );
+            assert_cass_error_eq!(
+                cass_ssl_set_private_key(
+                    ssl.borrow(),
+                    c"not a key".as_ptr(),
+                    c"".as_ptr() as *mut c_char
+                ),
+                CassError::CASS_ERROR_SSL_INVALID_PRIVATE_KEY
+            );
+
+            // Nothing was set - config without verification and client authentication is built.
+            let mut session_config = SessionConfig::new();
+            ArcFFI::as_ref(ssl.borrow())
+                .unwrap()
+                .apply_to(&mut session_config)
+                .unwrap();
+            assert!(session_config.tls_context.is_some());
+
+            cass_ssl_free(ssl);
+        }
+    }
+
+    #[test]
+    fn test_invalid_config_is_not_ignored() {
+        unsafe {
+            let ssl = cass_ssl_new();
+            cass_ssl_set_verify_flags(ssl.borrow(), CASS_SSL_VERIFY_PEER_CERT);
+            // Valid PEM, but not a valid certificate - the root store rejects it.
+            assert_cass_error_eq!(
+                cass_ssl_add_trusted_cert(
+                    ssl.borrow(),
+                    c"-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n".as_ptr()
+                ),
+                CassError::CASS_OK
+            );
+
+            let mut cluster_raw = cass_cluster_new();
+            cass_cluster_set_contact_points(cluster_raw.borrow_mut(), c"127.0.0.1".as_ptr());
+            cass_cluster_set_ssl(cluster_raw.borrow_mut(), ssl.borrow());
+            assert!(
+                BoxFFI::as_ref(cluster_raw.borrow())
+                    .unwrap()
+                    .get_ssl_error()
+                    .is_some()
+            );
+
+            // The session would fail to connect.
+            let mut message = std::ptr::null();
+            let mut message_len: size_t = 0;
+            assert_cass_error_eq!(
+                cass_cluster_validate(cluster_raw.borrow_mut(), &mut message, &mut message_len),
+                CassError::CASS_ERROR_SSL_INVALID_CERT
+            );
+
+            cass_cluster_free(cluster_raw);
+            cass_ssl_free(ssl);
+        }
+    }
+}
@@ -1,13 +1,17 @@
 use crate::cass_error::CassError;
+use crate::cass_request_priority_types::CassRequestPriority;
 use crate::cass_types::CassConsistency;
 use crate::exec_profile::PerStatementExecProfile;
 use crate::inet::CassInet;
 use crate::prepared::CassPrepared;
 use crate::query_result::{CassNode, CassResult};
+use crate::request_queue::RequestPriority;
 use crate::retry_policy::CassRetryPolicy;
+use crate::trace_context::TraceContext;
 use crate::types::*;
 use crate::value::CassCqlValue;
 use crate::{argconv::*, value};
+use scylla::client::execution_profile::ExecutionProfile;
 use scylla::frame::types::Consistency;
 use scylla::policies::load_balancing::{NodeIdentifier, SingleTargetLoadBalancingPolicy};
 use scylla::response::{PagingState, PagingStateResponse};
@@ -35,6 +39,42 @@ pub enum BoundStatement {
     Prepared(BoundPreparedStatement),
 }
 
+impl BoundStatement {
+    /// Serial consistency the statement is executed with - the one set on the statement
+    /// takes precedence over the one of the execution profile.
+    pub(crate) fn effective_serial_consistency(
+        &self,
+        profile: &ExecutionProfile,
+    ) -> Option<SerialConsistency> {
+        let statement_serial_consistency = match self {
+            BoundStatement::Simple(inner) => inner.query.get_serial_consistency(),
+            BoundStatement::Prepared(inner) => inner.statement.statement.get_serial_consistency(),
+        };
+        statement_serial_consistency.or_else(|| profile.get_serial_consistency())
+    }
+
+    pub(crate) fn set_page_size(&mut self, page_size: c_int) {
+        match self {
+            BoundStatement::Simple(inner) => inner.query.set_page_size(page_size),
+            BoundStatement::Prepared(inner) => Arc::make_mut(&mut inner.statement)
+                .statement
+                .set_page_size(page_size),
+        }
+    }
+
+    /// Whether the statement is a query fetching rows. Writes (including conditional ones),
+    /// schema changes etc. return no rows, or just the outcome of the statement.
+    pub(crate) fn fetches_rows(&self) -> bool {
+        match self {
+            BoundStatement::Simple(inner) => is_select_statement(&inner.query.contents),
+            // Only SELECT statements have the result metadata known upon preparation.
+            BoundStatement::Prepared(inner) => {
+                !inner.statement.result_metadata.col_specs.is_empty()
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct BoundPreparedStatement {
     // Arc is needed, because PreparedStatement is passed by reference to session.execute
@@ -73,8 +113,9 @@ impl BoundPreparedStatement {
         is_case_sensitive: bool,
         value: Option<CassCqlValue>,
     ) -> CassError {
-        let indices: Vec<usize> = self
-            .statement
+        // The same name may refer to multiple parameters - value is bound to all of them.
+        let statement = Arc::clone(&self.statement);
+        let mut indices = statement
             .statement
             .get_variable_col_specs()
             .iter()
@@ -83,30 +124,21 @@ impl BoundPreparedStatement {
                 is_case_sensitive && col.name() == name
                     || !is_case_sensitive && col.name().eq_ignore_ascii_case(name)
             })
-            .map(|(i, _)| i)
-            .collect();
+            .map(|(i, _)| i);
 
-        if indices.is_empty() {
+        let Some(mut index) = indices.next() else {
             return CassError::CASS_ERROR_LIB_NAME_DOES_NOT_EXIST;
-        }
-
-        self.bind_multiple_values_by_name(&indices, value)
-    }
-
-    fn bind_multiple_values_by_name(
-        &mut self,
-        indices: &[usize],
-        value: Option<CassCqlValue>,
-    ) -> CassError {
-        for i in indices {
-            let bind_status = self.bind_cql_value(*i, value.clone());
-
+        };
+        // The value is cloned only if there are more parameters to bind it to.
+        for next_index in indices {
+            let bind_status = self.bind_cql_value(index, value.clone());
             if bind_status != CassError::CASS_OK {
                 return bind_status;
             }
+            index = next_index;
         }
 
-        CassError::CASS_OK
+        self.bind_cql_value(index, value)
     }
 }
 
@@ -115,9 +147,19 @@ pub struct BoundSimpleQuery {
     pub query: Statement,
     pub bound_values: Vec<MaybeUnset<Option<CassCqlValue>>>,
     pub name_to_bound_index: HashMap<String, usize>,
+    // Server-side timeout, sent as the `USING TIMEOUT` clause.
+    pub(crate) server_timeout_ms: Option<cass_uint64_t>,
 }
 
 impl BoundSimpleQuery {
+    /// Appends the `USING TIMEOUT` clause to the query string, if the server-side timeout is set.
+    pub(crate) fn apply_server_timeout(&mut self) {
+        if let Some(timeout_ms) = self.server_timeout_ms.take() {
+            let contents = self.query.contents.trim_end().trim_end_matches(';');
+            self.query.contents = format!("{} USING TIMEOUT {}ms", contents, timeout_ms);
+        }
+    }
+
     fn bind_cql_value(&mut self, index: usize, value: Option<CassCqlValue>) -> CassError {
         match self.bound_values.get_mut(index) {
             Some(v) => {
@@ -214,10 +256,16 @@ impl SerializeRow for SimpleQueryRowSerializer {
 pub struct CassStatement {
     pub statement: BoundStatement,
     pub paging_state: PagingState,
-    pub paging_enabled: bool,
+    // Set if the paging state comes from `cass_statement_set_paging_state_token`. It is then
+    // verified upon execution, if the session signs the paging state tokens.
+    pub(crate) paging_state_from_token: bool,
+    // None if not set on the statement - the page size of the execution profile is used then.
+    pub paging_enabled: Option<bool>,
     pub request_timeout_ms: Option<cass_uint64_t>,
 
     pub(crate) exec_profile: Option<PerStatementExecProfile>,
+    pub(crate) trace_context: Option<TraceContext>,
+    pub(crate) priority: RequestPriority,
 }
 
 impl FFI for CassStatement {
@@ -289,15 +337,20 @@ pub unsafe extern "C" fn cass_statement_new_n(
         query,
         bound_values: vec![Unset; parameter_count as usize],
         name_to_bound_index: HashMap::with_capacity(parameter_count as usize),
+        server_timeout_ms: None,
     };
 
     BoxFFI::into_ptr(Box::new(CassStatement {
         statement: BoundStatement::Simple(simple_query),
         paging_state: PagingState::start(),
-        // Cpp driver disables paging by default.
-        paging_enabled: false,
+        paging_state_from_token: false,
+        // Not set - the page size of the execution profile applies. Unless it's
+        // configured, paging is disabled - as in cpp-driver.
+        paging_enabled: None,
         request_timeout_ms: None,
         exec_profile: None,
+        trace_context: None,
+        priority: RequestPriority::default(),
     }))
 }
 
@@ -344,15 +397,10 @@ pub unsafe extern "C" fn cass_statement_set_paging_size(
 
     if page_size <= 0 {
         // Cpp driver sets the page size flag only for positive page size provided by user.
-        statement.paging_enabled = false;
+        statement.paging_enabled = Some(false);
     } else {
-        statement.paging_enabled = true;
-        match &mut statement.statement {
-            BoundStatement::Simple(inner) => inner.query.set_page_size(page_size),
-            BoundStatement::Prepared(inner) => Arc::make_mut(&mut inner.statement)
-                .statement
-                .set_page_size(page_size),
-        }
+        statement.paging_enabled = Some(true);
+        statement.statement.set_page_size(page_size);
     }
 
     CassError::CASS_OK
@@ -376,6 +424,7 @@ pub unsafe extern "C" fn cass_statement_set_paging_state(
         PagingStateResponse::HasMorePages { state } => statement.paging_state.clone_from(state),
         PagingStateResponse::NoMorePages => statement.paging_state = PagingState::start(),
     }
+    statement.paging_state_from_token = false;
     CassError::CASS_OK
 }
 
@@ -394,13 +443,16 @@ pub unsafe extern "C" fn cass_statement_set_paging_state_token(
 
     if paging_state.is_null() {
         statement_from_raw.paging_state = PagingState::start();
+        statement_from_raw.paging_state_from_token = false;
         return CassError::CASS_ERROR_LIB_NULL_VALUE;
     }
 
     let paging_state_usize: usize = paging_state_size.try_into().unwrap();
     let paging_state_bytes =
         unsafe { slice::from_raw_parts(paging_state as *const u8, paging_state_usize) };
+    // The signature (if any) is verified upon execution - only then the signing key is known.
     statement_from_raw.paging_state = PagingState::new_from_raw_bytes(paging_state_bytes);
+    statement_from_raw.paging_state_from_token = true;
     CassError::CASS_OK
 }
 
@@ -695,6 +747,182 @@ pub unsafe extern "C" fn cass_statement_set_request_timeout(
     CassError::CASS_OK
 }
 
+// `USING TIMEOUT` can be simply appended to the query string only in case of SELECT statements.
+// Other statements have it placed in the middle of the query (e.g. UPDATE ... USING TIMEOUT ... SET).
+fn is_select_statement(query: &str) -> bool {
+    query
+        .trim_start()
+        .get(..6)
+        .is_some_and(|keyword| keyword.eq_ignore_ascii_case("select"))
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_statement_set_server_timeout(
+    statement: CassBorrowedExclusivePtr<CassStatement, CMut>,
+    timeout_ms: cass_uint64_t,
+) -> CassError {
+    let Some(statement_from_raw) = BoxFFI::as_mut_ref(statement) else {
+        tracing::error!("Provided null statement pointer to cass_statement_set_server_timeout!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    let BoundStatement::Simple(query) = &mut statement_from_raw.statement else {
+        tracing::error!(
+            "Server-side timeout of prepared statements must be a part of the prepared query (USING TIMEOUT)!"
+        );
+        return CassError::CASS_ERROR_LIB_INVALID_STATEMENT_TYPE;
+    };
+
+    if !is_select_statement(&query.query.contents) {
+        tracing::error!(
+            "Server-side timeout can be set only on SELECT statements. \
+            Other statements must include the USING TIMEOUT clause in the query string."
+        );
+        return CassError::CASS_ERROR_LIB_INVALID_STATEMENT_TYPE;
+    }
+
+    // 0 means no server-side timeout.
+    query.server_timeout_ms = (timeout_ms != 0).then_some(timeout_ms);
+
+    CassError::CASS_OK
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_statement_set_priority(
+    statement: CassBorrowedExclusivePtr<CassStatement, CMut>,
+    priority: CassRequestPriority,
+) -> CassError {
+    let Some(statement_from_raw) = BoxFFI::as_mut_ref(statement) else {
+        tracing::error!("Provided null statement pointer to cass_statement_set_priority!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    match RequestPriority::try_from(priority) {
+        Ok(priority) => {
+            statement_from_raw.priority = priority;
+            CassError::CASS_OK
+        }
+        Err(e) => e,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_statement_set_trace_context(
+    statement: CassBorrowedExclusivePtr<CassStatement, CMut>,
+    header: *const c_char,
+) -> CassError {
+    unsafe { cass_statement_set_trace_context_n(statement, header, strlen(header)) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_statement_set_trace_context_n(
+    statement: CassBorrowedExclusivePtr<CassStatement, CMut>,
+    header: *const c_char,
+    header_length: size_t,
+) -> CassError {
+    let Some(statement) = BoxFFI::as_mut_ref(statement) else {
+        tracing::error!("Provided null statement pointer to cass_statement_set_trace_context_n!");
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    match unsafe { TraceContext::from_header_n(header, header_length) } {
+        Ok(trace_context) => {
+            statement.trace_context = trace_context;
+            CassError::CASS_OK
+        }
+        Err(e) => e,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_statement_get_consistency(
+    statement_raw: CassBorrowedSharedPtr<CassStatement, CConst>,
+) -> CassConsistency {
+    let Some(statement) = BoxFFI::as_ref(statement_raw) else {
+        tracing::error!("Provided null statement pointer to cass_statement_get_consistency!");
+        return CassConsistency::CASS_CONSISTENCY_UNKNOWN;
+    };
+
+    let consistency = match &statement.statement {
+        BoundStatement::Simple(inner) => inner.query.get_consistency(),
+        BoundStatement::Prepared(inner) => inner.statement.statement.get_consistency(),
+    };
+
+    consistency
+        .map(CassConsistency::from)
+        .unwrap_or(CassConsistency::CASS_CONSISTENCY_UNKNOWN)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_statement_get_serial_consistency(
+    statement_raw: CassBorrowedSharedPtr<CassStatement, CConst>,
+) -> CassConsistency {
+    let Some(statement) = BoxFFI::as_ref(statement_raw) else {
+        tracing::error!(
+            "Provided null statement pointer to cass_statement_get_serial_consistency!"
+        );
+        return CassConsistency::CASS_CONSISTENCY_UNKNOWN;
+    };
+
+    let serial_consistency = match &statement.statement {
+        BoundStatement::Simple(inner) => inner.query.get_serial_consistency(),
+        BoundStatement::Prepared(inner) => inner.statement.statement.get_serial_consistency(),
+    };
+
+    serial_consistency
+        .map(CassConsistency::from)
+        .unwrap_or(CassConsistency::CASS_CONSISTENCY_UNKNOWN)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_statement_get_paging_size(
+    statement_raw: CassBorrowedSharedPtr<CassStatement, CConst>,
+) -> c_int {
+    let Some(statement) = BoxFFI::as_ref(statement_raw) else {
+        tracing::error!("Provided null statement pointer to cass_statement_get_paging_size!");
+        return -1;
+    };
+
+    if statement.paging_enabled != Some(true) {
+        return -1;
+    }
+
+    match &statement.statement {
+        BoundStatement::Simple(inner) => inner.query.get_page_size(),
+        BoundStatement::Prepared(inner) => inner.statement.statement.get_page_size(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_statement_get_request_timeout(
+    statement_raw: CassBorrowedSharedPtr<CassStatement, CConst>,
+) -> cass_uint64_t {
+    let Some(statement) = BoxFFI::as_ref(statement_raw) else {
+        tracing::error!("Provided null statement pointer to cass_statement_get_request_timeout!");
+        return cass_uint64_t::MAX;
+    };
+
+    // CASS_UINT64_MAX means that the cluster-level request timeout is used.
+    statement.request_timeout_ms.unwrap_or(cass_uint64_t::MAX)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_statement_get_is_idempotent(
+    statement_raw: CassBorrowedSharedPtr<CassStatement, CConst>,
+) -> cass_bool_t {
+    let Some(statement) = BoxFFI::as_ref(statement_raw) else {
+        tracing::error!("Provided null statement pointer to cass_statement_get_is_idempotent!");
+        return cass_false;
+    };
+
+    let is_idempotent = match &statement.statement {
+        BoundStatement::Simple(inner) => inner.query.get_is_idempotent(),
+        BoundStatement::Prepared(inner) => inner.statement.statement.get_is_idempotent(),
+    };
+
+    is_idempotent as cass_bool_t
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn cass_statement_reset_parameters(
     statement_raw: CassBorrowedExclusivePtr<CassStatement, CMut>,
@@ -782,6 +1010,12 @@ make_binders!(
     cass_statement_bind_bytes_by_name,
     cass_statement_bind_bytes_by_name_n
 );
+make_binders!(
+    serialized,
+    cass_statement_bind_serialized,
+    cass_statement_bind_serialized_by_name,
+    cass_statement_bind_serialized_by_name_n
+);
 make_binders!(
     uuid,
     cass_statement_bind_uuid,
@@ -833,11 +1067,19 @@ mod tests {
 
     use crate::argconv::{BoxFFI, RefFFI};
     use crate::cass_error::CassError;
+    use crate::cass_types::CassConsistency;
     use crate::inet::CassInet;
     use crate::statement::{
-        cass_statement_set_host, cass_statement_set_host_inet, cass_statement_set_node,
+        BoundStatement, cass_statement_get_consistency, cass_statement_get_is_idempotent,
+        cass_statement_get_paging_size, cass_statement_get_request_timeout,
+        cass_statement_get_serial_consistency, cass_statement_set_consistency,
+        cass_statement_set_host, cass_statement_set_host_inet, cass_statement_set_is_idempotent,
+        cass_statement_set_node, cass_statement_set_paging_size,
+        cass_statement_set_request_timeout, cass_statement_set_serial_consistency,
+        cass_statement_set_server_timeout,
     };
     use crate::testing::assert_cass_error_eq;
+    use crate::types::{cass_false, cass_true, cass_uint64_t};
 
     use super::{cass_statement_free, cass_statement_new};
 
@@ -978,4 +1220,130 @@ mod tests {
             cass_statement_free(statement_raw);
         }
     }
+
+    #[test]
+    fn test_statement_fetches_rows() {
+        unsafe {
+            for (query, fetches_rows) in [
+                (c" select * from ks.t", true),
+                (c"INSERT INTO ks.t (a) VALUES (1) IF NOT EXISTS", false),
+                (c"CREATE TABLE ks.t (a int PRIMARY KEY)", false),
+            ] {
+                let statement_raw = cass_statement_new(query.as_ptr(), 0);
+                let statement = BoxFFI::as_ref(statement_raw.borrow()).unwrap();
+                assert_eq!(statement.statement.fetches_rows(), fetches_rows);
+                cass_statement_free(statement_raw);
+            }
+        }
+    }
+
+    #[test]
+    fn test_statement_set_server_timeout() {
+        unsafe {
+            let mut select_raw = cass_statement_new(c" select * from ks.t;".as_ptr(), 0);
+            let mut insert_raw = cass_statement_new(c"INSERT INTO ks.t (a) VALUES (1)".as_ptr(), 0);
+
+            assert_cass_error_eq!(
+                CassError::CASS_ERROR_LIB_INVALID_STATEMENT_TYPE,
+                cass_statement_set_server_timeout(insert_raw.borrow_mut(), 500)
+            );
+            assert_cass_error_eq!(
+                CassError::CASS_OK,
+                cass_statement_set_server_timeout(select_raw.borrow_mut(), 500)
+            );
+
+            let statement = BoxFFI::as_mut_ref(select_raw.borrow_mut()).unwrap();
+            let BoundStatement::Simple(query) = &mut statement.statement else {
+                panic!("Expected simple statement");
+            };
+            let mut query = query.clone();
+            query.apply_server_timeout();
+            assert_eq!(
+                query.query.contents,
+                " select * from ks.t USING TIMEOUT 500ms"
+            );
+            // Applying the timeout again doesn't duplicate the clause.
+            query.apply_server_timeout();
+            assert_eq!(
+                query.query.contents,
+                " select * from ks.t USING TIMEOUT 500ms"
+            );
+
+            cass_statement_free(select_raw);
+            cass_statement_free(insert_raw);
+        }
+    }
+
+    #[test]
+    fn test_statement_getters() {
+        unsafe {
+            let mut statement_raw = cass_statement_new(c"SELECT * FROM ks.t".as_ptr(), 0);
+
+            // Defaults
+            assert_eq!(
+                cass_statement_get_consistency(statement_raw.borrow()),
+                CassConsistency::CASS_CONSISTENCY_UNKNOWN
+            );
+            assert_eq!(
+                cass_statement_get_serial_consistency(statement_raw.borrow()),
+                CassConsistency::CASS_CONSISTENCY_UNKNOWN
+            );
+            assert_eq!(cass_statement_get_paging_size(statement_raw.borrow()), -1);
+            assert_eq!(
+                cass_statement_get_request_timeout(statement_raw.borrow()),
+                cass_uint64_t::MAX
+            );
+            assert_eq!(
+                cass_statement_get_is_idempotent(statement_raw.borrow()),
+                cass_false
+            );
+
+            assert_cass_error_eq!(
+                CassError::CASS_OK,
+                cass_statement_set_consistency(
+                    statement_raw.borrow_mut(),
+                    CassConsistency::CASS_CONSISTENCY_LOCAL_QUORUM
+                )
+            );
+            assert_cass_error_eq!(
+                CassError::CASS_OK,
+                cass_statement_set_serial_consistency(
+                    statement_raw.borrow_mut(),
+                    CassConsistency::CASS_CONSISTENCY_LOCAL_SERIAL
+                )
+            );
+            assert_cass_error_eq!(
+                CassError::CASS_OK,
+                cass_statement_set_paging_size(statement_raw.borrow_mut(), 100)
+            );
+            assert_cass_error_eq!(
+                CassError::CASS_OK,
+                cass_statement_set_request_timeout(statement_raw.borrow_mut(), 1000)
+            );
+            assert_cass_error_eq!(
+                CassError::CASS_OK,
+                cass_statement_set_is_idempotent(statement_raw.borrow_mut(), cass_true)
+            );
+
+            assert_eq!(
+                cass_statement_get_consistency(statement_raw.borrow()),
+                CassConsistency::CASS_CONSISTENCY_LOCAL_QUORUM
+            );
+            assert_eq!(
+                cass_statement_get_serial_consistency(statement_raw.borrow()),
+                CassConsistency::CASS_CONSISTENCY_LOCAL_SERIAL
+            );
+            assert_eq!(cass_statement_get_paging_size(statement_raw.borrow()), 100);
+            assert_eq!(
+                cass_statement_get_request_timeout(statement_raw.borrow()),
+                1000
+            );
+            assert_eq!(
+                cass_statement_get_is_idempotent(statement_raw.borrow()),
+                cass_true
+            );
+
+            cass_statement_free(statement_raw);
+        }
+    }
 }
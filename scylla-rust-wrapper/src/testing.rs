@@ -1,3 +1,16 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use scylla::client::session_builder::SessionBuilder;
+use scylla::cluster::{ClusterState, NodeRef};
+use scylla::policies::load_balancing::{FallbackPlan, LoadBalancingPolicy, RoutingInfo};
+use scylla::routing::Shard;
+use scylla_proxy::{
+    Condition, Node, Proxy, Reaction, RequestFrame, RequestOpcode, RequestReaction, RequestRule,
+    ResponseFrame,
+};
+
 pub(crate) fn setup_tracing() {
     let _ = tracing_subscriber::fmt::fmt()
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
@@ -33,3 +46,93 @@ macro_rules! assert_cass_future_error_message_eq {
     };
 }
 pub(crate) use assert_cass_future_error_message_eq;
+
+/// Runs the test with the cluster state of a session connected to `node_count` proxy nodes.
+/// The nodes accept the connections, but fail all the queries (including the metadata fetch),
+/// so the session falls back to the nodes known from the contact points.
+pub(crate) async fn with_proxy_cluster_state(node_count: usize, test: impl FnOnce(&ClusterState)) {
+    let rules = || {
+        vec![
+            RequestRule(
+                Condition::RequestOpcode(RequestOpcode::Options),
+                RequestReaction::forge_response(Arc::new(move |frame: RequestFrame| {
+                    ResponseFrame::forged_supported(frame.params, &HashMap::new()).unwrap()
+                })),
+            ),
+            RequestRule(
+                Condition::RequestOpcode(RequestOpcode::Startup)
+                    .or(Condition::RequestOpcode(RequestOpcode::Register)),
+                RequestReaction::forge_response(Arc::new(move |frame: RequestFrame| {
+                    ResponseFrame::forged_ready(frame.params)
+                })),
+            ),
+            RequestRule(
+                Condition::RequestOpcode(RequestOpcode::Query),
+                RequestReaction::forge().server_error(),
+            ),
+        ]
+    };
+
+    let proxy_addrs: Vec<_> = (0..node_count)
+        .map(|_| SocketAddr::new(scylla_proxy::get_exclusive_local_address(), 9042))
+        .collect();
+    let proxy = proxy_addrs
+        .iter()
+        .fold(Proxy::builder(), |builder, &proxy_addr| {
+            builder.with_node(
+                Node::builder()
+                    .proxy_address(proxy_addr)
+                    .request_rules(rules())
+                    .build_dry_mode(),
+            )
+        })
+        .build()
+        .run()
+        .await
+        .unwrap();
+
+    let session = SessionBuilder::new()
+        .known_nodes_addr(proxy_addrs)
+        .build()
+        .await
+        .unwrap();
+    test(&session.get_cluster_state());
+
+    drop(session);
+    let _ = proxy.finish().await;
+}
+
+/// Load balancing policy returning the given nodes (indexes into the nodes of the cluster)
+/// with the given shards.
+#[derive(Debug)]
+pub(crate) struct MockLoadBalancingPolicy {
+    pub(crate) picked: Option<(usize, Option<Shard>)>,
+    pub(crate) fallback: Vec<(usize, Option<Shard>)>,
+}
+
+impl LoadBalancingPolicy for MockLoadBalancingPolicy {
+    fn pick<'a>(
+        &'a self,
+        _request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> Option<(NodeRef<'a>, Option<Shard>)> {
+        self.picked
+            .map(|(index, shard)| (&cluster.get_nodes_info()[index], shard))
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        _request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> FallbackPlan<'a> {
+        Box::new(
+            self.fallback
+                .iter()
+                .map(|&(index, shard)| (&cluster.get_nodes_info()[index], shard)),
+        )
+    }
+
+    fn name(&self) -> String {
+        "MockLoadBalancingPolicy".to_owned()
+    }
+}
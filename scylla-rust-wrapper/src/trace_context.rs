@@ -0,0 +1,220 @@
+use crate::argconv::ptr_to_cstr_n;
+use crate::cass_error::CassError;
+use crate::types::size_t;
+use std::fmt::Write;
+use std::os::raw::c_char;
+
+/// Distributed tracing context of the application's span, which the request
+/// is executed within.
+///
+/// Can be parsed from either W3C `traceparent` header or B3 single header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TraceContext {
+    pub(crate) trace_id: u128,
+    pub(crate) parent_span_id: u64,
+    pub(crate) sampled: bool,
+}
+
+fn parse_hex_u128(s: &str, len: usize) -> Option<u128> {
+    (s.len() == len && s.bytes().all(|b| b.is_ascii_hexdigit()))
+        .then(|| u128::from_str_radix(s, 16).ok())
+        .flatten()
+}
+
+fn parse_hex_u64(s: &str) -> Option<u64> {
+    parse_hex_u128(s, 16).map(|v| v as u64)
+}
+
+impl TraceContext {
+    /// Parses W3C `traceparent` header: `{version}-{trace-id}-{parent-id}-{trace-flags}`.
+    ///
+    /// See https://www.w3.org/TR/trace-context/#traceparent-header.
+    fn parse_traceparent(header: &str) -> Option<Self> {
+        let mut parts = header.split('-');
+        let version = parts.next()?;
+        let trace_id = parse_hex_u128(parts.next()?, 32)?;
+        let parent_span_id = parse_hex_u64(parts.next()?)?;
+        let flags = parse_hex_u128(parts.next()?, 2)? as u8;
+
+        // Version ff is forbidden. Future versions may append more fields,
+        // but version 00 must have exactly 4 of them.
+        let version = parse_hex_u128(version, 2)? as u8;
+        if version == 0xff || (version == 0 && parts.next().is_some()) {
+            return None;
+        }
+        // All-zero identifiers are invalid.
+        if trace_id == 0 || parent_span_id == 0 {
+            return None;
+        }
+
+        Some(TraceContext {
+            trace_id,
+            parent_span_id,
+            sampled: flags & 0x01 != 0,
+        })
+    }
+
+    /// Parses B3 single header: `{trace-id}-{span-id}[-{sampling-state}[-{parent-span-id}]]`.
+    ///
+    /// See https://github.com/openzipkin/b3-propagation#single-header.
+    fn parse_b3(header: &str) -> Option<Self> {
+        let mut parts = header.split('-');
+        let trace_id_str = parts.next()?;
+        // 64-bit trace ids are left-padded with zeros.
+        let trace_id = match trace_id_str.len() {
+            16 | 32 => parse_hex_u128(trace_id_str, trace_id_str.len())?,
+            _ => return None,
+        };
+        let span_id = parse_hex_u64(parts.next()?)?;
+        let sampled = match parts.next() {
+            // Sampling decision deferred - we record the span anyway.
+            None => true,
+            Some("1") | Some("d") => true,
+            Some("0") => false,
+            Some(_) => return None,
+        };
+        if let Some(parent_span_id) = parts.next() {
+            parse_hex_u64(parent_span_id)?;
+        }
+        if parts.next().is_some() || trace_id == 0 || span_id == 0 {
+            return None;
+        }
+
+        Some(TraceContext {
+            trace_id,
+            parent_span_id: span_id,
+            sampled,
+        })
+    }
+
+    pub(crate) fn parse(header: &str) -> Option<Self> {
+        let header = header.trim();
+        // W3C traceparent always starts with 2-digit version, while B3 starts with 16 or 32-digit trace id.
+        match header.find('-') {
+            Some(2) => Self::parse_traceparent(header),
+            _ => Self::parse_b3(header),
+        }
+    }
+
+    /// Parses the header passed to `cass_{statement,batch}_set_trace_context_n`.
+    /// NULL or empty header clears the trace context.
+    pub(crate) unsafe fn from_header_n(
+        header: *const c_char,
+        header_length: size_t,
+    ) -> Result<Option<Self>, CassError> {
+        if header.is_null() || header_length == 0 {
+            return Ok(None);
+        }
+
+        let Some(header) = (unsafe { ptr_to_cstr_n(header, header_length) }) else {
+            tracing::error!("Provided non-utf8 trace context header!");
+            return Err(CassError::CASS_ERROR_LIB_BAD_PARAMS);
+        };
+        match Self::parse(header) {
+            Some(trace_context) => Ok(Some(trace_context)),
+            None => {
+                tracing::error!("Provided invalid trace context header <{}>", header);
+                Err(CassError::CASS_ERROR_LIB_BAD_PARAMS)
+            }
+        }
+    }
+
+    /// Renders W3C `traceparent` header for a child span of this context.
+    pub(crate) fn child_traceparent(&self, span_id: u64) -> String {
+        let mut header = String::with_capacity(55);
+        write!(
+            header,
+            "00-{:032x}-{:016x}-{:02x}",
+            self.trace_id, span_id, self.sampled as u8
+        )
+        .unwrap();
+        header
+    }
+
+    /// Creates a `tracing` span representing the client side of a request.
+    pub(crate) fn client_span(&self, span_id: u64) -> tracing::Span {
+        tracing::info_span!(
+            "cql_request",
+            otel.kind = "client",
+            db.system = "cassandra",
+            trace_id = %format!("{:032x}", self.trace_id),
+            parent_span_id = %format!("{:016x}", self.parent_span_id),
+            span_id = %format!("{:016x}", span_id),
+            sampled = self.sampled,
+            traceparent = %self.child_traceparent(span_id),
+            // Id of the request reported to the request tracker callbacks.
+            request_id = tracing::field::Empty,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TraceContext;
+
+    #[test]
+    fn test_parse_trace_context() {
+        // W3C traceparent
+        assert_eq!(
+            TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+            Some(TraceContext {
+                trace_id: 0x4bf92f3577b34da6a3ce929d0e0e4736,
+                parent_span_id: 0x00f067aa0ba902b7,
+                sampled: true,
+            })
+        );
+        assert_eq!(
+            TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00")
+                .map(|ctx| ctx.sampled),
+            Some(false)
+        );
+        // Zero trace id, forbidden version, too short ids, trailing fields in version 00.
+        for invalid in [
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e47-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-foo",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-+0f067aa0ba902b7-01",
+        ] {
+            assert_eq!(TraceContext::parse(invalid), None, "{}", invalid);
+        }
+
+        // B3 single header
+        assert_eq!(
+            TraceContext::parse(
+                "80f198ee56343ba864fe8b2a57d3eff7-e457b5a2e4d86bd1-1-05e3ac9a4f6e3b90"
+            ),
+            Some(TraceContext {
+                trace_id: 0x80f198ee56343ba864fe8b2a57d3eff7,
+                parent_span_id: 0xe457b5a2e4d86bd1,
+                sampled: true,
+            })
+        );
+        assert_eq!(
+            TraceContext::parse("64fe8b2a57d3eff7-e457b5a2e4d86bd1-0"),
+            Some(TraceContext {
+                trace_id: 0x64fe8b2a57d3eff7,
+                parent_span_id: 0xe457b5a2e4d86bd1,
+                sampled: false,
+            })
+        );
+        for invalid in [
+            "0",
+            "64fe8b2a57d3eff7",
+            "64fe8b2a57d3eff7-e457b5a2e4d86bd1-x",
+            "",
+        ] {
+            assert_eq!(TraceContext::parse(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_child_traceparent() {
+        let ctx =
+            TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").unwrap();
+        assert_eq!(
+            ctx.child_traceparent(0xb7ad6b7169203331),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-b7ad6b7169203331-01"
+        );
+    }
+}
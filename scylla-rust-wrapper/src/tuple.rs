@@ -126,6 +126,9 @@ make_binders!(bool, cass_tuple_set_bool);
 make_binders!(string, cass_tuple_set_string);
 make_binders!(string_n, cass_tuple_set_string_n);
 make_binders!(bytes, cass_tuple_set_bytes);
+make_binders!(custom, cass_tuple_set_custom);
+make_binders!(custom_n, cass_tuple_set_custom_n);
+make_binders!(serialized, cass_tuple_set_serialized);
 make_binders!(uuid, cass_tuple_set_uuid);
 make_binders!(inet, cass_tuple_set_inet);
 make_binders!(duration, cass_tuple_set_duration);
@@ -136,11 +139,99 @@ make_binders!(user_type, cass_tuple_set_user_type);
 
 #[cfg(test)]
 mod tests {
+    use std::os::raw::c_char;
+
+    use crate::argconv::ArcFFI;
+    use crate::cass_error::CassError;
     use crate::cass_types::{
-        CassValueType, cass_data_type_add_sub_type, cass_data_type_free, cass_data_type_new,
+        CassDataType, CassDataTypeInner, CassValueType, cass_data_type_add_sub_type,
+        cass_data_type_free, cass_data_type_new,
+    };
+    use crate::testing::assert_cass_error_eq;
+    use crate::types::size_t;
+
+    use super::{
+        cass_tuple_data_type, cass_tuple_free, cass_tuple_new, cass_tuple_new_from_data_type,
+        cass_tuple_set_custom, cass_tuple_set_custom_n,
     };
 
-    use super::{cass_tuple_data_type, cass_tuple_new};
+    #[test]
+    fn test_tuple_set_custom() {
+        const CLASS_NAME: &str = "com.example.CustomType";
+        let value = [0x01, 0x02, 0x03];
+
+        unsafe {
+            let dt = CassDataType::new_arced(CassDataTypeInner::Tuple(vec![
+                CassDataType::new_arced(CassDataTypeInner::Value(
+                    CassValueType::CASS_VALUE_TYPE_INT,
+                )),
+                CassDataType::new_arced(CassDataTypeInner::Custom(CLASS_NAME.to_owned())),
+            ]));
+            let dt_ptr = ArcFFI::into_ptr(dt);
+            let mut tuple = cass_tuple_new_from_data_type(dt_ptr.borrow());
+
+            assert_cass_error_eq!(
+                cass_tuple_set_custom_n(
+                    tuple.borrow_mut(),
+                    1,
+                    CLASS_NAME.as_ptr() as *const c_char,
+                    CLASS_NAME.len() as size_t,
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_OK
+            );
+            // Class name mismatch.
+            assert_cass_error_eq!(
+                cass_tuple_set_custom(
+                    tuple.borrow_mut(),
+                    1,
+                    c"com.example.OtherType".as_ptr(),
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+            );
+            // Not a custom element.
+            assert_cass_error_eq!(
+                cass_tuple_set_custom(
+                    tuple.borrow_mut(),
+                    0,
+                    c"com.example.CustomType".as_ptr(),
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_ERROR_LIB_INVALID_VALUE_TYPE
+            );
+            assert_cass_error_eq!(
+                cass_tuple_set_custom(
+                    tuple.borrow_mut(),
+                    2,
+                    c"com.example.CustomType".as_ptr(),
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS
+            );
+
+            // Untyped tuple accepts any custom value.
+            let mut untyped_tuple = cass_tuple_new(1);
+            assert_cass_error_eq!(
+                cass_tuple_set_custom(
+                    untyped_tuple.borrow_mut(),
+                    0,
+                    c"com.example.OtherType".as_ptr(),
+                    value.as_ptr(),
+                    value.len() as size_t
+                ),
+                CassError::CASS_OK
+            );
+
+            cass_tuple_free(untyped_tuple);
+            cass_tuple_free(tuple);
+            ArcFFI::free(dt_ptr);
+        }
+    }
 
     #[test]
     fn regression_empty_tuple_data_type_test() {
@@ -12,6 +12,9 @@ pub struct CassUserType {
 
     // Vec to preserve the order of fields
     pub field_values: Vec<Option<CassCqlValue>>,
+
+    // If set, unquoted field names are matched case-sensitively as well.
+    pub case_sensitive_field_names: bool,
 }
 
 impl FFI for CassUserType {
@@ -33,6 +36,13 @@ impl CassUserType {
     }
 
     fn set_field_by_name(&mut self, name: &str, value: Option<CassCqlValue>) -> CassError {
+        // Same as in cpp-driver - if the name was quoted, then we should treat it as case sensitive.
+        let (name, is_case_sensitive) =
+            match name.strip_prefix('\"').and_then(|s| s.strip_suffix('\"')) {
+                Some(name_unquoted) => (name_unquoted, true),
+                None => (name, self.case_sensitive_field_names),
+            };
+
         let mut found_field: bool = false;
         for (index, (field_name, field_type)) in unsafe {
             self.data_type
@@ -42,7 +52,9 @@ impl CassUserType {
                 .iter()
                 .enumerate()
         } {
-            if *field_name == name {
+            if is_case_sensitive && *field_name == name
+                || !is_case_sensitive && field_name.eq_ignore_ascii_case(name)
+            {
                 found_field = true;
                 if index >= self.field_values.len() {
                     return CassError::CASS_ERROR_LIB_INDEX_OUT_OF_BOUNDS;
@@ -98,6 +110,7 @@ pub unsafe extern "C" fn cass_user_type_new_from_data_type(
             BoxFFI::into_ptr(Box::new(CassUserType {
                 data_type,
                 field_values,
+                case_sensitive_field_names: false,
             }))
         }
         _ => BoxFFI::null_mut(),
@@ -120,6 +133,23 @@ pub unsafe extern "C" fn cass_user_type_data_type(
     ArcFFI::as_ptr(&user_type.data_type)
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cass_user_type_set_case_sensitive_field_names(
+    user_type: CassBorrowedExclusivePtr<CassUserType, CMut>,
+    enabled: cass_bool_t,
+) -> CassError {
+    let Some(user_type) = BoxFFI::as_mut_ref(user_type) else {
+        tracing::error!(
+            "Provided null user type pointer to cass_user_type_set_case_sensitive_field_names!"
+        );
+        return CassError::CASS_ERROR_LIB_BAD_PARAMS;
+    };
+
+    user_type.case_sensitive_field_names = enabled != 0;
+
+    CassError::CASS_OK
+}
+
 prepare_binders_macro!(@index_and_name CassUserType,
     |udt: &mut CassUserType, index, v| udt.set_field_by_index(index, v),
     |udt: &mut CassUserType, name, v| udt.set_field_by_name(name, v));
@@ -193,6 +223,21 @@ make_binders!(
     cass_user_type_set_bytes_by_name,
     cass_user_type_set_bytes_by_name_n
 );
+make_binders!(
+    custom,
+    cass_user_type_set_custom,
+    custom,
+    cass_user_type_set_custom_by_name,
+    custom_n,
+    cass_user_type_set_custom_by_name_n
+);
+make_binders!(@index custom_n, cass_user_type_set_custom_n);
+make_binders!(
+    serialized,
+    cass_user_type_set_serialized,
+    cass_user_type_set_serialized_by_name,
+    cass_user_type_set_serialized_by_name_n
+);
 make_binders!(
     uuid,
     cass_user_type_set_uuid,
@@ -235,3 +280,72 @@ make_binders!(
     cass_user_type_set_user_type_by_name,
     cass_user_type_set_user_type_by_name_n
 );
+
+#[cfg(test)]
+mod tests {
+    use crate::argconv::ArcFFI;
+    use crate::cass_error::CassError;
+    use crate::cass_types::{CassDataType, CassDataTypeInner, CassValueType, UDTDataType};
+    use crate::testing::assert_cass_error_eq;
+    use crate::types::cass_true;
+
+    use super::{
+        cass_user_type_free, cass_user_type_new_from_data_type,
+        cass_user_type_set_case_sensitive_field_names, cass_user_type_set_int32_by_name,
+    };
+
+    #[test]
+    fn test_user_type_set_by_name_case_sensitivity() {
+        let int_type =
+            CassDataType::new_arced(CassDataTypeInner::Value(CassValueType::CASS_VALUE_TYPE_INT));
+        let mut udt = UDTDataType::new();
+        udt.add_field("field".to_owned(), int_type.clone());
+        udt.add_field("MixedCase".to_owned(), int_type);
+
+        unsafe {
+            let dt_ptr = ArcFFI::into_ptr(CassDataType::new_arced(CassDataTypeInner::UDT(udt)));
+            let mut user_type = cass_user_type_new_from_data_type(dt_ptr.borrow());
+
+            // Unquoted names are case insensitive.
+            assert_cass_error_eq!(
+                cass_user_type_set_int32_by_name(user_type.borrow_mut(), c"FIELD".as_ptr(), 1),
+                CassError::CASS_OK
+            );
+            assert_cass_error_eq!(
+                cass_user_type_set_int32_by_name(user_type.borrow_mut(), c"mixedcase".as_ptr(), 2),
+                CassError::CASS_OK
+            );
+
+            // Quoted names are case sensitive.
+            assert_cass_error_eq!(
+                cass_user_type_set_int32_by_name(
+                    user_type.borrow_mut(),
+                    c"\"MixedCase\"".as_ptr(),
+                    3
+                ),
+                CassError::CASS_OK
+            );
+            assert_cass_error_eq!(
+                cass_user_type_set_int32_by_name(user_type.borrow_mut(), c"\"FIELD\"".as_ptr(), 4),
+                CassError::CASS_ERROR_LIB_NAME_DOES_NOT_EXIST
+            );
+
+            // Strictly case sensitive lookups.
+            assert_cass_error_eq!(
+                cass_user_type_set_case_sensitive_field_names(user_type.borrow_mut(), cass_true),
+                CassError::CASS_OK
+            );
+            assert_cass_error_eq!(
+                cass_user_type_set_int32_by_name(user_type.borrow_mut(), c"FIELD".as_ptr(), 5),
+                CassError::CASS_ERROR_LIB_NAME_DOES_NOT_EXIST
+            );
+            assert_cass_error_eq!(
+                cass_user_type_set_int32_by_name(user_type.borrow_mut(), c"MixedCase".as_ptr(), 6),
+                CassError::CASS_OK
+            );
+
+            cass_user_type_free(user_type);
+            ArcFFI::free(dt_ptr);
+        }
+    }
+}
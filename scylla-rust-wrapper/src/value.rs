@@ -11,7 +11,7 @@ use scylla::serialize::writers::{CellWriter, WrittenCellProof};
 use scylla::value::{CqlDate, CqlDecimal, CqlDuration};
 use uuid::Uuid;
 
-use crate::cass_types::{CassDataType, CassValueType};
+use crate::cass_types::{CassDataType, CassDataTypeInner, CassValueType, MapDataType};
 
 /// A narrower version of rust driver's CqlValue.
 ///
@@ -35,28 +35,40 @@ pub enum CassCqlValue {
     Float(f32),
     Double(f64),
     Boolean(bool),
-    Text(String),
-    Blob(Vec<u8>),
+    // Text and blob values are shared, so cloning the bound values
+    // (e.g. when the statement is executed) does not copy them.
+    Text(Arc<str>),
+    Blob(Arc<[u8]>),
     Uuid(Uuid),
     Date(CqlDate),
     Inet(IpAddr),
     Duration(CqlDuration),
     Decimal(CqlDecimal),
+    /// Value of a custom type - serialized as is.
+    Custom {
+        class_name: Arc<str>,
+        value: Arc<[u8]>,
+    },
+    /// Value of `data_type`, already serialized by the user - written as is.
+    Serialized {
+        data_type: Arc<CassDataType>,
+        value: Arc<[u8]>,
+    },
     Tuple {
         data_type: Option<Arc<CassDataType>>,
         fields: Vec<Option<CassCqlValue>>,
     },
     List {
         data_type: Option<Arc<CassDataType>>,
-        values: Vec<CassCqlValue>,
+        values: SerializedItems,
     },
     Map {
         data_type: Option<Arc<CassDataType>>,
-        values: Vec<(CassCqlValue, CassCqlValue)>,
+        values: SerializedItems,
     },
     Set {
         data_type: Option<Arc<CassDataType>>,
-        values: Vec<CassCqlValue>,
+        values: SerializedItems,
     },
     UserDefinedType {
         data_type: Arc<CassDataType>,
@@ -65,7 +77,22 @@ pub enum CassCqlValue {
         /// wrong.
         fields: Vec<(String, Option<CassCqlValue>)>,
     },
-    // TODO: custom (?), duration and decimal
+}
+
+/// Items of a list, set or map. They are serialized when appended to the collection,
+/// so binding the collection does not copy the values one by one.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct SerializedItems {
+    /// Number of the items (entries, for maps).
+    pub(crate) count: usize,
+    /// Serialized items (keys and values, for maps), each prefixed with its length.
+    pub(crate) bytes: Arc<[u8]>,
+    /// Values with the types of the items (keys, for maps) - one per type. Used to typecheck
+    /// the items of untyped collections.
+    pub(crate) samples: Arc<[CassCqlValue]>,
+    /// Values with the types of the map values - one per type. Empty for lists and sets.
+    pub(crate) value_samples: Arc<[CassCqlValue]>,
 }
 
 pub fn is_type_compatible(value: &Option<CassCqlValue>, typ: &CassDataType) -> bool {
@@ -139,36 +166,89 @@ impl CassCqlValue {
             CassCqlValue::Decimal(_) => unsafe {
                 typ.get_unchecked().get_value_type() == CassValueType::CASS_VALUE_TYPE_DECIMAL
             },
-            CassCqlValue::Tuple { data_type, .. } => unsafe {
-                if let Some(dt) = data_type {
-                    return dt.get_unchecked().typecheck_equals(typ.get_unchecked());
-                }
-                // Untyped tuple.
-                typ.get_unchecked().get_value_type() == CassValueType::CASS_VALUE_TYPE_TUPLE
+            CassCqlValue::Custom { class_name, .. } => match unsafe { typ.get_unchecked() } {
+                CassDataTypeInner::Custom(typ_class_name) => **class_name == **typ_class_name,
+                _ => false,
             },
-            CassCqlValue::List { data_type, .. } => unsafe {
-                if let Some(dt) = data_type {
-                    dt.get_unchecked().typecheck_equals(typ.get_unchecked())
-                } else {
-                    // Untyped list.
-                    typ.get_unchecked().get_value_type() == CassValueType::CASS_VALUE_TYPE_LIST
-                }
+            CassCqlValue::Serialized { data_type, .. } => unsafe {
+                data_type
+                    .get_unchecked()
+                    .typecheck_equals(typ.get_unchecked())
             },
-            CassCqlValue::Map { data_type, .. } => unsafe {
-                if let Some(dt) = data_type {
-                    dt.get_unchecked().typecheck_equals(typ.get_unchecked())
-                } else {
-                    // Untyped map.
-                    typ.get_unchecked().get_value_type() == CassValueType::CASS_VALUE_TYPE_MAP
-                }
+            // Typed values are checked against their own type. The items of untyped values
+            // (e.g. created via `cass_collection_new`) are checked one by one, so the
+            // mismatch is detected when the value is appended/bound, and not by the server.
+            CassCqlValue::Tuple {
+                data_type: Some(dt),
+                ..
+            }
+            | CassCqlValue::List {
+                data_type: Some(dt),
+                ..
+            }
+            | CassCqlValue::Map {
+                data_type: Some(dt),
+                ..
+            }
+            | CassCqlValue::Set {
+                data_type: Some(dt),
+                ..
+            } => unsafe { dt.get_unchecked().typecheck_equals(typ.get_unchecked()) },
+            CassCqlValue::Tuple {
+                data_type: None,
+                fields,
+            } => match unsafe { typ.get_unchecked() } {
+                CassDataTypeInner::Tuple(types) => fields
+                    .iter()
+                    .zip(types)
+                    .all(|(field, field_type)| is_type_compatible(field, field_type)),
+                other => other.get_value_type() == CassValueType::CASS_VALUE_TYPE_TUPLE,
             },
-            CassCqlValue::Set { data_type, .. } => unsafe {
-                if let Some(dt) = data_type {
-                    dt.get_unchecked().typecheck_equals(typ.get_unchecked())
-                } else {
-                    // Untyped set.
-                    typ.get_unchecked().get_value_type() == CassValueType::CASS_VALUE_TYPE_SET
+            CassCqlValue::List {
+                data_type: None,
+                values,
+            } => match unsafe { typ.get_unchecked() } {
+                CassDataTypeInner::List {
+                    typ: Some(item_type),
+                    ..
+                } => values
+                    .samples
+                    .iter()
+                    .all(|v| v.is_type_compatible(item_type)),
+                other => other.get_value_type() == CassValueType::CASS_VALUE_TYPE_LIST,
+            },
+            CassCqlValue::Set {
+                data_type: None,
+                values,
+            } => match unsafe { typ.get_unchecked() } {
+                CassDataTypeInner::Set {
+                    typ: Some(item_type),
+                    ..
+                } => values
+                    .samples
+                    .iter()
+                    .all(|v| v.is_type_compatible(item_type)),
+                other => other.get_value_type() == CassValueType::CASS_VALUE_TYPE_SET,
+            },
+            CassCqlValue::Map {
+                data_type: None,
+                values,
+            } => match unsafe { typ.get_unchecked() } {
+                CassDataTypeInner::Map {
+                    typ: MapDataType::Key(k_typ),
+                    ..
+                } => values.samples.iter().all(|k| k.is_type_compatible(k_typ)),
+                CassDataTypeInner::Map {
+                    typ: MapDataType::KeyAndValue(k_typ, v_typ),
+                    ..
+                } => {
+                    values.samples.iter().all(|k| k.is_type_compatible(k_typ))
+                        && values
+                            .value_samples
+                            .iter()
+                            .all(|v| v.is_type_compatible(v_typ))
                 }
+                other => other.get_value_type() == CassValueType::CASS_VALUE_TYPE_MAP,
             },
             CassCqlValue::UserDefinedType { data_type, .. } => unsafe {
                 data_type
@@ -192,6 +272,12 @@ impl SerializeValue for CassCqlValue {
 }
 
 impl CassCqlValue {
+    /// Appends the value to `buf`, prefixed with its length - the way the items
+    /// of collections are serialized.
+    pub(crate) fn serialize_item(&self, buf: &mut Vec<u8>) -> Result<(), SerializationError> {
+        self.do_serialize(CellWriter::new(buf)).map(|_| ())
+    }
+
     fn do_serialize<'b>(
         &self,
         writer: CellWriter<'b>,
@@ -230,10 +316,10 @@ impl CassCqlValue {
                 <bool as SerializeValue>::serialize(v, &ColumnType::Native(Boolean), writer)
             }
             CassCqlValue::Text(v) => {
-                <String as SerializeValue>::serialize(v, &ColumnType::Native(Text), writer)
+                <&str as SerializeValue>::serialize(&&**v, &ColumnType::Native(Text), writer)
             }
             CassCqlValue::Blob(v) => {
-                <Vec<u8> as SerializeValue>::serialize(v, &ColumnType::Native(Blob), writer)
+                <&[u8] as SerializeValue>::serialize(&&**v, &ColumnType::Native(Blob), writer)
             }
             CassCqlValue::Uuid(v) => {
                 <uuid::Uuid as SerializeValue>::serialize(v, &ColumnType::Native(Uuid), writer)
@@ -250,15 +336,20 @@ impl CassCqlValue {
             CassCqlValue::Decimal(v) => {
                 <CqlDecimal as SerializeValue>::serialize(v, &ColumnType::Native(Decimal), writer)
             }
+            // Custom and pre-serialized values are written the same way as blobs - as raw bytes.
+            CassCqlValue::Custom { value, .. } | CassCqlValue::Serialized { value, .. } => {
+                <&[u8] as SerializeValue>::serialize(&&**value, &ColumnType::Native(Blob), writer)
+            }
             CassCqlValue::Tuple { fields, .. } => serialize_tuple_like(fields.iter(), writer),
-            CassCqlValue::List { values, .. } => {
-                serialize_sequence(values.len(), values.iter(), writer)
+            CassCqlValue::List { values, .. } | CassCqlValue::Set { values, .. } => {
+                serialize_items(
+                    values,
+                    SetOrListSerializationErrorKind::TooManyElements,
+                    writer,
+                )
             }
             CassCqlValue::Map { values, .. } => {
-                serialize_mapping(values.len(), values.iter().map(|p| (&p.0, &p.1)), writer)
-            }
-            CassCqlValue::Set { values, .. } => {
-                serialize_sequence(values.len(), values.iter(), writer)
+                serialize_items(values, MapSerializationErrorKind::TooManyElements, writer)
             }
             CassCqlValue::UserDefinedType { fields, .. } => serialize_udt(fields, writer),
         }
@@ -324,66 +415,23 @@ fn serialize_tuple_like<'t, 'b>(
         .map_err(|_| mk_ser_err::<CassCqlValue>(BuiltinSerializationErrorKind::SizeOverflow))
 }
 
-fn serialize_sequence<'t, 'b>(
-    len: usize,
-    iter: impl Iterator<Item = &'t CassCqlValue>,
+fn serialize_items<'b>(
+    items: &SerializedItems,
+    too_many_elements: impl Into<BuiltinSerializationErrorKind>,
     writer: CellWriter<'b>,
 ) -> Result<WrittenCellProof<'b>, SerializationError> {
-    let rust_name = std::any::type_name::<CassCqlValue>();
-
     let mut builder = writer.into_value_builder();
 
-    let element_count: i32 = len.try_into().map_err(|_| {
-        mk_ser_err_named(rust_name, SetOrListSerializationErrorKind::TooManyElements)
-    })?;
-    builder.append_bytes(&element_count.to_be_bytes());
-
-    for el in iter {
-        el.do_serialize(builder.make_sub_writer()).map_err(|err| {
-            mk_ser_err_named(
-                rust_name,
-                SetOrListSerializationErrorKind::ElementSerializationFailed(err),
-            )
-        })?;
-    }
-
-    builder
-        .finish()
-        .map_err(|_| mk_ser_err_named(rust_name, BuiltinSerializationErrorKind::SizeOverflow))
-}
-
-fn serialize_mapping<'t, 'b>(
-    len: usize,
-    iter: impl Iterator<Item = (&'t CassCqlValue, &'t CassCqlValue)>,
-    writer: CellWriter<'b>,
-) -> Result<WrittenCellProof<'b>, SerializationError> {
-    let rust_name = std::any::type_name::<CassCqlValue>();
-
-    let mut builder = writer.into_value_builder();
-
-    let element_count: i32 = len
+    let element_count: i32 = items
+        .count
         .try_into()
-        .map_err(|_| mk_ser_err_named(rust_name, MapSerializationErrorKind::TooManyElements))?;
+        .map_err(|_| mk_ser_err::<CassCqlValue>(too_many_elements))?;
     builder.append_bytes(&element_count.to_be_bytes());
-
-    for (k, v) in iter {
-        k.do_serialize(builder.make_sub_writer()).map_err(|err| {
-            mk_ser_err_named(
-                rust_name,
-                MapSerializationErrorKind::KeySerializationFailed(err),
-            )
-        })?;
-        v.do_serialize(builder.make_sub_writer()).map_err(|err| {
-            mk_ser_err_named(
-                rust_name,
-                MapSerializationErrorKind::ValueSerializationFailed(err),
-            )
-        })?;
-    }
+    builder.append_bytes(&items.bytes);
 
     builder
         .finish()
-        .map_err(|_| mk_ser_err_named(rust_name, BuiltinSerializationErrorKind::SizeOverflow))
+        .map_err(|_| mk_ser_err::<CassCqlValue>(BuiltinSerializationErrorKind::SizeOverflow))
 }
 
 fn serialize_udt<'b>(
@@ -417,7 +465,7 @@ mod tests {
 
     use crate::{
         cass_types::{CassDataType, CassDataTypeInner, CassValueType, MapDataType, UDTDataType},
-        value::{CassCqlValue, is_type_compatible},
+        value::{CassCqlValue, SerializedItems, is_type_compatible},
     };
 
     fn all_value_data_types() -> Vec<CassDataType> {
@@ -861,7 +909,7 @@ mod tests {
                 TestCase {
                     value: CassCqlValue::List {
                         data_type: None,
-                        values: vec![],
+                        values: SerializedItems::default(),
                     },
                     compatible_types: vec![
                         data_type_float_list.clone(),
@@ -887,7 +935,7 @@ mod tests {
                 TestCase {
                     value: CassCqlValue::List {
                         data_type: Some(data_type_float_list.clone()),
-                        values: vec![],
+                        values: SerializedItems::default(),
                     },
                     compatible_types: vec![
                         data_type_float_list.clone(),
@@ -913,7 +961,7 @@ mod tests {
                 TestCase {
                     value: CassCqlValue::Set {
                         data_type: None,
-                        values: vec![],
+                        values: SerializedItems::default(),
                     },
                     compatible_types: vec![
                         data_type_untyped_set.clone(),
@@ -939,7 +987,7 @@ mod tests {
                 TestCase {
                     value: CassCqlValue::Set {
                         data_type: Some(data_type_float_set.clone()),
-                        values: vec![],
+                        values: SerializedItems::default(),
                     },
                     compatible_types: vec![
                         data_type_untyped_set.clone(),
@@ -965,7 +1013,7 @@ mod tests {
                 TestCase {
                     value: CassCqlValue::Map {
                         data_type: None,
-                        values: vec![],
+                        values: SerializedItems::default(),
                     },
                     compatible_types: vec![
                         data_type_untyped_map.clone(),
@@ -991,7 +1039,7 @@ mod tests {
                 TestCase {
                     value: CassCqlValue::Map {
                         data_type: Some(data_type_typed_key_float_map.clone()),
-                        values: vec![],
+                        values: SerializedItems::default(),
                     },
                     compatible_types: vec![
                         data_type_typed_key_float_map.clone(),
@@ -1017,7 +1065,7 @@ mod tests {
                 TestCase {
                     value: CassCqlValue::Map {
                         data_type: Some(data_type_float_int_map.clone()),
-                        values: vec![],
+                        values: SerializedItems::default(),
                     },
                     compatible_types: vec![
                         data_type_float_int_map.clone(),
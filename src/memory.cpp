@@ -24,15 +24,6 @@
 
 using namespace datastax::internal;
 
-extern "C" {
-
-void cass_alloc_set_functions(CassMallocFunction malloc_func, CassReallocFunction realloc_func,
-                              CassFreeFunction free_func) {
-  Memory::set_functions(malloc_func, realloc_func, free_func);
-}
-
-} // extern "C"
-
 #ifdef DEBUG_CUSTOM_ALLOCATOR
 void* operator new(size_t size) throw(std::bad_alloc) {
   assert(false && "Attempted to use global operator new");
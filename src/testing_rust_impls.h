@@ -5,7 +5,7 @@
 
 extern "C" {
 // Retrieves a connect timeout from cluster config.
-CASS_EXPORT cass_uint16_t testing_cluster_get_connect_timeout(CassCluster* cluster);
+CASS_EXPORT cass_uint32_t testing_cluster_get_connect_timeout(CassCluster* cluster);
 
 // Retrieves a CQL connection port from cluster config.
 CASS_EXPORT cass_int32_t testing_cluster_get_port(CassCluster* cluster);
@@ -38,6 +38,107 @@ CASS_EXPORT void testing_statement_set_sleeping_history_listener(CassStatement *
 // This can be used to enforce a sleep time during batch execution, which increases the latency.
 CASS_EXPORT void testing_batch_set_sleeping_history_listener(CassBatch *batch,
     cass_uint64_t sleep_time_ms);
+
+// A proxy placed between the driver and the cluster nodes, used to simulate node failures
+// and increased latency.
+typedef struct TestingProxy_ TestingProxy;
+
+// Starts a proxy in front of the nodes from the comma-delimited list of hosts,
+// listening on the given port.
+//
+// Returns null if the hosts are invalid or the proxy could not be started.
+// The proxy needs to be stopped and freed with `testing_proxy_free`.
+CASS_EXPORT TestingProxy* testing_proxy_new(const char* hosts, cass_int32_t port);
+
+CASS_EXPORT void testing_proxy_free(TestingProxy* proxy);
+
+// Makes the cluster connect to the nodes through the proxy. Contact points are replaced
+// with the proxy addresses, and the addresses of the discovered nodes are translated.
+CASS_EXPORT void testing_cluster_set_proxy(CassCluster* cluster, const TestingProxy* proxy);
+
+// Closes the connections to the host upon their next use, and rejects the new ones,
+// so the host is perceived as down.
+//
+// Each of the fault injecting functions replaces the fault previously injected on the host.
+CASS_EXPORT CassError testing_proxy_close_connections(const TestingProxy* proxy, const char* host);
+
+// Rejects new connections to the host. Established connections keep working.
+CASS_EXPORT CassError testing_proxy_reject_new_connections(const TestingProxy* proxy,
+                                                           const char* host);
+
+// Responds to the next `count` requests sent to the host with `error`.
+// Only server errors (CASS_ERROR_SERVER_*) can be simulated.
+CASS_EXPORT CassError testing_proxy_fail_next_requests(const TestingProxy* proxy, const char* host,
+                                                       size_t count, CassError error);
+
+// Stops simulating the failure of the host.
+CASS_EXPORT CassError testing_proxy_heal_host(const TestingProxy* proxy, const char* host);
+
+// Delays the requests sent to the host by `latency_ms`, plus a random jitter of up to `jitter_ms`.
+// Zero latency and jitter stop delaying the requests. Requests failed by the proxy are not delayed.
+//
+// Note that changing the latency of the host restarts counting the requests
+// to fail (see `testing_proxy_fail_next_requests`).
+CASS_EXPORT CassError testing_proxy_set_host_latency(const TestingProxy* proxy, const char* host,
+                                                     cass_uint64_t latency_ms,
+                                                     cass_uint64_t jitter_ms);
+
+// Request interception rules, programmed per host. The reaction of the first rule whose
+// condition is true for a request is applied. User rules are evaluated after the injected
+// faults, and the requests they match are not delayed by `testing_proxy_set_host_latency`.
+//
+// As the rules of the host are reinstalled on every change, the counters of
+// `testing_proxy_condition_true_for_limited_times` conditions are then restarted.
+typedef struct TestingProxyCondition_ TestingProxyCondition;
+typedef struct TestingProxyReaction_ TestingProxyReaction;
+
+CASS_EXPORT TestingProxyCondition* testing_proxy_condition_true();
+
+// Returns null if the opcode (as defined by the CQL protocol) does not denote a request.
+CASS_EXPORT TestingProxyCondition* testing_proxy_condition_request_opcode(cass_uint8_t opcode);
+
+CASS_EXPORT TestingProxyCondition* testing_proxy_condition_body_contains(const cass_byte_t* bytes,
+                                                                         size_t bytes_size,
+                                                                         cass_bool_t case_sensitive);
+
+CASS_EXPORT TestingProxyCondition* testing_proxy_condition_true_for_limited_times(size_t times);
+
+// The combinators take ownership of the provided conditions.
+CASS_EXPORT TestingProxyCondition* testing_proxy_condition_not(TestingProxyCondition* condition);
+
+CASS_EXPORT TestingProxyCondition* testing_proxy_condition_and(TestingProxyCondition* condition1,
+                                                               TestingProxyCondition* condition2);
+
+CASS_EXPORT TestingProxyCondition* testing_proxy_condition_or(TestingProxyCondition* condition1,
+                                                              TestingProxyCondition* condition2);
+
+CASS_EXPORT void testing_proxy_condition_free(TestingProxyCondition* condition);
+
+// Passes the request to the node.
+CASS_EXPORT TestingProxyReaction* testing_proxy_reaction_noop();
+
+// Drops the request, so it's never responded to.
+CASS_EXPORT TestingProxyReaction* testing_proxy_reaction_drop_frame();
+
+// Closes the connection the request was sent on.
+CASS_EXPORT TestingProxyReaction* testing_proxy_reaction_drop_connection();
+
+// Responds to the request with the error. Only server errors (CASS_ERROR_SERVER_*)
+// can be forged - returns null for other errors.
+CASS_EXPORT TestingProxyReaction* testing_proxy_reaction_forge_error(CassError error);
+
+CASS_EXPORT void testing_proxy_reaction_set_delay(TestingProxyReaction* reaction,
+                                                  cass_uint64_t delay_ms);
+
+CASS_EXPORT void testing_proxy_reaction_free(TestingProxyReaction* reaction);
+
+// Appends the rule to the rules of the host. Takes ownership of the condition and reaction.
+CASS_EXPORT CassError testing_proxy_add_rule(const TestingProxy* proxy, const char* host,
+                                             TestingProxyCondition* condition,
+                                             TestingProxyReaction* reaction);
+
+// Removes all the rules of the host added with `testing_proxy_add_rule`.
+CASS_EXPORT CassError testing_proxy_clear_rules(const TestingProxy* proxy, const char* host);
 }
 
 #endif
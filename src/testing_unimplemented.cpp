@@ -51,19 +51,6 @@ CASS_EXPORT CassError cass_cluster_set_authenticator_callbacks(
     CassAuthenticatorDataCleanupCallback cleanup_callback, void* data) {
   throw std::runtime_error("UNIMPLEMENTED cass_cluster_set_authenticator_callbacks\n");
 }
-CASS_EXPORT CassError cass_cluster_set_cloud_secure_connection_bundle_no_ssl_lib_init(
-    CassCluster* cluster, const char* path) {
-  throw std::runtime_error(
-      "UNIMPLEMENTED cass_cluster_set_cloud_secure_connection_bundle_no_ssl_lib_init\n");
-}
-CASS_EXPORT void cass_cluster_set_constant_reconnect(CassCluster* cluster, cass_uint64_t delay_ms) {
-  throw std::runtime_error("UNIMPLEMENTED cass_cluster_set_constant_reconnect\n");
-}
-CASS_EXPORT CassError cass_cluster_set_host_listener_callback(CassCluster* cluster,
-                                                              CassHostListenerCallback callback,
-                                                              void* data) {
-  throw std::runtime_error("UNIMPLEMENTED cass_cluster_set_host_listener_callback\n");
-}
 CASS_EXPORT CassError cass_cluster_set_no_compact(CassCluster* cluster, cass_bool_t enabled) {
   throw std::runtime_error("UNIMPLEMENTED cass_cluster_set_no_compact\n");
 }
@@ -78,11 +65,6 @@ CASS_EXPORT CassError cass_cluster_set_prepare_on_up_or_add_host(CassCluster* cl
                                                                  cass_bool_t enabled) {
   throw std::runtime_error("UNIMPLEMENTED cass_cluster_set_prepare_on_up_or_add_host\n");
 }
-CASS_EXPORT CassError cass_collection_append_custom(CassCollection* collection,
-                                                    const char* class_name,
-                                                    const cass_byte_t* value, size_t value_size) {
-  throw std::runtime_error("UNIMPLEMENTED cass_collection_append_custom\n");
-}
 CASS_EXPORT const CassValue* cass_column_meta_field_by_name(const CassColumnMeta* column_meta,
                                                             const char* name) {
   throw std::runtime_error("UNIMPLEMENTED cass_column_meta_field_by_name\n");
@@ -210,18 +192,3 @@ CASS_EXPORT size_t cass_table_meta_index_count(const CassTableMeta* table_meta)
 CASS_EXPORT cass_bool_t cass_table_meta_is_virtual(const CassTableMeta* table_meta) {
   throw std::runtime_error("UNIMPLEMENTED cass_table_meta_is_virtual\n");
 }
-CASS_EXPORT CassError cass_tuple_set_custom(CassTuple* tuple, size_t index, const char* class_name,
-                                            const cass_byte_t* value, size_t value_size) {
-  throw std::runtime_error("UNIMPLEMENTED cass_tuple_set_custom\n");
-}
-CASS_EXPORT CassError cass_user_type_set_custom(CassUserType* user_type, size_t index,
-                                                const char* class_name, const cass_byte_t* value,
-                                                size_t value_size) {
-  throw std::runtime_error("UNIMPLEMENTED cass_user_type_set_custom\n");
-}
-CASS_EXPORT CassError cass_user_type_set_custom_by_name(CassUserType* user_type, const char* name,
-                                                        const char* class_name,
-                                                        const cass_byte_t* value,
-                                                        size_t value_size) {
-  throw std::runtime_error("UNIMPLEMENTED cass_user_type_set_custom_by_name\n");
-}